@@ -1,3 +1,4 @@
+use bitter::LittleEndianReader;
 use boxcars::crc::calc_crc;
 use boxcars::*;
 use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
@@ -15,6 +16,34 @@ fn bench_crc(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_vector_decode(c: &mut Criterion) {
+    // Vectors are the most frequently decoded attribute, so decode a run of them with the size
+    // bits of both net versions. Bytes are generated so the sizes vary
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let data: Vec<u8> = (0..4096)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed as u8
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("vector_decode");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    for net_version in [5, 7].iter() {
+        group.bench_function(format!("bench_vector_decode_v{}", net_version), |b| {
+            b.iter(|| {
+                let mut bits = LittleEndianReader::new(&data);
+                while let Some(vector) = Vector3i::decode(&mut bits, *net_version) {
+                    black_box(vector);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
 fn bench_json_serialization(c: &mut Criterion) {
     let data = include_bytes!("../assets/replays/good/3381.replay");
     let json_data_bytes = 19484480_u64;
@@ -125,14 +154,68 @@ fn bench_parse_crc_json(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_object_index(c: &mut Criterion) {
+    // 204c has the largest object table of the sample replays
+    let data = include_bytes!("../assets/replays/good/204c.replay");
+    let replay = ParserBuilder::new(data)
+        .on_error_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let mut group = c.benchmark_group("object_index");
+    group.throughput(Throughput::Elements(replay.objects.len() as u64));
+    group.bench_function("bench_object_index", |b| {
+        b.iter(|| black_box(ObjectIndex::new(&replay.objects)));
+    });
+    group.finish();
+}
+
+fn bench_delta_encode(c: &mut Criterion) {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(data)
+        .on_error_check_crc()
+        .parse()
+        .unwrap();
+    let frames = &replay.network_frames.as_ref().unwrap().frames;
+
+    let mut group = c.benchmark_group("delta_encode");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(frames.len() as u64));
+    group.bench_function("bench_delta_encode", |b| {
+        b.iter(|| black_box(delta::encode(frames)));
+    });
+    group.finish();
+}
+
+fn bench_cache_deserialize(c: &mut Criterion) {
+    let data = include_bytes!("../assets/replays/good/3381.replay");
+    let replay = ParserBuilder::new(data)
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let cached = cache::serialize(&replay);
+
+    let mut group = c.benchmark_group("cache_deserialize");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("bench_cache_deserialize", |b| {
+        b.iter(|| black_box(cache::deserialize(&cached).unwrap()));
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_crc,
+    bench_vector_decode,
     bench_json_serialization,
     bench_parse_crc_body,
     bench_parse_no_crc_body,
     bench_parse_no_crc_no_body,
     bench_parse_crc_json,
+    bench_object_index,
+    bench_delta_encode,
+    bench_cache_deserialize,
 );
 
 criterion_main!(benches);
@@ -89,24 +89,22 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     eprintln!("player_name_id: {}, ping_id: {}", player_name_id, ping_id);
 
     let mut actor_pings: Vec<PlayerPings> = Vec::new();
-    let network = replay.network_frames.unwrap();
-
-    for frame in network.frames {
-        for attr in frame.updated_actors {
+    for frame in replay.frames_or_empty() {
+        for attr in &frame.updated_actors {
             if attr.object_id == player_name_id {
                 let act_id = attr.actor_id;
-                if let Attribute::String(name) = attr.attribute {
+                if let Attribute::String(name) = &attr.attribute {
                     // Fill in the name of the latest entry with the same
                     // actor id that either has the same name or no name.
                     let entry = actor_pings.iter().rev().rposition(|x| {
-                        x.actor_id == act_id && x.name.as_ref().map_or(true, |n| n == &name)
+                        x.actor_id == act_id && x.name.as_ref().map_or(true, |n| n == name)
                     });
                     if let Some(position) = entry {
-                        actor_pings[position].name.replace(name);
+                        actor_pings[position].name.replace(name.clone());
                     } else {
                         actor_pings.push(PlayerPings {
                             actor_id: attr.actor_id,
-                            name: Some(name),
+                            name: Some(name.clone()),
                             pings: vec![],
                         });
                     }
@@ -0,0 +1,226 @@
+use crate::network::attributes::Attribute;
+use crate::network::{normalize_object, ActorId, Frame, NewActor, ObjectId, UpdatedAttribute};
+use crate::object_index::ObjectIndex;
+use fnv::FnvHashMap;
+
+/// The latest known value of every attribute that has been replicated for an actor
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorState {
+    attributes: FnvHashMap<ObjectId, Attribute>,
+    object_id: ObjectId,
+    name_id: Option<i32>,
+}
+
+impl ActorState {
+    fn new(new_actor: &NewActor) -> Self {
+        ActorState {
+            attributes: FnvHashMap::default(),
+            object_id: new_actor.object_id,
+            name_id: new_actor.name_id,
+        }
+    }
+
+    /// The object id of the actor's type
+    pub fn object_id(&self) -> ObjectId {
+        self.object_id
+    }
+
+    /// The normalized name of the actor's type. Level specific objects are prefixed with the map
+    /// and numbered per instance, like
+    /// `stadium_foggy_p.TheWorld:PersistentLevel.VehiclePickup_Boost_TA_30`, so they are
+    /// normalized to the name shared by every instance:
+    /// `TheWorld:PersistentLevel.VehiclePickup_Boost_TA`. Other names are returned as is.
+    pub fn object_name<'a>(&self, objects: &'a ObjectIndex) -> Option<&'a str> {
+        objects.get_name(self.object_id).map(normalize_object)
+    }
+
+    /// The name id of the actor, if the replay tracks actor names
+    pub fn name_id(&self) -> Option<i32> {
+        self.name_id
+    }
+
+    /// The latest value of the attribute with the given object id
+    pub fn attribute(&self, object_id: ObjectId) -> Option<&Attribute> {
+        self.attributes.get(&object_id)
+    }
+
+    /// All the attributes that have been replicated for the actor
+    pub fn attributes(&self) -> impl Iterator<Item = (ObjectId, &Attribute)> {
+        self.attributes.iter().map(|(k, v)| (*k, v))
+    }
+}
+
+/// Reconstructs the state of every live actor by applying network frames in order.
+///
+/// Within a frame, deleted actors are removed first, then new actors are created, and finally
+/// attribute updates are applied. Keyframes periodically re-send new actors that already exist;
+/// when the actor's type is unchanged, the existing state is kept. Updates for actors that are not
+/// alive are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ActorStateModeler {
+    actor_states: FnvHashMap<ActorId, ActorState>,
+    actor_ids_by_type: FnvHashMap<ObjectId, Vec<ActorId>>,
+}
+
+impl ActorStateModeler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies all the actor changes in the frame
+    pub fn process_frame(&mut self, frame: &Frame) {
+        for actor_id in &frame.deleted_actors {
+            self.delete_actor(*actor_id);
+        }
+
+        for new_actor in &frame.new_actors {
+            self.new_actor(new_actor);
+        }
+
+        for update in &frame.updated_actors {
+            self.update_attribute(update);
+        }
+    }
+
+    /// The state of the actor with the given id, if it is alive
+    pub fn get(&self, actor_id: ActorId) -> Option<&ActorState> {
+        self.actor_states.get(&actor_id)
+    }
+
+    /// Iterates over every live actor
+    pub fn actors(&self) -> impl Iterator<Item = (ActorId, &ActorState)> {
+        self.actor_states.iter().map(|(k, v)| (*k, v))
+    }
+
+    /// The ids of the live actors of the given type in the order that they were created
+    pub fn actor_ids_by_type(&self, object_id: ObjectId) -> &[ActorId] {
+        self.actor_ids_by_type
+            .get(&object_id)
+            .map(|x| x.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn new_actor(&mut self, new_actor: &NewActor) {
+        if let Some(state) = self.actor_states.get(&new_actor.actor_id) {
+            if state.object_id == new_actor.object_id {
+                return;
+            }
+
+            self.delete_actor(new_actor.actor_id);
+        }
+
+        self.actor_states
+            .insert(new_actor.actor_id, ActorState::new(new_actor));
+        self.actor_ids_by_type
+            .entry(new_actor.object_id)
+            .or_default()
+            .push(new_actor.actor_id);
+    }
+
+    pub(crate) fn update_attribute(&mut self, update: &UpdatedAttribute) {
+        if let Some(state) = self.actor_states.get_mut(&update.actor_id) {
+            state
+                .attributes
+                .insert(update.object_id, update.attribute.clone());
+        }
+    }
+
+    pub(crate) fn delete_actor(&mut self, actor_id: ActorId) -> Option<ActorState> {
+        let state = self.actor_states.remove(&actor_id)?;
+        if let Some(ids) = self.actor_ids_by_type.get_mut(&state.object_id) {
+            ids.retain(|x| *x != actor_id);
+        }
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{StreamId, Trajectory};
+
+    fn new_actor(actor_id: i32, object_id: i32) -> NewActor {
+        NewActor {
+            actor_id: ActorId(actor_id),
+            name_id: None,
+            object_id: ObjectId(object_id),
+            initial_trajectory: Trajectory {
+                location: None,
+                rotation: None,
+            },
+        }
+    }
+
+    fn update(actor_id: i32, value: u8) -> UpdatedAttribute {
+        UpdatedAttribute {
+            actor_id: ActorId(actor_id),
+            stream_id: StreamId(0),
+            object_id: ObjectId(5),
+            attribute: Attribute::Byte(value),
+        }
+    }
+
+    fn frame(
+        new_actors: Vec<NewActor>,
+        deleted_actors: Vec<ActorId>,
+        updated_actors: Vec<UpdatedAttribute>,
+    ) -> Frame {
+        Frame {
+            time: 0.0,
+            delta: 0.0,
+            new_actors,
+            deleted_actors,
+            updated_actors,
+        }
+    }
+
+    #[test]
+    fn test_keyframe_respawn_keeps_state() {
+        let mut modeler = ActorStateModeler::new();
+        modeler.process_frame(&frame(vec![new_actor(1, 2)], vec![], vec![update(1, 10)]));
+        modeler.process_frame(&frame(vec![new_actor(1, 2)], vec![], vec![update(3, 1)]));
+
+        let state = modeler.get(ActorId(1)).unwrap();
+        assert_eq!(state.attribute(ObjectId(5)), Some(&Attribute::Byte(10)));
+        assert_eq!(modeler.actor_ids_by_type(ObjectId(2)), &[ActorId(1)]);
+    }
+
+    #[test]
+    fn test_actor_replaced_and_deleted() {
+        let mut modeler = ActorStateModeler::new();
+        modeler.process_frame(&frame(vec![new_actor(1, 2)], vec![], vec![update(1, 10)]));
+        modeler.process_frame(&frame(vec![new_actor(1, 3)], vec![], vec![]));
+
+        let state = modeler.get(ActorId(1)).unwrap();
+        assert_eq!(state.object_id(), ObjectId(3));
+        assert_eq!(state.attribute(ObjectId(5)), None);
+        assert!(modeler.actor_ids_by_type(ObjectId(2)).is_empty());
+
+        modeler.process_frame(&frame(vec![], vec![ActorId(1)], vec![]));
+        assert!(modeler.get(ActorId(1)).is_none());
+        assert_eq!(modeler.actors().count(), 0);
+    }
+
+    #[test]
+    fn test_object_name() {
+        let objects = ObjectIndex::new(&[
+            String::from("Archetypes.Car.Car_Default"),
+            String::from("stadium_foggy_p.TheWorld:PersistentLevel.VehiclePickup_Boost_TA_30"),
+        ]);
+
+        let mut modeler = ActorStateModeler::new();
+        modeler.process_frame(&frame(
+            vec![new_actor(1, 0), new_actor(2, 1), new_actor(3, 2)],
+            vec![],
+            vec![],
+        ));
+
+        let name = |x| modeler.get(ActorId(x)).unwrap().object_name(&objects);
+        assert_eq!(name(1), Some("Archetypes.Car.Car_Default"));
+        assert_eq!(
+            name(2),
+            Some("TheWorld:PersistentLevel.VehiclePickup_Boost_TA")
+        );
+        assert_eq!(name(3), None);
+    }
+}
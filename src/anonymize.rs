@@ -0,0 +1,286 @@
+//! Replaces the names and online ids of players with pseudonyms so that a replay can be shared
+//! without identifying who played in it.
+//!
+//! Players are numbered in the order that they are first seen: the header's "PlayerStats" and then
+//! the network data. A player's name and online id are linked whenever they are replicated by the
+//! same player replication info actor (or reservation), so a player is given the same number
+//! wherever their name or id appears.
+
+use crate::models::{HeaderProp, Replay};
+use crate::network::attributes::{Attribute, Ps4Id, PsyNetId, RemoteId, SwitchId, UniqueId};
+use crate::network::ActorId;
+use crate::object_index::ObjectIndex;
+use crate::processor::{PLAYER_NAME_KEY, UNIQUE_ID_KEY};
+use std::collections::HashMap;
+
+const REMOTE_USER_DATA_KEY: &str = "Engine.PlayerReplicationInfo:RemoteUserData";
+
+/// Debug info recorded by BakkesMod that is keyed by a player's platform and online id, like
+/// "MMR:Steam|76561198034699794|0:PRE"
+const MMR_DEBUG_PREFIX: &str = "MMR:";
+
+#[derive(Debug, Default)]
+struct Pseudonyms {
+    names: HashMap<String, usize>,
+    ids: HashMap<String, usize>,
+    count: usize,
+}
+
+impl Pseudonyms {
+    /// Returns the number of the player with the name and online id, linking the two if the
+    /// player was only known by one of them
+    fn player(&mut self, name: Option<&str>, id: Option<String>) -> usize {
+        let known = name
+            .and_then(|x| self.names.get(x))
+            .or_else(|| id.as_ref().and_then(|x| self.ids.get(x)))
+            .copied();
+
+        let number = match known {
+            Some(x) => x,
+            None => {
+                self.count += 1;
+                self.count
+            }
+        };
+
+        if let Some(name) = name {
+            self.names.entry(String::from(name)).or_insert(number);
+        }
+
+        if let Some(id) = id {
+            self.ids.entry(id).or_insert(number);
+        }
+
+        number
+    }
+
+    fn name(&mut self, name: &str) -> String {
+        pseudonym(self.player(Some(name), None))
+    }
+
+    /// The pseudonym of the online id in the same format: a decimal number, or for Epic ids, 32
+    /// hex digits
+    fn id_text(&mut self, id: &str) -> String {
+        let number = self.player(None, Some(String::from(id)));
+        if id.bytes().all(|x| x.is_ascii_digit()) {
+            number.to_string()
+        } else {
+            format!("{:032x}", number)
+        }
+    }
+
+    fn remote_id(&mut self, remote: &RemoteId) -> RemoteId {
+        // PlayStation ids also hold the player's name, which may be all there is to go by
+        let name = match remote {
+            RemoteId::PlayStation(x) => Some(x.name.as_str()).filter(|x| !x.is_empty()),
+            _ => None,
+        };
+
+        let id = online_id(remote);
+        if name.is_none() && id.is_none() {
+            return remote.clone();
+        }
+
+        let number = self.player(name, id);
+        let id = number as u64;
+        match remote {
+            RemoteId::PlayStation(x) => RemoteId::PlayStation(Ps4Id {
+                online_id: if x.online_id == 0 { 0 } else { id },
+                name: pseudonym(number),
+                unknown1: vec![0; x.unknown1.len()],
+            }),
+            RemoteId::PsyNet(x) => RemoteId::PsyNet(PsyNetId {
+                online_id: id,
+                unknown1: vec![0; x.unknown1.len()],
+            }),
+            RemoteId::Switch(x) => RemoteId::Switch(SwitchId {
+                online_id: id,
+                unknown1: vec![0; x.unknown1.len()],
+            }),
+            RemoteId::Steam(_) => RemoteId::Steam(id),
+            RemoteId::Xbox(_) => RemoteId::Xbox(id),
+            RemoteId::QQ(_) => RemoteId::QQ(id),
+            RemoteId::Epic(_) => RemoteId::Epic(format!("{:032x}", number)),
+            RemoteId::SplitScreen(x) => RemoteId::SplitScreen(*x),
+        }
+    }
+
+    fn unique_id(&mut self, unique_id: &UniqueId) -> UniqueId {
+        UniqueId {
+            remote_id: self.remote_id(&unique_id.remote_id),
+            ..unique_id.clone()
+        }
+    }
+
+    /// Replaces every known player name within the text, longest names first so that a name
+    /// containing another name is replaced whole
+    fn replace_names(&self, text: &str) -> String {
+        let mut names: Vec<(&String, &usize)> =
+            self.names.iter().filter(|(x, _)| !x.is_empty()).collect();
+        names.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(b.0)));
+
+        let mut result = String::from(text);
+        for (name, number) in names {
+            result = result.replace(name.as_str(), &pseudonym(*number));
+        }
+        result
+    }
+}
+
+fn pseudonym(number: usize) -> String {
+    format!("Player {}", number)
+}
+
+/// The text form of an online id as written in the header and debug info. Bots and split screen
+/// players don't have one.
+fn online_id(remote: &RemoteId) -> Option<String> {
+    let id = match remote {
+        RemoteId::PlayStation(x) => x.online_id,
+        RemoteId::PsyNet(x) => x.online_id,
+        RemoteId::Switch(x) => x.online_id,
+        RemoteId::Steam(x) | RemoteId::Xbox(x) | RemoteId::QQ(x) => *x,
+        RemoteId::Epic(x) => return Some(x.clone()).filter(|x| !x.is_empty()),
+        RemoteId::SplitScreen(_) => return None,
+    };
+
+    Some(id).filter(|x| *x != 0).map(|x| x.to_string())
+}
+
+fn header_text(prop: &HeaderProp) -> Option<&str> {
+    match prop {
+        HeaderProp::Str(x) | HeaderProp::Name(x) => Some(x.as_str()),
+        _ => None,
+    }
+}
+
+fn set_header_text(prop: &mut HeaderProp, text: String) {
+    match prop {
+        HeaderProp::Str(x) | HeaderProp::Name(x) => *x = text,
+        _ => {}
+    }
+}
+
+pub(crate) fn anonymize(replay: &Replay) -> Replay {
+    let mut pseudonyms = Pseudonyms::default();
+    for entry in replay.player_stats() {
+        let id = entry.online_id.filter(|x| *x != 0).map(|x| x.to_string());
+        pseudonyms.player(entry.name.as_deref(), id);
+    }
+
+    let index = ObjectIndex::new(&replay.objects);
+    let name_key = index.get_object_id(PLAYER_NAME_KEY);
+    let id_key = index.get_object_id(UNIQUE_ID_KEY);
+    let user_data_key = index.get_object_id(REMOTE_USER_DATA_KEY);
+
+    // Link the names and ids replicated by each player replication info actor
+    let mut names: HashMap<ActorId, &str> = HashMap::new();
+    let mut ids: HashMap<ActorId, String> = HashMap::new();
+    for frame in replay.frames_or_empty() {
+        for actor_id in &frame.deleted_actors {
+            names.remove(actor_id);
+            ids.remove(actor_id);
+        }
+
+        for update in &frame.updated_actors {
+            match &update.attribute {
+                Attribute::String(x) if Some(update.object_id) == name_key => {
+                    names.insert(update.actor_id, x);
+                }
+                Attribute::UniqueId(x) if Some(update.object_id) == id_key => {
+                    match online_id(&x.remote_id) {
+                        Some(id) => ids.insert(update.actor_id, id),
+                        None => ids.remove(&update.actor_id),
+                    };
+                }
+                Attribute::Reservation(x) => {
+                    pseudonyms.player(x.name.as_deref(), online_id(&x.unique_id.remote_id));
+                    continue;
+                }
+                _ => continue,
+            }
+
+            let name = names.get(&update.actor_id).copied();
+            pseudonyms.player(name, ids.get(&update.actor_id).cloned());
+        }
+    }
+
+    let mut result = replay.clone();
+    // The undecoded network data holds the names and ids that are scrubbed below
+    result.network_data = None;
+    for (key, prop) in &mut result.properties {
+        match key.as_str() {
+            "PlayerName" => {
+                if let Some(name) = header_text(prop).map(|x| pseudonyms.name(x)) {
+                    set_header_text(prop, name);
+                }
+            }
+            "ReplayName" => {
+                if let Some(name) = header_text(prop).map(|x| pseudonyms.replace_names(x)) {
+                    set_header_text(prop, name);
+                }
+            }
+            "PlayerStats" | "Goals" => {
+                if let HeaderProp::Array(entries) = prop {
+                    for (field, value) in entries.iter_mut().flatten() {
+                        match field.as_str() {
+                            "Name" | "PlayerName" => {
+                                if let Some(name) = header_text(value).map(|x| pseudonyms.name(x)) {
+                                    set_header_text(value, name);
+                                }
+                            }
+                            "OnlineID" | "PartyLeader" => {
+                                if let HeaderProp::QWord(id) = value {
+                                    if *id != 0 {
+                                        *id = pseudonyms.player(None, Some(id.to_string())) as u64;
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    result.map_frames(|frame| {
+        for update in &mut frame.updated_actors {
+            match &mut update.attribute {
+                Attribute::String(x) if Some(update.object_id) == name_key => {
+                    *x = pseudonyms.name(x);
+                }
+                Attribute::String(x)
+                    if Some(update.object_id) == user_data_key && !x.is_empty() =>
+                {
+                    *x = pseudonyms.id_text(x);
+                }
+                Attribute::UniqueId(x) => **x = pseudonyms.unique_id(x),
+                Attribute::PartyLeader(Some(x)) => **x = pseudonyms.unique_id(x),
+                Attribute::Reservation(x) => {
+                    x.unique_id = pseudonyms.unique_id(&x.unique_id);
+                    if let Some(name) = x.name.as_mut() {
+                        *name = pseudonyms.name(name);
+                    }
+                }
+                Attribute::PrivateMatch(x) => {
+                    x.game_name.clear();
+                    x.password.clear();
+                }
+                _ => {}
+            }
+        }
+    });
+
+    for info in &mut result.debug_info {
+        if info.user.starts_with(MMR_DEBUG_PREFIX) {
+            let mut parts: Vec<String> = info.user.split('|').map(String::from).collect();
+            if let Some(id) = parts.get_mut(1) {
+                *id = pseudonyms.id_text(id);
+            }
+            info.user = parts.join("|");
+        }
+    }
+
+    result
+}
@@ -1,10 +1,13 @@
-use bitter::{BitReader, LittleEndianReader};
+use bitter::BitReader;
 
 #[inline]
 pub(crate) const fn bit_width(input: u64) -> u32 {
     (core::mem::size_of::<u64>() as u32) * 8 - input.leading_zeros()
 }
 
+/// Replay specific reads on top of bitter's [`BitReader`]. The network data is decoded through
+/// any `BitReader`, so alternative bit readers can be swapped in for experiments and benchmarks
+/// without touching the decoding logic. `LittleEndianReader` is what the parser uses.
 pub(crate) trait RlBits {
     fn peek_and_consume(&mut self, bits: u32) -> u64;
     fn peek_bits_max_computed(&mut self, bits: u32, max: u64) -> u64;
@@ -16,7 +19,7 @@ pub(crate) trait RlBits {
         F: FnMut(&mut Self) -> Option<T>;
 }
 
-impl<'a> RlBits for LittleEndianReader<'a> {
+impl<R: BitReader> RlBits for R {
     #[inline]
     fn read_u64(&mut self) -> Option<u64> {
         let mut out = [0u8; 8];
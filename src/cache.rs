@@ -0,0 +1,842 @@
+//! A compact binary format for caching fully decoded replays on disk, so that loading a replay
+//! again skips decoding the network data.
+//!
+//! The format is specific to boxcars and is not meant for interchange: unlike JSON it can be read
+//! back into a [`Replay`], and unlike general purpose binary formats it is versioned. A cache
+//! starts with the [`MAGIC`] bytes and the [`FORMAT_VERSION`] it was written with, and
+//! [`deserialize`] refuses caches of any other version with
+//! [`CacheError::VersionMismatch`], in which case the replay should be decoded again from its
+//! original file. The version is bumped whenever the layout of the decoded models changes.
+//!
+//! Integers are written as variable length (zigzag for signed integers) and floats as their little
+//! endian bits, so the round trip is exact. Enum variants are written as a tag byte; attributes use
+//! their [`AttributeTag`] as the tag. On the sample replays, a cache is about a sixth of the size
+//! of the replay's JSON (and about twice the size of the original replay file), and reading it back
+//! is three to four times faster than decoding the replay.
+//!
+//! ```rust
+//! let data = include_bytes!("../assets/replays/good/rumble.replay");
+//! let replay = boxcars::ParserBuilder::new(&data[..])
+//!     .must_parse_network_data()
+//!     .parse()
+//!     .unwrap();
+//!
+//! let cached = boxcars::cache::serialize(&replay);
+//! assert_eq!(boxcars::cache::deserialize(&cached).unwrap(), replay);
+//! ```
+
+use crate::errors::CacheError;
+use crate::models::*;
+use crate::network::attributes::*;
+use crate::network::*;
+use std::convert::TryFrom;
+use std::str;
+
+/// The bytes that every cache starts with
+pub const MAGIC: [u8; 4] = *b"BXRC";
+
+/// The version of the format written by [`serialize`]. Caches of other versions are not read.
+pub const FORMAT_VERSION: u8 = 2;
+
+/// Encodes the replay into the cache format
+pub fn serialize(replay: &Replay) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&MAGIC);
+    out.push(FORMAT_VERSION);
+    replay.encode(&mut out);
+    out
+}
+
+/// Decodes a replay from data written by [`serialize`]
+pub fn deserialize(data: &[u8]) -> Result<Replay, CacheError> {
+    let mut reader = Reader { data };
+    if reader.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+        return Err(CacheError::InvalidMagic);
+    }
+
+    let version = u8::decode(&mut reader)?;
+    if version != FORMAT_VERSION {
+        return Err(CacheError::VersionMismatch(version, FORMAT_VERSION));
+    }
+
+    let replay = Replay::decode(&mut reader)?;
+    if !reader.data.is_empty() {
+        return Err(CacheError::TrailingData(reader.data.len()));
+    }
+
+    Ok(replay)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, size: usize) -> Result<&'a [u8], CacheError> {
+        if size > self.data.len() {
+            return Err(CacheError::UnexpectedEof);
+        }
+
+        let (head, tail) = self.data.split_at(size);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, CacheError> {
+        self.take(1).map(|x| x[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, CacheError> {
+        let mut result = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            result |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+
+        Err(CacheError::InvalidVarint)
+    }
+
+    /// The length of a sequence. Each element takes at least a byte, so a length that exceeds
+    /// the remaining data is rejected before anything is allocated for it.
+    fn len(&mut self) -> Result<usize, CacheError> {
+        let len = self.varint()?;
+        if len > self.data.len() as u64 {
+            return Err(CacheError::UnexpectedEof);
+        }
+
+        Ok(len as usize)
+    }
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+trait Decode: Sized {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError>;
+}
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        match reader.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            x => Err(CacheError::InvalidTag("bool", x)),
+        }
+    }
+}
+
+impl Encode for u8 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self);
+    }
+}
+
+impl Decode for u8 {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        reader.byte()
+    }
+}
+
+impl Encode for i8 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl Decode for i8 {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        reader.byte().map(|x| x as i8)
+    }
+}
+
+macro_rules! varint_codec {
+    ($($ty:ty),*) => {$(
+        impl Encode for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                write_varint(u64::from(*self), out);
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+                let value = reader.varint()?;
+                <$ty>::try_from(value).map_err(|_| CacheError::InvalidVarint)
+            }
+        }
+    )*};
+}
+
+varint_codec!(u16, u32, u64);
+
+macro_rules! zigzag_codec {
+    ($($ty:ty),*) => {$(
+        impl Encode for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                write_varint(zigzag(i64::from(*self)), out);
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+                let value = unzigzag(reader.varint()?);
+                <$ty>::try_from(value).map_err(|_| CacheError::InvalidVarint)
+            }
+        }
+    )*};
+}
+
+zigzag_codec!(i32, i64);
+
+impl Encode for f32 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_bits().to_le_bytes());
+    }
+}
+
+impl Decode for f32 {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(reader.take(4)?);
+        Ok(f32::from_bits(u32::from_le_bytes(bytes)))
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(self.len() as u64, out);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        let len = reader.len()?;
+        let data = reader.take(len)?;
+        str::from_utf8(data)
+            .map(String::from)
+            .map_err(CacheError::Utf8Error)
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(self.len() as u64, out);
+        for x in self {
+            x.encode(out);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        let len = reader.len()?;
+        let mut result = Vec::with_capacity(len);
+        for _ in 0..len {
+            result.push(T::decode(reader)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(0),
+            Some(x) => {
+                out.push(1);
+                x.encode(out);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        match reader.byte()? {
+            0 => Ok(None),
+            1 => T::decode(reader).map(Some),
+            x => Err(CacheError::InvalidTag("Option", x)),
+        }
+    }
+}
+
+impl<T: Encode> Encode for Box<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_ref().encode(out);
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        T::decode(reader).map(Box::new)
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for (A, B) {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        Ok((A::decode(reader)?, B::decode(reader)?))
+    }
+}
+
+macro_rules! id_codec {
+    ($($ty:ident),*) => {$(
+        impl Encode for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                self.0.encode(out);
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+                i32::decode(reader).map($ty)
+            }
+        }
+    )*};
+}
+
+id_codec!(ActorId, ObjectId, StreamId);
+
+/// Encodes the fields of a struct in the order listed. Decoding fails to compile if a field is
+/// missing from the list.
+macro_rules! struct_codec {
+    ($($ty:ident { $($field:ident),* $(,)? })*) => {$(
+        impl Encode for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                $(self.$field.encode(out);)*
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+                Ok($ty {
+                    $($field: Decode::decode(reader)?,)*
+                })
+            }
+        }
+    )*};
+}
+
+struct_codec! {
+    Replay {
+        header_size,
+        header_crc,
+        major_version,
+        minor_version,
+        net_version,
+        game_type,
+        properties,
+        content_size,
+        content_crc,
+        network_frames,
+        network_data,
+        levels,
+        keyframes,
+        debug_info,
+        tick_marks,
+        packages,
+        objects,
+        names,
+        class_indices,
+        net_cache,
+    }
+    NetworkFrames { frames }
+    TickMark { description, frame }
+    KeyFrame { time, frame, position }
+    DebugInfo { frame, user, text }
+    ClassIndex { class, index }
+    CacheProp { object_ind, stream_id }
+    ClassNetCache { object_ind, parent_id, cache_id, properties }
+    Frame { time, delta, new_actors, deleted_actors, updated_actors }
+    NewActor { actor_id, name_id, object_id, initial_trajectory }
+    Trajectory { location, rotation }
+    UpdatedAttribute { actor_id, stream_id, object_id, attribute }
+    Vector3f { x, y, z }
+    Vector3i { x, y, z }
+    Quaternion { x, y, z, w }
+    Rotation { yaw, pitch, roll }
+    CustomAttribute { name, value }
+    ActiveActor { active, actor }
+    CamSettings { fov, height, angle, distance, stiffness, swivel, transition }
+    ClubColors { blue_flag, blue_color, orange_flag, orange_color }
+    AppliedDamage { id, position, damage_index, total_damage }
+    DamageState { tile_state, damaged, offender, ball_position, direct_hit, unknown1 }
+    Demolish { attacker_flag, attacker, victim_flag, victim, attack_velocity, victim_velocity }
+    DemolishFx {
+        custom_demo_flag,
+        custom_demo_id,
+        attacker_flag,
+        attacker,
+        victim_flag,
+        victim,
+        attack_velocity,
+        victim_velocity,
+    }
+    Explosion { flag, actor, location }
+    ExtendedExplosion { explosion, unknown1, secondary_actor }
+    Loadout {
+        version,
+        body,
+        decal,
+        wheels,
+        rocket_trail,
+        antenna,
+        topper,
+        unknown1,
+        unknown2,
+        engine_audio,
+        trail,
+        goal_explosion,
+        banner,
+        product_id,
+    }
+    TeamLoadout { blue, orange }
+    StatEvent { unknown1, object_id }
+    MusicStinger { flag, cue, trigger }
+    Pickup { instigator, picked_up }
+    PickupNew { instigator, picked_up }
+    Welded { active, actor, offset, mass, rotation }
+    TeamPaint { team, primary_color, accent_color, primary_finish, accent_finish }
+    RigidBody { sleeping, location, rotation, linear_velocity, angular_velocity }
+    UniqueId { system_id, remote_id, local_id }
+    PsyNetId { online_id, unknown1 }
+    SwitchId { online_id, unknown1 }
+    Ps4Id { online_id, name, unknown1 }
+    Reservation { number, unique_id, name, unknown1, unknown2, unknown3 }
+    PrivateMatchSettings { mutators, joinable_by, max_players, game_name, password, flag }
+    Product { unknown, object_ind, value }
+    LoadoutsOnline { blue, orange, unknown1, unknown2 }
+    RepStatTitle { unknown, name, unknown2, index, value }
+    PickupInfo { active, actor, items_are_preview, unknown, unknown2 }
+    Impulse { compressed_rotation, speed }
+}
+
+impl Encode for HeaderProp {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            HeaderProp::Array(x) => {
+                out.push(0);
+                x.encode(out);
+            }
+            HeaderProp::Bool(x) => {
+                out.push(1);
+                x.encode(out);
+            }
+            HeaderProp::Byte { kind, value } => {
+                out.push(2);
+                kind.encode(out);
+                value.encode(out);
+            }
+            HeaderProp::Float(x) => {
+                out.push(3);
+                x.encode(out);
+            }
+            HeaderProp::Int(x) => {
+                out.push(4);
+                x.encode(out);
+            }
+            HeaderProp::Name(x) => {
+                out.push(5);
+                x.encode(out);
+            }
+            HeaderProp::QWord(x) => {
+                out.push(6);
+                x.encode(out);
+            }
+            HeaderProp::Str(x) => {
+                out.push(7);
+                x.encode(out);
+            }
+        }
+    }
+}
+
+impl Decode for HeaderProp {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        match reader.byte()? {
+            0 => Decode::decode(reader).map(HeaderProp::Array),
+            1 => Decode::decode(reader).map(HeaderProp::Bool),
+            2 => Ok(HeaderProp::Byte {
+                kind: Decode::decode(reader)?,
+                value: Decode::decode(reader)?,
+            }),
+            3 => Decode::decode(reader).map(HeaderProp::Float),
+            4 => Decode::decode(reader).map(HeaderProp::Int),
+            5 => Decode::decode(reader).map(HeaderProp::Name),
+            6 => Decode::decode(reader).map(HeaderProp::QWord),
+            7 => Decode::decode(reader).map(HeaderProp::Str),
+            x => Err(CacheError::InvalidTag("HeaderProp", x)),
+        }
+    }
+}
+
+impl Encode for RemoteId {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            RemoteId::PlayStation(x) => {
+                out.push(0);
+                x.encode(out);
+            }
+            RemoteId::PsyNet(x) => {
+                out.push(1);
+                x.encode(out);
+            }
+            RemoteId::SplitScreen(x) => {
+                out.push(2);
+                x.encode(out);
+            }
+            RemoteId::Steam(x) => {
+                out.push(3);
+                x.encode(out);
+            }
+            RemoteId::Switch(x) => {
+                out.push(4);
+                x.encode(out);
+            }
+            RemoteId::Xbox(x) => {
+                out.push(5);
+                x.encode(out);
+            }
+            RemoteId::QQ(x) => {
+                out.push(6);
+                x.encode(out);
+            }
+            RemoteId::Epic(x) => {
+                out.push(7);
+                x.encode(out);
+            }
+        }
+    }
+}
+
+impl Decode for RemoteId {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        match reader.byte()? {
+            0 => Decode::decode(reader).map(RemoteId::PlayStation),
+            1 => Decode::decode(reader).map(RemoteId::PsyNet),
+            2 => Decode::decode(reader).map(RemoteId::SplitScreen),
+            3 => Decode::decode(reader).map(RemoteId::Steam),
+            4 => Decode::decode(reader).map(RemoteId::Switch),
+            5 => Decode::decode(reader).map(RemoteId::Xbox),
+            6 => Decode::decode(reader).map(RemoteId::QQ),
+            7 => Decode::decode(reader).map(RemoteId::Epic),
+            x => Err(CacheError::InvalidTag("RemoteId", x)),
+        }
+    }
+}
+
+impl Encode for ProductValue {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            ProductValue::NoColor => out.push(0),
+            ProductValue::Absent => out.push(1),
+            ProductValue::OldColor(x) => {
+                out.push(2);
+                x.encode(out);
+            }
+            ProductValue::NewColor(x) => {
+                out.push(3);
+                x.encode(out);
+            }
+            ProductValue::OldPaint(x) => {
+                out.push(4);
+                x.encode(out);
+            }
+            ProductValue::NewPaint(x) => {
+                out.push(5);
+                x.encode(out);
+            }
+            ProductValue::Title(x) => {
+                out.push(6);
+                x.encode(out);
+            }
+            ProductValue::SpecialEdition(x) => {
+                out.push(7);
+                x.encode(out);
+            }
+            ProductValue::OldTeamEdition(x) => {
+                out.push(8);
+                x.encode(out);
+            }
+            ProductValue::NewTeamEdition(x) => {
+                out.push(9);
+                x.encode(out);
+            }
+        }
+    }
+}
+
+impl Decode for ProductValue {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        match reader.byte()? {
+            0 => Ok(ProductValue::NoColor),
+            1 => Ok(ProductValue::Absent),
+            2 => Decode::decode(reader).map(ProductValue::OldColor),
+            3 => Decode::decode(reader).map(ProductValue::NewColor),
+            4 => Decode::decode(reader).map(ProductValue::OldPaint),
+            5 => Decode::decode(reader).map(ProductValue::NewPaint),
+            6 => Decode::decode(reader).map(ProductValue::Title),
+            7 => Decode::decode(reader).map(ProductValue::SpecialEdition),
+            8 => Decode::decode(reader).map(ProductValue::OldTeamEdition),
+            9 => Decode::decode(reader).map(ProductValue::NewTeamEdition),
+            x => Err(CacheError::InvalidTag("ProductValue", x)),
+        }
+    }
+}
+
+impl Encode for Attribute {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.tag() as u8);
+        match self {
+            Attribute::Boolean(x) => x.encode(out),
+            Attribute::Byte(x) => x.encode(out),
+            Attribute::AppliedDamage(x) => x.encode(out),
+            Attribute::DamageState(x) => x.encode(out),
+            Attribute::CamSettings(x) => x.encode(out),
+            Attribute::ClubColors(x) => x.encode(out),
+            Attribute::Demolish(x) => x.encode(out),
+            Attribute::DemolishFx(x) => x.encode(out),
+            Attribute::Enum(x) => x.encode(out),
+            Attribute::Explosion(x) => x.encode(out),
+            Attribute::ExtendedExplosion(x) => x.encode(out),
+            Attribute::FlaggedByte(flag, x) => {
+                flag.encode(out);
+                x.encode(out);
+            }
+            Attribute::ActiveActor(x) => x.encode(out),
+            Attribute::Float(x) => x.encode(out),
+            Attribute::GameMode(a, b) => {
+                a.encode(out);
+                b.encode(out);
+            }
+            Attribute::Int(x) => x.encode(out),
+            Attribute::Int64(x) => x.encode(out),
+            Attribute::Loadout(x) => x.encode(out),
+            Attribute::TeamLoadout(x) => x.encode(out),
+            Attribute::Location(x) => x.encode(out),
+            Attribute::MusicStinger(x) => x.encode(out),
+            Attribute::PlayerHistoryKey(x) => x.encode(out),
+            Attribute::Pickup(x) => x.encode(out),
+            Attribute::PickupNew(x) => x.encode(out),
+            Attribute::QWord(x) => x.encode(out),
+            Attribute::Welded(x) => x.encode(out),
+            Attribute::Title(a, b, c, d, e, f, g, h) => {
+                a.encode(out);
+                b.encode(out);
+                c.encode(out);
+                d.encode(out);
+                e.encode(out);
+                f.encode(out);
+                g.encode(out);
+                h.encode(out);
+            }
+            Attribute::TeamPaint(x) => x.encode(out),
+            Attribute::RigidBody(x) => x.encode(out),
+            Attribute::String(x) => x.encode(out),
+            Attribute::UniqueId(x) => x.encode(out),
+            Attribute::Reservation(x) => x.encode(out),
+            Attribute::PartyLeader(x) => x.encode(out),
+            Attribute::PrivateMatch(x) => x.encode(out),
+            Attribute::LoadoutOnline(x) => x.encode(out),
+            Attribute::LoadoutsOnline(x) => x.encode(out),
+            Attribute::StatEvent(x) => x.encode(out),
+            Attribute::Rotation(x) => x.encode(out),
+            Attribute::RepStatTitle(x) => x.encode(out),
+            Attribute::PickupInfo(x) => x.encode(out),
+            Attribute::Impulse(x) => x.encode(out),
+            Attribute::Custom(x) => x.encode(out),
+        }
+    }
+}
+
+impl Decode for Attribute {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, CacheError> {
+        let r = reader;
+        let tag = r.byte()?;
+        let attribute = match tag {
+            x if x == AttributeTag::Boolean as u8 => Attribute::Boolean(Decode::decode(r)?),
+            x if x == AttributeTag::Byte as u8 => Attribute::Byte(Decode::decode(r)?),
+            x if x == AttributeTag::AppliedDamage as u8 => {
+                Attribute::AppliedDamage(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::DamageState as u8 => Attribute::DamageState(Decode::decode(r)?),
+            x if x == AttributeTag::CamSettings as u8 => Attribute::CamSettings(Decode::decode(r)?),
+            x if x == AttributeTag::ClubColors as u8 => Attribute::ClubColors(Decode::decode(r)?),
+            x if x == AttributeTag::Demolish as u8 => Attribute::Demolish(Decode::decode(r)?),
+            x if x == AttributeTag::DemolishFx as u8 => Attribute::DemolishFx(Decode::decode(r)?),
+            x if x == AttributeTag::Enum as u8 => Attribute::Enum(Decode::decode(r)?),
+            x if x == AttributeTag::Explosion as u8 => Attribute::Explosion(Decode::decode(r)?),
+            x if x == AttributeTag::ExtendedExplosion as u8 => {
+                Attribute::ExtendedExplosion(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::FlaggedByte as u8 => {
+                Attribute::FlaggedByte(Decode::decode(r)?, Decode::decode(r)?)
+            }
+            x if x == AttributeTag::ActiveActor as u8 => Attribute::ActiveActor(Decode::decode(r)?),
+            x if x == AttributeTag::Float as u8 => Attribute::Float(Decode::decode(r)?),
+            x if x == AttributeTag::GameMode as u8 => {
+                Attribute::GameMode(Decode::decode(r)?, Decode::decode(r)?)
+            }
+            x if x == AttributeTag::Int as u8 => Attribute::Int(Decode::decode(r)?),
+            x if x == AttributeTag::Int64 as u8 => Attribute::Int64(Decode::decode(r)?),
+            x if x == AttributeTag::Loadout as u8 => Attribute::Loadout(Decode::decode(r)?),
+            x if x == AttributeTag::TeamLoadout as u8 => Attribute::TeamLoadout(Decode::decode(r)?),
+            x if x == AttributeTag::Location as u8 => Attribute::Location(Decode::decode(r)?),
+            x if x == AttributeTag::MusicStinger as u8 => {
+                Attribute::MusicStinger(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::PlayerHistoryKey as u8 => {
+                Attribute::PlayerHistoryKey(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::Pickup as u8 => Attribute::Pickup(Decode::decode(r)?),
+            x if x == AttributeTag::PickupNew as u8 => Attribute::PickupNew(Decode::decode(r)?),
+            x if x == AttributeTag::QWord as u8 => Attribute::QWord(Decode::decode(r)?),
+            x if x == AttributeTag::Welded as u8 => Attribute::Welded(Decode::decode(r)?),
+            x if x == AttributeTag::Title as u8 => Attribute::Title(
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+                Decode::decode(r)?,
+            ),
+            x if x == AttributeTag::TeamPaint as u8 => Attribute::TeamPaint(Decode::decode(r)?),
+            x if x == AttributeTag::RigidBody as u8 => Attribute::RigidBody(Decode::decode(r)?),
+            x if x == AttributeTag::String as u8 => Attribute::String(Decode::decode(r)?),
+            x if x == AttributeTag::UniqueId as u8 => Attribute::UniqueId(Decode::decode(r)?),
+            x if x == AttributeTag::Reservation as u8 => Attribute::Reservation(Decode::decode(r)?),
+            x if x == AttributeTag::PartyLeader as u8 => Attribute::PartyLeader(Decode::decode(r)?),
+            x if x == AttributeTag::PrivateMatch as u8 => {
+                Attribute::PrivateMatch(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::LoadoutOnline as u8 => {
+                Attribute::LoadoutOnline(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::LoadoutsOnline as u8 => {
+                Attribute::LoadoutsOnline(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::StatEvent as u8 => Attribute::StatEvent(Decode::decode(r)?),
+            x if x == AttributeTag::Rotation as u8 => Attribute::Rotation(Decode::decode(r)?),
+            x if x == AttributeTag::RepStatTitle as u8 => {
+                Attribute::RepStatTitle(Decode::decode(r)?)
+            }
+            x if x == AttributeTag::PickupInfo as u8 => Attribute::PickupInfo(Decode::decode(r)?),
+            x if x == AttributeTag::Impulse as u8 => Attribute::Impulse(Decode::decode(r)?),
+            x if x == AttributeTag::Custom as u8 => Attribute::Custom(Decode::decode(r)?),
+            x => return Err(CacheError::InvalidTag("Attribute", x)),
+        };
+
+        Ok(attribute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint_round_trip() {
+        for value in [
+            0i64,
+            1,
+            -1,
+            63,
+            -64,
+            64,
+            i64::from(i32::MAX),
+            i64::MIN,
+            i64::MAX,
+        ]
+        .iter()
+        {
+            let mut out = Vec::new();
+            value.encode(&mut out);
+            let mut reader = Reader { data: &out };
+            assert_eq!(i64::decode(&mut reader).unwrap(), *value);
+            assert!(reader.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_caches() {
+        let replay = Replay {
+            header_size: 0,
+            header_crc: 0,
+            major_version: 868,
+            minor_version: 32,
+            net_version: Some(10),
+            game_type: String::from("TAGame.Replay_Soccar_TA"),
+            properties: vec![(String::from("TeamSize"), HeaderProp::Int(3))],
+            content_size: 0,
+            content_crc: 0,
+            network_frames: None,
+            network_data: None,
+            levels: Vec::new(),
+            keyframes: Vec::new(),
+            debug_info: Vec::new(),
+            tick_marks: Vec::new(),
+            packages: Vec::new(),
+            objects: Vec::new(),
+            names: Vec::new(),
+            class_indices: Vec::new(),
+            net_cache: Vec::new(),
+        };
+
+        let data = serialize(&replay);
+        assert_eq!(deserialize(&data), Ok(replay));
+        assert_eq!(deserialize(b"JSON{}"), Err(CacheError::InvalidMagic));
+
+        let mut newer = data.clone();
+        newer[MAGIC.len()] = FORMAT_VERSION + 1;
+        assert_eq!(
+            deserialize(&newer),
+            Err(CacheError::VersionMismatch(
+                FORMAT_VERSION + 1,
+                FORMAT_VERSION
+            ))
+        );
+
+        assert_eq!(
+            deserialize(&data[..data.len() - 1]),
+            Err(CacheError::UnexpectedEof)
+        );
+
+        let mut trailing = data;
+        trailing.push(0);
+        assert_eq!(deserialize(&trailing), Err(CacheError::TrailingData(1)));
+    }
+}
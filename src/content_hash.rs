@@ -0,0 +1,429 @@
+//! A serde serializer that feeds a canonical encoding of a value into a 64 bit FNV-1a hash.
+//!
+//! FNV-1a is used as its output is fixed by its specification, unlike the standard library's
+//! `DefaultHasher`, which is randomly seeded and may change between releases. Every value is
+//! prefixed with a tag for its shape so that, for instance, an empty sequence and a `None` hash
+//! differently. Struct field and enum variant names are hashed, so the hash changes whenever the
+//! decoded models change. Map entries are hashed independently and combined with a commutative
+//! operation, so the hash does not depend on the iteration order of maps.
+
+use fnv::FnvHasher;
+use serde::ser::{self, Serialize};
+use std::fmt;
+use std::hash::Hasher;
+
+/// Hashes the serialized representation of the value
+pub(crate) fn hash<T: Serialize + ?Sized>(value: &T) -> u64 {
+    let mut hasher = ContentHasher::default();
+    match value.serialize(&mut hasher) {
+        Ok(()) => hasher.finish(),
+        Err(Never) => unreachable!(),
+    }
+}
+
+#[derive(Default)]
+struct ContentHasher {
+    hasher: FnvHasher,
+}
+
+impl ContentHasher {
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    fn tag(&mut self, tag: Tag) {
+        self.hasher.write_u8(tag as u8);
+    }
+
+    fn bytes(&mut self, data: &[u8]) {
+        self.hasher.write_u64(data.len() as u64);
+        self.hasher.write(data);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Tag {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+    Char,
+    Str,
+    Bytes,
+    None,
+    Some,
+    Unit,
+    Variant,
+    Seq,
+    Tuple,
+    Map,
+    Struct,
+    End,
+}
+
+/// Serializing into a hash can't fail
+#[derive(Debug)]
+pub(crate) struct Never;
+
+impl fmt::Display for Never {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "content hash error")
+    }
+}
+
+impl std::error::Error for Never {}
+
+impl ser::Error for Never {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        Never
+    }
+}
+
+macro_rules! hash_number {
+    ($name:ident, $ty:ty, $tag:expr) => {
+        fn $name(self, v: $ty) -> Result<(), Never> {
+            self.tag($tag);
+            self.hasher.write(&v.to_le_bytes());
+            Ok(())
+        }
+    };
+}
+
+impl<'a> ser::Serializer for &'a mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = MapHasher<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    hash_number!(serialize_i8, i8, Tag::I8);
+    hash_number!(serialize_i16, i16, Tag::I16);
+    hash_number!(serialize_i32, i32, Tag::I32);
+    hash_number!(serialize_i64, i64, Tag::I64);
+    hash_number!(serialize_i128, i128, Tag::I128);
+    hash_number!(serialize_u8, u8, Tag::U8);
+    hash_number!(serialize_u16, u16, Tag::U16);
+    hash_number!(serialize_u32, u32, Tag::U32);
+    hash_number!(serialize_u64, u64, Tag::U64);
+    hash_number!(serialize_u128, u128, Tag::U128);
+
+    fn serialize_bool(self, v: bool) -> Result<(), Never> {
+        self.tag(Tag::Bool);
+        self.hasher.write_u8(u8::from(v));
+        Ok(())
+    }
+
+    // Floats are hashed by their bits, so the hash does not depend on how they are formatted
+    fn serialize_f32(self, v: f32) -> Result<(), Never> {
+        self.tag(Tag::F32);
+        self.hasher.write_u32(v.to_bits());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Never> {
+        self.tag(Tag::F64);
+        self.hasher.write_u64(v.to_bits());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Never> {
+        self.tag(Tag::Char);
+        self.hasher.write_u32(u32::from(v));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Never> {
+        self.tag(Tag::Str);
+        self.bytes(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Never> {
+        self.tag(Tag::Bytes);
+        self.bytes(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Never> {
+        self.tag(Tag::None);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Never> {
+        self.tag(Tag::Some);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Never> {
+        self.tag(Tag::Unit);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Never> {
+        self.tag(Tag::Unit);
+        self.bytes(name.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Never> {
+        self.tag(Tag::Variant);
+        self.bytes(variant.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Never> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Never> {
+        self.tag(Tag::Variant);
+        self.bytes(variant.as_bytes());
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Never> {
+        self.tag(Tag::Seq);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Never> {
+        self.tag(Tag::Tuple);
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Never> {
+        self.tag(Tag::Tuple);
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Never> {
+        self.tag(Tag::Variant);
+        self.bytes(variant.as_bytes());
+        self.tag(Tag::Tuple);
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapHasher<'a>, Never> {
+        Ok(MapHasher {
+            parent: self,
+            entry: ContentHasher::default(),
+            sum: 0,
+            len: 0,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Never> {
+        self.tag(Tag::Struct);
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Never> {
+        self.tag(Tag::Variant);
+        self.bytes(variant.as_bytes());
+        self.tag(Tag::Struct);
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Never> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.tag(Tag::End);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Never> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.tag(Tag::End);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Never> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.tag(Tag::End);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Never> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.tag(Tag::End);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Never> {
+        self.bytes(key.as_bytes());
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.tag(Tag::End);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut ContentHasher {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Never> {
+        self.bytes(key.as_bytes());
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.tag(Tag::End);
+        Ok(())
+    }
+}
+
+/// Hashes every entry of a map on its own and sums the entry hashes
+pub(crate) struct MapHasher<'a> {
+    parent: &'a mut ContentHasher,
+    entry: ContentHasher,
+    sum: u64,
+    len: u64,
+}
+
+impl<'a> ser::SerializeMap for MapHasher<'a> {
+    type Ok = ();
+    type Error = Never;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Never> {
+        self.entry = ContentHasher::default();
+        key.serialize(&mut self.entry)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Never> {
+        value.serialize(&mut self.entry)?;
+        self.sum = self.sum.wrapping_add(self.entry.finish());
+        self.len += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Never> {
+        self.parent.tag(Tag::Map);
+        self.parent.hasher.write_u64(self.len);
+        self.parent.hasher.write_u64(self.sum);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_map_order_independent() {
+        let a: Vec<(u32, &str)> = (0..100).map(|x| (x, "value")).collect();
+        let mut b = a.clone();
+        b.reverse();
+
+        let a: HashMap<u32, &str> = a.into_iter().collect();
+        let b: HashMap<u32, &str> = b.into_iter().collect();
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn test_shapes_differ() {
+        let empty: Vec<u8> = Vec::new();
+        assert_ne!(hash(&None::<u8>), hash(&empty));
+        assert_ne!(hash(&Some(1u8)), hash(&1u8));
+        assert_ne!(hash(&1u8), hash(&1u16));
+        assert_ne!(hash(&("ab", "c")), hash(&("a", "bc")));
+        assert_ne!(
+            hash(&vec![vec![1u8], vec![]]),
+            hash(&vec![vec![], vec![1u8]])
+        );
+    }
+
+    #[test]
+    fn test_known_value() {
+        // Guards against the encoding changing unintentionally
+        assert_eq!(hash(&(1u8, "a", Some(0.5f32))), 14526075840883093830);
+        assert_ne!(hash(&0.0f32), hash(&-0.0f32));
+    }
+}
@@ -1,3 +1,5 @@
+use std::io::{self, Read};
+
 const fn gen_crc_table<const N: usize>(poly: u32) -> [[u32; 256]; N] {
     let mut table = [[0u32; 256]; N];
 
@@ -47,39 +49,155 @@ const CRC_TABLE: [[u32; 256]; 16] = gen_crc_table(0x04c1_1db7);
 /// This implementation is a slice by 16 from the unreal engine seen in Bakkes' CPPRP
 /// (https://github.com/Bakkes/CPPRP/blob/58fc19a972a7a0af059407982bdf553cfe091831/CPPRP/CRC.h#L245)
 pub fn calc_crc(data: &[u8]) -> u32 {
-    let mut crc = !(0xefcb_f201_u32.swap_bytes());
-    crc = data.chunks_exact(16).fold(crc, |acc, sl| {
-        let top = u32::from_le_bytes([sl[0], sl[1], sl[2], sl[3]]);
-        let one = top ^ acc;
-        CRC_TABLE[0][sl[15] as usize]
-            ^ CRC_TABLE[1][sl[14] as usize]
-            ^ CRC_TABLE[2][sl[13] as usize]
-            ^ CRC_TABLE[3][sl[12] as usize]
-            ^ CRC_TABLE[4][sl[11] as usize]
-            ^ CRC_TABLE[5][sl[10] as usize]
-            ^ CRC_TABLE[6][sl[9] as usize]
-            ^ CRC_TABLE[7][sl[8] as usize]
-            ^ CRC_TABLE[8][sl[7] as usize]
-            ^ CRC_TABLE[9][sl[6] as usize]
-            ^ CRC_TABLE[10][sl[5] as usize]
-            ^ CRC_TABLE[11][sl[4] as usize]
-            ^ CRC_TABLE[12][((one >> 24) & 0xFF) as usize]
-            ^ CRC_TABLE[13][((one >> 16) & 0xFF) as usize]
-            ^ CRC_TABLE[14][((one >> 8) & 0xFF) as usize]
-            ^ CRC_TABLE[15][(one & 0xFF) as usize]
-    });
-
-    let left_over = data.len() % 16;
-    crc = data[data.len() - left_over..].iter().fold(crc, |acc, &x| {
-        (acc >> 8) ^ CRC_TABLE[0][(u32::from(x) ^ (acc & 0xFF)) as usize]
-    });
-
-    (!crc).swap_bytes()
+    let mut crc = Crc::new();
+    crc.update(data);
+    crc.finalize()
+}
+
+/// An incremental [`calc_crc`]: data is hashed as it is fed, in chunks of any size, so that a
+/// section can be checked as it is streamed instead of buffered.
+///
+/// ```
+/// use boxcars::crc::{calc_crc, Crc};
+/// let mut crc = Crc::new();
+/// crc.update(b"rocket ");
+/// crc.update(b"league");
+/// assert_eq!(crc.finalize(), calc_crc(b"rocket league"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc {
+    crc: u32,
+}
+
+impl Crc {
+    pub fn new() -> Self {
+        Crc {
+            crc: !(0xefcb_f201_u32.swap_bytes()),
+        }
+    }
+
+    /// Hashes the data that follows everything hashed so far
+    pub fn update(&mut self, data: &[u8]) {
+        let crc = data.chunks_exact(16).fold(self.crc, |acc, sl| {
+            let top = u32::from_le_bytes([sl[0], sl[1], sl[2], sl[3]]);
+            let one = top ^ acc;
+            CRC_TABLE[0][sl[15] as usize]
+                ^ CRC_TABLE[1][sl[14] as usize]
+                ^ CRC_TABLE[2][sl[13] as usize]
+                ^ CRC_TABLE[3][sl[12] as usize]
+                ^ CRC_TABLE[4][sl[11] as usize]
+                ^ CRC_TABLE[5][sl[10] as usize]
+                ^ CRC_TABLE[6][sl[9] as usize]
+                ^ CRC_TABLE[7][sl[8] as usize]
+                ^ CRC_TABLE[8][sl[7] as usize]
+                ^ CRC_TABLE[9][sl[6] as usize]
+                ^ CRC_TABLE[10][sl[5] as usize]
+                ^ CRC_TABLE[11][sl[4] as usize]
+                ^ CRC_TABLE[12][((one >> 24) & 0xFF) as usize]
+                ^ CRC_TABLE[13][((one >> 16) & 0xFF) as usize]
+                ^ CRC_TABLE[14][((one >> 8) & 0xFF) as usize]
+                ^ CRC_TABLE[15][(one & 0xFF) as usize]
+        });
+
+        let left_over = data.len() % 16;
+        self.crc = data[data.len() - left_over..].iter().fold(crc, |acc, &x| {
+            (acc >> 8) ^ CRC_TABLE[0][(u32::from(x) ^ (acc & 0xFF)) as usize]
+        });
+    }
+
+    /// The crc of all the data hashed so far
+    pub fn finalize(&self) -> u32 {
+        (!self.crc).swap_bytes()
+    }
+}
+
+impl Default for Crc {
+    fn default() -> Self {
+        Crc::new()
+    }
+}
+
+/// The crc that a replay section declares and the crc of its data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionCrc {
+    pub expected: u32,
+    pub actual: u32,
+}
+
+impl SectionCrc {
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// The crcs of a replay's header and body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayCrcs {
+    pub header: SectionCrc,
+    pub body: SectionCrc,
+}
+
+impl ReplayCrcs {
+    /// Whether both sections match their declared crc
+    pub fn is_valid(&self) -> bool {
+        self.header.is_valid() && self.body.is_valid()
+    }
+}
+
+/// Size of the buffer that [`verify_reader`] hashes a section through
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Computes the crcs of the header and body of the replay read from the reader, which is read up
+/// to the end of the body. The sections are hashed through a small fixed size buffer as they are
+/// read, so that a replay can be checked for corruption without holding the whole file in memory,
+/// with the same result as [`ParserBuilder::always_check_crc`](crate::ParserBuilder::always_check_crc).
+/// A replay that ends before the end of its body fails with
+/// [`ErrorKind::UnexpectedEof`](std::io::ErrorKind::UnexpectedEof).
+pub fn verify_reader<R: Read>(mut reader: R) -> io::Result<ReplayCrcs> {
+    let header = section_crc(&mut reader)?;
+    let body = section_crc(&mut reader)?;
+    Ok(ReplayCrcs { header, body })
+}
+
+/// Reads a section's size and crc, and then hashes the section's data
+fn section_crc<R: Read>(reader: &mut R) -> io::Result<SectionCrc> {
+    let mut prefix = [0u8; 8];
+    reader.read_exact(&mut prefix)?;
+    let size = u32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]);
+    let expected = u32::from_le_bytes([prefix[4], prefix[5], prefix[6], prefix[7]]);
+
+    let mut crc = Crc::new();
+    let mut buf = [0u8; READ_BUFFER_SIZE];
+    let mut remaining = size as usize;
+    while remaining > 0 {
+        let chunk = &mut buf[..remaining.min(READ_BUFFER_SIZE)];
+        reader.read_exact(chunk)?;
+        crc.update(chunk);
+        remaining -= chunk.len();
+    }
+
+    Ok(SectionCrc {
+        expected,
+        actual: crc.finalize(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::crc::calc_crc;
+    use crate::crc::{calc_crc, verify_reader, Crc};
+    use std::io::{self, Read};
+
+    /// Yields at most a few bytes per read
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.0.len()).min(7);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
 
     #[test]
     fn crc_rumble_test() {
@@ -91,4 +209,33 @@ mod tests {
     fn single_byte_test() {
         assert_eq!(calc_crc(&[0xa0]), 0x76cc8c81);
     }
+
+    #[test]
+    fn incremental_crc_test() {
+        let data = include_bytes!("../assets/replays/good/rumble.replay");
+        for size in &[1, 7, 16, 33, 4096] {
+            let mut crc = Crc::new();
+            data.chunks(*size).for_each(|x| crc.update(x));
+            assert_eq!(crc.finalize(), calc_crc(&data[..]));
+        }
+    }
+
+    #[test]
+    fn verify_reader_test() {
+        let data = include_bytes!("../assets/replays/good/rumble.replay");
+        let crcs = verify_reader(Trickle(&data[..])).unwrap();
+        assert!(crcs.is_valid());
+        assert_eq!(crcs.header.expected, 0x1423_13e7);
+        assert_eq!(crcs.body.expected, 0xe077_b6c7);
+
+        let mut corrupt = data.to_vec();
+        let last = corrupt.len() - 1;
+        corrupt[last] ^= 1;
+        let crcs = verify_reader(&corrupt[..]).unwrap();
+        assert!(crcs.header.is_valid());
+        assert!(!crcs.body.is_valid());
+
+        let error = verify_reader(&data[..100]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
 }
@@ -1,5 +1,6 @@
 #![cfg_attr(rustfmt, rustfmt::skip)]
-use crate::network::{AttributeTag, SpawnTrajectory};
+use crate::models::{ArenaVariant, GameMode};
+use crate::network::{AttributeDecoding, SpawnTrajectory};
 
 pub(crate) static SPAWN_STATS: phf::Map<&'static str, SpawnTrajectory> = phf::phf_map! {
     "TAGame.Ball_Breakout_TA" => SpawnTrajectory::LocationAndRotation,
@@ -121,232 +122,233 @@ pub(crate) static SPAWN_STATS: phf::Map<&'static str, SpawnTrajectory> = phf::ph
     "TAGame.Default__PRI_KnockOut_TA" => SpawnTrajectory::Location,
 };
 
-pub(crate) static ATTRIBUTES: phf::Map<&'static str, AttributeTag> = phf::phf_map! {
-    "Engine.Actor:bBlockActors" => AttributeTag::Boolean,
-    "Engine.Actor:bCollideActors" => AttributeTag::Boolean,
-    "Engine.Actor:bCollideWorld" => AttributeTag::Boolean,
-    "Engine.Actor:bHidden" => AttributeTag::Boolean,
-    "Engine.Actor:bTearOff" => AttributeTag::Boolean,
-    "Engine.Actor:bNetOwner" => AttributeTag::Boolean,
-    "Engine.Actor:DrawScale" => AttributeTag::Float,
-    "Engine.Actor:RemoteRole" => AttributeTag::Enum,
-    "Engine.Actor:Role" => AttributeTag::Enum,
-    "Engine.Actor:Rotation" => AttributeTag::RotationTag,
-    "Engine.GameReplicationInfo:bMatchIsOver" => AttributeTag::Boolean,
-    "Engine.GameReplicationInfo:GameClass" => AttributeTag::ActiveActor,
-    "Engine.GameReplicationInfo:ServerName" => AttributeTag::String,
-    "Engine.Pawn:PlayerReplicationInfo" => AttributeTag::ActiveActor,
-    "Engine.Pawn:HealthMax" => AttributeTag::Int,
-    "Engine.PlayerReplicationInfo:bBot" => AttributeTag::Boolean,
-    "Engine.PlayerReplicationInfo:bIsSpectator" => AttributeTag::Boolean,
-    "Engine.PlayerReplicationInfo:bReadyToPlay" => AttributeTag::Boolean,
-    "Engine.PlayerReplicationInfo:bTimedOut" => AttributeTag::Boolean,
-    "Engine.PlayerReplicationInfo:bWaitingPlayer" => AttributeTag::Boolean,
-    "Engine.PlayerReplicationInfo:Ping" => AttributeTag::Byte,
-    "Engine.PlayerReplicationInfo:PlayerID" => AttributeTag::Int,
-    "Engine.PlayerReplicationInfo:PlayerName" => AttributeTag::String,
-    "Engine.PlayerReplicationInfo:RemoteUserData" => AttributeTag::String,
-    "Engine.PlayerReplicationInfo:Score" => AttributeTag::Int,
-    "Engine.PlayerReplicationInfo:Team" => AttributeTag::ActiveActor,
-    "Engine.PlayerReplicationInfo:UniqueId" => AttributeTag::UniqueId,
-    "Engine.TeamInfo:Score" => AttributeTag::Int,
-    "Engine.ReplicatedActor_ORS:ReplicatedOwner" => AttributeTag::ActiveActor,
-    "ProjectX.GRI_X:bGameStarted" => AttributeTag::Boolean,
-    "ProjectX.GRI_X:GameServerID" => AttributeTag::QWordString,
-    "ProjectX.GRI_X:MatchGUID" => AttributeTag::String,
-    "ProjectX.GRI_X:MatchGuid" => AttributeTag::String,
-    "ProjectX.GRI_X:ReplicatedGameMutatorIndex" => AttributeTag::Int,
-    "ProjectX.GRI_X:ReplicatedGamePlaylist" => AttributeTag::Int,
-    "ProjectX.GRI_X:ReplicatedServerRegion" => AttributeTag::String,
-    "ProjectX.GRI_X:Reservations" => AttributeTag::Reservation,
-    "TAGame.Ball_Breakout_TA:AppliedDamage" => AttributeTag::AppliedDamage,
-    "TAGame.Ball_Breakout_TA:DamageIndex" => AttributeTag::Int,
-    "TAGame.Ball_Breakout_TA:LastTeamTouch" => AttributeTag::Byte,
-    "TAGame.Ball_TA:GameEvent" => AttributeTag::ActiveActor,
-    "TAGame.Ball_TA:HitTeamNum" => AttributeTag::Byte,
-    "TAGame.Ball_TA:ReplicatedAddedCarBounceScale" => AttributeTag::Float,
-    "TAGame.Ball_TA:ReplicatedBallMaxLinearSpeedScale" => AttributeTag::Float,
-    "TAGame.Ball_TA:ReplicatedBallScale" => AttributeTag::Float,
-    "TAGame.Ball_TA:ReplicatedExplosionData" => AttributeTag::Explosion,
-    "TAGame.Ball_TA:ReplicatedExplosionDataExtended" => AttributeTag::ExtendedExplosion,
-    "TAGame.Ball_TA:ReplicatedWorldBounceScale" => AttributeTag::Float,
-    "TAGame.Ball_God_TA:TargetSpeed" => AttributeTag::Float,
-    "TAGame.BreakOutActor_Platform_TA:DamageState" => AttributeTag::DamageState,
-    "TAGame.CameraSettingsActor_TA:bUsingBehindView" => AttributeTag::Boolean,
-    "TAGame.CameraSettingsActor_TA:bMouseCameraToggleEnabled" => AttributeTag::Boolean,
-    "TAGame.CameraSettingsActor_TA:bUsingSecondaryCamera" => AttributeTag::Boolean,
-    "TAGame.CameraSettingsActor_TA:bUsingSwivel" => AttributeTag::Boolean,
-    "TAGame.CameraSettingsActor_TA:CameraPitch" => AttributeTag::Byte,
-    "TAGame.CameraSettingsActor_TA:CameraYaw" => AttributeTag::Byte,
-    "TAGame.CameraSettingsActor_TA:PRI" => AttributeTag::ActiveActor,
-    "TAGame.CameraSettingsActor_TA:ProfileSettings" => AttributeTag::CamSettings,
-    "TAGame.Car_TA:AddedBallForceMultiplier" => AttributeTag::Float,
-    "TAGame.Car_TA:AddedCarForceMultiplier" => AttributeTag::Float,
-    "TAGame.Car_TA:AttachedPickup" => AttributeTag::ActiveActor,
-    "TAGame.Car_TA:ClubColors" => AttributeTag::ClubColors,
-    "TAGame.Car_TA:ReplicatedCarScale" => AttributeTag::Float,
-    "TAGame.Car_TA:ReplicatedDemolish" => AttributeTag::Demolish,
-    "TAGame.Car_TA:ReplicatedDemolish_CustomFX" => AttributeTag::DemolishFx,
-    "TAGame.Car_TA:ReplicatedDemolishGoalExplosion" => AttributeTag::DemolishFx,
-    "TAGame.Car_TA:RumblePickups" => AttributeTag::ActiveActor,
-    "TAGame.RumblePickups_TA:ConcurrentItemCount" => AttributeTag::Int,
-    "TAGame.RumblePickups_TA:AttachedPickup" => AttributeTag::ActiveActor,
-    "TAGame.RumblePickups_TA:PickupInfo" => AttributeTag::PickupInfo,
-    "TAGame.Car_TA:TeamPaint" => AttributeTag::TeamPaint,
-    "TAGame.CarComponent_Boost_TA:bNoBoost" => AttributeTag::Boolean,
-    "TAGame.CarComponent_Boost_TA:BoostModifier" => AttributeTag::Float,
-    "TAGame.CarComponent_Boost_TA:bUnlimitedBoost" => AttributeTag::Boolean,
-    "TAGame.CarComponent_Boost_TA:bRechargeGroundOnly" => AttributeTag::Boolean,
-    "TAGame.CarComponent_Boost_TA:RechargeDelay" => AttributeTag::Float,
-    "TAGame.CarComponent_Boost_TA:RechargeRate" => AttributeTag::Float,
-    "TAGame.CarComponent_Boost_TA:ReplicatedBoostAmount" => AttributeTag::Byte,
-    "TAGame.CarComponent_Boost_TA:UnlimitedBoostRefCount" => AttributeTag::Int,
-    "TAGame.CarComponent_Dodge_TA:DodgeTorque" => AttributeTag::Location,
-    "TAGame.CarComponent_Dodge_TA:DodgeImpulse" => AttributeTag::Location,
-    "TAGame.CarComponent_FlipCar_TA:bFlipRight" => AttributeTag::Boolean,
-    "TAGame.CarComponent_FlipCar_TA:FlipCarTime" => AttributeTag::Float,
-    "TAGame.CarComponent_TA:ReplicatedActive" => AttributeTag::Byte,
-    "TAGame.CarComponent_TA:ReplicatedActivityTime" => AttributeTag::Float,
-    "TAGame.CarComponent_TA:Vehicle" => AttributeTag::ActiveActor,
-    "TAGame.CrowdActor_TA:GameEvent" => AttributeTag::ActiveActor,
-    "TAGame.CrowdActor_TA:ModifiedNoise" => AttributeTag::Float,
-    "TAGame.CrowdActor_TA:ReplicatedCountDownNumber" => AttributeTag::Int,
-    "TAGame.CrowdActor_TA:ReplicatedOneShotSound" => AttributeTag::ActiveActor,
-    "TAGame.CrowdActor_TA:ReplicatedRoundCountDownNumber" => AttributeTag::Int,
-    "TAGame.CrowdManager_TA:GameEvent" => AttributeTag::ActiveActor,
-    "TAGame.CrowdManager_TA:ReplicatedGlobalOneShotSound" => AttributeTag::ActiveActor,
-    "TAGame.GameEvent_Soccar_TA:bBallHasBeenHit" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Soccar_TA:bClubMatch" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Soccar_TA:bOverTime" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Soccar_TA:bMatchEnded" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Soccar_TA:bNoContest" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Soccar_TA:bUnlimitedTime" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Soccar_TA:GameTime" => AttributeTag::Int,
-    "TAGame.GameEvent_Soccar_TA:GameWinner" => AttributeTag::ActiveActor,
-    "TAGame.GameEvent_Soccar_TA:MatchWinner" => AttributeTag::ActiveActor,
-    "TAGame.GameEvent_Soccar_TA:MaxScore" => AttributeTag::Int,
-    "TAGame.GameEvent_Soccar_TA:MVP" => AttributeTag::ActiveActor,
-    "TAGame.GameEvent_Soccar_TA:ReplicatedMusicStinger" => AttributeTag::MusicStinger,
-    "TAGame.GameEvent_Soccar_TA:ReplicatedScoredOnTeam" => AttributeTag::Byte,
-    "TAGame.GameEvent_Soccar_TA:ReplicatedServerPerformanceState" => AttributeTag::Byte,
-    "TAGame.GameEvent_Soccar_TA:ReplicatedStatEvent" => AttributeTag::StatEvent,
-    "TAGame.GameEvent_Soccar_TA:RoundNum" => AttributeTag::Int,
-    "TAGame.GameEvent_Soccar_TA:SecondsRemaining" => AttributeTag::Int,
-    "TAGame.GameEvent_Soccar_TA:SeriesLength" => AttributeTag::Int,
-    "TAGame.GameEvent_Soccar_TA:SubRulesArchetype" => AttributeTag::ActiveActor,
-    "TAGame.GameEvent_SoccarPrivate_TA:MatchSettings" => AttributeTag::PrivateMatchSettings,
-    "TAGame.GameEvent_TA:bAllowReadyUp" => AttributeTag::Boolean,
-    "TAGame.GameEvent_TA:bCanVoteToForfeit" => AttributeTag::Boolean,
-    "TAGame.GameEvent_TA:bHasLeaveMatchPenalty" => AttributeTag::Boolean,
-    "TAGame.GameEvent_TA:BotSkill" => AttributeTag::Int,
-    "TAGame.GameEvent_TA:GameMode" => AttributeTag::GameMode,
-    "TAGame.GameEvent_TA:MatchTypeClass" => AttributeTag::ActiveActor,
-    "TAGame.GameEvent_TA:ReplicatedGameStateTimeRemaining" => AttributeTag::Int,
-    "TAGame.GameEvent_TA:ReplicatedRoundCountDownNumber" => AttributeTag::Int,
-    "TAGame.GameEvent_TA:ReplicatedStateIndex" => AttributeTag::Byte,
-    "TAGame.GameEvent_TA:ReplicatedStateName" => AttributeTag::Int,
-    "TAGame.GameEvent_Team_TA:bForfeit" => AttributeTag::Boolean,
-    "TAGame.GameEvent_Team_TA:MaxTeamSize" => AttributeTag::Int,
-    "TAGame.MaxTimeWarningData_TA:EndGameWarningEpochTime" => AttributeTag::Int64,
-    "TAGame.MaxTimeWarningData_TA:EndGameEpochTime" => AttributeTag::Int64,
-    "TAGame.GRI_TA:NewDedicatedServerIP" => AttributeTag::String,
-    "TAGame.PRI_TA:bIsDistracted" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bIsInSplitScreen" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bMatchMVP" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bOnlineLoadoutSet" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bOnlineLoadoutsSet" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:BotProductName" => AttributeTag::Int,
-    "TAGame.PRI_TA:bReady" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bUsingBehindView" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bUsingItems" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:bUsingSecondaryCamera" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:CameraPitch" => AttributeTag::Byte,
-    "TAGame.PRI_TA:CameraSettings" => AttributeTag::CamSettings,
-    "TAGame.PRI_TA:CameraYaw" => AttributeTag::Byte,
-    "TAGame.PRI_TA:ClientLoadout" => AttributeTag::Loadout,
-    "TAGame.PRI_TA:ClientLoadoutOnline" => AttributeTag::LoadoutOnline,
-    "TAGame.PRI_TA:ClientLoadouts" => AttributeTag::TeamLoadout,
-    "TAGame.PRI_TA:ClientLoadoutsOnline" => AttributeTag::LoadoutsOnline,
-    "TAGame.PRI_TA:ClubID" => AttributeTag::Int64,
-    "TAGame.PRI_TA:MatchAssists" => AttributeTag::Int,
-    "TAGame.PRI_TA:MatchBreakoutDamage" => AttributeTag::Int,
-    "TAGame.PRI_TA:MatchGoals" => AttributeTag::Int,
-    "TAGame.PRI_TA:MatchSaves" => AttributeTag::Int,
-    "TAGame.PRI_TA:MatchScore" => AttributeTag::Int,
-    "TAGame.PRI_TA:MatchShots" => AttributeTag::Int,
-    "TAGame.PRI_TA:MaxTimeTillItem" => AttributeTag::Int,
-    "TAGame.PRI_TA:PartyLeader" => AttributeTag::PartyLeader,
-    "TAGame.PRI_TA:PawnType" => AttributeTag::Byte,
-    "TAGame.PRI_TA:PersistentCamera" => AttributeTag::ActiveActor,
-    "TAGame.PRI_TA:PlayerHistoryKey" => AttributeTag::PlayerHistoryKey,
-    "TAGame.PRI_TA:PlayerHistoryValid" => AttributeTag::Boolean,
-    "TAGame.PRI_TA:ReplicatedGameEvent" => AttributeTag::ActiveActor,
-    "TAGame.PRI_TA:ReplicatedWorstNetQualityBeyondLatency" => AttributeTag::Byte,
-    "TAGame.PRI_TA:RepStatTitles" => AttributeTag::RepStatTitle,
-    "TAGame.PRI_TA:SteeringSensitivity" => AttributeTag::Float,
-    "TAGame.PRI_TA:SkillTier" => AttributeTag::FlaggedByte,
-    "TAGame.PRI_TA:TimeTillItem" => AttributeTag::Int,
-    "TAGame.PRI_TA:Title" => AttributeTag::Int,
-    "TAGame.PRI_TA:TotalXP" => AttributeTag::Int,
-    "TAGame.PRI_TA:PrimaryTitle" => AttributeTag::Title,
-    "TAGame.PRI_TA:SecondaryTitle" => AttributeTag::Title,
-    "TAGame.PRI_TA:SpectatorShortcut" => AttributeTag::Int,
-    "TAGame.PRI_TA:CurrentVoiceRoom" => AttributeTag::String,
-    "TAGame.RBActor_TA:bFrozen" => AttributeTag::Boolean,
-    "TAGame.RBActor_TA:bIgnoreSyncing" => AttributeTag::Boolean,
-    "TAGame.RBActor_TA:bReplayActor" => AttributeTag::Boolean,
-    "TAGame.RBActor_TA:ReplicatedRBState" => AttributeTag::RigidBody,
-    "TAGame.RBActor_TA:WeldedInfo" => AttributeTag::Welded,
-    "TAGame.SpecialPickup_BallFreeze_TA:RepOrigSpeed" => AttributeTag::Float,
-    "TAGame.SpecialPickup_BallVelcro_TA:AttachTime" => AttributeTag::Float,
-    "TAGame.SpecialPickup_BallVelcro_TA:bBroken" => AttributeTag::Boolean,
-    "TAGame.SpecialPickup_BallVelcro_TA:bHit" => AttributeTag::Boolean,
-    "TAGame.SpecialPickup_BallVelcro_TA:BreakTime" => AttributeTag::Float,
-    "TAGame.SpecialPickup_Targeted_TA:Targeted" => AttributeTag::ActiveActor,
-    "TAGame.SpecialPickup_Football_TA:WeldedBall" => AttributeTag::ActiveActor,
-    "TAGame.Team_Soccar_TA:GameScore" => AttributeTag::Int,
-    "TAGame.Team_TA:ClubColors" => AttributeTag::ClubColors,
-    "TAGame.Team_TA:ClubID" => AttributeTag::Int64,
-    "TAGame.Team_TA:CustomTeamName" => AttributeTag::String,
-    "TAGame.Team_TA:Difficulty" => AttributeTag::Int,
-    "TAGame.Team_TA:GameEvent" => AttributeTag::ActiveActor,
-    "TAGame.Team_TA:LogoData" => AttributeTag::ActiveActor,
-    "TAGame.Vehicle_TA:bDriving" => AttributeTag::Boolean,
-    "TAGame.Vehicle_TA:bPodiumMode" => AttributeTag::Boolean,
-    "TAGame.Vehicle_TA:bReplicatedHandbrake" => AttributeTag::Boolean,
-    "TAGame.Vehicle_TA:ReplicatedSteer" => AttributeTag::Byte,
-    "TAGame.Vehicle_TA:ReplicatedThrottle" => AttributeTag::Byte,
-    "TAGame.VehiclePickup_TA:bNoPickup" => AttributeTag::Boolean,
-    "TAGame.VehiclePickup_TA:ReplicatedPickupData" => AttributeTag::Pickup,
-    "TAGame.VehiclePickup_TA:NewReplicatedPickupData" => AttributeTag::PickupNew,
-    "TAGame.Ball_Haunted_TA:LastTeamTouch" => AttributeTag::Byte,
-    "TAGame.Ball_Haunted_TA:TotalActiveBeams" => AttributeTag::Byte,
-    "TAGame.Ball_Haunted_TA:DeactivatedGoalIndex" => AttributeTag::Byte,
-    "TAGame.Ball_Haunted_TA:ReplicatedBeamBrokenValue" => AttributeTag::Byte,
-    "TAGame.Ball_Haunted_TA:bIsBallBeamed" => AttributeTag::Boolean,
-    "TAGame.SpecialPickup_Rugby_TA:bBallWelded" => AttributeTag::Boolean,
-    "TAGame.Cannon_TA:Pitch" => AttributeTag::Float,
-    "TAGame.Cannon_TA:FireCount" => AttributeTag::Byte,
-    "TAGame.PlayerStart_Platform_TA:bActive" => AttributeTag::Boolean,
-    "TAGame.Stunlock_TA:Car" => AttributeTag::ActiveActor,
-    "TAGame.CarComponent_AirActivate_TA:AirActivateCount" => AttributeTag::Int,
-    "TAGame.CarComponent_DoubleJump_TA:DoubleJumpImpulse" => AttributeTag::Location,
-    "TAGame.CarComponent_Dodge_KO_TA:DodgeRotationCompressed" => AttributeTag::Int,
-    "TAGame.Car_KnockOut_TA:ReplicatedStateChanged" => AttributeTag::Byte,
-    "TAGame.Car_KnockOut_TA:ReplicatedStateName" => AttributeTag::Int,
-    "TAGame.Car_KnockOut_TA:UsedAttackComponent" => AttributeTag::ActiveActor,
-    "TAGame.Car_KnockOut_TA:ReplicatedImpulse" => AttributeTag::Impulse,
-    "TAGame.PRI_KnockOut_TA:Knockouts" => AttributeTag::Int,
-    "TAGame.PRI_KnockOut_TA:KnockoutDeaths" => AttributeTag::Int,
-    "TAGame.PRI_KnockOut_TA:DamageCaused" => AttributeTag::Int,
-    "TAGame.PRI_KnockOut_TA:Hits" => AttributeTag::Int,
-    "TAGame.PRI_KnockOut_TA:Grabs" => AttributeTag::Int,
-    "TAGame.PRI_KnockOut_TA:Blocks" => AttributeTag::Int,
-    "TAGame.PRI_KnockOut_TA:bIsActiveMVP" => AttributeTag::Boolean,
-    "TAGame.CarComponent_Torque_TA:ReplicatedTorqueInput" => AttributeTag::Int,
-    "TAGame.CarComponent_Torque_TA:TorqueScale" => AttributeTag::Float,
-    "TAGame.Stunlock_TA:StunTimeRemaining" => AttributeTag::Float,
-    "TAGame.Stunlock_TA:MaxStunTime" => AttributeTag::Float,
+pub(crate) static ATTRIBUTES: phf::Map<&'static str, AttributeDecoding> = phf::phf_map! {
+    "Engine.Actor:bBlockActors" => AttributeDecoding::Boolean,
+    "Engine.Actor:bCollideActors" => AttributeDecoding::Boolean,
+    "Engine.Actor:bCollideWorld" => AttributeDecoding::Boolean,
+    "Engine.Actor:bHidden" => AttributeDecoding::Boolean,
+    "Engine.Actor:bTearOff" => AttributeDecoding::Boolean,
+    "Engine.Actor:bNetOwner" => AttributeDecoding::Boolean,
+    "Engine.Actor:DrawScale" => AttributeDecoding::Float,
+    "Engine.Actor:RemoteRole" => AttributeDecoding::Enum,
+    "Engine.Actor:Role" => AttributeDecoding::Enum,
+    "Engine.Actor:Rotation" => AttributeDecoding::RotationTag,
+    "Engine.GameReplicationInfo:bMatchIsOver" => AttributeDecoding::Boolean,
+    "Engine.GameReplicationInfo:GameClass" => AttributeDecoding::ActiveActor,
+    "Engine.GameReplicationInfo:ServerName" => AttributeDecoding::String,
+    "Engine.Pawn:PlayerReplicationInfo" => AttributeDecoding::ActiveActor,
+    "Engine.Pawn:HealthMax" => AttributeDecoding::Int,
+    "Engine.PlayerReplicationInfo:bBot" => AttributeDecoding::Boolean,
+    "Engine.PlayerReplicationInfo:bIsSpectator" => AttributeDecoding::Boolean,
+    "Engine.PlayerReplicationInfo:bReadyToPlay" => AttributeDecoding::Boolean,
+    "Engine.PlayerReplicationInfo:bTimedOut" => AttributeDecoding::Boolean,
+    "Engine.PlayerReplicationInfo:bWaitingPlayer" => AttributeDecoding::Boolean,
+    "Engine.PlayerReplicationInfo:Ping" => AttributeDecoding::Byte,
+    "Engine.PlayerReplicationInfo:PlayerID" => AttributeDecoding::Int,
+    "Engine.PlayerReplicationInfo:PlayerName" => AttributeDecoding::String,
+    "Engine.PlayerReplicationInfo:RemoteUserData" => AttributeDecoding::String,
+    "Engine.PlayerReplicationInfo:Score" => AttributeDecoding::Int,
+    "Engine.PlayerReplicationInfo:Team" => AttributeDecoding::ActiveActor,
+    "Engine.PlayerReplicationInfo:UniqueId" => AttributeDecoding::UniqueId,
+    "Engine.TeamInfo:Score" => AttributeDecoding::Int,
+    "Engine.ReplicatedActor_ORS:ReplicatedOwner" => AttributeDecoding::ActiveActor,
+    "ProjectX.GRI_X:bGameStarted" => AttributeDecoding::Boolean,
+    "ProjectX.GRI_X:GameServerID" => AttributeDecoding::QWordString,
+    "ProjectX.GRI_X:MatchGUID" => AttributeDecoding::String,
+    "ProjectX.GRI_X:MatchGuid" => AttributeDecoding::String,
+    "ProjectX.GRI_X:ReplicatedGameMutatorIndex" => AttributeDecoding::Int,
+    "ProjectX.GRI_X:ReplicatedGamePlaylist" => AttributeDecoding::Int,
+    "ProjectX.GRI_X:ReplicatedServerRegion" => AttributeDecoding::String,
+    "ProjectX.GRI_X:Reservations" => AttributeDecoding::Reservation,
+    "TAGame.Ball_Breakout_TA:AppliedDamage" => AttributeDecoding::AppliedDamage,
+    "TAGame.Ball_Breakout_TA:DamageIndex" => AttributeDecoding::Int,
+    "TAGame.Ball_Breakout_TA:LastTeamTouch" => AttributeDecoding::Byte,
+    "TAGame.Ball_TA:GameEvent" => AttributeDecoding::ActiveActor,
+    "TAGame.Ball_TA:HitTeamNum" => AttributeDecoding::Byte,
+    "TAGame.Ball_TA:ReplicatedAddedCarBounceScale" => AttributeDecoding::Float,
+    "TAGame.Ball_TA:ReplicatedBallMaxLinearSpeedScale" => AttributeDecoding::Float,
+    "TAGame.Ball_TA:ReplicatedBallScale" => AttributeDecoding::Float,
+    "TAGame.Ball_TA:ReplicatedExplosionData" => AttributeDecoding::Explosion,
+    "TAGame.Ball_TA:ReplicatedExplosionDataExtended" => AttributeDecoding::ExtendedExplosion,
+    "TAGame.Ball_TA:ReplicatedWorldBounceScale" => AttributeDecoding::Float,
+    "TAGame.Ball_God_TA:TargetSpeed" => AttributeDecoding::Float,
+    "TAGame.BreakOutActor_Platform_TA:DamageState" => AttributeDecoding::DamageState,
+    "TAGame.CameraSettingsActor_TA:bUsingBehindView" => AttributeDecoding::Boolean,
+    "TAGame.CameraSettingsActor_TA:bMouseCameraToggleEnabled" => AttributeDecoding::Boolean,
+    "TAGame.CameraSettingsActor_TA:bUsingSecondaryCamera" => AttributeDecoding::Boolean,
+    "TAGame.CameraSettingsActor_TA:bUsingSwivel" => AttributeDecoding::Boolean,
+    "TAGame.CameraSettingsActor_TA:CameraPitch" => AttributeDecoding::Byte,
+    "TAGame.CameraSettingsActor_TA:CameraYaw" => AttributeDecoding::Byte,
+    "TAGame.CameraSettingsActor_TA:PRI" => AttributeDecoding::ActiveActor,
+    "TAGame.CameraSettingsActor_TA:ProfileSettings" => AttributeDecoding::CamSettings,
+    "TAGame.Car_TA:AddedBallForceMultiplier" => AttributeDecoding::Float,
+    "TAGame.Car_TA:AddedCarForceMultiplier" => AttributeDecoding::Float,
+    "TAGame.Car_TA:AttachedPickup" => AttributeDecoding::ActiveActor,
+    "TAGame.Car_TA:ClubColors" => AttributeDecoding::ClubColors,
+    "TAGame.Car_TA:ReplicatedCarScale" => AttributeDecoding::Float,
+    "TAGame.Car_TA:ReplicatedDemolish" => AttributeDecoding::Demolish,
+    "TAGame.Car_TA:ReplicatedDemolish_CustomFX" => AttributeDecoding::DemolishFx,
+    "TAGame.Car_TA:ReplicatedDemolishGoalExplosion" => AttributeDecoding::DemolishFx,
+    "TAGame.Car_TA:RumblePickups" => AttributeDecoding::ActiveActor,
+    "TAGame.RumblePickups_TA:ConcurrentItemCount" => AttributeDecoding::Int,
+    "TAGame.RumblePickups_TA:AttachedPickup" => AttributeDecoding::ActiveActor,
+    "TAGame.RumblePickups_TA:PickupInfo" => AttributeDecoding::PickupInfo,
+    "TAGame.Car_TA:TeamPaint" => AttributeDecoding::TeamPaint,
+    "TAGame.CarComponent_Boost_TA:bNoBoost" => AttributeDecoding::Boolean,
+    "TAGame.CarComponent_Boost_TA:BoostModifier" => AttributeDecoding::Float,
+    "TAGame.CarComponent_Boost_TA:bUnlimitedBoost" => AttributeDecoding::Boolean,
+    "TAGame.CarComponent_Boost_TA:bRechargeGroundOnly" => AttributeDecoding::Boolean,
+    "TAGame.CarComponent_Boost_TA:RechargeDelay" => AttributeDecoding::Float,
+    "TAGame.CarComponent_Boost_TA:RechargeRate" => AttributeDecoding::Float,
+    "TAGame.CarComponent_Boost_TA:ReplicatedBoostAmount" => AttributeDecoding::Byte,
+    "TAGame.CarComponent_Boost_TA:UnlimitedBoostRefCount" => AttributeDecoding::Int,
+    "TAGame.CarComponent_Dodge_TA:DodgeTorque" => AttributeDecoding::Location,
+    "TAGame.CarComponent_Dodge_TA:DodgeImpulse" => AttributeDecoding::Location,
+    "TAGame.CarComponent_FlipCar_TA:bFlipRight" => AttributeDecoding::Boolean,
+    "TAGame.CarComponent_FlipCar_TA:FlipCarTime" => AttributeDecoding::Float,
+    "TAGame.CarComponent_TA:ReplicatedActive" => AttributeDecoding::Byte,
+    "TAGame.CarComponent_TA:ReplicatedActivityTime" => AttributeDecoding::Float,
+    "TAGame.CarComponent_TA:Vehicle" => AttributeDecoding::ActiveActor,
+    "TAGame.CrowdActor_TA:GameEvent" => AttributeDecoding::ActiveActor,
+    "TAGame.CrowdActor_TA:ModifiedNoise" => AttributeDecoding::Float,
+    "TAGame.CrowdActor_TA:ReplicatedCountDownNumber" => AttributeDecoding::Int,
+    "TAGame.CrowdActor_TA:ReplicatedOneShotSound" => AttributeDecoding::ActiveActor,
+    "TAGame.CrowdActor_TA:ReplicatedRoundCountDownNumber" => AttributeDecoding::Int,
+    "TAGame.CrowdManager_TA:GameEvent" => AttributeDecoding::ActiveActor,
+    "TAGame.CrowdManager_TA:ReplicatedGlobalOneShotSound" => AttributeDecoding::ActiveActor,
+    "TAGame.GameEvent_Soccar_TA:bBallHasBeenHit" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Soccar_TA:bClubMatch" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Soccar_TA:bOverTime" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Soccar_TA:bMatchEnded" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Soccar_TA:bNoContest" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Soccar_TA:bUnlimitedTime" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Soccar_TA:GameTime" => AttributeDecoding::Int,
+    "TAGame.GameEvent_Soccar_TA:GameWinner" => AttributeDecoding::ActiveActor,
+    "TAGame.GameEvent_Soccar_TA:MatchWinner" => AttributeDecoding::ActiveActor,
+    "TAGame.GameEvent_Soccar_TA:MaxScore" => AttributeDecoding::Int,
+    "TAGame.GameEvent_Soccar_TA:MVP" => AttributeDecoding::ActiveActor,
+    "TAGame.GameEvent_Soccar_TA:ReplicatedMusicStinger" => AttributeDecoding::MusicStinger,
+    "TAGame.GameEvent_Soccar_TA:ReplicatedScoredOnTeam" => AttributeDecoding::Byte,
+    "TAGame.GameEvent_Soccar_TA:ReplicatedServerPerformanceState" => AttributeDecoding::Byte,
+    "TAGame.GameEvent_Soccar_TA:ReplicatedStatEvent" => AttributeDecoding::StatEvent,
+    "TAGame.GameEvent_Soccar_TA:RoundNum" => AttributeDecoding::Int,
+    "TAGame.GameEvent_Soccar_TA:SecondsRemaining" => AttributeDecoding::Int,
+    "TAGame.GameEvent_Soccar_TA:SeriesLength" => AttributeDecoding::Int,
+    "TAGame.GameEvent_Soccar_TA:SubRulesArchetype" => AttributeDecoding::ActiveActor,
+    "TAGame.GameEvent_SoccarPrivate_TA:MatchSettings" => AttributeDecoding::PrivateMatchSettings,
+    "TAGame.GameEvent_TA:bAllowReadyUp" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_TA:bCanVoteToForfeit" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_TA:bHasLeaveMatchPenalty" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_TA:BotSkill" => AttributeDecoding::Int,
+    "TAGame.GameEvent_TA:GameMode" => AttributeDecoding::GameMode,
+    "TAGame.GameEvent_TA:MatchTypeClass" => AttributeDecoding::ActiveActor,
+    "TAGame.GameEvent_TA:ReplicatedGameStateTimeRemaining" => AttributeDecoding::Int,
+    "TAGame.GameEvent_TA:ReplicatedRoundCountDownNumber" => AttributeDecoding::Int,
+    "TAGame.GameEvent_TA:ReplicatedStateIndex" => AttributeDecoding::Byte,
+    "TAGame.GameEvent_TA:ReplicatedStateName" => AttributeDecoding::Int,
+    "TAGame.GameEvent_Team_TA:bForfeit" => AttributeDecoding::Boolean,
+    "TAGame.GameEvent_Team_TA:MaxTeamSize" => AttributeDecoding::Int,
+    "TAGame.MaxTimeWarningData_TA:EndGameWarningEpochTime" => AttributeDecoding::Int64,
+    "TAGame.MaxTimeWarningData_TA:EndGameEpochTime" => AttributeDecoding::Int64,
+    "TAGame.GRI_TA:NewDedicatedServerIP" => AttributeDecoding::String,
+    "TAGame.PRI_TA:bIdleBanned" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bIsDistracted" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bIsInSplitScreen" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bMatchMVP" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bOnlineLoadoutSet" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bOnlineLoadoutsSet" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:BotProductName" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:bReady" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bUsingBehindView" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bUsingItems" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:bUsingSecondaryCamera" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:CameraPitch" => AttributeDecoding::Byte,
+    "TAGame.PRI_TA:CameraSettings" => AttributeDecoding::CamSettings,
+    "TAGame.PRI_TA:CameraYaw" => AttributeDecoding::Byte,
+    "TAGame.PRI_TA:ClientLoadout" => AttributeDecoding::Loadout,
+    "TAGame.PRI_TA:ClientLoadoutOnline" => AttributeDecoding::LoadoutOnline,
+    "TAGame.PRI_TA:ClientLoadouts" => AttributeDecoding::TeamLoadout,
+    "TAGame.PRI_TA:ClientLoadoutsOnline" => AttributeDecoding::LoadoutsOnline,
+    "TAGame.PRI_TA:ClubID" => AttributeDecoding::Int64,
+    "TAGame.PRI_TA:MatchAssists" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:MatchBreakoutDamage" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:MatchGoals" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:MatchSaves" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:MatchScore" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:MatchShots" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:MaxTimeTillItem" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:PartyLeader" => AttributeDecoding::PartyLeader,
+    "TAGame.PRI_TA:PawnType" => AttributeDecoding::Byte,
+    "TAGame.PRI_TA:PersistentCamera" => AttributeDecoding::ActiveActor,
+    "TAGame.PRI_TA:PlayerHistoryKey" => AttributeDecoding::PlayerHistoryKey,
+    "TAGame.PRI_TA:PlayerHistoryValid" => AttributeDecoding::Boolean,
+    "TAGame.PRI_TA:ReplicatedGameEvent" => AttributeDecoding::ActiveActor,
+    "TAGame.PRI_TA:ReplicatedWorstNetQualityBeyondLatency" => AttributeDecoding::Byte,
+    "TAGame.PRI_TA:RepStatTitles" => AttributeDecoding::RepStatTitle,
+    "TAGame.PRI_TA:SteeringSensitivity" => AttributeDecoding::Float,
+    "TAGame.PRI_TA:SkillTier" => AttributeDecoding::FlaggedByte,
+    "TAGame.PRI_TA:TimeTillItem" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:Title" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:TotalXP" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:PrimaryTitle" => AttributeDecoding::Title,
+    "TAGame.PRI_TA:SecondaryTitle" => AttributeDecoding::Title,
+    "TAGame.PRI_TA:SpectatorShortcut" => AttributeDecoding::Int,
+    "TAGame.PRI_TA:CurrentVoiceRoom" => AttributeDecoding::String,
+    "TAGame.RBActor_TA:bFrozen" => AttributeDecoding::Boolean,
+    "TAGame.RBActor_TA:bIgnoreSyncing" => AttributeDecoding::Boolean,
+    "TAGame.RBActor_TA:bReplayActor" => AttributeDecoding::Boolean,
+    "TAGame.RBActor_TA:ReplicatedRBState" => AttributeDecoding::RigidBody,
+    "TAGame.RBActor_TA:WeldedInfo" => AttributeDecoding::Welded,
+    "TAGame.SpecialPickup_BallFreeze_TA:RepOrigSpeed" => AttributeDecoding::Float,
+    "TAGame.SpecialPickup_BallVelcro_TA:AttachTime" => AttributeDecoding::Float,
+    "TAGame.SpecialPickup_BallVelcro_TA:bBroken" => AttributeDecoding::Boolean,
+    "TAGame.SpecialPickup_BallVelcro_TA:bHit" => AttributeDecoding::Boolean,
+    "TAGame.SpecialPickup_BallVelcro_TA:BreakTime" => AttributeDecoding::Float,
+    "TAGame.SpecialPickup_Targeted_TA:Targeted" => AttributeDecoding::ActiveActor,
+    "TAGame.SpecialPickup_Football_TA:WeldedBall" => AttributeDecoding::ActiveActor,
+    "TAGame.Team_Soccar_TA:GameScore" => AttributeDecoding::Int,
+    "TAGame.Team_TA:ClubColors" => AttributeDecoding::ClubColors,
+    "TAGame.Team_TA:ClubID" => AttributeDecoding::Int64,
+    "TAGame.Team_TA:CustomTeamName" => AttributeDecoding::String,
+    "TAGame.Team_TA:Difficulty" => AttributeDecoding::Int,
+    "TAGame.Team_TA:GameEvent" => AttributeDecoding::ActiveActor,
+    "TAGame.Team_TA:LogoData" => AttributeDecoding::ActiveActor,
+    "TAGame.Vehicle_TA:bDriving" => AttributeDecoding::Boolean,
+    "TAGame.Vehicle_TA:bPodiumMode" => AttributeDecoding::Boolean,
+    "TAGame.Vehicle_TA:bReplicatedHandbrake" => AttributeDecoding::Boolean,
+    "TAGame.Vehicle_TA:ReplicatedSteer" => AttributeDecoding::Byte,
+    "TAGame.Vehicle_TA:ReplicatedThrottle" => AttributeDecoding::Byte,
+    "TAGame.VehiclePickup_TA:bNoPickup" => AttributeDecoding::Boolean,
+    "TAGame.VehiclePickup_TA:ReplicatedPickupData" => AttributeDecoding::Pickup,
+    "TAGame.VehiclePickup_TA:NewReplicatedPickupData" => AttributeDecoding::PickupNew,
+    "TAGame.Ball_Haunted_TA:LastTeamTouch" => AttributeDecoding::Byte,
+    "TAGame.Ball_Haunted_TA:TotalActiveBeams" => AttributeDecoding::Byte,
+    "TAGame.Ball_Haunted_TA:DeactivatedGoalIndex" => AttributeDecoding::Byte,
+    "TAGame.Ball_Haunted_TA:ReplicatedBeamBrokenValue" => AttributeDecoding::Byte,
+    "TAGame.Ball_Haunted_TA:bIsBallBeamed" => AttributeDecoding::Boolean,
+    "TAGame.SpecialPickup_Rugby_TA:bBallWelded" => AttributeDecoding::Boolean,
+    "TAGame.Cannon_TA:Pitch" => AttributeDecoding::Float,
+    "TAGame.Cannon_TA:FireCount" => AttributeDecoding::Byte,
+    "TAGame.PlayerStart_Platform_TA:bActive" => AttributeDecoding::Boolean,
+    "TAGame.Stunlock_TA:Car" => AttributeDecoding::ActiveActor,
+    "TAGame.CarComponent_AirActivate_TA:AirActivateCount" => AttributeDecoding::Int,
+    "TAGame.CarComponent_DoubleJump_TA:DoubleJumpImpulse" => AttributeDecoding::Location,
+    "TAGame.CarComponent_Dodge_KO_TA:DodgeRotationCompressed" => AttributeDecoding::Int,
+    "TAGame.Car_KnockOut_TA:ReplicatedStateChanged" => AttributeDecoding::Byte,
+    "TAGame.Car_KnockOut_TA:ReplicatedStateName" => AttributeDecoding::Int,
+    "TAGame.Car_KnockOut_TA:UsedAttackComponent" => AttributeDecoding::ActiveActor,
+    "TAGame.Car_KnockOut_TA:ReplicatedImpulse" => AttributeDecoding::Impulse,
+    "TAGame.PRI_KnockOut_TA:Knockouts" => AttributeDecoding::Int,
+    "TAGame.PRI_KnockOut_TA:KnockoutDeaths" => AttributeDecoding::Int,
+    "TAGame.PRI_KnockOut_TA:DamageCaused" => AttributeDecoding::Int,
+    "TAGame.PRI_KnockOut_TA:Hits" => AttributeDecoding::Int,
+    "TAGame.PRI_KnockOut_TA:Grabs" => AttributeDecoding::Int,
+    "TAGame.PRI_KnockOut_TA:Blocks" => AttributeDecoding::Int,
+    "TAGame.PRI_KnockOut_TA:bIsActiveMVP" => AttributeDecoding::Boolean,
+    "TAGame.CarComponent_Torque_TA:ReplicatedTorqueInput" => AttributeDecoding::Int,
+    "TAGame.CarComponent_Torque_TA:TorqueScale" => AttributeDecoding::Float,
+    "TAGame.Stunlock_TA:StunTimeRemaining" => AttributeDecoding::Float,
+    "TAGame.Stunlock_TA:MaxStunTime" => AttributeDecoding::Float,
 };
 
 pub(crate) fn object_classes() -> Vec<(&'static str, &'static str)> {
@@ -509,3 +511,146 @@ pub(crate) static PARENT_CLASSES: phf::Map<&'static str, &'static str> = phf::ph
     "TAGame.CarComponent_Boost_KO_TA" => "TAGame.CarComponent_Boost_TA",
     "TAGame.GameEvent_KnockOut_TA" => "TAGame.GameEvent_Soccar_TA",
 };
+
+pub(crate) static TITLES: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "Est_2015" => "Est. 2015",
+    "Est_2016" => "Est. 2016",
+    "Est_2017" => "Est. 2017",
+    "Est_2018" => "Est. 2018",
+    "XP_Rookie" => "Rookie",
+    "XP_SemiPro" => "Semi-Pro",
+    "XP_Pro" => "Pro",
+    "XP_Veteran" => "Veteran",
+    "XP_Expert" => "Expert",
+    "XP_Master" => "Master",
+    "XP_Legend" => "Legend",
+    "XP_Rocketeer" => "Rocketeer",
+    "XP_AllStar" => "All-Star",
+    "XP_SupremeRival" => "Supreme Rival",
+    "XP_EliteChallenger" => "Elite Challenger",
+    "XP_UltimateBaller" => "Ultimate Baller",
+    "XP_SupersonicLegend" => "Supersonic Legend",
+    "FR_BackboardBaller" => "Backboard Baller",
+    "RP1_AbsoluteMadman" => "Absolute Madman",
+    "RP1_BallChaser" => "Ball Chaser",
+    "RP1_CaptainClutch" => "Captain Clutch",
+    "RP1_CrossbarHero" => "Crossbar Hero",
+    "RP1_Sherpa" => "Sherpa",
+    "RP1_Skylord" => "Skylord",
+    "RP1_TacticalWhiffer" => "Tactical Whiffer",
+    "RP1_TheFabulous" => "The Fabulous",
+    "RP2_BackseatDriver" => "Backseat Driver",
+    "RP2_BoostHog" => "Boost Hog",
+    "RP2_Leadfoot" => "Leadfoot",
+    "RP2_ProfessorOfPhysics" => "Professor of Physics",
+    "RP2_ReliableRotator" => "Reliable Rotator",
+    "RP2_TheInsane" => "The Insane",
+    "RP2_WallCrawler" => "Wall Crawler",
+    "RP3_Ballistic" => "Ballistic",
+    "RP3_Floater" => "Floater",
+    "RP3_Goaltender" => "Goaltender",
+    "RP3_Reckless" => "Reckless",
+    "RP3_Showboat" => "Showboat",
+    "RP3_Steamroller" => "Steamroller",
+    "RP3_SundayDriver" => "Sunday Driver",
+    "RP3_TheFearless" => "The Fearless",
+    "RP3_WildCard" => "Wild Card",
+    "RP4_DriftKing" => "Drift King",
+    "RP4_RankAmateur" => "Rank Amateur",
+    "RP5_OGPlayer" => "OG Player",
+    "RP5_SoccerSenpai" => "Soccer Senpai",
+    "RP5_TheFantastic" => "The Fantastic",
+    "RP6_Fliptastic" => "Fliptastic",
+    "RP6_Technician" => "Technician",
+    "RP7_Gearshifter" => "Gearshifter",
+    "RP7_Passtronaut" => "Passtronaut",
+    "RP7_RocketRookie" => "Rocket Rookie",
+    "RP8_Diva" => "Diva",
+    "RP8_The_Entertainer" => "The Entertainer",
+    "RP12_Flat_Washed" => "Flat Washed",
+    "RP12_Major_Creator" => "Major Creator",
+    "RP14_Urbanite" => "Urbanite",
+    "SE_Agent007" => "Agent 007",
+    "SE_DarkKnight" => "Dark Knight",
+    "SE_DEMOgorgon" => "DEMOgorgon",
+    "SE_Long_Time_Fan" => "Long Time Fan",
+    "SE_PeppermintPowerhouse" => "Peppermint Powerhouse",
+    "Renegade_Cup_Elite" => "Renegade Cup Elite",
+    "S16_SuperSonic_Legend" => "Season 16 Supersonic Legend",
+    "S17_SuperSonic_Legend" => "Season 17 Supersonic Legend",
+    "S17_Grand_Champion_Rumble" => "Season 17 Grand Champion (Rumble)",
+};
+
+/// The first day of every free to play season, from oldest to newest, in the format of a
+/// replay's "Date" property. The competitive seasons that preceded free to play aren't listed.
+pub(crate) static SEASONS: [(&str, &str); 14] = [
+    ("2020-09-23", "Season 1"),
+    ("2020-12-09", "Season 2"),
+    ("2021-04-07", "Season 3"),
+    ("2021-08-11", "Season 4"),
+    ("2021-11-17", "Season 5"),
+    ("2022-03-09", "Season 6"),
+    ("2022-06-15", "Season 7"),
+    ("2022-09-07", "Season 8"),
+    ("2022-12-07", "Season 9"),
+    ("2023-03-08", "Season 10"),
+    ("2023-06-07", "Season 11"),
+    ("2023-09-06", "Season 12"),
+    ("2023-12-06", "Season 13"),
+    ("2024-03-06", "Season 14"),
+];
+
+/// Game versions by the engine build ("BuildVersion") that shipped them
+pub(crate) static PATCHES: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "180517.71295.194805" => "v1.44",
+    "200528.64125.276964" => "v1.78",
+    "210802.68523.337523" => "v2.01",
+    "221120.42953.406184" => "v2.23",
+};
+
+/// Game versions by the version triplet (major, minor, net) that they introduced, for builds
+/// missing from [`PATCHES`]. Patches that don't change the replay format keep the triplet of the
+/// patch before them, so a triplet only identifies the earliest patch that could have recorded a
+/// replay.
+pub(crate) static PATCH_ERAS: [((i32, i32, i32), &str); 3] = [
+    ((868, 22, 7), "v1.44"),
+    ((868, 29, 10), "v1.78"),
+    ((868, 32, 10), "v2.01"),
+];
+
+/// Visual variants by the token in the internal stadium name that selects them (eg: "Rainy" in
+/// "EuroStadium_Rainy_P"). Stormy skins are named foggy or rainy depending on the arena.
+pub(crate) static ARENA_VARIANTS: phf::Map<&'static str, ArenaVariant> = phf::phf_map! {
+    "standard" => ArenaVariant::Standard,
+    "s" => ArenaVariant::Standard,
+    "day" => ArenaVariant::Day,
+    "dawn" => ArenaVariant::Dawn,
+    "dusk" => ArenaVariant::Dusk,
+    "night" => ArenaVariant::Night,
+    "foggy" => ArenaVariant::Stormy,
+    "rainy" => ArenaVariant::Stormy,
+    "snow" => ArenaVariant::Snowy,
+    "snowy" => ArenaVariant::Snowy,
+    "winter" => ArenaVariant::Snowy,
+    "haunted" => ArenaVariant::Haunted,
+};
+
+pub(crate) static GAME_MODES: phf::Map<&'static str, GameMode> = phf::phf_map! {
+    "Archetypes.GameEvent.GameEvent_Soccar" => GameMode::Soccar,
+    "Archetypes.GameEvent.GameEvent_SoccarLan" => GameMode::Soccar,
+    "Archetypes.GameEvent.GameEvent_SoccarPrivate" => GameMode::Soccar,
+    "Archetypes.GameEvent.GameEvent_SoccarSplitscreen" => GameMode::Soccar,
+    "Archetypes.GameEvent.GameEvent_Season" => GameMode::Soccar,
+    "Archetypes.GameEvent.GameEvent_Basketball" => GameMode::Hoops,
+    "Archetypes.GameEvent.GameEvent_BasketballPrivate" => GameMode::Hoops,
+    "Archetypes.GameEvent.GameEvent_BasketballSplitscreen" => GameMode::Hoops,
+    "Archetypes.GameEvent.GameEvent_Breakout" => GameMode::Dropshot,
+    "Archetypes.GameEvent.GameEvent_Items" => GameMode::Rumble,
+    "Archetypes.GameEvent.GameEvent_Hockey" => GameMode::Snowday,
+    "Archetypes.GameEvent.GameEvent_HockeyPrivate" => GameMode::Snowday,
+    "Archetypes.GameEvent.GameEvent_HockeySplitscreen" => GameMode::Snowday,
+    "gameinfo_godball.GameInfo.gameinfo_godball:Archetype" => GameMode::Heatseeker,
+    "GameInfo_FootBall.GameInfo.GameInfo_FootBall:Archetype" => GameMode::Gridiron,
+    "Archetypes.KnockOut.GameEvent_Knockout" => GameMode::Knockout,
+    "GameInfo_Tutorial.GameEvent.GameEvent_Tutorial_Aerial" => GameMode::Training,
+};
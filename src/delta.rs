@@ -0,0 +1,357 @@
+//! Delta encoding of the reconstructed actor state of every frame.
+//!
+//! Network frames are already deltas, but of the wire protocol: keyframes re-send actors that
+//! already exist, attributes are re-replicated with unchanged values, and updates can target
+//! actors that are no longer alive. Consumers that store the state of every frame would otherwise
+//! need to re-run the [`ActorStateModeler`](crate::ActorStateModeler) when reading it back.
+//! [`encode`] records only what changes between the reconstructed states of consecutive frames,
+//! and [`decode`] (or [`Decoder`] for one frame at a time) turns the deltas back into states that
+//! are identical to those of the modeler.
+
+use crate::network::attributes::Attribute;
+use crate::network::{ActorId, Frame, NewActor, ObjectId, UpdatedAttribute};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// An actor as reconstructed at a frame
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct ActorSnapshot {
+    pub object_id: ObjectId,
+    pub name_id: Option<i32>,
+
+    /// The latest value of every attribute that has been replicated for the actor
+    pub attributes: BTreeMap<ObjectId, Attribute>,
+}
+
+/// The state of every live actor after a frame has been applied
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct FrameState {
+    pub time: f32,
+    pub delta: f32,
+    pub actors: BTreeMap<ActorId, ActorSnapshot>,
+}
+
+impl FrameState {
+    /// The number of attribute values held across all actors
+    pub fn attribute_count(&self) -> usize {
+        self.actors.values().map(|x| x.attributes.len()).sum()
+    }
+}
+
+/// An actor that was created in the frame. Its attributes are listed with the frame's changes.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct AddedActor {
+    pub actor_id: ActorId,
+    pub object_id: ObjectId,
+    pub name_id: Option<i32>,
+}
+
+/// An attribute whose value differs from the previous frame's
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct AttributeChange {
+    pub actor_id: ActorId,
+    pub object_id: ObjectId,
+    pub attribute: Attribute,
+}
+
+/// The changes between the reconstructed states of two consecutive frames. They are applied in
+/// field order: removed actors, then added actors, then attribute changes. An actor that is
+/// replaced by one of another type is both removed and added. Each list is ordered by actor id
+/// (and then attribute object id).
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct FrameDelta {
+    pub time: f32,
+    pub delta: f32,
+    pub removed: Vec<ActorId>,
+    pub added: Vec<AddedActor>,
+    pub changed: Vec<AttributeChange>,
+}
+
+/// Encodes the reconstructed state after each frame as the changes from the state after the
+/// previous frame (the first frame's delta is from no actors). Actors re-sent by keyframes and
+/// attributes re-replicated with the same value produce no changes.
+pub fn encode(frames: &[Frame]) -> Vec<FrameDelta> {
+    let mut actors: BTreeMap<ActorId, ActorSnapshot> = BTreeMap::new();
+    frames
+        .iter()
+        .map(|x| encode_frame(&mut actors, x))
+        .collect()
+}
+
+/// Decodes every frame's state from the deltas returned by [`encode`]
+pub fn decode(deltas: &[FrameDelta]) -> Vec<FrameState> {
+    let mut decoder = Decoder::new();
+    deltas.iter().map(|x| decoder.apply(x).clone()).collect()
+}
+
+/// The reconstructed state after each frame, the same as what [`decode`] returns for the
+/// encoded frames but without the intermediate deltas
+pub fn states(frames: &[Frame]) -> Vec<FrameState> {
+    let mut actors: BTreeMap<ActorId, ActorSnapshot> = BTreeMap::new();
+    frames
+        .iter()
+        .map(|frame| {
+            encode_frame(&mut actors, frame);
+            FrameState {
+                time: frame.time,
+                delta: frame.delta,
+                actors: actors.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Decodes deltas one at a time so that only the current state is held in memory
+#[derive(Debug, Clone, Default)]
+pub struct Decoder {
+    state: FrameState,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the delta to the previous frame's state and returns the resulting state
+    pub fn apply(&mut self, delta: &FrameDelta) -> &FrameState {
+        let actors = &mut self.state.actors;
+        for actor_id in &delta.removed {
+            actors.remove(actor_id);
+        }
+
+        for added in &delta.added {
+            actors.insert(
+                added.actor_id,
+                ActorSnapshot {
+                    object_id: added.object_id,
+                    name_id: added.name_id,
+                    attributes: BTreeMap::new(),
+                },
+            );
+        }
+
+        for change in &delta.changed {
+            if let Some(actor) = actors.get_mut(&change.actor_id) {
+                actor
+                    .attributes
+                    .insert(change.object_id, change.attribute.clone());
+            }
+        }
+
+        self.state.time = delta.time;
+        self.state.delta = delta.delta;
+        &self.state
+    }
+
+    /// The state after the last applied delta
+    pub fn state(&self) -> &FrameState {
+        &self.state
+    }
+}
+
+/// The changes made while applying a single frame
+#[derive(Default)]
+struct FrameChanges {
+    /// Actors alive before the frame that were deleted or replaced
+    removed: BTreeSet<ActorId>,
+
+    /// Actors created in the frame
+    added: BTreeSet<ActorId>,
+
+    /// The value before the frame of each attribute updated on an actor that was not created in
+    /// the frame
+    previous: HashMap<(ActorId, ObjectId), Option<Attribute>>,
+}
+
+impl FrameChanges {
+    fn remove(&mut self, actors: &mut BTreeMap<ActorId, ActorSnapshot>, actor_id: ActorId) {
+        if actors.remove(&actor_id).is_some() && !self.added.remove(&actor_id) {
+            self.removed.insert(actor_id);
+        }
+    }
+
+    fn add(&mut self, actors: &mut BTreeMap<ActorId, ActorSnapshot>, new_actor: &NewActor) {
+        if let Some(actor) = actors.get(&new_actor.actor_id) {
+            if actor.object_id == new_actor.object_id {
+                return;
+            }
+
+            self.remove(actors, new_actor.actor_id);
+        }
+
+        actors.insert(
+            new_actor.actor_id,
+            ActorSnapshot {
+                object_id: new_actor.object_id,
+                name_id: new_actor.name_id,
+                attributes: BTreeMap::new(),
+            },
+        );
+        self.added.insert(new_actor.actor_id);
+    }
+
+    fn update(&mut self, actors: &mut BTreeMap<ActorId, ActorSnapshot>, update: &UpdatedAttribute) {
+        if let Some(actor) = actors.get_mut(&update.actor_id) {
+            let previous = actor
+                .attributes
+                .insert(update.object_id, update.attribute.clone());
+            if !self.added.contains(&update.actor_id) {
+                self.previous
+                    .entry((update.actor_id, update.object_id))
+                    .or_insert(previous);
+            }
+        }
+    }
+}
+
+/// Applies the frame with the same rules as the actor state modeler and returns what changed
+fn encode_frame(actors: &mut BTreeMap<ActorId, ActorSnapshot>, frame: &Frame) -> FrameDelta {
+    let mut changes = FrameChanges::default();
+    for actor_id in &frame.deleted_actors {
+        changes.remove(actors, *actor_id);
+    }
+
+    for new_actor in &frame.new_actors {
+        changes.add(actors, new_actor);
+    }
+
+    for update in &frame.updated_actors {
+        changes.update(actors, update);
+    }
+
+    let mut changed = Vec::new();
+    for actor_id in &changes.added {
+        if let Some(actor) = actors.get(actor_id) {
+            changed.extend(
+                actor
+                    .attributes
+                    .iter()
+                    .map(|(object_id, attribute)| AttributeChange {
+                        actor_id: *actor_id,
+                        object_id: *object_id,
+                        attribute: attribute.clone(),
+                    }),
+            );
+        }
+    }
+
+    for ((actor_id, object_id), previous) in changes.previous {
+        let current = actors
+            .get(&actor_id)
+            .and_then(|x| x.attributes.get(&object_id));
+        if let Some(attribute) = current {
+            if previous.as_ref() != Some(attribute) {
+                changed.push(AttributeChange {
+                    actor_id,
+                    object_id,
+                    attribute: attribute.clone(),
+                });
+            }
+        }
+    }
+
+    changed.sort_by_key(|x| (x.actor_id, x.object_id));
+    let added = changes
+        .added
+        .iter()
+        .filter_map(|actor_id| {
+            let actor = actors.get(actor_id)?;
+            Some(AddedActor {
+                actor_id: *actor_id,
+                object_id: actor.object_id,
+                name_id: actor.name_id,
+            })
+        })
+        .collect();
+
+    FrameDelta {
+        time: frame.time,
+        delta: frame.delta,
+        removed: changes.removed.into_iter().collect(),
+        added,
+        changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{StreamId, Trajectory};
+
+    fn new_actor(actor_id: i32, object_id: i32) -> NewActor {
+        NewActor {
+            actor_id: ActorId(actor_id),
+            name_id: None,
+            object_id: ObjectId(object_id),
+            initial_trajectory: Trajectory {
+                location: None,
+                rotation: None,
+            },
+        }
+    }
+
+    fn update(actor_id: i32, value: u8) -> UpdatedAttribute {
+        UpdatedAttribute {
+            actor_id: ActorId(actor_id),
+            stream_id: StreamId(0),
+            object_id: ObjectId(5),
+            attribute: Attribute::Byte(value),
+        }
+    }
+
+    fn frame(
+        new_actors: Vec<NewActor>,
+        deleted_actors: Vec<ActorId>,
+        updated_actors: Vec<UpdatedAttribute>,
+    ) -> Frame {
+        Frame {
+            time: 0.0,
+            delta: 0.0,
+            new_actors,
+            deleted_actors,
+            updated_actors,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_state_has_empty_delta() {
+        let frames = [
+            frame(vec![new_actor(1, 2)], vec![], vec![update(1, 10)]),
+            frame(
+                vec![new_actor(1, 2)],
+                vec![],
+                vec![update(1, 10), update(3, 1)],
+            ),
+            frame(vec![], vec![], vec![update(1, 11), update(1, 10)]),
+        ];
+
+        let deltas = encode(&frames);
+        assert_eq!(deltas[0].added.len(), 1);
+        assert_eq!(deltas[0].changed.len(), 1);
+        assert_eq!(deltas[1], FrameDelta::default());
+        assert_eq!(deltas[2], FrameDelta::default());
+        assert_eq!(decode(&deltas), states(&frames));
+    }
+
+    #[test]
+    fn test_replaced_actor_is_removed_and_added() {
+        let frames = [
+            frame(vec![new_actor(1, 2)], vec![], vec![update(1, 10)]),
+            frame(vec![new_actor(1, 3)], vec![], vec![update(1, 10)]),
+            frame(vec![new_actor(2, 3)], vec![ActorId(1)], vec![]),
+            frame(vec![new_actor(1, 2)], vec![ActorId(1)], vec![]),
+        ];
+
+        let deltas = encode(&frames);
+        assert_eq!(deltas[1].removed, vec![ActorId(1)]);
+        assert_eq!(deltas[1].added[0].object_id, ObjectId(3));
+        assert_eq!(deltas[1].changed.len(), 1);
+        assert_eq!(deltas[2].removed, vec![ActorId(1)]);
+        assert_eq!(deltas[3].removed, vec![]);
+        assert_eq!(deltas[3].added[0].actor_id, ActorId(1));
+
+        let decoded = decode(&deltas);
+        assert_eq!(decoded, states(&frames));
+        assert_eq!(decoded[2].actors.len(), 1);
+        assert_eq!(decoded[3].actors.len(), 2);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::data::ATTRIBUTES;
+use crate::network::attributes::UniqueId;
 use crate::network::{ActorId, Frame, NewActor, ObjectId, StreamId, UpdatedAttribute};
 use fnv::FnvHashMap;
 use std::error::Error;
@@ -6,6 +7,7 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::str;
+use std::time::Duration;
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum ParseError {
@@ -19,6 +21,16 @@ pub enum ParseError {
     CorruptReplay(String, Box<ParseError>),
     ListTooLarge(usize),
     NetworkError(Box<NetworkError>),
+
+    /// The replay's net version is outside of the range required by
+    /// [`ParserBuilder::require_net_version_range`](crate::ParserBuilder::require_net_version_range):
+    /// (net version, min, max)
+    NetVersionOutOfRange(i32, i32, i32),
+
+    /// The number of decoded frames differs from the number declared in the header, as checked by
+    /// [`ParserBuilder::verify_frame_count`](crate::ParserBuilder::verify_frame_count):
+    /// (expected, decoded)
+    FrameCountMismatch(usize, usize),
 }
 
 impl Display for ParseError {
@@ -54,6 +66,16 @@ impl Display for ParseError {
                 section, bytes_read, parse_error
             ),
             ParseError::NetworkError(network_error) => write!(f, "{}", network_error),
+            ParseError::NetVersionOutOfRange(version, min, max) => write!(
+                f,
+                "Net version {} is outside of the required range of {} to {}",
+                version, min, max
+            ),
+            ParseError::FrameCountMismatch(expected, decoded) => write!(
+                f,
+                "Expected {} frames but {} were decoded",
+                expected, decoded
+            ),
         }
     }
 }
@@ -70,6 +92,24 @@ impl Error for ParseError {
     }
 }
 
+impl ParseError {
+    /// Whether the header and body of the replay were decoded and only the network data failed.
+    /// Such a replay can still be parsed when the network data is skipped or ignored on error,
+    /// while other errors leave nothing usable.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            ParseError::NetworkError(_) | ParseError::FrameCountMismatch(..)
+        )
+    }
+}
+
+impl From<NetworkError> for ParseError {
+    fn from(error: NetworkError) -> Self {
+        ParseError::NetworkError(Box::new(error))
+    }
+}
+
 impl From<str::Utf8Error> for ParseError {
     fn from(error: str::Utf8Error) -> Self {
         ParseError::Utf8Error(error)
@@ -404,6 +444,11 @@ pub enum NetworkError {
     ParentHasNoAttributes(ObjectId, ObjectId),
     FrameError(FrameError, Box<FrameContext>),
     TooManyFrames(i32),
+
+    /// Decoding took longer than the deadline set with
+    /// [`ParserBuilder::with_deadline`](crate::ParserBuilder::with_deadline): (deadline, frames
+    /// decoded before the deadline passed)
+    DecodeTimeout(Duration, Vec<Frame>),
 }
 
 impl Error for NetworkError {
@@ -438,6 +483,12 @@ impl Display for NetworkError {
                 parent_id, object_id
             ),
             NetworkError::TooManyFrames(size) => write!(f, "Too many frames to decode: {}", size),
+            NetworkError::DecodeTimeout(deadline, frames) => write!(
+                f,
+                "Decoding exceeded the deadline of {:?} after {} frames",
+                deadline,
+                frames.len()
+            ),
             NetworkError::FrameError(err, context) => {
                 write!(f, "Error decoding frame: {}. ", err)?;
                 err.contextualize(f, context)?;
@@ -446,3 +497,188 @@ impl Display for NetworkError {
         }
     }
 }
+
+/// Why a [`single_player_timeline`](crate::single_player_timeline) couldn't be reconstructed
+#[derive(PartialEq, Debug, Clone)]
+pub enum TimelineError {
+    /// The network data was not parsed
+    NetworkDataMissing,
+
+    /// The player doesn't appear in the network data
+    PlayerNotFound(UniqueId),
+}
+
+impl Error for TimelineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for TimelineError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TimelineError::NetworkDataMissing => write!(f, "Network data was not parsed"),
+            TimelineError::PlayerNotFound(id) => {
+                write!(
+                    f,
+                    "Player not found in the network data: {:?}",
+                    id.remote_id
+                )
+            }
+        }
+    }
+}
+
+/// Why a [`ReplayProcessor`](crate::ReplayProcessor) couldn't produce a player's frame
+#[derive(PartialEq, Debug, Clone)]
+pub enum ProcessorError {
+    /// The player hasn't appeared in the frames processed so far
+    PlayerNotFound(UniqueId),
+
+    /// The player isn't driving a live car, as when demolished or spectating
+    CarNotFound(UniqueId),
+
+    /// The car hasn't replicated its rigid body
+    RigidBodyMissing(ActorId),
+
+    /// The car hasn't replicated its boost amount
+    BoostMissing(ActorId),
+}
+
+impl Error for ProcessorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for ProcessorError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ProcessorError::PlayerNotFound(id) => {
+                write!(f, "Player not found: {:?}", id.remote_id)
+            }
+            ProcessorError::CarNotFound(id) => {
+                write!(f, "Player is not driving a car: {:?}", id.remote_id)
+            }
+            ProcessorError::RigidBodyMissing(car) => {
+                write!(f, "Car {} has not replicated its rigid body", car)
+            }
+            ProcessorError::BoostMissing(car) => {
+                write!(f, "Car {} has not replicated its boost amount", car)
+            }
+        }
+    }
+}
+
+/// A recoverable oddity encountered while parsing a replay that otherwise parsed successfully.
+/// Warnings are only gathered when requested with
+/// [`ParserBuilder::collect_errors`](crate::ParserBuilder::collect_errors).
+#[derive(PartialEq, Debug, Clone)]
+pub enum ParseWarning {
+    /// The network data failed to decode and was skipped, as the parser was configured to ignore
+    /// network errors
+    NetworkDataSkipped(Box<NetworkError>),
+
+    /// The header does not declare the number of frames, so the network data was not decoded
+    MissingFrameCount,
+
+    /// The network data ended before the number of frames declared in the header were decoded
+    FrameCountMismatch { expected: usize, decoded: usize },
+
+    /// A frame failed to decode and decoding resumed at the next plausible frame boundary, as the
+    /// parser was configured to [attempt a resync](crate::ParserBuilder::attempt_resync). The
+    /// frame at the given index of the decoded frames is the first after the gap, and the number
+    /// of frames skipped is estimated from the time that elapsed.
+    FramesSkipped {
+        frame: usize,
+        skipped: usize,
+        error: FrameError,
+    },
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseWarning::NetworkDataSkipped(err) => {
+                write!(f, "Network data was skipped: {}", err)
+            }
+            ParseWarning::MissingFrameCount => {
+                write!(
+                    f,
+                    "Network data was not decoded as the frame count is missing"
+                )
+            }
+            ParseWarning::FrameCountMismatch { expected, decoded } => write!(
+                f,
+                "Expected {} frames but only {} were decoded",
+                expected, decoded
+            ),
+            ParseWarning::FramesSkipped {
+                frame,
+                skipped,
+                error,
+            } => write!(
+                f,
+                "About {} frames were skipped before frame {}: {}",
+                skipped, frame, error
+            ),
+        }
+    }
+}
+
+/// Why a replay couldn't be read back with [`cache::deserialize`](crate::cache::deserialize)
+#[derive(PartialEq, Debug, Clone)]
+pub enum CacheError {
+    /// The data does not start with the cache's magic bytes
+    InvalidMagic,
+
+    /// The cache was written with another version of the format: (found, expected)
+    VersionMismatch(u8, u8),
+
+    /// The data ended in the middle of a value
+    UnexpectedEof,
+
+    /// A variable length integer is malformed or out of range for its type
+    InvalidVarint,
+
+    /// An enum tag that does not correspond to a variant: (type, tag)
+    InvalidTag(&'static str, u8),
+
+    Utf8Error(str::Utf8Error),
+
+    /// Bytes remained after the replay was decoded
+    TrailingData(usize),
+}
+
+impl Error for CacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CacheError::Utf8Error(utf8_error) => Some(utf8_error),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CacheError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CacheError::InvalidMagic => write!(f, "Data is not a boxcars replay cache"),
+            CacheError::VersionMismatch(found, expected) => write!(
+                f,
+                "Cache format version {} does not match the expected version {}",
+                found, expected
+            ),
+            CacheError::UnexpectedEof => write!(f, "Cache ended unexpectedly"),
+            CacheError::InvalidVarint => write!(f, "Cache contains a malformed integer"),
+            CacheError::InvalidTag(kind, tag) => {
+                write!(f, "Cache contains an unknown {} tag: {}", kind, tag)
+            }
+            CacheError::Utf8Error(utf8_error) => {
+                write!(f, "Unable decode cached text as utf8: {}", utf8_error)
+            }
+            CacheError::TrailingData(size) => {
+                write!(f, "Cache has {} bytes after the replay", size)
+            }
+        }
+    }
+}
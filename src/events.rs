@@ -0,0 +1,2233 @@
+//! # Events
+//!
+//! Detect gameplay events by reconstructing actor state from the network frames. Detection assumes
+//! the dimensions of a standard soccar field (see the [`field`] module), so results on
+//! non-standard arenas and other game modes may be inaccurate.
+
+use crate::data::GAME_MODES;
+use crate::field::{self, distance, Surface, BALL_RADIUS, GOAL_LINE_Y};
+use crate::models::{GameMode, HeaderProp, Replay};
+use crate::network::attributes::{Attribute, Explosion, RigidBody, UniqueId};
+use crate::network::{ActorId, Frame, ObjectId, UpdatedAttribute, Vector3f};
+use crate::processor::{
+    scale, ReplayProcessor, ACTIVE_STATE, COMPONENT_ACTIVE_KEY, DODGE_TYPE, DOUBLE_JUMP_TYPE,
+    GAME_STATE_KEY, JUMP_TYPE, TEAM_KEY, VEHICLE_KEY,
+};
+use fnv::FnvHashMap;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The maximum number of frames that a header goal and a network goal may differ by to be
+/// considered the same goal
+pub const GOAL_FRAME_WINDOW: usize = 90;
+
+/// The furthest into the future, in seconds, that the last known ball state is projected when the
+/// ball is removed from play
+const MAX_PROJECTION_SECONDS: f32 = 0.25;
+
+/// The minimum change in the ball's velocity (uu/s) between updates to be considered a touch
+const TOUCH_MIN_IMPULSE: f32 = 250.0;
+
+/// The maximum distance between the center of a car and the ball for the car to have touched it
+const TOUCH_MAX_DISTANCE: f32 = 300.0;
+
+/// Touches by the same player closer together than this (in seconds) are considered the same
+const TOUCH_DEBOUNCE_SECONDS: f32 = 0.1;
+
+/// The longest time, in seconds, between a teammate's touch and the scorer's final touch for the
+/// teammate to be credited with the assist
+pub const ASSIST_WINDOW_SECONDS: f32 = 5.0;
+
+/// The longest (seconds) that an on-target ball may be from the goal line for a defender's touch
+/// to be a save
+const SAVE_MAX_SECONDS: f32 = 1.0;
+
+/// How close (uu) to the goal line the ball must be for a save to be epic
+const EPIC_SAVE_DISTANCE: f32 = 1000.0;
+
+/// How fast (uu/s) the ball must be traveling for a save to be epic
+const EPIC_SAVE_MIN_SPEED: f32 = 1500.0;
+
+const HIT_TEAM_KEY: &str = "TAGame.Ball_TA:HitTeamNum";
+
+const PING_KEY: &str = "Engine.PlayerReplicationInfo:Ping";
+
+const SERVER_PERFORMANCE_KEY: &str = "TAGame.GameEvent_Soccar_TA:ReplicatedServerPerformanceState";
+
+const SECONDS_REMAINING_KEY: &str = "TAGame.GameEvent_Soccar_TA:SecondsRemaining";
+
+const OVERTIME_KEY: &str = "TAGame.GameEvent_Soccar_TA:bOverTime";
+
+/// The replicated ping is the round trip time divided by this many milliseconds
+const PING_MS_PER_UNIT: u16 = 4;
+
+const RUMBLE_ITEM_PREFIX: &str = "Archetypes.SpecialPickups.SpecialPickup_";
+
+/// Explosion locations are always replicated in hundredths of a world unit
+const EXPLOSION_LOCATION_SCALE: f32 = 100.0;
+
+const BOT_KEY: &str = "Engine.PlayerReplicationInfo:bBot";
+
+const TIMED_OUT_KEY: &str = "Engine.PlayerReplicationInfo:bTimedOut";
+
+const IDLE_BANNED_KEY: &str = "TAGame.PRI_TA:bIdleBanned";
+
+/// The longest time, in seconds, between a car leaving the ceiling and touching the ball for the
+/// touch to be a ceiling shot
+const CEILING_SHOT_SECONDS: f32 = 3.0;
+
+/// The longest time, in seconds, between a car leaving a wall and dodging for the dodge to be part
+/// of a wall dash
+const WALL_DASH_LEAVE_SECONDS: f32 = 0.5;
+
+/// The longest time, in seconds, between consecutive dodges of a wall dash
+const WALL_DASH_CHAIN_SECONDS: f32 = 1.5;
+
+/// The fewest dodges off a wall in a row that make a wall dash
+const WALL_DASH_MIN_DODGES: usize = 2;
+
+/// The farthest, in world units, that the ball's center may be from the car's center when viewed
+/// from above for the ball to be on the car's roof
+const DRIBBLE_MAX_DISTANCE: f32 = 160.0;
+
+/// The range of heights, in world units, of the ball's center above the car's center when the
+/// ball is on the car's roof
+const DRIBBLE_HEIGHT: (f32, f32) = (100.0, 250.0);
+
+/// The largest difference in horizontal speed, in uu/s, between the ball and the car for them to
+/// be moving together
+const DRIBBLE_MAX_RELATIVE_SPEED: f32 = 500.0;
+
+/// The shortest time, in seconds, that a car must carry the ball to be dribbling
+const DRIBBLE_MIN_SECONDS: f32 = 1.0;
+
+/// The longest time, in seconds, that the ball may bounce off the roof during a dribble
+const DRIBBLE_MAX_GAP_SECONDS: f32 = 0.25;
+
+/// How closely the underside of a car has to point at the ball for a touch to be with its wheels,
+/// as the cosine of the angle between the car's down direction and the direction to the ball
+const FLIP_RESET_ALIGNMENT: f32 = 0.7;
+
+/// The farthest, in world units, that the ball's center may be from the car's center for a touch
+/// to be with the car's wheels. The ball's radius is 91 uu and the touch is only observed once
+/// the ball's velocity changes, by which time the ball has moved away from the car.
+const FLIP_RESET_MAX_DISTANCE: f32 = 250.0;
+
+/// The time, in seconds, after a car jumps that it can use its second jump (a double jump or a
+/// dodge). A car that leaves a surface without jumping keeps its second jump until it lands.
+const FLIP_SECONDS: f32 = 1.25;
+
+/// The longest time, in seconds, that a car takes to leave the surface that it jumped off
+const JUMP_LIFTOFF_SECONDS: f32 = 0.25;
+
+/// The longest time, in seconds, between a player leaving and a bot joining the same team for the
+/// bot to be considered the player's replacement
+const BOT_TAKEOVER_SECONDS: f32 = 5.0;
+
+/// A goal as observed in the network data
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct Goal {
+    /// Index of the first frame where the ball was observed in the goal
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// The team that scored: 0 for blue and 1 for orange
+    pub scoring_team: u8,
+
+    /// Ball location in world units when the goal was detected
+    pub location: Vector3f,
+
+    /// Whether the last touch before the goal was by the team that conceded
+    pub kind: GoalKind,
+}
+
+/// Whether a goal was put in by the scoring team or by the team whose net it went into
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum GoalKind {
+    /// The last touch before the goal was by the scoring team
+    Regular,
+
+    /// The last touch before the goal was by the team that conceded
+    OwnGoal,
+
+    /// No touch was detected since the previous goal, or the team of the last toucher is unknown
+    Unknown,
+}
+
+/// The ball exploding in a goal, which the server replicates at the moment a goal is scored
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct GoalExplosion {
+    /// Index of the frame where the explosion was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// The team that scored: 0 for blue and 1 for orange
+    pub scoring_team: u8,
+
+    /// Ball location in world units when it exploded
+    pub location: Vector3f,
+
+    /// Name of the player credited with the goal. Only replays with extended explosion data
+    /// (net version 10 and later) replicate who scored.
+    pub attacker: Option<String>,
+
+    /// Unique id of the player credited with the goal
+    pub attacker_id: Option<UniqueId>,
+}
+
+/// A goal as recorded in the replay header
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct HeaderGoal {
+    pub frame: usize,
+    pub player_name: Option<String>,
+    pub team: Option<i32>,
+}
+
+/// A difference between the goals recorded in the header and the goals observed in the network
+/// data
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub enum GoalDiscrepancy {
+    /// The goal was found in both, but at different frames
+    FrameOffset {
+        header_frame: usize,
+        network_frame: usize,
+    },
+
+    /// The header records a goal that was not observed in the network data
+    MissingFromNetwork(HeaderGoal),
+
+    /// The network data contains a goal that the header does not record
+    MissingFromHeader(Goal),
+}
+
+/// Tracks the ball's path and reports when it enters a goal
+#[derive(Debug, Default)]
+struct GoalDetector {
+    /// The last distinct ball state and the frame time when it was observed
+    last: Option<(f32, RigidBody)>,
+
+    /// Set once a goal is recorded so that the ball resting in the net or exploding is not
+    /// counted again
+    latched: bool,
+
+    /// Keyframes replicate the explosion again, so only the first one for a ball is considered
+    exploded: bool,
+}
+
+impl GoalDetector {
+    /// Called when the ball actor is removed from play. Newer replays delete the ball at the same
+    /// time the goal is scored, sometimes before the ball's last replicated position is across the
+    /// line, so the last state is projected forward to the time of removal.
+    fn ball_removed(&mut self, time: f32) -> Option<Vector3f> {
+        let latched = std::mem::replace(&mut self.latched, false);
+        self.exploded = false;
+        let (last_time, body) = self.last.take()?;
+        if latched {
+            return None;
+        }
+
+        let velocity = body.linear_velocity?;
+        let dt = (time - last_time).clamp(0.0, MAX_PROJECTION_SECONDS);
+        let projected = Vector3f {
+            x: body.location.x + velocity.x * dt,
+            y: body.location.y + velocity.y * dt,
+            z: body.location.z + velocity.z * dt,
+        };
+
+        let toward_goal = velocity.y * projected.y > 0.0;
+        let at_line = projected.y.abs() >= GOAL_LINE_Y - BALL_RADIUS;
+        if toward_goal && at_line && field::in_goal_mouth(&projected) {
+            Some(projected)
+        } else {
+            None
+        }
+    }
+
+    /// Called when the server replicates the ball's goal explosion, which happens for every goal
+    /// in most replays even when the crossing itself was not replicated
+    fn exploded(&mut self, location: Vector3f) -> Option<Vector3f> {
+        if std::mem::replace(&mut self.exploded, true) {
+            return None;
+        }
+
+        if std::mem::replace(&mut self.latched, true) {
+            None
+        } else {
+            Some(location)
+        }
+    }
+
+    /// Called with the ball's state for every frame that the ball is alive
+    fn ball_moved(&mut self, time: f32, body: RigidBody) -> Option<Vector3f> {
+        if self.last.map(|(_, x)| x) != Some(body) {
+            self.last = Some((time, body));
+        }
+
+        let location = body.location;
+        if self.latched {
+            // The ball has been played back into the field
+            self.latched = location.y.abs() >= GOAL_LINE_Y;
+            return None;
+        }
+
+        if field::ball_past_goal_line(&location) && field::in_goal_mouth(&location) {
+            self.latched = true;
+            Some(location)
+        } else {
+            None
+        }
+    }
+}
+
+/// Detects goals by finding when the ball crosses a goal line in the reconstructed ball path.
+///
+/// The ball is considered scored once it has entirely crossed the goal line within the goal mouth.
+/// Ball updates are not replicated every frame, so the crossing is not always observed. In that
+/// case, the goal is placed at the frame where the ball's goal explosion is replicated, or when the
+/// ball is removed from play and its last known state projected forward to the time of removal
+/// reaches the goal line. Goals are not detected again until the ball is reset or played back into
+/// the field. An empty list is returned if the network data was not parsed.
+///
+/// Each goal is classified as an own goal when the last of the [`touches`] since the
+/// previous goal was by a player of the team defending the net that the ball went into. In the
+/// rare case that touches by both teams are detected in that same frame, the scoring team's touch
+/// is taken as the last one, so the goal is regular.
+pub fn goals_from_network(replay: &Replay) -> Vec<Goal> {
+    let touches = touches(replay);
+    let mut goals = detect_goals(replay);
+    classify_goals(&mut goals, &touches);
+    goals
+}
+
+fn detect_goals(replay: &Replay) -> Vec<Goal> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut detector = GoalDetector::default();
+    let mut goals = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        let previous_ball = processor.ball_actor();
+        processor.process_frame(frame);
+
+        let mut record = |location: Vector3f| {
+            goals.push(Goal {
+                frame: i,
+                time: frame.time,
+                scoring_team: 1 - field::defending_team(location.y),
+                location,
+                kind: GoalKind::Unknown,
+            })
+        };
+
+        let ball = processor.ball_actor();
+        if previous_ball.is_some() && previous_ball != ball {
+            if let Some(location) = detector.ball_removed(frame.time) {
+                record(location);
+            }
+        }
+
+        if let Some(body) = processor.ball_rigid_body() {
+            if let Some(location) = detector.ball_moved(frame.time, body) {
+                record(location);
+            }
+        }
+
+        let explosion = frame
+            .updated_actors
+            .iter()
+            .filter(|x| Some(x.actor_id) == ball)
+            .find_map(|x| match &x.attribute {
+                Attribute::Explosion(e) => Some(e.location),
+                Attribute::ExtendedExplosion(e) => Some(e.explosion.location),
+                _ => None,
+            });
+
+        if let Some(location) = explosion {
+            if let Some(location) = detector.exploded(scale(location, EXPLOSION_LOCATION_SCALE)) {
+                record(location);
+            }
+        }
+    }
+
+    goals
+}
+
+/// Sets the kind of each goal from the touches since the previous goal
+fn classify_goals(goals: &mut [Goal], touches: &[Touch]) {
+    let mut start = 0;
+    for goal in goals.iter_mut() {
+        let end = start + touches[start..].partition_point(|x| x.frame <= goal.frame);
+        let before = &touches[start..end];
+        start = end;
+
+        let conceding = field::defending_team(goal.location.y);
+        goal.kind = match deciding_touch(before, conceding).and_then(|x| before[x].team) {
+            Some(team) if team == conceding => GoalKind::OwnGoal,
+            Some(_) => GoalKind::Regular,
+            None => GoalKind::Unknown,
+        };
+    }
+}
+
+/// The index of the touch that decides who put the ball in: the last touch or, when several
+/// touches were detected in the last frame, the last of those not by the conceding team
+fn deciding_touch(touches: &[Touch], conceding: u8) -> Option<usize> {
+    let frame = touches.last()?.frame;
+    let first = touches.len()
+        - touches
+            .iter()
+            .rev()
+            .take_while(|x| x.frame == frame)
+            .count();
+    let attacker = touches[first..]
+        .iter()
+        .rposition(|x| x.team.is_some() && x.team != Some(conceding));
+    Some(attacker.map_or(touches.len() - 1, |x| first + x))
+}
+
+/// Extracts the ball's goal explosions, which give the network's own account of when and where
+/// each goal was scored, independent of the frames recorded in the header. The server may
+/// replicate an explosion again a short while later, so repeats of the previous explosion are
+/// skipped. An empty list is returned if the network data was not parsed.
+pub fn goal_explosions(replay: &Replay) -> Vec<GoalExplosion> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut previous: Option<Explosion> = None;
+    let mut explosions = Vec::new();
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+        for update in &frame.updated_actors {
+            let (explosion, attacker) = match &update.attribute {
+                Attribute::Explosion(e) => (*e, None),
+                Attribute::ExtendedExplosion(e) => (e.explosion, Some(e.secondary_actor)),
+                _ => continue,
+            };
+
+            if previous.replace(explosion) == Some(explosion) {
+                continue;
+            }
+
+            let location = scale(explosion.location, EXPLOSION_LOCATION_SCALE);
+            explosions.push(GoalExplosion {
+                frame: i,
+                time: frame.time,
+                scoring_team: 1 - field::defending_team(location.y),
+                location,
+                attacker: attacker
+                    .and_then(|x| processor.player_name(x))
+                    .map(String::from),
+                attacker_id: attacker
+                    .and_then(|x| processor.player_unique_id(x))
+                    .cloned(),
+            });
+        }
+    }
+
+    explosions
+}
+
+/// Extracts the goals recorded in the header's "Goals" property
+pub fn header_goals(replay: &Replay) -> Vec<HeaderGoal> {
+    let goals = match replay.property("Goals") {
+        Some(HeaderProp::Array(goals)) => goals,
+        _ => return Vec::new(),
+    };
+
+    goals
+        .iter()
+        .filter_map(|props| {
+            let mut frame = None;
+            let mut player_name = None;
+            let mut team = None;
+            for (key, value) in props {
+                match (key.as_str(), value) {
+                    ("frame", HeaderProp::Int(x)) => frame = usize::try_from(*x).ok(),
+                    ("PlayerName", HeaderProp::Str(x)) => player_name = Some(x.clone()),
+                    ("PlayerTeam", HeaderProp::Int(x)) => team = Some(*x),
+                    _ => {}
+                }
+            }
+
+            frame.map(|frame| HeaderGoal {
+                frame,
+                player_name,
+                team,
+            })
+        })
+        .collect()
+}
+
+/// Pairs each header goal with the closest network goal by the same team that is within
+/// [`GOAL_FRAME_WINDOW`] frames and reports every goal that could not be paired or was paired at a
+/// different frame. An empty result means the header and network data agree exactly.
+pub fn reconcile_goals(header: &[HeaderGoal], network: &[Goal]) -> Vec<GoalDiscrepancy> {
+    let mut matched = vec![false; network.len()];
+    let mut discrepancies = Vec::new();
+
+    for goal in header {
+        let candidate = network
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !matched[*i])
+            .filter(|(_, x)| match goal.team {
+                Some(team) => team == i32::from(x.scoring_team),
+                None => true,
+            })
+            .map(|(i, x)| (i, goal.frame.abs_diff(x.frame)))
+            .filter(|(_, dist)| *dist <= GOAL_FRAME_WINDOW)
+            .min_by_key(|(_, dist)| *dist);
+
+        match candidate {
+            Some((i, dist)) => {
+                matched[i] = true;
+                if dist != 0 {
+                    discrepancies.push(GoalDiscrepancy::FrameOffset {
+                        header_frame: goal.frame,
+                        network_frame: network[i].frame,
+                    });
+                }
+            }
+            None => discrepancies.push(GoalDiscrepancy::MissingFromNetwork(goal.clone())),
+        }
+    }
+
+    discrepancies.extend(
+        network
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(goal, _)| GoalDiscrepancy::MissingFromHeader(*goal)),
+    );
+
+    discrepancies
+}
+
+/// A player touching the ball
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Touch {
+    /// Index of the first frame where the ball's change in velocity was observed
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Name of the player whose car was closest to the ball
+    pub player: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+
+    /// Team of the player: 0 for blue and 1 for orange
+    pub team: Option<u8>,
+
+    /// Ball location in world units
+    pub location: Vector3f,
+}
+
+/// Who contributed to a goal and the touches that led up to it
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct GoalContribution {
+    pub goal: Goal,
+
+    /// The final touch before the goal. For own goals, this is the player that put the ball into
+    /// their own net.
+    pub scorer: Option<Touch>,
+
+    /// The touch of the scorer's teammate that set up the goal
+    pub assister: Option<Touch>,
+
+    /// True if the last player to touch the ball was on the team that conceded
+    pub own_goal: bool,
+
+    /// The uninterrupted run of touches by the scoring team that ended with the scorer's touch,
+    /// in order
+    pub possession: Vec<Touch>,
+}
+
+/// A defender keeping a shot on target out of their net
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Save {
+    /// Index of the frame of the saving touch
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// The defender's touch that kept the ball out
+    pub saver: Touch,
+
+    /// The opponent's touch before the save, which put the ball on target
+    pub shooter: Touch,
+
+    /// Speed of the ball (uu/s) before the save
+    pub ball_speed: f32,
+
+    /// How many seconds the ball was from crossing the goal line before the save
+    pub seconds_to_goal: f32,
+
+    /// True if the ball was fast and close to the goal line before the save
+    pub epic: bool,
+}
+
+/// A player demolishing another player's car
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Demolition {
+    /// Index of the frame where the demolition was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Name of the player that caused the demolition
+    pub attacker: Option<String>,
+
+    /// Name of the player whose car was demolished
+    pub victim: Option<String>,
+
+    /// Unique id of the player that caused the demolition
+    pub attacker_id: Option<UniqueId>,
+
+    /// Unique id of the player whose car was demolished
+    pub victim_id: Option<UniqueId>,
+
+    /// Location of the victim's car in world units, if known
+    pub location: Option<Vector3f>,
+}
+
+/// A rumble power-up
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub enum RumbleItem {
+    Boot,
+    Disruptor,
+    Freezer,
+    GrapplingHook,
+    Haymaker,
+    Magnetizer,
+    Plunger,
+    PowerHitter,
+    Spikes,
+    Swapper,
+    Tornado,
+
+    /// An item without a dedicated variant, like those of limited time modes. Contains the
+    /// suffix of the item's archetype (eg: "Batarang").
+    Other(String),
+}
+
+impl RumbleItem {
+    /// The item for the name of a special pickup archetype
+    fn from_archetype(name: &str) -> Option<RumbleItem> {
+        let item = match name.strip_prefix(RUMBLE_ITEM_PREFIX)? {
+            "CarSpring" => RumbleItem::Boot,
+            "BoostOverride" => RumbleItem::Disruptor,
+            "BallFreeze" => RumbleItem::Freezer,
+            "BallGrapplingHook" => RumbleItem::GrapplingHook,
+            "BallSpring" => RumbleItem::Haymaker,
+            "GravityWell" => RumbleItem::Magnetizer,
+            "BallLasso" => RumbleItem::Plunger,
+            "StrongHit" => RumbleItem::PowerHitter,
+            "BallVelcro" => RumbleItem::Spikes,
+            "Swapper" => RumbleItem::Swapper,
+            "Tornado" => RumbleItem::Tornado,
+            x => RumbleItem::Other(String::from(x)),
+        };
+        Some(item)
+    }
+}
+
+/// What happened to a rumble item
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RumbleAction {
+    PickedUp,
+    Used,
+
+    /// The item was removed without being used, as when its owner is demolished or a goal is
+    /// scored
+    Expired,
+}
+
+/// A player receiving, using, or losing a rumble item
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct RumbleEvent {
+    /// Index of the frame where the action was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Name of the player that holds the item
+    pub player: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+
+    pub item: RumbleItem,
+    pub action: RumbleAction,
+}
+
+/// A player's ping as replicated by the server
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct PingSample {
+    /// Index of the frame where the ping was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Round trip time in milliseconds. The ping is replicated in steps of 4 milliseconds.
+    pub ping_ms: u16,
+}
+
+/// A change in the server's reported performance
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct ServerPerformance {
+    /// Index of the frame where the state was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// The raw performance state. Zero is a healthy server, while other values signal a server
+    /// that is struggling to keep up.
+    pub state: u8,
+}
+
+/// The game event of a match: the actor that runs the match's rules and replicates its clock
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct GameEvent {
+    /// The object name of the game event's archetype (eg: "Archetypes.GameEvent.GameEvent_Soccar")
+    pub archetype: String,
+
+    /// The game mode of the archetype, or `GameMode::Unknown` for the game events of custom modes
+    pub mode: GameMode,
+}
+
+/// A change in the match clock replicated on the game event
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct ClockUpdate {
+    /// Index of the frame where the change was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Seconds left in regulation. The clock counts up from zero while in overtime.
+    pub seconds_remaining: i32,
+
+    pub overtime: bool,
+}
+
+/// Whether a player joined or left the match
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RosterAction {
+    Joined,
+    Left,
+}
+
+/// Why a player left the match
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LeaveReason {
+    /// The player left on their own: neither a dropped connection nor a removal was recorded
+    Quit,
+
+    /// The player's connection timed out
+    Disconnected,
+
+    /// The player was removed from the match for being idle
+    Removed,
+}
+
+/// A player joining or leaving a team after the match started
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct RosterChange {
+    /// Index of the frame where the change was replicated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    pub action: RosterAction,
+
+    /// Name of the player
+    pub name: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+
+    /// The team that was joined or left: 0 for blue and 1 for orange
+    pub team: u8,
+
+    /// True if the player is a bot
+    pub bot: bool,
+
+    /// For a bot that joined, the name of the player that left the team shortly before and whose
+    /// place the bot took
+    pub replaces: Option<String>,
+
+    /// Why a human player left the match. `None` for joins, for bots, for players that switched
+    /// teams, and for replays that can't record a dropped connection.
+    pub reason: Option<LeaveReason>,
+}
+
+/// A notable mechanic that was detected. See [`mechanics`] for the criteria of each.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum MechanicKind {
+    CeilingShot,
+    WallDash,
+}
+
+/// Whether a jump was the car leaving the ground or its second jump in the air
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum JumpKind {
+    Jump,
+    DoubleJump,
+}
+
+/// A player using one of their car's jumps
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct JumpEvent {
+    /// Index of the frame where the jump component was activated
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Name of the player
+    pub player: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+
+    pub kind: JumpKind,
+}
+
+/// A player performing a notable mechanic
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct MechanicEvent {
+    /// Index of the frame where the mechanic was detected: the touch of a ceiling shot or the
+    /// first dodge of a wall dash
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Name of the player
+    pub player: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+
+    pub kind: MechanicKind,
+}
+
+/// A player getting their flip back by touching the ball with all four wheels in the air
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct FlipReset {
+    /// Index of the frame of the touch that reset the flip
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Index of the frame where the player used the flip that the touch gave back
+    pub used_frame: usize,
+
+    /// Name of the player
+    pub player: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+}
+
+/// A player carrying the ball on their car's roof
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Dribble {
+    /// Index of the first frame where the ball was on the car
+    pub start_frame: usize,
+
+    /// Index of the last frame of the dribble: the last frame where the ball was on the car, or
+    /// the frame of the flick
+    pub end_frame: usize,
+
+    /// Time, in seconds, between the start and end frames
+    pub duration: f32,
+
+    /// Name of the player
+    pub player: Option<String>,
+
+    /// Unique id of the player
+    pub player_id: Option<UniqueId>,
+
+    /// True if the dribble ended with the car dodging while carrying the ball
+    pub flick: bool,
+}
+
+/// Attributes each goal detected by [`goals_from_network`] to the touches detected by
+/// [`touches`] since the previous goal. The scorer made the last touch before the goal (preferring
+/// the scoring team's touch when both teams touched the ball in that frame, as for the goal's
+/// [kind](GoalKind)). The scoring team's possession is the run of their touches back to the last
+/// touch by the other team (or by a player on an unknown team), and the assister is the last player
+/// in the possession other than the scorer who touched the ball within [`ASSIST_WINDOW_SECONDS`]
+/// of the scorer's touch. Own goals have neither an assister nor a possession.
+pub fn goal_contributions(replay: &Replay) -> Vec<GoalContribution> {
+    let touches = touches(replay);
+    let mut goals = detect_goals(replay);
+    classify_goals(&mut goals, &touches);
+
+    let mut contributions = Vec::new();
+    let mut start = 0;
+    for goal in goals {
+        let end = start + touches[start..].partition_point(|x| x.frame <= goal.frame);
+        let conceding = 1 - goal.scoring_team;
+        let before = match deciding_touch(&touches[start..end], conceding) {
+            Some(x) => &touches[start..=start + x],
+            None => &touches[start..end],
+        };
+        start = end;
+
+        let scorer = before.last();
+        let own_goal = goal.kind == GoalKind::OwnGoal;
+        let possession: Vec<Touch> = if own_goal {
+            Vec::new()
+        } else {
+            let run = before
+                .iter()
+                .rev()
+                .take_while(|x| x.team == Some(goal.scoring_team))
+                .count();
+            before[before.len() - run..].to_vec()
+        };
+
+        let assister = scorer.and_then(|scorer| {
+            possession
+                .iter()
+                .rev()
+                .take_while(|x| scorer.time - x.time <= ASSIST_WINDOW_SECONDS)
+                .find(|x| (&x.player_id, &x.player) != (&scorer.player_id, &scorer.player))
+                .cloned()
+        });
+
+        contributions.push(GoalContribution {
+            goal,
+            scorer: scorer.cloned(),
+            assister,
+            own_goal,
+            possession,
+        });
+    }
+
+    contributions
+}
+
+/// Detects saves from the [`touches`]: a touch by a player on the team defending a goal is a
+/// save when the ball, as last observed before the touch, was [on target](field::seconds_to_goal)
+/// to cross the goal line within a second and the previous touch was by an opponent (the
+/// shooter). A touch that fails to keep the ball out, where the next goal is conceded before
+/// anyone touches the ball again, is not a save, while a save whose rebound is put in by another
+/// touch still is.
+///
+/// A save is epic when the ball was within 1000 uu of the goal line and traveling at least 1500
+/// uu/s. The ball's path is projected without bounces, so shots that would only go in off the
+/// floor or a wall are not detected.
+pub fn saves(replay: &Replay) -> Vec<Save> {
+    let touches = touches(replay);
+    let goals = detect_goals(replay);
+    let mut processor = ReplayProcessor::new(replay);
+    let bodies: Vec<Option<RigidBody>> = processor
+        .frames()
+        .iter()
+        .map(|frame| {
+            processor.process_frame(frame);
+            processor.ball_rigid_body()
+        })
+        .collect();
+
+    let mut saves = Vec::new();
+    for (i, saver) in touches.iter().enumerate().skip(1) {
+        let (shooter, team) = match (&touches[i - 1], saver.team) {
+            (shooter, Some(team)) if shooter.team == Some(1 - team) => (shooter, team),
+            _ => continue,
+        };
+
+        let body = match saver.frame.checked_sub(1).and_then(|x| bodies[x]) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let velocity = match body.linear_velocity {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let seconds = match field::seconds_to_goal(&body.location, &velocity, team) {
+            Some(x) if x <= SAVE_MAX_SECONDS => x,
+            _ => continue,
+        };
+
+        let next_touch = touches.get(i + 1).map_or(usize::MAX, |x| x.frame);
+        let conceded = goals.iter().any(|goal| {
+            goal.scoring_team != team && goal.frame >= saver.frame && goal.frame < next_touch
+        });
+
+        if conceded {
+            continue;
+        }
+
+        let (x, y, z) = (velocity.x, velocity.y, velocity.z);
+        let ball_speed = (x * x + y * y + z * z).sqrt();
+        let line_distance = GOAL_LINE_Y - body.location.y.abs();
+        saves.push(Save {
+            frame: saver.frame,
+            time: saver.time,
+            saver: saver.clone(),
+            shooter: shooter.clone(),
+            ball_speed,
+            seconds_to_goal: seconds,
+            epic: line_distance <= EPIC_SAVE_DISTANCE && ball_speed >= EPIC_SAVE_MIN_SPEED,
+        });
+    }
+
+    saves
+}
+
+/// Detects touches by finding abrupt changes in the ball's velocity while a car is close enough to
+/// have made contact, or by when the ball records that a different team touched it. The touch is
+/// attributed to the closest car. Touches are not replicated, so they are inferred and glancing
+/// touches may be missed.
+pub fn touches(replay: &Replay) -> Vec<Touch> {
+    let mut processor = ReplayProcessor::new(replay);
+    let hit_team_key = processor.object_id(HIT_TEAM_KEY);
+    let mut last_body: Option<RigidBody> = None;
+    let mut last_hit_team = None;
+    let mut touches: Vec<Touch> = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        let previous_ball = processor.ball_actor();
+        processor.process_frame(frame);
+        let ball = match processor.ball_actor() {
+            Some(x) => x,
+            None => continue,
+        };
+
+        if previous_ball != Some(ball) {
+            last_body = None;
+        }
+
+        // The team is replicated again when the ball is respawned and in keyframes
+        let respawned = frame.new_actors.iter().any(|x| x.actor_id == ball);
+        let hit_team = frame
+            .updated_actors
+            .iter()
+            .filter(|x| x.actor_id == ball && Some(x.object_id) == hit_team_key)
+            .find_map(|x| match x.attribute {
+                Attribute::Byte(team) => Some(team),
+                _ => None,
+            });
+
+        let changed_team = match hit_team {
+            Some(team) if !respawned && last_hit_team != Some(team) => Some(team),
+            _ => None,
+        };
+        last_hit_team = hit_team.or(last_hit_team);
+
+        let body = match processor.ball_rigid_body() {
+            Some(x) => x,
+            None => continue,
+        };
+
+        // A ball that ignores syncing is being placed rather than hit, eg: frozen after a goal
+        let synced = !processor.ignore_syncing(ball);
+        let impulse = match last_body.replace(body) {
+            Some(previous) if synced && previous != body => {
+                match (body.linear_velocity, previous.linear_velocity) {
+                    (Some(a), Some(b)) => distance(&a, &b),
+                    _ => 0.0,
+                }
+            }
+            _ => 0.0,
+        };
+
+        let closest = |team: Option<u8>| {
+            processor
+                .cars()
+                .iter()
+                .filter(|car| {
+                    team.is_none()
+                        || processor
+                            .car_player(**car)
+                            .and_then(|x| processor.player_team(x))
+                            == team
+                })
+                .filter_map(|car| {
+                    let car_body = processor.rigid_body(*car)?;
+                    Some((*car, distance(&car_body.location, &body.location)))
+                })
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        };
+
+        let toucher = match changed_team {
+            Some(team) => closest(Some(team)),
+            None if impulse >= TOUCH_MIN_IMPULSE => {
+                closest(None).filter(|(_, dist)| *dist < TOUCH_MAX_DISTANCE)
+            }
+            None => None,
+        };
+
+        let player = match toucher {
+            Some((car, _)) => processor.car_player(car),
+            None => continue,
+        };
+
+        let touch = Touch {
+            frame: i,
+            time: frame.time,
+            player: player
+                .and_then(|x| processor.player_name(x))
+                .map(String::from),
+            player_id: player.and_then(|x| processor.player_unique_id(x)).cloned(),
+            team: player.and_then(|x| processor.player_team(x)),
+            location: body.location,
+        };
+
+        // A single hit can be spread across several updates
+        let repeated = matches!(touches.last(), Some(last)
+            if last.player == touch.player && touch.time - last.time < TOUCH_DEBOUNCE_SECONDS);
+
+        if !repeated {
+            touches.push(touch);
+        }
+    }
+
+    touches
+}
+
+/// The attacker's and victim's cars of a demolition
+type Demo = (ActorId, ActorId, Vector3f);
+
+/// Returns the demolition if the update replicates a new one on the victim's car. Keyframes and
+/// respawned cars replicate the latest demolition again, so repeats of the last demolition seen on
+/// the actor are skipped.
+pub(crate) fn new_demolition(
+    seen: &mut FnvHashMap<ActorId, Demo>,
+    frame: &Frame,
+    update: &UpdatedAttribute,
+) -> Option<Demo> {
+    let demo = match &update.attribute {
+        Attribute::Demolish(x) => (x.attacker, x.victim, x.victim_velocity),
+        Attribute::DemolishFx(x) => (x.attacker, x.victim, x.victim_velocity),
+        _ => return None,
+    };
+
+    let respawned = frame
+        .new_actors
+        .iter()
+        .any(|x| x.actor_id == update.actor_id);
+    if seen.insert(update.actor_id, demo) == Some(demo) || respawned {
+        None
+    } else {
+        Some(demo)
+    }
+}
+
+/// Extracts demolitions from the demolish attributes replicated on the victim's car. Keyframes and
+/// respawned cars replicate the latest demolition again, so repeats are skipped.
+pub fn demolitions(replay: &Replay) -> Vec<Demolition> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut seen = FnvHashMap::default();
+    let mut demolitions = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+        for update in &frame.updated_actors {
+            let (attacker, victim, _) = match new_demolition(&mut seen, frame, update) {
+                Some(x) => x,
+                None => continue,
+            };
+            let name = |car| {
+                processor
+                    .car_player(car)
+                    .and_then(|x| processor.player_name(x))
+                    .map(String::from)
+            };
+            let id = |car| {
+                processor
+                    .car_player(car)
+                    .and_then(|x| processor.player_unique_id(x))
+                    .cloned()
+            };
+
+            demolitions.push(Demolition {
+                frame: i,
+                time: frame.time,
+                attacker: name(attacker),
+                victim: name(victim),
+                attacker_id: id(attacker),
+                victim_id: id(victim),
+                location: processor.rigid_body(victim).map(|x| x.location),
+            });
+        }
+    }
+
+    demolitions
+}
+
+/// Extracts every change in each player's ping, keyed by the player's unique id. The ping is
+/// replicated every few seconds and repeated in keyframes, so only changes are reported. Empty for
+/// replays without ping updates, like offline matches.
+pub fn ping_timeline(replay: &Replay) -> HashMap<UniqueId, Vec<PingSample>> {
+    let mut processor = ReplayProcessor::new(replay);
+    let ping_key = processor.object_id(PING_KEY);
+    let mut timeline: HashMap<UniqueId, Vec<PingSample>> = HashMap::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+        for update in &frame.updated_actors {
+            let ping = match update.attribute {
+                Attribute::Byte(x) if Some(update.object_id) == ping_key => x,
+                _ => continue,
+            };
+
+            let player = match processor.player_unique_id(update.actor_id) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let samples = timeline.entry(player.clone()).or_default();
+            let ping_ms = u16::from(ping) * PING_MS_PER_UNIT;
+            if samples.last().map(|x| x.ping_ms) != Some(ping_ms) {
+                samples.push(PingSample {
+                    frame: i,
+                    time: frame.time,
+                    ping_ms,
+                });
+            }
+        }
+    }
+
+    timeline
+}
+
+/// Extracts every change in the server performance state replicated on the game event. Most
+/// replays never replicate the state, in which case the timeline is empty.
+pub fn server_performance_timeline(replay: &Replay) -> Vec<ServerPerformance> {
+    let key = match replay
+        .objects
+        .iter()
+        .position(|x| x == SERVER_PERFORMANCE_KEY)
+    {
+        Some(x) => x,
+        None => return Vec::new(),
+    };
+
+    let mut timeline: Vec<ServerPerformance> = Vec::new();
+    for (i, frame) in replay.frames_or_empty().iter().enumerate() {
+        for update in &frame.updated_actors {
+            let state = match update.attribute {
+                Attribute::Byte(x) if usize::from(update.object_id) == key => x,
+                _ => continue,
+            };
+
+            if timeline.last().map(|x| x.state) != Some(state) {
+                timeline.push(ServerPerformance {
+                    frame: i,
+                    time: frame.time,
+                    state,
+                });
+            }
+        }
+    }
+
+    timeline
+}
+
+/// Detects the game event in the network data. Game events are identified by a list of known
+/// archetypes, falling back to any actor that replicates game event attributes so that the game
+/// events of custom and community modes are found too. Returns `None` if the network data was not
+/// parsed or no game event was replicated.
+pub fn game_event(replay: &Replay) -> Option<GameEvent> {
+    let mut processor = ReplayProcessor::new(replay);
+    for frame in processor.frames() {
+        processor.process_frame(frame);
+        let actor = match processor.game_event().and_then(|x| processor.actor(x)) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let archetype = replay.objects.get(usize::from(actor.object_id()))?;
+        let mode = match GAME_MODES.get(archetype.as_str()) {
+            Some(_) => replay.game_mode(),
+            None => GameMode::Unknown,
+        };
+
+        return Some(GameEvent {
+            archetype: archetype.clone(),
+            mode,
+        });
+    }
+
+    None
+}
+
+/// Extracts every change in the match clock replicated on the game event, as detected by
+/// [`game_event`]. Replays without a replicated clock have an empty timeline.
+pub fn game_clock(replay: &Replay) -> Vec<ClockUpdate> {
+    let mut processor = ReplayProcessor::new(replay);
+    let seconds_key = match processor.object_id(SECONDS_REMAINING_KEY) {
+        Some(x) => x,
+        None => return Vec::new(),
+    };
+    let overtime_key = processor.object_id(OVERTIME_KEY);
+
+    let mut timeline: Vec<ClockUpdate> = Vec::new();
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+        let actor = match processor.game_event().and_then(|x| processor.actor(x)) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let seconds_remaining = match actor.attribute(seconds_key) {
+            Some(Attribute::Int(x)) => *x,
+            _ => continue,
+        };
+
+        let overtime = matches!(
+            overtime_key.and_then(|x| actor.attribute(x)),
+            Some(Attribute::Boolean(true))
+        );
+
+        let changed = timeline
+            .last()
+            .map(|x| (x.seconds_remaining, x.overtime) != (seconds_remaining, overtime))
+            .unwrap_or(true);
+        if changed {
+            timeline.push(ClockUpdate {
+                frame: i,
+                time: frame.time,
+                seconds_remaining,
+                overtime,
+            });
+        }
+    }
+
+    timeline
+}
+
+/// The match clock for every frame with sub-second precision, as the game would display it before
+/// rounding: seconds left in regulation, or seconds elapsed in overtime. Only whole seconds are
+/// replicated (see [`game_clock`]), so the clock is the replicated second at the frame where it
+/// changes, and in between it runs with the frames' deltas while the game's state is active. The
+/// clock is paused during the kickoff countdowns, after goals, and in the warmup before the first
+/// kickoff, and it never runs past the next second that would be replicated. Regulation's clock
+/// stops at zero while the game waits for the ball to hit the ground.
+///
+/// Frames before the clock is first replicated, and every frame of a replay without a replicated
+/// clock (eg: a match with unlimited time), are `None`. Replays that don't replicate the game's
+/// state run the clock on every frame, so the clock only pauses at whole seconds.
+pub fn continuous_clock(replay: &Replay) -> Vec<Option<f32>> {
+    let processor = ReplayProcessor::new(replay);
+    let frames = processor.frames();
+    let mut updates = game_clock(replay).into_iter().peekable();
+    let state_key = processor.object_id(GAME_STATE_KEY);
+    let active = replay.names.iter().position(|x| x == ACTIVE_STATE);
+
+    let mut state = None;
+    let mut clock: Option<(f32, ClockUpdate)> = None;
+    let mut result = Vec::with_capacity(frames.len());
+    for (i, frame) in frames.iter().enumerate() {
+        let running = state_key.is_none() || (state.is_some() && state == active);
+        for update in &frame.updated_actors {
+            if let (true, Attribute::Int(x)) =
+                (Some(update.object_id) == state_key, &update.attribute)
+            {
+                state = usize::try_from(*x).ok();
+            }
+        }
+
+        match updates.next_if(|x| x.frame == i) {
+            Some(update) => clock = Some((update.seconds_remaining as f32, update)),
+            None => {
+                if let (Some((seconds, update)), true) = (clock.as_mut(), running) {
+                    let displayed = update.seconds_remaining as f32;
+                    *seconds = if update.overtime {
+                        (*seconds + frame.delta).min(displayed + 1.0)
+                    } else {
+                        (*seconds - frame.delta).max(displayed - 1.0).max(0.0)
+                    };
+                }
+            }
+        }
+
+        result.push(clock.map(|(seconds, _)| seconds));
+    }
+
+    result
+}
+
+/// Extracts the rumble items that each player picked up and what became of them. Every item is a
+/// car component actor: it is picked up once it is attached to a car, used once the component is
+/// activated, and expires if the actor is destroyed before it was used.
+pub fn rumble(replay: &Replay) -> Vec<RumbleEvent> {
+    let mut processor = ReplayProcessor::new(replay);
+    let vehicle_key = processor.object_id(VEHICLE_KEY);
+    let active_key = processor.object_id(COMPONENT_ACTIVE_KEY);
+    let mut items: FnvHashMap<ActorId, HeldItem> = FnvHashMap::default();
+    let mut events = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+
+        let event = |held: &HeldItem, action| RumbleEvent {
+            frame: i,
+            time: frame.time,
+            player: held.player.clone(),
+            player_id: held.player_id.clone(),
+            item: held.item.clone(),
+            action,
+        };
+
+        for actor_id in &frame.deleted_actors {
+            if let Some(held) = items.remove(actor_id) {
+                if held.picked_up && !held.used {
+                    events.push(event(&held, RumbleAction::Expired));
+                }
+            }
+        }
+
+        // Keyframes re-send the items that are still alive
+        for new_actor in &frame.new_actors {
+            let item = replay
+                .objects
+                .get(usize::from(new_actor.object_id))
+                .and_then(|x| RumbleItem::from_archetype(x));
+            if let Some(item) = item {
+                items.entry(new_actor.actor_id).or_insert(HeldItem {
+                    item,
+                    player: None,
+                    player_id: None,
+                    picked_up: false,
+                    used: false,
+                });
+            }
+        }
+
+        for update in &frame.updated_actors {
+            let held = match items.get_mut(&update.actor_id) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let key = Some(update.object_id);
+            match &update.attribute {
+                Attribute::ActiveActor(car) if key == vehicle_key && !held.picked_up => {
+                    let player = processor.car_player(car.actor);
+                    held.player = player
+                        .and_then(|x| processor.player_name(x))
+                        .map(String::from);
+                    held.player_id = player.and_then(|x| processor.player_unique_id(x)).cloned();
+                    held.picked_up = true;
+                    events.push(event(held, RumbleAction::PickedUp));
+                }
+
+                // Components are active when the byte is odd
+                Attribute::Byte(x) if key == active_key && x % 2 == 1 && !held.used => {
+                    held.used = true;
+                    events.push(event(held, RumbleAction::Used));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
+
+/// A rumble item and the player that holds it
+struct HeldItem {
+    item: RumbleItem,
+    player: Option<String>,
+    player_id: Option<UniqueId>,
+    picked_up: bool,
+    used: bool,
+}
+
+/// Extracts the players that joined or left a team after the first frame. A player joins when
+/// their player replication info is assigned a team and leaves when the team is cleared or the
+/// actor is destroyed. When a player leaves an online match, a bot commonly joins their team in
+/// their place; such a bot names the player it replaced.
+///
+/// A human that leaves is annotated with the [reason](LeaveReason) from the flags replicated on
+/// their player replication info when they left: a timed out connection or a removal for being
+/// idle, or else a quit.
+pub fn roster_changes(replay: &Replay) -> Vec<RosterChange> {
+    let mut processor = ReplayProcessor::new(replay);
+    let team_key = processor.object_id(TEAM_KEY);
+    let bot_key = processor.object_id(BOT_KEY);
+    let reason_keys = (
+        processor.object_id(TIMED_OUT_KEY),
+        processor.object_id(IDLE_BANNED_KEY),
+    );
+    let mut roster: FnvHashMap<ActorId, RosterChange> = FnvHashMap::default();
+    let mut changes: Vec<RosterChange> = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        let left = |mut member: RosterChange, reason: Option<LeaveReason>| {
+            member.frame = i;
+            member.time = frame.time;
+            member.action = RosterAction::Left;
+            member.reason = reason.filter(|_| !member.bot);
+            member
+        };
+
+        for actor_id in &frame.deleted_actors {
+            if let Some(member) = roster.remove(actor_id) {
+                let reason = leave_reason(&processor, *actor_id, reason_keys);
+                changes.push(left(member, reason));
+            }
+        }
+
+        processor.process_frame(frame);
+        for update in &frame.updated_actors {
+            if team_key.is_none() || Some(update.object_id) != team_key {
+                continue;
+            }
+
+            let player = update.actor_id;
+            let team = processor.player_team(player);
+            if roster.get(&player).map(|x| x.team) == team {
+                continue;
+            }
+
+            if let Some(member) = roster.remove(&player) {
+                // Switching teams isn't leaving the match
+                let reason = match team {
+                    Some(_) => None,
+                    None => leave_reason(&processor, player, reason_keys),
+                };
+                changes.push(left(member, reason));
+            }
+
+            let team = match team {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let bot = matches!(
+                processor.actor(player).and_then(|x| x.attribute(bot_key?)),
+                Some(Attribute::Boolean(true))
+            );
+
+            let member = RosterChange {
+                frame: i,
+                time: frame.time,
+                action: RosterAction::Joined,
+                name: processor.player_name(player).map(String::from),
+                player_id: processor.player_unique_id(player).cloned(),
+                team,
+                bot,
+                replaces: None,
+                reason: None,
+            };
+
+            if i > 0 {
+                changes.push(member.clone());
+            }
+            roster.insert(player, member);
+        }
+    }
+
+    // A bot takes the place of the most recent human that left its team and wasn't replaced yet
+    let mut replaced = vec![false; changes.len()];
+    for i in 0..changes.len() {
+        let joined = &changes[i];
+        if !joined.bot || joined.action != RosterAction::Joined {
+            continue;
+        }
+
+        let leaver = (0..i).rev().find(|x| {
+            let left = &changes[*x];
+            left.action == RosterAction::Left
+                && !left.bot
+                && !replaced[*x]
+                && left.team == joined.team
+                && joined.time - left.time <= BOT_TAKEOVER_SECONDS
+        });
+
+        if let Some(x) = leaver {
+            replaced[x] = true;
+            changes[i].replaces = changes[x].name.clone();
+        }
+    }
+
+    changes
+}
+
+/// Why the player of the replication info left, from its timed out and idle banned flags. `None`
+/// if the replay has no timed out flag to record a dropped connection with.
+fn leave_reason(
+    processor: &ReplayProcessor,
+    player: ActorId,
+    (timed_out_key, idle_banned_key): (Option<ObjectId>, Option<ObjectId>),
+) -> Option<LeaveReason> {
+    let flag = |key: ObjectId| {
+        matches!(
+            processor.actor(player).and_then(|x| x.attribute(key)),
+            Some(Attribute::Boolean(true))
+        )
+    };
+
+    if flag(timed_out_key?) {
+        Some(LeaveReason::Disconnected)
+    } else if matches!(idle_banned_key, Some(key) if flag(key)) {
+        Some(LeaveReason::Removed)
+    } else {
+        Some(LeaveReason::Quit)
+    }
+}
+
+/// Tags notable mechanics by combining the [surface](field::surface) that each car is on, how long
+/// it has been in the air, its dodges, and the [`touches`]. The detection is heuristic:
+///
+/// - [`CeilingShot`](MechanicKind::CeilingShot): the car touches the ball within 3 seconds of
+///   leaving the ceiling without having landed on the ground or a wall in between. A car that
+///   falls from the ceiling and happens to bump the ball is also tagged.
+/// - [`WallDash`](MechanicKind::WallDash): the car dodges at least twice in a row, each time
+///   within half a second of leaving a wall and within 1.5 seconds of the previous dodge. A player
+///   that repeatedly dodges off a wall to get down is also tagged, while a wall dash that is
+///   replicated too coarsely to observe the car on the wall between dodges is missed.
+pub fn mechanics(replay: &Replay) -> Vec<MechanicEvent> {
+    let mut touched: FnvHashMap<usize, Vec<UniqueId>> = FnvHashMap::default();
+    for touch in touches(replay) {
+        if let Some(player) = touch.player_id {
+            touched.entry(touch.frame).or_default().push(player);
+        }
+    }
+
+    let mut processor = ReplayProcessor::new(replay);
+    let dodge_type = processor.object_id(DODGE_TYPE);
+    let vehicle_key = processor.object_id(VEHICLE_KEY);
+    let active_key = processor.object_id(COMPONENT_ACTIVE_KEY);
+    let mut cars: FnvHashMap<ActorId, CarContact> = FnvHashMap::default();
+    let mut dodging: FnvHashMap<ActorId, bool> = FnvHashMap::default();
+    let mut events = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            cars.remove(actor_id);
+            dodging.remove(actor_id);
+        }
+
+        processor.process_frame(frame);
+        let event = |car: ActorId, kind| {
+            let player = processor.car_player(car);
+            MechanicEvent {
+                frame: i,
+                time: frame.time,
+                player: player
+                    .and_then(|x| processor.player_name(x))
+                    .map(String::from),
+                player_id: player.and_then(|x| processor.player_unique_id(x)).cloned(),
+                kind,
+            }
+        };
+
+        for car in processor.cars() {
+            let contact = cars.entry(*car).or_default();
+            if let Some(body) = processor.rigid_body(*car) {
+                contact.surface = field::surface(&body.location, &body.rotation);
+                if contact.surface != Surface::Air {
+                    contact.last = Some((contact.surface, frame.time));
+                }
+            }
+
+            let player = processor
+                .car_player(*car)
+                .and_then(|x| processor.player_unique_id(x));
+            let touched = touched
+                .get(&i)
+                .into_iter()
+                .flatten()
+                .any(|x| Some(x) == player);
+
+            if let (true, Surface::Air, Some((Surface::Ceiling, left))) =
+                (touched, contact.surface, contact.last)
+            {
+                if frame.time - left <= CEILING_SHOT_SECONDS {
+                    events.push(event(*car, MechanicKind::CeilingShot));
+
+                    // Only the first touch after leaving the ceiling is the shot
+                    contact.last = None;
+                }
+            }
+        }
+
+        // A dodge starts when the dodge component of a car is activated
+        for update in &frame.updated_actors {
+            let active = match update.attribute {
+                Attribute::Byte(x) if Some(update.object_id) == active_key => x % 2 == 1,
+                _ => continue,
+            };
+
+            let component = match processor.actor(update.actor_id) {
+                Some(x) if Some(x.object_id()) == dodge_type => x,
+                _ => continue,
+            };
+
+            let was_active = dodging.insert(update.actor_id, active).unwrap_or(false);
+            if !active || was_active {
+                continue;
+            }
+
+            let car = match vehicle_key.and_then(|x| component.attribute(x)) {
+                Some(Attribute::ActiveActor(x)) => x.actor,
+                _ => continue,
+            };
+
+            let contact = match cars.get_mut(&car) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let off_wall = matches!(contact.last, Some((Surface::Wall, left))
+                if frame.time - left <= WALL_DASH_LEAVE_SECONDS);
+            if !off_wall {
+                contact.wall_dodges.clear();
+                continue;
+            }
+
+            let chained = matches!(contact.wall_dodges.last(), Some((_, last))
+                if frame.time - last <= WALL_DASH_CHAIN_SECONDS);
+            if !chained {
+                contact.wall_dodges.clear();
+            }
+
+            contact.wall_dodges.push((i, frame.time));
+            if contact.wall_dodges.len() == WALL_DASH_MIN_DODGES {
+                let (first, time) = contact.wall_dodges[0];
+                let mut dash = event(car, MechanicKind::WallDash);
+                dash.frame = first;
+                dash.time = time;
+                events.push(dash);
+            }
+        }
+    }
+
+    events.sort_by_key(|x| x.frame);
+    events
+}
+
+/// Detects flip resets: a car touching the ball with its wheels while in the air, which gives back
+/// its second jump. A touch is a flip reset candidate when:
+///
+/// - the car is in the air, per [`field::surface`]
+/// - the player made the [touch](touches)
+/// - the car's underside points toward the ball: the angle between the car's down direction and
+///   the direction to the ball has a cosine of at least 0.7 (within about 45 degrees)
+/// - the ball's center is within 250 uu of the car's center
+///
+/// Touching the ball with the wheels is not observable in itself, so a candidate is only reported
+/// once the car uses a second jump (a double jump or dodge), after the touch and before landing,
+/// that it would not have had otherwise: it had already used its second jump in the air, or the
+/// 1.25 seconds that a car has to use its second jump after jumping have passed. A flip reset that
+/// is never used, or used while the car still had its original second jump, is missed. A touch
+/// with the wheels that glances off the ball, or another car's touch attributed to the player, may
+/// be reported when the player happens to have an unexplained second jump, as when jump
+/// activations were not replicated.
+pub fn flip_resets(replay: &Replay) -> Vec<FlipReset> {
+    let mut touched: FnvHashMap<usize, Vec<UniqueId>> = FnvHashMap::default();
+    for touch in touches(replay) {
+        if let Some(player) = touch.player_id {
+            touched.entry(touch.frame).or_default().push(player);
+        }
+    }
+
+    let mut processor = ReplayProcessor::new(replay);
+    let jump_type = processor.object_id(JUMP_TYPE);
+    let double_jump_type = processor.object_id(DOUBLE_JUMP_TYPE);
+    let dodge_type = processor.object_id(DODGE_TYPE);
+    let vehicle_key = processor.object_id(VEHICLE_KEY);
+    let active_key = processor.object_id(COMPONENT_ACTIVE_KEY);
+    let mut components: FnvHashMap<ActorId, bool> = FnvHashMap::default();
+    let mut airtimes: FnvHashMap<ActorId, Airtime> = FnvHashMap::default();
+    let mut resets = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            components.remove(actor_id);
+            airtimes.remove(actor_id);
+        }
+
+        processor.process_frame(frame);
+        let ball = processor.ball_rigid_body();
+        for car in processor.cars() {
+            let body = match processor.rigid_body(*car) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            // A car that jumped is still on the surface for the first few frames of the jump
+            if field::surface(&body.location, &body.rotation) != Surface::Air {
+                let jumped = airtimes
+                    .get(car)
+                    .and_then(|x| x.jumped)
+                    .filter(|x| frame.time - x <= JUMP_LIFTOFF_SECONDS);
+                airtimes.insert(
+                    *car,
+                    Airtime {
+                        jumped,
+                        ..Airtime::default()
+                    },
+                );
+                continue;
+            }
+
+            let airtime = airtimes.entry(*car).or_default();
+            let player = processor
+                .car_player(*car)
+                .and_then(|x| processor.player_unique_id(x));
+            let touched = touched
+                .get(&i)
+                .into_iter()
+                .flatten()
+                .any(|x| Some(x) == player);
+
+            let wheels = match &ball {
+                Some(ball) => {
+                    let to_ball = (
+                        ball.location.x - body.location.x,
+                        ball.location.y - body.location.y,
+                        ball.location.z - body.location.z,
+                    );
+                    let distance = distance(&ball.location, &body.location);
+                    let up = field::up_vector(&body.rotation);
+                    let down = -(up.x * to_ball.0 + up.y * to_ball.1 + up.z * to_ball.2) / distance;
+                    distance <= FLIP_RESET_MAX_DISTANCE && down >= FLIP_RESET_ALIGNMENT
+                }
+                None => false,
+            };
+
+            if touched && wheels {
+                airtime.reset = Some((i, frame.time));
+            }
+        }
+
+        for update in &frame.updated_actors {
+            let active = match update.attribute {
+                Attribute::Byte(x) if Some(update.object_id) == active_key => x % 2 == 1,
+                _ => continue,
+            };
+
+            let component = match processor.actor(update.actor_id) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let object_id = Some(component.object_id());
+            let jump = if object_id == jump_type {
+                true
+            } else if object_id == double_jump_type || object_id == dodge_type {
+                false
+            } else {
+                continue;
+            };
+
+            let was_active = components.insert(update.actor_id, active).unwrap_or(false);
+            if !active || was_active {
+                continue;
+            }
+
+            let car = match vehicle_key.and_then(|x| component.attribute(x)) {
+                Some(Attribute::ActiveActor(x)) => x.actor,
+                _ => continue,
+            };
+
+            let airtime = airtimes.entry(car).or_default();
+            if jump {
+                *airtime = Airtime {
+                    jumped: Some(frame.time),
+                    ..Airtime::default()
+                };
+                continue;
+            }
+
+            let expired = matches!(airtime.jumped, Some(x) if frame.time - x > FLIP_SECONDS);
+            // The car's own dodge or double jump may have made the touch, as a touch is only
+            // observed once the ball's velocity changes
+            let reset = airtime.reset.filter(|(x, _)| *x < i);
+            if let (true, Some((reset, time))) = (airtime.flipped || expired, reset) {
+                let player = processor.car_player(car);
+                resets.push(FlipReset {
+                    frame: reset,
+                    time,
+                    used_frame: i,
+                    player: player
+                        .and_then(|x| processor.player_name(x))
+                        .map(String::from),
+                    player_id: player.and_then(|x| processor.player_unique_id(x)).cloned(),
+                });
+                airtime.reset = None;
+            }
+
+            airtime.flipped = true;
+        }
+    }
+
+    resets
+}
+
+/// Reconstructs when each player jumped and double jumped from the activations of their cars' jump
+/// and double jump components. A car only has one second jump in the air: a dodge consumes it, so
+/// a double jump activation that follows a dodge without a jump in between is not reported.
+/// Activations of a component whose car is not known are skipped.
+pub fn jumps(replay: &Replay) -> Vec<JumpEvent> {
+    let mut processor = ReplayProcessor::new(replay);
+    let jump_type = processor.object_id(JUMP_TYPE);
+    let double_jump_type = processor.object_id(DOUBLE_JUMP_TYPE);
+    let dodge_type = processor.object_id(DODGE_TYPE);
+    let vehicle_key = processor.object_id(VEHICLE_KEY);
+    let active_key = processor.object_id(COMPONENT_ACTIVE_KEY);
+    let mut components: FnvHashMap<ActorId, bool> = FnvHashMap::default();
+    let mut dodged: FnvHashMap<ActorId, bool> = FnvHashMap::default();
+    let mut events = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            components.remove(actor_id);
+            dodged.remove(actor_id);
+        }
+
+        processor.process_frame(frame);
+        for update in &frame.updated_actors {
+            let active = match update.attribute {
+                Attribute::Byte(x) if Some(update.object_id) == active_key => x % 2 == 1,
+                _ => continue,
+            };
+
+            let component = match processor.actor(update.actor_id) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let object_id = Some(component.object_id());
+            let kind = if object_id == jump_type {
+                Some(JumpKind::Jump)
+            } else if object_id == double_jump_type {
+                Some(JumpKind::DoubleJump)
+            } else if object_id == dodge_type {
+                None
+            } else {
+                continue;
+            };
+
+            let was_active = components.insert(update.actor_id, active).unwrap_or(false);
+            if !active || was_active {
+                continue;
+            }
+
+            let car = match vehicle_key.and_then(|x| component.attribute(x)) {
+                Some(Attribute::ActiveActor(x)) => x.actor,
+                _ => continue,
+            };
+
+            let kind = match kind {
+                Some(JumpKind::Jump) => {
+                    dodged.insert(car, false);
+                    JumpKind::Jump
+                }
+                Some(JumpKind::DoubleJump) if dodged.get(&car) != Some(&true) => {
+                    JumpKind::DoubleJump
+                }
+                Some(JumpKind::DoubleJump) => continue,
+                None => {
+                    dodged.insert(car, true);
+                    continue;
+                }
+            };
+
+            let player = processor.car_player(car);
+            events.push(JumpEvent {
+                frame: i,
+                time: frame.time,
+                player: player
+                    .and_then(|x| processor.player_name(x))
+                    .map(String::from),
+                player_id: player.and_then(|x| processor.player_unique_id(x)).cloned(),
+                kind,
+            });
+        }
+    }
+
+    events
+}
+
+/// Detects dribbles: a car carrying the ball on its roof for at least a second. The ball is on the
+/// roof while its center is within 160 uu of the car's center when viewed from above, 100 to 250
+/// uu above the car's center, and the two move together (their horizontal velocities differ by
+/// less than 500 uu/s). The ball may bounce off the roof for up to a quarter of a second without
+/// ending the dribble.
+///
+/// A dribble that ends with the car dodging (a flick) ends at the frame where the dodge starts,
+/// when the ball is still on the roof. Heights are measured along the world's z axis, so carrying
+/// the ball on the roof while driving up a wall is not detected.
+pub fn dribbles(replay: &Replay) -> Vec<Dribble> {
+    let mut processor = ReplayProcessor::new(replay);
+    let dodge_type = processor.object_id(DODGE_TYPE);
+    let vehicle_key = processor.object_id(VEHICLE_KEY);
+    let active_key = processor.object_id(COMPONENT_ACTIVE_KEY);
+    let mut dodging: FnvHashMap<ActorId, bool> = FnvHashMap::default();
+
+    // The ongoing carry of each car: the dribble so far and when the ball was last on the roof
+    let mut carries: FnvHashMap<ActorId, (Dribble, f32, f32)> = FnvHashMap::default();
+    let mut dribbles = Vec::new();
+    let mut finish = |carry: Option<(Dribble, f32, f32)>| {
+        if let Some((mut dribble, start, end)) = carry {
+            dribble.duration = end - start;
+            if dribble.duration >= DRIBBLE_MIN_SECONDS {
+                dribbles.push(dribble);
+            }
+        }
+    };
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            finish(carries.remove(actor_id));
+            dodging.remove(actor_id);
+        }
+
+        processor.process_frame(frame);
+
+        // A flick ends the dribble when the dodge starts
+        for update in &frame.updated_actors {
+            let active = match update.attribute {
+                Attribute::Byte(x) if Some(update.object_id) == active_key => x % 2 == 1,
+                _ => continue,
+            };
+
+            let component = match processor.actor(update.actor_id) {
+                Some(x) if Some(x.object_id()) == dodge_type => x,
+                _ => continue,
+            };
+
+            let was_active = dodging.insert(update.actor_id, active).unwrap_or(false);
+            if !active || was_active {
+                continue;
+            }
+
+            let car = match vehicle_key.and_then(|x| component.attribute(x)) {
+                Some(Attribute::ActiveActor(x)) => x.actor,
+                _ => continue,
+            };
+
+            if let Some((mut dribble, start, _)) = carries.remove(&car) {
+                dribble.end_frame = i;
+                dribble.flick = true;
+                finish(Some((dribble, start, frame.time)));
+            }
+        }
+
+        let ball = processor.ball_rigid_body();
+        for car in processor.cars() {
+            let carrying = match (ball, processor.rigid_body(*car)) {
+                (Some(ball), Some(body)) => on_roof(&body, &ball),
+                _ => false,
+            };
+
+            match carries.get_mut(car) {
+                Some((dribble, _, last)) if carrying => {
+                    dribble.end_frame = i;
+                    *last = frame.time;
+                }
+                Some((_, _, last)) if frame.time - *last > DRIBBLE_MAX_GAP_SECONDS => {
+                    finish(carries.remove(car));
+                }
+                Some(_) => {}
+                None if carrying => {
+                    let player = processor.car_player(*car);
+                    let dribble = Dribble {
+                        start_frame: i,
+                        end_frame: i,
+                        duration: 0.0,
+                        player: player
+                            .and_then(|x| processor.player_name(x))
+                            .map(String::from),
+                        player_id: player.and_then(|x| processor.player_unique_id(x)).cloned(),
+                        flick: false,
+                    };
+                    carries.insert(*car, (dribble, frame.time, frame.time));
+                }
+                None => {}
+            }
+        }
+    }
+
+    for (_, carry) in carries.drain() {
+        finish(Some(carry));
+    }
+
+    dribbles.sort_by_key(|x| x.start_frame);
+    dribbles
+}
+
+/// Whether the ball is resting on the car's roof and moving with the car
+fn on_roof(car: &RigidBody, ball: &RigidBody) -> bool {
+    let (x, y) = (
+        ball.location.x - car.location.x,
+        ball.location.y - car.location.y,
+    );
+    let height = ball.location.z - car.location.z;
+    let relative_speed = match (car.linear_velocity, ball.linear_velocity) {
+        (Some(a), Some(b)) => {
+            let (x, y) = (a.x - b.x, a.y - b.y);
+            (x * x + y * y).sqrt()
+        }
+        _ => return false,
+    };
+
+    (x * x + y * y).sqrt() <= DRIBBLE_MAX_DISTANCE
+        && height >= DRIBBLE_HEIGHT.0
+        && height <= DRIBBLE_HEIGHT.1
+        && relative_speed < DRIBBLE_MAX_RELATIVE_SPEED
+}
+
+/// What a car has done since it last left a surface, for detecting flip resets
+#[derive(Debug, Default)]
+struct Airtime {
+    /// When the car jumped off the surface, if it did
+    jumped: Option<f32>,
+
+    /// Whether the car has used a second jump
+    flipped: bool,
+
+    /// The frame and time of the latest touch with the car's wheels
+    reset: Option<(usize, f32)>,
+}
+
+/// Which surfaces a car has been on
+struct CarContact {
+    surface: Surface,
+
+    /// The latest surface that the car was on and when it was last there
+    last: Option<(Surface, f32)>,
+
+    /// The frames and times of the car's consecutive dodges off a wall
+    wall_dodges: Vec<(usize, f32)>,
+}
+
+impl Default for CarContact {
+    fn default() -> Self {
+        CarContact {
+            surface: Surface::Air,
+            last: None,
+            wall_dodges: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network_goal(frame: usize, scoring_team: u8) -> Goal {
+        Goal {
+            frame,
+            time: 0.0,
+            scoring_team,
+            location: Vector3f {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            kind: GoalKind::Unknown,
+        }
+    }
+
+    fn touch(frame: usize, team: u8) -> Touch {
+        Touch {
+            frame,
+            time: 0.0,
+            player: None,
+            player_id: None,
+            team: Some(team),
+            location: Vector3f {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_classify_goals() {
+        // Orange scores into the blue net (negative y)
+        let mut goals = vec![
+            network_goal(10, 1),
+            network_goal(20, 1),
+            network_goal(30, 1),
+        ];
+        for goal in goals.iter_mut() {
+            goal.location.y = -5200.0;
+        }
+
+        let touches = [
+            touch(8, 1),
+            touch(9, 0),
+            // Both teams touched the ball in the last frame before the second goal
+            touch(15, 1),
+            touch(18, 1),
+            touch(18, 0),
+        ];
+
+        classify_goals(&mut goals, &touches);
+        let kinds: Vec<_> = goals.iter().map(|x| x.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![GoalKind::OwnGoal, GoalKind::Regular, GoalKind::Unknown]
+        );
+    }
+
+    fn header_goal(frame: usize, team: i32) -> HeaderGoal {
+        HeaderGoal {
+            frame,
+            player_name: None,
+            team: Some(team),
+        }
+    }
+
+    #[test]
+    fn test_reconcile_goals() {
+        let header = vec![
+            header_goal(100, 0),
+            header_goal(500, 1),
+            header_goal(900, 0),
+        ];
+        let network = vec![
+            network_goal(100, 0),
+            network_goal(498, 1),
+            network_goal(700, 1),
+        ];
+
+        let discrepancies = reconcile_goals(&header, &network);
+        assert_eq!(
+            discrepancies,
+            vec![
+                GoalDiscrepancy::FrameOffset {
+                    header_frame: 500,
+                    network_frame: 498
+                },
+                GoalDiscrepancy::MissingFromNetwork(header_goal(900, 0)),
+                GoalDiscrepancy::MissingFromHeader(network_goal(700, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_goals_requires_same_team() {
+        let header = vec![header_goal(100, 0)];
+        let network = vec![network_goal(100, 1)];
+        assert_eq!(reconcile_goals(&header, &network).len(), 2);
+    }
+}
@@ -0,0 +1,780 @@
+//! # Export
+//!
+//! Curated, render-ready views of a replay for consumers that do not need every replicated
+//! attribute, like lightweight web viewers.
+//!
+//! ## Minimal schema
+//!
+//! [`minimal_replay`] reconstructs the ball and car transforms for every network frame alongside
+//! goal, demolition, and touch markers. When serialized (eg: with `Replay::to_minimal_json`,
+//! available with the `json` feature), the payload looks like:
+//!
+//! ```json
+//! {
+//!   "schema_version": 2,
+//!   "players": [{ "id": "Steam:76561198101748375", "name": "comagoosie", "team": 0 }],
+//!   "frames": [
+//!     {
+//!       "time": 12.5,
+//!       "ball": { "position": [0.0, 0.0, 93.0], "rotation": [0.0, 0.0, 0.0, 1.0] },
+//!       "cars": [
+//!         {
+//!           "player": 0,
+//!           "position": [0.0, -4608.0, 17.0],
+//!           "rotation": [0.0, 0.0, 0.7, 0.7],
+//!           "boost": 33.3
+//!         }
+//!       ]
+//!     }
+//!   ],
+//!   "events": [
+//!     { "type": "goal", "frame": 301, "time": 22.1, "team": 0 },
+//!     { "type": "demolition", "frame": 350, "time": 24.0, "attacker": 0, "victim": 1 },
+//!     { "type": "touch", "frame": 380, "time": 25.2, "player": 0 }
+//!   ]
+//! }
+//! ```
+//!
+//! - Positions are `[x, y, z]` in world units rounded to hundredths and rotations are
+//!   `[x, y, z, w]` quaternions rounded to four decimal places
+//! - Players are identified by their unique id, formatted like the player keys of the
+//!   [analysis schema](#analysis-schema), so players that share a name are kept apart. `id` is
+//!   `null` for players without a unique id (bots in older replays), which are told apart by name.
+//! - `player`, `attacker`, and `victim` are indices into `players` and are `null` when unknown
+//! - `team` is 0 for blue and 1 for orange and is `null` when unknown
+//! - `boost` is a percentage rounded to tenths and is `null` until the car's boost is replicated
+//! - `ball` is `null` while there is no ball in play
+//! - `frame` in events is an index into `frames`
+//!
+//! Fields will only be added or changed alongside an increase in [`MINIMAL_SCHEMA_VERSION`].
+//!
+//! For previews or smaller datasets, [`MinimalReplay::downsample`] (or
+//! `Replay::to_minimal_json_with_stride`) keeps only every Nth frame. Every frame is still
+//! decoded so that the kept frames are exact, and events on skipped frames are moved to the
+//! nearest kept frame. Only the minimal schema can be downsampled, as each of its frames is a
+//! complete snapshot of the transforms. The default serialization and the analysis schema record
+//! each frame as the actors spawned, updated, and deleted since the previous frame, so skipping a
+//! frame would drop changes that the following frames depend on.
+//!
+//! ## Analysis schema
+//!
+//! [`AnalysisReplay`] serializes every network frame like the default serialization of
+//! [`Replay`], but with the values resolved for analysis instead of kept as decoded:
+//!
+//! ```json
+//! {
+//!   "properties": { "TeamSize": 3 },
+//!   "players": { "Steam:76561198122624102": { "name": "comagoosie", "team": 0 } },
+//!   "frames": [
+//!     {
+//!       "time": 12.5,
+//!       "delta": 0.03,
+//!       "new_actors": [
+//!         {
+//!           "actor_id": 4,
+//!           "object": "Archetypes.Car.Car_Default",
+//!           "name": "Car_TA_1",
+//!           "position": [0.0, -4608.0, 17.0],
+//!           "rotation": [0.0, 0.0, 0.7071, 0.7071]
+//!         }
+//!       ],
+//!       "updated_actors": [
+//!         {
+//!           "actor_id": 4,
+//!           "attribute": "TAGame.RBActor_TA:ReplicatedRBState",
+//!           "player": "Steam:76561198122624102",
+//!           "value": {
+//!             "RigidBody": {
+//!               "sleeping": false,
+//!               "position": [0.0, -4608.0, 17.0],
+//!               "rotation": [0.0, 0.0, 0.7071, 0.7071],
+//!               "linear_velocity": [0.0, 0.0, 0.0],
+//!               "angular_velocity": [0.0, 0.0, 0.0]
+//!             }
+//!           }
+//!         }
+//!       ],
+//!       "deleted_actors": [7]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Compared to the default serialization:
+//!
+//! - Only the header properties and network frames are included
+//! - Object and name ids are replaced with the names that they index (`object`, `name`, and
+//!   `attribute`). Stream ids are omitted.
+//! - Rigid body positions and velocities are in world units and rotations are `[x, y, z, w]`
+//!   quaternions for every net version. Older replays encode them in hundredths of a world unit
+//!   and as compressed rotators.
+//! - Spawn rotations are converted from compressed rotators to quaternions
+//! - Players are keyed by their unique id formatted as `"<platform>:<online id>"` (suffixed with
+//!   `":<local id>"` for split screen guests). PlayStation players are keyed by their name as
+//!   older replays don't record their online id. `UniqueId` attributes are emitted as this key and
+//!   the updates of a player's replication info or car carry the key as `player`.
+//! - Every other attribute value is serialized as it is by default
+//!
+//! The analysis schema is lossy, so the default serialization should be used when the replay
+//! needs to be reproduced exactly.
+//!
+//! ## Pretty JSON
+//!
+//! With the `json` feature, `to_json_pretty` and `to_writer_pretty` write either the default
+//! serialization or the analysis schema as indented JSON for reading by hand. Non-finite floats are
+//! written as `null`, as JSON has no representation for them. Like the compact output of these
+//! schemas, every frame is written (see the minimal schema for downsampling).
+//!
+//! ## glTF
+//!
+//! With the `gltf` feature, `to_gltf` writes the ball and cars as animated nodes of a binary glTF
+//! file for 3D tools.
+//!
+//! ## Arrow
+//!
+//! With the `arrow` feature, `to_arrow` flattens the transforms and boost of the minimal view into
+//! an Arrow record batch with a row per entity and frame.
+
+use crate::events;
+use crate::models::{pair_vec, Replay};
+use crate::network::attributes::{Attribute, RemoteId, RigidBody, UniqueId};
+use crate::network::{ActorId, Frame, Quaternion, Rotation, Vector3f};
+use crate::processor::{rotator_to_quaternion, ReplayProcessor};
+use serde::ser::{SerializeSeq, SerializeStruct, Serializer};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "gltf")]
+mod gltf;
+#[cfg(feature = "gltf")]
+pub use self::gltf::to_gltf;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "arrow")]
+pub use self::arrow::to_arrow;
+
+/// The version of the minimal replay schema
+pub const MINIMAL_SCHEMA_VERSION: u32 = 2;
+
+/// A replay reduced to object transforms and key events
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct MinimalReplay {
+    pub schema_version: u32,
+    pub players: Vec<MinimalPlayer>,
+    pub frames: Vec<MinimalFrame>,
+    pub events: Vec<MinimalEvent>,
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct MinimalPlayer {
+    #[serde(serialize_with = "serialize_player_key")]
+    pub id: Option<UniqueId>,
+    pub name: String,
+    pub team: Option<u8>,
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct MinimalFrame {
+    pub time: f32,
+    pub ball: Option<Transform>,
+    pub cars: Vec<CarTransform>,
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct Transform {
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct CarTransform {
+    pub player: usize,
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+    pub boost: Option<f32>,
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MinimalEvent {
+    Goal {
+        frame: usize,
+        time: f32,
+        team: u8,
+    },
+    Demolition {
+        frame: usize,
+        time: f32,
+        attacker: Option<usize>,
+        victim: Option<usize>,
+    },
+    Touch {
+        frame: usize,
+        time: f32,
+        player: Option<usize>,
+    },
+}
+
+impl MinimalReplay {
+    /// Keeps every `stride`th frame, starting with the first. Each event's `frame` is remapped to
+    /// index the kept frames, and events that fall on a skipped frame are snapped to the nearest
+    /// kept frame so that no marker is lost. An event's `time` is left as the time of the event. A
+    /// stride of 0 or 1 keeps every frame.
+    pub fn downsample(mut self, stride: usize) -> Self {
+        if stride <= 1 || self.frames.is_empty() {
+            return self;
+        }
+
+        let last = (self.frames.len() - 1) / stride;
+        self.frames = self.frames.into_iter().step_by(stride).collect();
+        for event in &mut self.events {
+            let (MinimalEvent::Goal { frame, .. }
+            | MinimalEvent::Demolition { frame, .. }
+            | MinimalEvent::Touch { frame, .. }) = event;
+            *frame = ((*frame + stride / 2) / stride).min(last);
+        }
+
+        self
+    }
+}
+
+impl From<RigidBody> for Transform {
+    fn from(body: RigidBody) -> Self {
+        let (l, r) = (body.location, body.rotation);
+        let position = [l.x, l.y, l.z].map(|x| round(x, 100.0));
+        let rotation = [r.x, r.y, r.z, r.w].map(|x| round(x, 10000.0));
+        Transform { position, rotation }
+    }
+}
+
+/// Resamples a track of `(time, transform)` samples at a fixed rate (samples per second), for
+/// consumers that expect evenly spaced keyframes like animation formats. The samples must be in
+/// time order. Positions are linearly interpolated and rotations are spherically interpolated
+/// between the surrounding samples. When either surrounding sample is `None` (eg: the car was
+/// demolished or the ball is waiting to respawn), the earlier sample is held instead.
+pub fn resample(track: &[(f32, Option<Transform>)], fps: f32) -> Vec<(f32, Option<Transform>)> {
+    let (start, end) = match (track.first(), track.last()) {
+        (Some(first), Some(last)) if fps > 0.0 => (first.0, last.0),
+        _ => return Vec::new(),
+    };
+
+    let count = ((end - start) * fps).floor() as usize + 1;
+    (0..count)
+        .map(|i| {
+            let time = start + i as f32 / fps;
+            let after = track.partition_point(|x| x.0 <= time);
+            let (prev_time, prev) = track[after.saturating_sub(1)];
+            let transform = match (prev, track.get(after)) {
+                (Some(a), Some((next_time, Some(b)))) if *next_time > prev_time => {
+                    let t = (time - prev_time) / (next_time - prev_time);
+                    Some(interpolate(&a, b, t))
+                }
+                _ => prev,
+            };
+            (time, transform)
+        })
+        .collect()
+}
+
+fn interpolate(a: &Transform, b: &Transform, t: f32) -> Transform {
+    let mut position = a.position;
+    for (x, y) in position.iter_mut().zip(b.position.iter()) {
+        *x += (y - *x) * t;
+    }
+
+    Transform {
+        position,
+        rotation: slerp(a.rotation, b.rotation, t),
+    }
+}
+
+/// Spherical linear interpolation along the shortest arc between two quaternions
+fn slerp(a: [f32; 4], mut b: [f32; 4], t: f32) -> [f32; 4] {
+    let mut dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    if dot < 0.0 {
+        b = b.map(|x| -x);
+        dot = -dot;
+    }
+
+    // Nearly identical rotations would divide by a vanishing sine
+    let (wa, wb) = if dot > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let theta = dot.min(1.0).acos();
+        let sin = theta.sin();
+        (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+    };
+
+    let mut result = [0.0; 4];
+    for (i, x) in result.iter_mut().enumerate() {
+        *x = wa * a[i] + wb * b[i];
+    }
+
+    let norm = result.iter().map(|x| x * x).sum::<f32>().sqrt();
+    result.map(|x| x / norm)
+}
+
+/// Values are rounded to well beyond what is perceptible in a viewer, as the extra digits would
+/// otherwise dominate the size of the payload
+fn round(x: f32, precision: f32) -> f32 {
+    (x * precision).round() / precision
+}
+
+/// Reconstructs the minimal view of the replay. Cars that are not driven by a named player are
+/// omitted. The frames and events are empty if the network data was not parsed.
+pub fn minimal_replay(replay: &Replay) -> MinimalReplay {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut players: Vec<MinimalPlayer> = Vec::new();
+    let mut frames = Vec::with_capacity(processor.frames().len());
+
+    for frame in processor.frames() {
+        processor.process_frame(frame);
+
+        let mut cars = Vec::new();
+        for car in processor.cars() {
+            let pri = match processor.car_player(*car) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let (name, body) = match (processor.player_name(pri), processor.rigid_body(*car)) {
+                (Some(name), Some(body)) => (name, body),
+                _ => continue,
+            };
+
+            let id = processor.player_unique_id(pri);
+            let team = processor.player_team(pri);
+            let player = match players.iter().position(|x| match (&x.id, id) {
+                (Some(a), Some(b)) => a == b,
+                (None, None) => x.name == name,
+                _ => false,
+            }) {
+                Some(i) => {
+                    players[i].team = team.or(players[i].team);
+                    i
+                }
+                None => {
+                    players.push(MinimalPlayer {
+                        id: id.cloned(),
+                        name: String::from(name),
+                        team,
+                    });
+                    players.len() - 1
+                }
+            };
+
+            let transform = Transform::from(body);
+            cars.push(CarTransform {
+                player,
+                position: transform.position,
+                rotation: transform.rotation,
+                boost: processor
+                    .car_boost(*car)
+                    .map(|x| round(f32::from(x) * 100.0 / 255.0, 10.0)),
+            });
+        }
+
+        frames.push(MinimalFrame {
+            time: frame.time,
+            ball: processor.ball_rigid_body().map(Transform::from),
+            cars,
+        });
+    }
+
+    let player_index = |id: &Option<UniqueId>| {
+        id.as_ref()
+            .and_then(|id| players.iter().position(|x| x.id.as_ref() == Some(id)))
+    };
+
+    let goals = events::goals_from_network(replay)
+        .into_iter()
+        .map(|x| MinimalEvent::Goal {
+            frame: x.frame,
+            time: x.time,
+            team: x.scoring_team,
+        });
+
+    let demolitions = events::demolitions(replay)
+        .into_iter()
+        .map(|x| MinimalEvent::Demolition {
+            frame: x.frame,
+            time: x.time,
+            attacker: player_index(&x.attacker_id),
+            victim: player_index(&x.victim_id),
+        });
+
+    let touches = events::touches(replay)
+        .into_iter()
+        .map(|x| MinimalEvent::Touch {
+            frame: x.frame,
+            time: x.time,
+            player: player_index(&x.player_id),
+        });
+
+    let mut events: Vec<_> = goals.chain(demolitions).chain(touches).collect();
+    events.sort_by_key(|x| match x {
+        MinimalEvent::Goal { frame, .. }
+        | MinimalEvent::Demolition { frame, .. }
+        | MinimalEvent::Touch { frame, .. } => *frame,
+    });
+
+    MinimalReplay {
+        schema_version: MINIMAL_SCHEMA_VERSION,
+        players,
+        frames,
+        events,
+    }
+}
+
+/// Serializes the replay with the [analysis schema](crate::export#analysis-schema)
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisReplay<'a> {
+    replay: &'a Replay,
+}
+
+impl<'a> AnalysisReplay<'a> {
+    pub fn new(replay: &'a Replay) -> Self {
+        AnalysisReplay { replay }
+    }
+}
+
+impl<'a> Serialize for AnalysisReplay<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Properties<'a>(&'a Replay);
+        impl<'a> Serialize for Properties<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                pair_vec(&self.0.properties, serializer)
+            }
+        }
+
+        let mut state = serializer.serialize_struct("AnalysisReplay", 3)?;
+        state.serialize_field("properties", &Properties(self.replay))?;
+        state.serialize_field("players", &analysis_players(self.replay))?;
+        state.serialize_field("frames", &AnalysisFrames::new(self.replay))?;
+        state.end()
+    }
+}
+
+#[derive(Serialize)]
+struct AnalysisPlayer {
+    name: Option<String>,
+    team: Option<u8>,
+}
+
+/// Every player seen in the network data keyed by their formatted unique id
+fn analysis_players(replay: &Replay) -> BTreeMap<String, AnalysisPlayer> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut players: BTreeMap<String, AnalysisPlayer> = BTreeMap::new();
+    for frame in processor.frames() {
+        processor.process_frame(frame);
+        for update in &frame.updated_actors {
+            let id = match processor.player_unique_id(update.actor_id) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let player = players.entry(player_key(id)).or_insert(AnalysisPlayer {
+                name: None,
+                team: None,
+            });
+            if let Some(name) = processor.player_name(update.actor_id) {
+                player.name = Some(String::from(name));
+            }
+            player.team = processor.player_team(update.actor_id).or(player.team);
+        }
+    }
+
+    players
+}
+
+fn serialize_player_key<S: Serializer>(
+    id: &Option<UniqueId>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match id {
+        Some(id) => serializer.serialize_some(&player_key(id)),
+        None => serializer.serialize_none(),
+    }
+}
+
+impl MinimalPlayer {
+    /// Identifies the player among the other players: the player's unique id formatted like the
+    /// player keys of the [analysis schema](crate::export#analysis-schema), or the player's name
+    /// when the player has no unique id
+    pub fn key(&self) -> String {
+        match &self.id {
+            Some(id) => player_key(id),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Formats the unique id as `"<platform>:<online id>"` with a `":<local id>"` suffix for split
+/// screen guests
+fn player_key(id: &UniqueId) -> String {
+    let key = match &id.remote_id {
+        // Older replays don't record the online id of PlayStation players
+        RemoteId::PlayStation(x) => format!("PlayStation:{}", x.name),
+        RemoteId::PsyNet(x) => format!("PsyNet:{}", x.online_id),
+        RemoteId::SplitScreen(x) => format!("SplitScreen:{}", x),
+        RemoteId::Steam(x) => format!("Steam:{}", x),
+        RemoteId::Switch(x) => format!("Switch:{}", x.online_id),
+        RemoteId::Xbox(x) => format!("Xbox:{}", x),
+        RemoteId::QQ(x) => format!("QQ:{}", x),
+        RemoteId::Epic(x) => format!("Epic:{}", x),
+    };
+
+    match id.local_id {
+        0 => key,
+        local => format!("{}:{}", key, local),
+    }
+}
+
+/// Serializes the frames while walking them, so the resolved frames are never all in memory
+struct AnalysisFrames<'a> {
+    processor: RefCell<ReplayProcessor<'a>>,
+}
+
+impl<'a> AnalysisFrames<'a> {
+    fn new(replay: &'a Replay) -> Self {
+        AnalysisFrames {
+            processor: RefCell::new(ReplayProcessor::new(replay)),
+        }
+    }
+}
+
+impl<'a> Serialize for AnalysisFrames<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut processor = self.processor.borrow_mut();
+        let frames = processor.frames();
+        let mut seq = serializer.serialize_seq(Some(frames.len()))?;
+        for frame in frames {
+            processor.process_frame(frame);
+            seq.serialize_element(&analysis_frame(&processor, frame))?;
+        }
+        seq.end()
+    }
+}
+
+#[derive(Serialize)]
+struct AnalysisFrame<'a> {
+    time: f32,
+    delta: f32,
+    new_actors: Vec<AnalysisNewActor<'a>>,
+    updated_actors: Vec<AnalysisUpdate<'a>>,
+    deleted_actors: &'a [ActorId],
+}
+
+#[derive(Serialize)]
+struct AnalysisNewActor<'a> {
+    actor_id: ActorId,
+    object: Option<&'a str>,
+    name: Option<&'a str>,
+    position: Option<[f32; 3]>,
+    rotation: Option<[f32; 4]>,
+}
+
+#[derive(Serialize)]
+struct AnalysisUpdate<'a> {
+    actor_id: ActorId,
+    attribute: Option<&'a str>,
+    player: Option<String>,
+    value: AnalysisValue<'a>,
+}
+
+/// Attribute values that are resolved are tagged with their variant like the default attribute
+/// serialization
+#[derive(Serialize)]
+#[serde(untagged)]
+enum AnalysisValue<'a> {
+    Resolved(ResolvedValue),
+    Raw(&'a Attribute),
+}
+
+#[derive(Serialize)]
+enum ResolvedValue {
+    RigidBody(AnalysisRigidBody),
+    UniqueId(String),
+}
+
+#[derive(Serialize)]
+struct AnalysisRigidBody {
+    sleeping: bool,
+    position: [f32; 3],
+    rotation: [f32; 4],
+    linear_velocity: Option<[f32; 3]>,
+    angular_velocity: Option<[f32; 3]>,
+}
+
+fn analysis_frame<'a>(processor: &ReplayProcessor<'a>, frame: &'a Frame) -> AnalysisFrame<'a> {
+    let replay = processor.replay();
+    let new_actors = frame
+        .new_actors
+        .iter()
+        .map(|actor| AnalysisNewActor {
+            actor_id: actor.actor_id,
+            object: replay
+                .objects
+                .get(usize::from(actor.object_id))
+                .map(|x| x.as_str()),
+            name: actor
+                .name_id
+                .and_then(|x| replay.names.get(x as usize))
+                .map(|x| x.as_str()),
+            position: actor
+                .initial_trajectory
+                .location
+                .map(|x| [x.x as f32, x.y as f32, x.z as f32]),
+            rotation: actor
+                .initial_trajectory
+                .rotation
+                .map(|x| quaternion(rotation_to_quaternion(x))),
+        })
+        .collect();
+
+    let updated_actors = frame
+        .updated_actors
+        .iter()
+        .map(|update| {
+            let value = match &update.attribute {
+                Attribute::RigidBody(body) => {
+                    let body = processor.to_world(*body);
+                    AnalysisValue::Resolved(ResolvedValue::RigidBody(AnalysisRigidBody {
+                        sleeping: body.sleeping,
+                        position: vector(body.location),
+                        rotation: quaternion(body.rotation),
+                        linear_velocity: body.linear_velocity.map(vector),
+                        angular_velocity: body.angular_velocity.map(vector),
+                    }))
+                }
+                Attribute::UniqueId(id) => {
+                    AnalysisValue::Resolved(ResolvedValue::UniqueId(player_key(id)))
+                }
+                attribute => AnalysisValue::Raw(attribute),
+            };
+
+            AnalysisUpdate {
+                actor_id: update.actor_id,
+                attribute: replay
+                    .objects
+                    .get(usize::from(update.object_id))
+                    .map(|x| x.as_str()),
+                player: actor_player(processor, update.actor_id).map(player_key),
+                value,
+            }
+        })
+        .collect();
+
+    AnalysisFrame {
+        time: frame.time,
+        delta: frame.delta,
+        new_actors,
+        updated_actors,
+        deleted_actors: &frame.deleted_actors,
+    }
+}
+
+/// The player behind a player replication info actor or the car that they drive
+fn actor_player<'a>(processor: &'a ReplayProcessor, actor_id: ActorId) -> Option<&'a UniqueId> {
+    processor.player_unique_id(actor_id).or_else(|| {
+        processor
+            .car_player(actor_id)
+            .and_then(|x| processor.player_unique_id(x))
+    })
+}
+
+/// Spawn rotations are compressed to 256 steps per revolution. They are encoded as pitch, yaw,
+/// then roll, so the `yaw` field holds the pitch and the `pitch` field holds the yaw.
+fn rotation_to_quaternion(rotation: Rotation) -> Quaternion {
+    let fraction = |x: Option<i8>| f32::from(x.unwrap_or(0)) / 128.0;
+    rotator_to_quaternion(Quaternion {
+        x: fraction(rotation.yaw),
+        y: fraction(rotation.pitch),
+        z: fraction(rotation.roll),
+        w: 0.0,
+    })
+}
+
+fn vector(v: Vector3f) -> [f32; 3] {
+    [v.x, v.y, v.z]
+}
+
+fn quaternion(q: Quaternion) -> [f32; 4] {
+    [q.x, q.y, q.z, q.w]
+}
+
+#[cfg(feature = "json")]
+impl Replay {
+    /// Serializes the [minimal view](crate::export) of the replay to JSON
+    pub fn to_minimal_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&minimal_replay(self))
+    }
+
+    /// Serializes the [minimal view](crate::export) of the replay to JSON with only every
+    /// `stride`th frame (see [`MinimalReplay::downsample`])
+    pub fn to_minimal_json_with_stride(&self, stride: usize) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&minimal_replay(self).downsample(stride))
+    }
+
+    /// Serializes the replay to JSON with the [analysis schema](crate::export#analysis-schema)
+    pub fn to_analysis_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&AnalysisReplay::new(self))
+    }
+}
+
+/// The layout of the JSON written by [`to_json_pretty`] and [`to_writer_pretty`]
+#[cfg(feature = "json")]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum JsonSchema {
+    /// The default serialization of the replay, which mirrors its parsed structure
+    Raw,
+
+    /// The [analysis schema](crate::export#analysis-schema)
+    Analysis,
+}
+
+/// Serializes the replay to indented JSON with the given schema
+#[cfg(feature = "json")]
+pub fn to_json_pretty(replay: &Replay, schema: JsonSchema) -> Result<String, serde_json::Error> {
+    match schema {
+        JsonSchema::Raw => serde_json::to_string_pretty(replay),
+        JsonSchema::Analysis => serde_json::to_string_pretty(&AnalysisReplay::new(replay)),
+    }
+}
+
+/// Writes the replay as indented JSON with the given schema. The writer is not buffered, so wrap
+/// files and sockets in a `BufWriter`.
+#[cfg(feature = "json")]
+pub fn to_writer_pretty<W: std::io::Write>(
+    writer: W,
+    replay: &Replay,
+    schema: JsonSchema,
+) -> Result<(), serde_json::Error> {
+    match schema {
+        JsonSchema::Raw => serde_json::to_writer_pretty(writer, replay),
+        JsonSchema::Analysis => serde_json::to_writer_pretty(writer, &AnalysisReplay::new(replay)),
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> AnalysisReplay<'a> {
+    /// Converts the replay to a JSON value with the [analysis schema](crate::export#analysis-schema)
+    /// so that fields can be added or merged with external data before it is written. The entire
+    /// value tree is allocated up front, which for a full match takes several times the memory of
+    /// the serialized string, so serialize the `AnalysisReplay` directly when it is written as is.
+    pub fn to_json_value(&self) -> serde_json::Value {
+        // Every map in the schema is keyed by strings and non-finite floats become null, so the
+        // conversion does not fail
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> From<&'a Replay> for serde_json::Value {
+    /// Converts the replay with the [analysis schema](crate::export#analysis-schema)
+    fn from(replay: &'a Replay) -> Self {
+        AnalysisReplay::new(replay).to_json_value()
+    }
+}
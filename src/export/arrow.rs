@@ -0,0 +1,114 @@
+use super::minimal_replay;
+use crate::models::Replay;
+use arrow_array::builder::{Float32Builder, StringDictionaryBuilder, UInt32Builder};
+use arrow_array::types::Int32Type;
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// The entity of the ball's rows
+const BALL_ENTITY: &str = "ball";
+
+/// The schema of the record batch written by [`to_arrow`]
+fn schema() -> Schema {
+    let float = |name| Field::new(name, DataType::Float32, false);
+    let dictionary = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+    Schema::new(vec![
+        Field::new("frame", DataType::UInt32, false),
+        float("time"),
+        Field::new("entity", dictionary.clone(), false),
+        Field::new("name", dictionary, true),
+        float("x"),
+        float("y"),
+        float("z"),
+        float("qx"),
+        float("qy"),
+        float("qz"),
+        float("qw"),
+        Field::new("boost", DataType::Float32, true),
+    ])
+}
+
+/// Reconstructs the ball and every player's car like the [minimal view](crate::export#minimal-schema)
+/// and flattens them into an Arrow record batch, for handing the replay to analytics tools (eg:
+/// pandas through pyarrow) without copying. There is a row for each entity in every network frame
+/// where it is present: the ball first, followed by the cars in the order of the minimal view's
+/// players. The columns are:
+///
+/// | Column | Type | |
+/// |---|---|---|
+/// | `frame` | `UInt32` | Index of the network frame |
+/// | `time` | `Float32` | Replay time of the frame |
+/// | `entity` | `Dictionary(Int32, Utf8)` | `"ball"` or the [key](super::MinimalPlayer::key) of the player driving the car |
+/// | `name` | `Dictionary(Int32, Utf8)` | The name of the player driving the car. Null for the ball. |
+/// | `x`, `y`, `z` | `Float32` | Position in world units |
+/// | `qx`, `qy`, `qz`, `qw` | `Float32` | Rotation as a quaternion |
+/// | `boost` | `Float32` | The car's boost as a percentage. Null for the ball and until the car's boost is replicated. |
+///
+/// Every network frame is kept, so rows are not evenly spaced in time.
+pub fn to_arrow(replay: &Replay) -> Result<RecordBatch, ArrowError> {
+    let minimal = minimal_replay(replay);
+    let rows = minimal
+        .frames
+        .iter()
+        .map(|x| x.cars.len() + usize::from(x.ball.is_some()))
+        .sum();
+
+    let mut frame = UInt32Builder::with_capacity(rows);
+    let mut time = Float32Builder::with_capacity(rows);
+    let mut entity = StringDictionaryBuilder::<Int32Type>::new();
+    let mut name = StringDictionaryBuilder::<Int32Type>::new();
+    let mut position: [Float32Builder; 3] = Default::default();
+    let mut rotation: [Float32Builder; 4] = Default::default();
+    let mut boost = Float32Builder::with_capacity(rows);
+
+    let keys: Vec<String> = minimal.players.iter().map(|x| x.key()).collect();
+    for (i, minimal_frame) in minimal.frames.iter().enumerate() {
+        let ball = minimal_frame
+            .ball
+            .map(|x| (BALL_ENTITY, None, x.position, x.rotation, None));
+        let cars = minimal_frame.cars.iter().map(|x| {
+            let player = minimal.players[x.player].name.as_str();
+            let key = keys[x.player].as_str();
+            (key, Some(player), x.position, x.rotation, x.boost)
+        });
+
+        for (key, player, xyz, quaternion, amount) in ball.into_iter().chain(cars) {
+            frame.append_value(u32::try_from(i).unwrap_or(u32::MAX));
+            time.append_value(minimal_frame.time);
+            entity.append(key)?;
+            match player {
+                Some(x) => name.append(x).map(|_| ())?,
+                None => name.append_null(),
+            }
+            for (builder, value) in position.iter_mut().zip(xyz.iter()) {
+                builder.append_value(*value);
+            }
+            for (builder, value) in rotation.iter_mut().zip(quaternion.iter()) {
+                builder.append_value(*value);
+            }
+            boost.append_option(amount);
+        }
+    }
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(frame.finish()),
+        Arc::new(time.finish()),
+        Arc::new(entity.finish()),
+        Arc::new(name.finish()),
+    ];
+    columns.extend(
+        position
+            .iter_mut()
+            .map(|x| Arc::new(x.finish()) as ArrayRef),
+    );
+    columns.extend(
+        rotation
+            .iter_mut()
+            .map(|x| Arc::new(x.finish()) as ArrayRef),
+    );
+    columns.push(Arc::new(boost.finish()));
+
+    RecordBatch::try_new(Arc::new(schema()), columns)
+}
@@ -0,0 +1,283 @@
+use super::{minimal_replay, resample, Transform};
+use crate::models::Replay;
+use serde_json::json;
+use std::io::{self, Write};
+
+/// The rate (keyframes per second) that the transforms are resampled at
+const GLTF_FPS: f32 = 30.0;
+
+/// glTF is in meters while a world unit is a centimeter
+const METERS_PER_UNIT: f32 = 0.01;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+const COMPONENT_FLOAT: u32 = 5126;
+
+/// Writes the ball and every player's car as animated nodes of a binary glTF (`.glb`) file. The
+/// transforms are reconstructed like the [minimal view](crate::export#minimal-schema) and
+/// [resampled](resample) at 30 keyframes per second, with the animation starting at the first
+/// network frame. Every node has translation and rotation channels and a scale channel that
+/// hides the node (scale of zero) while its object is absent, such as when a car is demolished.
+///
+/// The nodes are converted from Rocket League's left handed, Z up coordinates in world units to
+/// glTF's right handed, Y up coordinates in meters by swapping the Y and Z axes. The ball is the
+/// first node and is followed by the players in the order of the minimal view's players. A
+/// player's node is named by the player's [key](super::MinimalPlayer::key) so that players who
+/// share a name get distinct nodes, and the player's name is kept in the node's `extras`. Nodes have no meshes, so viewers show them as empties that models can be attached to.
+pub fn to_gltf<W: Write>(replay: &Replay, mut w: W) -> io::Result<()> {
+    let minimal = minimal_replay(replay);
+    let start = minimal.frames.first().map_or(0.0, |x| x.time);
+
+    let mut tracks = Vec::with_capacity(minimal.players.len() + 1);
+    let ball: Vec<_> = minimal.frames.iter().map(|x| (x.time, x.ball)).collect();
+    tracks.push((json!({ "name": "Ball" }), ball));
+
+    for (i, player) in minimal.players.iter().enumerate() {
+        let track = minimal
+            .frames
+            .iter()
+            .map(|frame| {
+                let car = frame.cars.iter().find(|x| x.player == i);
+                let transform = car.map(|x| Transform {
+                    position: x.position,
+                    rotation: x.rotation,
+                });
+                (frame.time, transform)
+            })
+            .collect::<Vec<_>>();
+        let node = json!({
+            "name": player.key(),
+            "extras": { "player": player.name },
+        });
+        tracks.push((node, track));
+    }
+
+    let mut buffer = GltfBuffer::default();
+    let times: Vec<f32> = resample(&tracks[0].1, GLTF_FPS)
+        .iter()
+        .map(|(time, _)| time - start)
+        .collect();
+    let input = buffer.accessor(&times, "SCALAR", 1, true);
+
+    let mut nodes = Vec::with_capacity(tracks.len());
+    let mut samplers = Vec::with_capacity(tracks.len() * 3);
+    let mut channels = Vec::with_capacity(tracks.len() * 3);
+
+    for (node, (gltf_node, track)) in tracks.iter().enumerate() {
+        let keyframes = held_keyframes(&resample(track, GLTF_FPS));
+        let translations: Vec<f32> = keyframes
+            .iter()
+            .flat_map(|(x, _)| gltf_position(x.position))
+            .collect();
+        let rotations: Vec<f32> = keyframes
+            .iter()
+            .flat_map(|(x, _)| gltf_rotation(x.rotation))
+            .collect();
+        let scales: Vec<f32> = keyframes
+            .iter()
+            .flat_map(|(_, present)| [f32::from(u8::from(*present)); 3])
+            .collect();
+
+        let outputs = [
+            (
+                "translation",
+                buffer.accessor(&translations, "VEC3", 3, false),
+                "LINEAR",
+            ),
+            (
+                "rotation",
+                buffer.accessor(&rotations, "VEC4", 4, false),
+                "LINEAR",
+            ),
+            ("scale", buffer.accessor(&scales, "VEC3", 3, false), "STEP"),
+        ];
+
+        for (path, output, interpolation) in outputs.iter() {
+            channels.push(json!({
+                "sampler": samplers.len(),
+                "target": { "node": node, "path": path },
+            }));
+            samplers.push(json!({
+                "input": input,
+                "output": output,
+                "interpolation": interpolation,
+            }));
+        }
+
+        nodes.push(gltf_node.clone());
+    }
+
+    let document = json!({
+        "asset": { "version": "2.0", "generator": "boxcars" },
+        "scene": 0,
+        "scenes": [{ "nodes": (0..nodes.len()).collect::<Vec<_>>() }],
+        "nodes": nodes,
+        "animations": [{ "name": "replay", "samplers": samplers, "channels": channels }],
+        "buffers": [{ "byteLength": buffer.data.len() }],
+        "bufferViews": buffer.views,
+        "accessors": buffer.accessors,
+    });
+
+    let mut json = serde_json::to_vec(&document)?;
+    pad(&mut json, b' ');
+    let mut bin = buffer.data;
+    pad(&mut bin, 0);
+
+    let length = 12 + 8 + json.len() + 8 + bin.len();
+    w.write_all(&GLB_MAGIC.to_le_bytes())?;
+    w.write_all(&GLB_VERSION.to_le_bytes())?;
+    w.write_all(&(length as u32).to_le_bytes())?;
+    w.write_all(&(json.len() as u32).to_le_bytes())?;
+    w.write_all(&CHUNK_JSON.to_le_bytes())?;
+    w.write_all(&json)?;
+    w.write_all(&(bin.len() as u32).to_le_bytes())?;
+    w.write_all(&CHUNK_BIN.to_le_bytes())?;
+    w.write_all(&bin)?;
+    Ok(())
+}
+
+/// The binary chunk with a buffer view and accessor for every array of floats
+#[derive(Default)]
+struct GltfBuffer {
+    data: Vec<u8>,
+    views: Vec<serde_json::Value>,
+    accessors: Vec<serde_json::Value>,
+}
+
+impl GltfBuffer {
+    /// Appends the floats and returns the index of their accessor. Animation inputs must declare
+    /// their bounds.
+    fn accessor(&mut self, values: &[f32], kind: &str, width: usize, bounds: bool) -> usize {
+        let offset = self.data.len();
+        for x in values {
+            self.data.extend_from_slice(&x.to_le_bytes());
+        }
+
+        self.views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": self.data.len() - offset,
+        }));
+
+        let mut accessor = json!({
+            "bufferView": self.views.len() - 1,
+            "componentType": COMPONENT_FLOAT,
+            "count": values.len() / width,
+            "type": kind,
+        });
+
+        if bounds {
+            let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            accessor["min"] = json!([min]);
+            accessor["max"] = json!([max]);
+        }
+
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+}
+
+/// Fills the keyframes where the object is absent with the closest transform, preferring the
+/// previous one, so that the object doesn't glide while hidden
+fn held_keyframes(keyframes: &[(f32, Option<Transform>)]) -> Vec<(Transform, bool)> {
+    let identity = Transform {
+        position: [0.0; 3],
+        rotation: [0.0, 0.0, 0.0, 1.0],
+    };
+
+    let first = keyframes.iter().find_map(|(_, x)| *x).unwrap_or(identity);
+    let mut held = first;
+    keyframes
+        .iter()
+        .map(|(_, transform)| match transform {
+            Some(x) => {
+                held = *x;
+                (*x, true)
+            }
+            None => (held, false),
+        })
+        .collect()
+}
+
+/// Swaps the Y and Z axes and converts to meters
+fn gltf_position([x, y, z]: [f32; 3]) -> [f32; 3] {
+    [x, z, y].map(|x| x * METERS_PER_UNIT)
+}
+
+/// Swapping two axes is a reflection, which permutes the rotation axis the same way as positions
+/// and also reverses the direction of the rotation
+fn gltf_rotation([x, y, z, w]: [f32; 4]) -> [f32; 4] {
+    [-x, -z, -y, w]
+}
+
+/// Pads the chunk to a multiple of four bytes as required by the container
+fn pad(chunk: &mut Vec<u8>, with: u8) {
+    let padding = (4 - chunk.len() % 4) % 4;
+    chunk.resize(chunk.len() + padding, with);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rotates the vector by the quaternion
+    fn rotate([x, y, z, w]: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+        let u = [x, y, z];
+        let cross = |a: [f32; 3], b: [f32; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+
+        let t = cross(u, v).map(|x| x * 2.0);
+        let c = cross(u, t);
+        [
+            v[0] + w * t[0] + c[0],
+            v[1] + w * t[1] + c[1],
+            v[2] + w * t[2] + c[2],
+        ]
+    }
+
+    #[test]
+    fn test_rotation_matches_positions() {
+        let half = (0.6f32 / 2.0).sin_cos();
+        let axis = [0.48f32, 0.6, 0.64];
+        let rotation = [axis[0] * half.0, axis[1] * half.0, axis[2] * half.0, half.1];
+        let point = [100.0, -250.0, 40.0];
+
+        let expected = gltf_position(rotate(rotation, point));
+        let actual = rotate(gltf_rotation(rotation), gltf_position(point));
+        for (a, b) in expected.iter().zip(actual.iter()) {
+            assert!((a - b).abs() < 1e-4, "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_held_keyframes() {
+        let at = |x: f32| Transform {
+            position: [x, 0.0, 0.0],
+            rotation: [0.0, 0.0, 0.0, 1.0],
+        };
+
+        let held = held_keyframes(&[
+            (0.0, None),
+            (0.1, Some(at(1.0))),
+            (0.2, None),
+            (0.3, Some(at(3.0))),
+        ]);
+        let positions: Vec<_> = held
+            .iter()
+            .map(|(x, present)| (x.position[0], *present))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![(1.0, false), (1.0, true), (1.0, false), (3.0, true)]
+        );
+    }
+}
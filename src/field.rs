@@ -0,0 +1,495 @@
+//! # Field
+//!
+//! Geometry of a standard soccar field in world units (uu). The field is centered at the origin
+//! with the blue team (team 0) defending the goal at negative y and the orange team (team 1)
+//! defending the goal at positive y. Non-standard arenas (eg: Throwback Stadium) and other modes
+//! (hoops, dropshot) have different dimensions.
+
+use crate::network::{Quaternion, Vector3f};
+
+/// Distance from the center of the field to each goal line along the y axis
+pub const GOAL_LINE_Y: f32 = 5120.0;
+
+/// Distance from the center of the field to each side wall along the x axis
+pub const SIDE_WALL_X: f32 = 4096.0;
+
+/// The diagonal corner walls are where `|x| + |y|` reaches this distance
+pub const CORNER_WALL_DISTANCE: f32 = 8064.0;
+
+/// Distance from the center of the field to the boundaries between the thirds of the field along
+/// the y axis
+pub const THIRD_LINE_Y: f32 = GOAL_LINE_Y / 3.0;
+
+/// Half of the width of the goal mouth along the x axis
+pub const GOAL_HALF_WIDTH: f32 = 892.755;
+
+/// Height of the goal mouth
+pub const GOAL_HEIGHT: f32 = 642.775;
+
+/// Radius of the standard soccar ball
+pub const BALL_RADIUS: f32 = 91.25;
+
+/// Height of the ceiling
+pub const CEILING_Z: f32 = 2044.0;
+
+/// Acceleration of a ball in flight toward the ground (uu/s²)
+const GRAVITY: f32 = 650.0;
+
+/// How far (uu) from the center of a boost pad a car can be, when measured along the ground,
+/// and still be said to be at the pad. Wider than the pickup radius as a car covers ground
+/// between frames.
+const BOOST_PAD_RADIUS: f32 = 400.0;
+
+/// A boost pad of a standard soccar field
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct BoostPadLocation {
+    pub x: f32,
+    pub y: f32,
+
+    /// Large pads fill the tank and take 10 seconds to respawn, while small pads grant 12 boost
+    /// and take 4 seconds to respawn
+    pub large: bool,
+}
+
+const fn pad(x: f32, y: f32, large: bool) -> BoostPadLocation {
+    BoostPadLocation { x, y, large }
+}
+
+/// The 34 boost pads of a standard soccar field ordered by y and then x
+pub const BOOST_PADS: [BoostPadLocation; 34] = [
+    pad(0.0, -4240.0, false),
+    pad(-1792.0, -4184.0, false),
+    pad(1792.0, -4184.0, false),
+    pad(-3072.0, -4096.0, true),
+    pad(3072.0, -4096.0, true),
+    pad(-940.0, -3308.0, false),
+    pad(940.0, -3308.0, false),
+    pad(0.0, -2816.0, false),
+    pad(-3584.0, -2484.0, false),
+    pad(3584.0, -2484.0, false),
+    pad(-1788.0, -2300.0, false),
+    pad(1788.0, -2300.0, false),
+    pad(-2048.0, -1036.0, false),
+    pad(0.0, -1024.0, false),
+    pad(2048.0, -1036.0, false),
+    pad(-3584.0, 0.0, true),
+    pad(-1024.0, 0.0, false),
+    pad(1024.0, 0.0, false),
+    pad(3584.0, 0.0, true),
+    pad(-2048.0, 1036.0, false),
+    pad(0.0, 1024.0, false),
+    pad(2048.0, 1036.0, false),
+    pad(-1788.0, 2300.0, false),
+    pad(1788.0, 2300.0, false),
+    pad(-3584.0, 2484.0, false),
+    pad(3584.0, 2484.0, false),
+    pad(0.0, 2816.0, false),
+    pad(-940.0, 3310.0, false),
+    pad(940.0, 3308.0, false),
+    pad(-3072.0, 4096.0, true),
+    pad(3072.0, 4096.0, true),
+    pad(-1792.0, 4184.0, false),
+    pad(1792.0, 4184.0, false),
+    pad(0.0, 4240.0, false),
+];
+
+/// The index in [`BOOST_PADS`] of the pad that a car at the location is at, if any
+pub fn boost_pad_at(location: &Vector3f) -> Option<usize> {
+    BOOST_PADS
+        .iter()
+        .map(|pad| (pad.x - location.x).hypot(pad.y - location.y))
+        .enumerate()
+        .filter(|(_, distance)| *distance < BOOST_PAD_RADIUS)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// How far (uu) from a kickoff spawn a car can be, when measured along the ground, and still be
+/// said to be at the spawn
+const KICKOFF_SPAWN_RADIUS: f32 = 150.0;
+
+/// A spot where a car is placed for a kickoff. Each team has two of every spot but the back.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum KickoffSpawn {
+    /// The diagonal spots nearest the ball
+    Corner,
+
+    /// The spots just off-center in front of the back spot
+    OffCenter,
+
+    /// The spot straight in front of the goal, furthest from the ball
+    Back,
+}
+
+/// The kickoff spawns of the blue team as `(x, y, spawn)`. The orange team's spawns are mirrored
+/// through the center of the field. Replays from older versions of the game, where the spawns
+/// were closer to the ball, use other spots.
+pub const KICKOFF_SPAWNS: [(f32, f32, KickoffSpawn); 5] = [
+    (-2048.0, -2560.0, KickoffSpawn::Corner),
+    (2048.0, -2560.0, KickoffSpawn::Corner),
+    (-256.0, -3840.0, KickoffSpawn::OffCenter),
+    (256.0, -3840.0, KickoffSpawn::OffCenter),
+    (0.0, -4608.0, KickoffSpawn::Back),
+];
+
+/// The kickoff spawn that a car at the location is at, if any, for either team
+pub fn kickoff_spawn(location: &Vector3f) -> Option<KickoffSpawn> {
+    // Orange spawns are mirrored through the center
+    let (x, y) = if location.y > 0.0 {
+        (-location.x, -location.y)
+    } else {
+        (location.x, location.y)
+    };
+
+    KICKOFF_SPAWNS
+        .iter()
+        .find(|(spawn_x, spawn_y, _)| (spawn_x - x).hypot(spawn_y - y) < KICKOFF_SPAWN_RADIUS)
+        .map(|(_, _, spawn)| *spawn)
+}
+
+/// The furthest that the center of a car can be from a surface while driving on it. A car's
+/// center rests about 17 uu from the surface, and the margin absorbs suspension and curved
+/// transitions between surfaces.
+const SURFACE_MARGIN: f32 = 60.0;
+
+/// How closely a car's roof has to point away from a surface for the car to be on its wheels,
+/// as the cosine of the angle between the roof and the surface's normal
+const SURFACE_ALIGNMENT: f32 = 0.7;
+
+/// The team that defends the goal on the same side of the field as the given y coordinate
+pub fn defending_team(y: f32) -> u8 {
+    if y < 0.0 {
+        0
+    } else {
+        1
+    }
+}
+
+/// Returns true if the ball at the given location is within the goal mouth when viewed down the
+/// y axis (ie: it would be in the goal if it was past the goal line)
+pub fn in_goal_mouth(location: &Vector3f) -> bool {
+    location.x.abs() < GOAL_HALF_WIDTH && location.z < GOAL_HEIGHT
+}
+
+/// Returns true if the ball at the given location has entirely crossed either goal line
+pub fn ball_past_goal_line(location: &Vector3f) -> bool {
+    location.y.abs() > GOAL_LINE_Y + BALL_RADIUS
+}
+
+/// A third of the field relative to a team
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Third {
+    /// The third that contains the team's own goal
+    Defensive,
+    Neutral,
+
+    /// The third that contains the opponent's goal
+    Offensive,
+}
+
+/// The third of the field that the y coordinate is in, from the perspective of the team
+pub fn third(y: f32, team: u8) -> Third {
+    // Blue (team 0) defends negative y
+    let y = if team == 0 { y } else { -y };
+    if y < -THIRD_LINE_Y {
+        Third::Defensive
+    } else if y > THIRD_LINE_Y {
+        Third::Offensive
+    } else {
+        Third::Neutral
+    }
+}
+
+/// The distance from the location to the center of the team's goal line
+pub fn goal_distance(location: &Vector3f, team: u8) -> f32 {
+    let goal_y = if team == 0 { -GOAL_LINE_Y } else { GOAL_LINE_Y };
+    let (x, y, z) = (location.x, location.y - goal_y, location.z);
+    (x * x + y * y + z * z).sqrt()
+}
+
+/// The team whose goal is threatened by a ball at the location moving with the velocity: the ball
+/// is within the given distance (uu) of the center of the team's goal line and moving toward it.
+/// A ball that has crossed the goal line has already been scored and threatens no one.
+pub fn threatened_team(location: &Vector3f, velocity: &Vector3f, max_distance: f32) -> Option<u8> {
+    if ball_past_goal_line(location) {
+        return None;
+    }
+
+    let team = defending_team(location.y);
+    let goal_y = if team == 0 { -GOAL_LINE_Y } else { GOAL_LINE_Y };
+    let toward = -location.x * velocity.x + (goal_y - location.y) * velocity.y;
+    if goal_distance(location, team) <= max_distance && toward > 0.0 {
+        Some(team)
+    } else {
+        None
+    }
+}
+
+/// The number of seconds until a ball at the location moving with the velocity crosses the team's
+/// goal line within the goal mouth, or `None` if the ball is moving away from the goal or would
+/// miss it. The ball's path follows gravity but ignores drag, spin, and bounces: a ball that would
+/// fall through the floor is taken to roll along it instead.
+pub fn seconds_to_goal(location: &Vector3f, velocity: &Vector3f, team: u8) -> Option<f32> {
+    let goal_y = if team == 0 { -GOAL_LINE_Y } else { GOAL_LINE_Y };
+    let seconds = (goal_y - location.y) / velocity.y;
+    if seconds.is_nan() || seconds.is_infinite() || seconds < 0.0 {
+        return None;
+    }
+
+    let fall = GRAVITY * seconds * seconds / 2.0;
+    let crossing = Vector3f {
+        x: location.x + velocity.x * seconds,
+        y: goal_y,
+        z: (location.z + velocity.z * seconds - fall).max(BALL_RADIUS),
+    };
+
+    if in_goal_mouth(&crossing) {
+        Some(seconds)
+    } else {
+        None
+    }
+}
+
+/// The shortest distance from the location to the side, back, or corner walls. Negative when the
+/// location is beyond a wall, as when the ball is inside a goal.
+pub fn wall_distance(location: &Vector3f) -> f32 {
+    let (x, y) = (location.x.abs(), location.y.abs());
+    let corner = (CORNER_WALL_DISTANCE - x - y) / std::f32::consts::SQRT_2;
+    (SIDE_WALL_X - x).min(GOAL_LINE_Y - y).min(corner)
+}
+
+/// What a car is driving on
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Surface {
+    Ground,
+
+    /// The side, back, or corner walls
+    Wall,
+    Ceiling,
+
+    /// Not in contact with any surface, or touching one with something other than its wheels
+    Air,
+}
+
+/// Classifies the surface that a car at the location with the rotation is driving on. A car is on
+/// a surface when its center is close to the surface and its roof points away from it, so a car
+/// that lands on its roof or flies alongside a wall is in the air. Goals and the curves between
+/// surfaces are not modeled, so a car inside a goal or on a curve may be classified as either
+/// neighboring surface or the air.
+pub fn surface(location: &Vector3f, rotation: &Quaternion) -> Surface {
+    let up = up_vector(rotation);
+    if location.z < SURFACE_MARGIN && up.z > SURFACE_ALIGNMENT {
+        Surface::Ground
+    } else if location.z > CEILING_Z - SURFACE_MARGIN && up.z < -SURFACE_ALIGNMENT {
+        Surface::Ceiling
+    } else if wall_distance(location) < SURFACE_MARGIN && up.z.abs() < SURFACE_ALIGNMENT {
+        Surface::Wall
+    } else {
+        Surface::Air
+    }
+}
+
+/// The direction that the roof of a car with the rotation points in
+pub(crate) fn up_vector(q: &Quaternion) -> Vector3f {
+    Vector3f {
+        x: 2.0 * (q.x * q.z + q.w * q.y),
+        y: 2.0 * (q.y * q.z - q.w * q.x),
+        z: 1.0 - 2.0 * (q.x * q.x + q.y * q.y),
+    }
+}
+
+/// The straight line distance between two points
+pub(crate) fn distance(a: &Vector3f, b: &Vector3f) -> f32 {
+    let (x, y, z) = (a.x - b.x, a.y - b.y, a.z - b.z);
+    (x * x + y * y + z * z).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boost_pad_at() {
+        let pad = |x, y| boost_pad_at(&Vector3f { x, y, z: 17.0 });
+
+        assert_eq!(pad(-3000.0, -4000.0), Some(3));
+        assert!(BOOST_PADS[3].large);
+        assert_eq!(pad(1100.0, 50.0), Some(17));
+        assert!(!BOOST_PADS[17].large);
+        assert_eq!(pad(0.0, 0.0), None);
+
+        // The field is symmetric
+        for pad in BOOST_PADS.iter() {
+            assert!(BOOST_PADS
+                .iter()
+                .any(|x| (x.x + pad.x).abs() < 5.0 && (x.y + pad.y).abs() < 5.0));
+        }
+    }
+
+    #[test]
+    fn test_kickoff_spawn() {
+        let spawn = |x, y| kickoff_spawn(&Vector3f { x, y, z: 17.0 });
+
+        assert_eq!(spawn(-2048.0, -2560.0), Some(KickoffSpawn::Corner));
+        assert_eq!(spawn(-2000.0, 2600.0), Some(KickoffSpawn::Corner));
+        assert_eq!(spawn(256.0, 3840.0), Some(KickoffSpawn::OffCenter));
+        assert_eq!(spawn(0.0, -4600.0), Some(KickoffSpawn::Back));
+        assert_eq!(spawn(0.0, -3840.0), None);
+        assert_eq!(spawn(0.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_goal_geometry() {
+        let scored = Vector3f {
+            x: 100.0,
+            y: -5250.0,
+            z: 93.0,
+        };
+        assert!(in_goal_mouth(&scored));
+        assert!(ball_past_goal_line(&scored));
+        assert_eq!(defending_team(scored.y), 0);
+
+        let on_line = Vector3f {
+            x: 0.0,
+            y: 5150.0,
+            z: 93.0,
+        };
+        assert!(!ball_past_goal_line(&on_line));
+        assert_eq!(defending_team(on_line.y), 1);
+
+        let wide = Vector3f {
+            x: 1000.0,
+            y: 4000.0,
+            z: 93.0,
+        };
+        assert!(!in_goal_mouth(&wide));
+        assert!((wall_distance(&wide) - 1120.0).abs() < 0.01);
+        assert!(wall_distance(&scored) < 0.0);
+
+        let corner = Vector3f {
+            x: 3800.0,
+            y: 4500.0,
+            z: 93.0,
+        };
+        assert!(wall_distance(&corner) < 0.0);
+
+        let toward_orange = Vector3f {
+            x: 0.0,
+            y: 2000.0,
+            z: 0.0,
+        };
+        assert_eq!(seconds_to_goal(&on_line, &toward_orange, 1), None);
+
+        let shot = Vector3f {
+            x: 0.0,
+            y: 3120.0,
+            z: 93.0,
+        };
+        assert_eq!(seconds_to_goal(&shot, &toward_orange, 1), Some(1.0));
+        assert_eq!(seconds_to_goal(&shot, &toward_orange, 0), None);
+
+        let lob = Vector3f {
+            z: 1000.0,
+            ..toward_orange
+        };
+        assert_eq!(seconds_to_goal(&shot, &lob, 1), None);
+    }
+
+    #[test]
+    fn test_surface() {
+        let at = |x, y, z| Vector3f { x, y, z };
+        let upright = Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+        let upside_down = Quaternion {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+            w: 0.0,
+        };
+
+        // Rolled a quarter turn so that the roof points toward negative x
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let rolled = Quaternion {
+            x: 0.0,
+            y: -half,
+            z: 0.0,
+            w: half,
+        };
+
+        let side_wall = at(4060.0, 0.0, 500.0);
+        assert_eq!(surface(&at(0.0, 0.0, 17.0), &upright), Surface::Ground);
+        assert_eq!(surface(&at(0.0, 0.0, 17.0), &upside_down), Surface::Air);
+        assert_eq!(
+            surface(&at(0.0, 0.0, 2027.0), &upside_down),
+            Surface::Ceiling
+        );
+        assert_eq!(surface(&at(0.0, 0.0, 500.0), &upside_down), Surface::Air);
+        assert_eq!(surface(&side_wall, &rolled), Surface::Wall);
+        assert_eq!(surface(&side_wall, &upright), Surface::Air);
+        assert_eq!(surface(&at(0.0, 0.0, 500.0), &rolled), Surface::Air);
+    }
+
+    #[test]
+    fn test_thirds() {
+        assert_eq!(third(-4000.0, 0), Third::Defensive);
+        assert_eq!(third(-4000.0, 1), Third::Offensive);
+        assert_eq!(third(-1700.0, 0), Third::Neutral);
+        assert_eq!(third(1700.0, 1), Third::Neutral);
+        assert_eq!(third(1710.0, 0), Third::Offensive);
+        assert_eq!(third(1710.0, 1), Third::Defensive);
+        assert_eq!(third(0.0, 0), Third::Neutral);
+    }
+
+    #[test]
+    fn test_threatened_team() {
+        let toward_blue = Vector3f {
+            x: 0.0,
+            y: -1000.0,
+            z: 0.0,
+        };
+        let toward_orange = Vector3f {
+            x: 0.0,
+            y: 1000.0,
+            z: 0.0,
+        };
+        let near_blue = Vector3f {
+            x: 500.0,
+            y: -4000.0,
+            z: 93.0,
+        };
+
+        assert!((goal_distance(&near_blue, 0) - 1230.06).abs() < 0.01);
+        assert_eq!(threatened_team(&near_blue, &toward_blue, 2000.0), Some(0));
+        assert_eq!(threatened_team(&near_blue, &toward_blue, 1000.0), None);
+        assert_eq!(threatened_team(&near_blue, &toward_orange, 2000.0), None);
+
+        // Moving across the face of the goal toward the center is still a threat
+        let across = Vector3f {
+            x: -1000.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert_eq!(threatened_team(&near_blue, &across, 2000.0), Some(0));
+
+        let near_orange = Vector3f {
+            x: 0.0,
+            y: 4500.0,
+            z: 300.0,
+        };
+        assert_eq!(
+            threatened_team(&near_orange, &toward_orange, 2000.0),
+            Some(1)
+        );
+
+        let scored = Vector3f {
+            x: 0.0,
+            y: 5300.0,
+            z: 93.0,
+        };
+        assert_eq!(threatened_team(&scored, &toward_orange, 2000.0), None);
+    }
+}
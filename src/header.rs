@@ -41,6 +41,21 @@ impl Header {
             .find(|&(key, _)| key == "BuildVersion")
             .and_then(|&(_, ref prop)| prop.as_string())
     }
+
+    /// The network frame of the earliest goal recorded in the header's goal timeline
+    pub fn first_goal_frame(&self) -> Option<i32> {
+        self.properties
+            .iter()
+            .find(|(key, _)| key == "Goals")
+            .and_then(|(_, prop)| prop.as_array())?
+            .iter()
+            .filter_map(|goal| {
+                goal.iter()
+                    .find(|(key, _)| key == "frame")
+                    .and_then(|(_, prop)| prop.as_i32())
+            })
+            .min()
+    }
 }
 
 pub fn parse_header(rlp: &mut CoreParser) -> Result<Header, ParseError> {
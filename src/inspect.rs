@@ -0,0 +1,203 @@
+//! # Inspect
+//!
+//! Utilities for maintainers and tool authors to understand what data a replay actually
+//! exercises.
+
+use crate::models::Replay;
+use crate::network::attributes::Attribute;
+use crate::network::{ActorId, ObjectId};
+use crate::object_index::ObjectIndex;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Number of times an object was seen in the decoded network frames
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct ObjectUsage {
+    /// Number of times the object was the type of a new actor
+    pub as_new_actor: usize,
+
+    /// Number of times the object was the type of an updated attribute
+    pub as_attribute: usize,
+}
+
+impl ObjectUsage {
+    /// The total number of times the object was seen
+    pub fn total(&self) -> usize {
+        self.as_new_actor + self.as_attribute
+    }
+}
+
+/// Counts how many times each object appears as a new actor and as an updated attribute across all
+/// frames. Objects that never appear are omitted. The returned ids can be resolved to names by
+/// indexing `Replay::objects`. An empty map is returned if the network data was not parsed.
+pub fn object_usage(replay: &Replay) -> HashMap<ObjectId, ObjectUsage> {
+    let mut usage: HashMap<ObjectId, ObjectUsage> = HashMap::new();
+    let frames = replay.frames_or_empty();
+
+    for frame in frames {
+        for actor in &frame.new_actors {
+            usage.entry(actor.object_id).or_default().as_new_actor += 1;
+        }
+
+        for attr in &frame.updated_actors {
+            usage.entry(attr.object_id).or_default().as_attribute += 1;
+        }
+    }
+
+    usage
+}
+
+/// Formats the object usage as a table sorted by the most used objects first (ties are broken by
+/// object id). Object names are resolved from the replay's object index.
+pub fn format_object_usage(replay: &Replay, usage: &HashMap<ObjectId, ObjectUsage>) -> String {
+    let mut entries: Vec<_> = usage.iter().collect();
+    entries.sort_by(|(a_id, a), (b_id, b)| b.total().cmp(&a.total()).then(a_id.cmp(b_id)));
+
+    let mut out = String::new();
+    for (id, counts) in entries {
+        let name = replay
+            .objects
+            .get(usize::from(*id))
+            .map(|x| x.as_str())
+            .unwrap_or("<unknown>");
+
+        let _ = writeln!(
+            out,
+            "{:>8} {:>8} {:>6} {}",
+            counts.as_new_actor, counts.as_attribute, id.0, name
+        );
+    }
+
+    out
+}
+
+/// Lazily yields every update of the attribute with the given object name across all frames, as
+/// the index of the frame, the actor that was updated, and the attribute's value. Nothing is
+/// yielded if the replay has no object with the name or the network data was not parsed.
+///
+/// ```
+/// let data = include_bytes!("../assets/replays/good/rumble.replay");
+/// let replay = boxcars::ParserBuilder::new(&data[..])
+///     .must_parse_network_data()
+///     .parse()
+///     .unwrap();
+///
+/// let pings = boxcars::inspect::attribute_values(&replay, "Engine.PlayerReplicationInfo:Ping");
+/// assert!(pings.count() > 0);
+/// ```
+pub fn attribute_values<'a>(
+    replay: &'a Replay,
+    object_name: &str,
+) -> impl Iterator<Item = (usize, ActorId, &'a Attribute)> + 'a {
+    let object_id = ObjectIndex::new(&replay.objects).get_object_id(object_name);
+    let frames = match object_id {
+        Some(_) => replay.frames_or_empty(),
+        None => &[],
+    };
+
+    frames.iter().enumerate().flat_map(move |(i, frame)| {
+        frame
+            .updated_actors
+            .iter()
+            .filter(move |x| Some(x.object_id) == object_id)
+            .map(move |x| (i, x.actor_id, &x.attribute))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{ActorId, Frame, NewActor, StreamId, Trajectory, UpdatedAttribute};
+    use crate::{Attribute, NetworkFrames};
+
+    fn replay_with(frames: Vec<Frame>) -> Replay {
+        let data = include_bytes!("../assets/replays/good/small-frames.replay");
+        let replay = crate::ParserBuilder::new(&data[..])
+            .never_parse_network_data()
+            .parse()
+            .unwrap();
+        Replay {
+            network_frames: Some(NetworkFrames { frames }),
+            ..replay
+        }
+    }
+
+    #[test]
+    fn test_object_usage() {
+        let frame = Frame {
+            time: 1.0,
+            delta: 0.03,
+            new_actors: vec![NewActor {
+                actor_id: ActorId(1),
+                name_id: None,
+                object_id: ObjectId(0),
+                initial_trajectory: Trajectory {
+                    location: None,
+                    rotation: None,
+                },
+            }],
+            deleted_actors: Vec::new(),
+            updated_actors: vec![
+                UpdatedAttribute {
+                    actor_id: ActorId(1),
+                    stream_id: StreamId(2),
+                    object_id: ObjectId(1),
+                    attribute: Attribute::Byte(10),
+                };
+                2
+            ],
+        };
+
+        let replay = replay_with(vec![frame]);
+        let usage = object_usage(&replay);
+        assert_eq!(
+            usage[&ObjectId(0)],
+            ObjectUsage {
+                as_new_actor: 1,
+                as_attribute: 0
+            }
+        );
+        assert_eq!(
+            usage[&ObjectId(1)],
+            ObjectUsage {
+                as_new_actor: 0,
+                as_attribute: 2
+            }
+        );
+
+        let table = format_object_usage(&replay, &usage);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(replay.objects[1].as_str()));
+        assert!(lines[1].ends_with(replay.objects[0].as_str()));
+    }
+
+    #[test]
+    fn test_attribute_values() {
+        let frame = |byte: u8| Frame {
+            time: 1.0,
+            delta: 0.03,
+            new_actors: Vec::new(),
+            deleted_actors: Vec::new(),
+            updated_actors: vec![UpdatedAttribute {
+                actor_id: ActorId(1),
+                stream_id: StreamId(2),
+                object_id: ObjectId(1),
+                attribute: Attribute::Byte(byte),
+            }],
+        };
+
+        let replay = replay_with(vec![frame(10), frame(20)]);
+        let values: Vec<_> = attribute_values(&replay, &replay.objects[1]).collect();
+        assert_eq!(
+            values,
+            vec![
+                (0, ActorId(1), &Attribute::Byte(10)),
+                (1, ActorId(1), &Attribute::Byte(20)),
+            ]
+        );
+
+        assert_eq!(attribute_values(&replay, &replay.objects[0]).count(), 0);
+        assert_eq!(attribute_values(&replay, "Not.An.Object").count(), 0);
+    }
+}
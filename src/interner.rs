@@ -0,0 +1,170 @@
+use fnv::FnvHasher;
+use std::hash::Hasher;
+
+/// The fewest slots allocated for the hash table once a string is interned
+const MIN_SLOTS: usize = 16;
+
+/// A cheap handle to a string stored in an [`Interner`]. Symbols are numbered in the order that
+/// their strings were first interned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The position of the symbol's string in the order that strings were interned
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Stores each distinct string once and hands out a [`Symbol`] for it. The strings are
+/// concatenated into a single buffer and looked up through an open addressing hash table of
+/// symbols, so interning any number of strings takes three allocations (plus growth) instead of
+/// one or more per string.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Interner {
+    buffer: String,
+
+    /// The end offset of each symbol's string in the buffer. A string starts where the previous
+    /// one ends.
+    ends: Vec<usize>,
+
+    /// Linear probing slots holding a symbol plus one, with zero marking an empty slot
+    slots: Vec<u32>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Creates an interner that can hold the given number of strings with the given total length
+    /// without reallocating
+    pub fn with_capacity(strings: usize, bytes: usize) -> Self {
+        let mut interner = Interner {
+            buffer: String::with_capacity(bytes),
+            ends: Vec::with_capacity(strings),
+            slots: Vec::new(),
+        };
+        interner.rehash(slots_for(strings));
+        interner
+    }
+
+    /// Returns the symbol of the string, storing the string if it hasn't been seen before
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(symbol) = self.get(s) {
+            return symbol;
+        }
+
+        if self.slots.len() < slots_for(self.ends.len() + 1) {
+            self.rehash(slots_for(self.ends.len() + 1));
+        }
+
+        let symbol = Symbol(self.ends.len() as u32);
+        self.buffer.push_str(s);
+        self.ends.push(self.buffer.len());
+
+        let slot = self.probe(s);
+        self.slots[slot] = symbol.0 + 1;
+        symbol
+    }
+
+    /// Returns the symbol of the string if it has been interned
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        match self.slots[self.probe(s)] {
+            0 => None,
+            x => Some(Symbol(x - 1)),
+        }
+    }
+
+    /// Returns the string of the symbol, or `None` if the symbol is from another interner
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        let end = *self.ends.get(symbol.index())?;
+        let start = match symbol.index() {
+            0 => 0,
+            x => self.ends[x - 1],
+        };
+        Some(&self.buffer[start..end])
+    }
+
+    /// The number of distinct strings
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the slot that holds the string or the empty slot where it belongs
+    fn probe(&self, s: &str) -> usize {
+        let mask = self.slots.len() - 1;
+        let mut slot = hash(s) & mask;
+        loop {
+            match self.slots[slot] {
+                0 => return slot,
+                x if self.resolve(Symbol(x - 1)) == Some(s) => return slot,
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    }
+
+    fn rehash(&mut self, slots: usize) {
+        self.slots = vec![0; slots];
+        for i in 0..self.ends.len() {
+            let start = if i == 0 { 0 } else { self.ends[i - 1] };
+            let slot = self.probe(&self.buffer[start..self.ends[i]]);
+            self.slots[slot] = i as u32 + 1;
+        }
+    }
+}
+
+/// Keeps the table at most half full so that probes stay short
+fn slots_for(strings: usize) -> usize {
+    (strings * 2).next_power_of_two().max(MIN_SLOTS)
+}
+
+fn hash(s: &str) -> usize {
+    let mut hasher = FnvHasher::default();
+    hasher.write(s.as_bytes());
+    hasher.finish() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_round_trip() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.get("a"), None);
+
+        let names: Vec<String> = (0..100).map(|x| format!("Object{}", x)).collect();
+        let symbols: Vec<Symbol> = names.iter().map(|x| interner.intern(x)).collect();
+        assert_eq!(interner.len(), 100);
+        assert_eq!(symbols[42].index(), 42);
+
+        for (name, symbol) in names.iter().zip(symbols.iter()) {
+            assert_eq!(interner.intern(name), *symbol);
+            assert_eq!(interner.get(name), Some(*symbol));
+            assert_eq!(interner.resolve(*symbol), Some(name.as_str()));
+        }
+
+        assert_eq!(interner.len(), 100);
+        assert_eq!(interner.get("Object100"), None);
+        assert_eq!(interner.resolve(Symbol(100)), None);
+    }
+
+    #[test]
+    fn test_empty_string() {
+        let mut interner = Interner::with_capacity(2, 1);
+        let empty = interner.intern("");
+        let a = interner.intern("a");
+        assert_ne!(empty, a);
+        assert_eq!(interner.resolve(empty), Some(""));
+        assert_eq!(interner.get(""), Some(empty));
+    }
+}
@@ -62,25 +62,62 @@
 //!
 //! Boxcars will also check for replay corruption on error, but this can be configured to always
 //! check for corruption or never check.
+//!
+//! With the `log` feature enabled, the recoverable oddities encountered while parsing (skipped
+//! network data, crc mismatches, attributes without a known decoding) are reported through the
+//! [log](https://docs.rs/log) crate, so they can be routed to an application's logger. Nothing is
+//! logged without the feature.
+//!
+//! With the `approx` feature enabled, [`Vector3f`] and [`Quaternion`] implement the `AbsDiffEq`
+//! and `RelativeEq` traits of the [approx](https://docs.rs/approx) crate, so that computed
+//! locations and rotations can be compared with `assert_relative_eq!` in tests. Quaternions are
+//! compared as rotations, so a quaternion is approximately equal to its negation.
 
 #[macro_use]
 extern crate serde;
 
 #[macro_use]
 mod macros;
-pub use self::errors::{AttributeError, FrameContext, FrameError, NetworkError, ParseError};
+pub use self::actor_state::{ActorState, ActorStateModeler};
+pub use self::errors::{
+    AttributeError, CacheError, FrameContext, FrameError, NetworkError, ParseError, ParseWarning,
+    ProcessorError, TimelineError,
+};
+pub use self::interner::{Interner, Symbol};
 pub use self::models::*;
 pub use self::network::attributes::*;
 pub use self::network::*;
+pub use self::object_index::ObjectIndex;
 pub use self::parser::{CrcCheck, NetworkParse, ParserBuilder};
+pub use self::processor::{BallFrame, CarComponent, PlayerFrame, ReplayProcessor};
+pub use self::scoreboard::{ScoreboardEntry, ScoreboardState};
+pub use self::timeline::{single_player_timeline, PlayerTimeline, PlayerTimelineFrame};
+pub use self::visitor::FrameVisitor;
+mod actor_state;
+mod anonymize;
 mod bits;
+pub mod cache;
+mod content_hash;
 mod core_parser;
 pub mod crc;
 mod data;
+pub mod delta;
 mod errors;
+pub mod events;
+pub mod export;
+pub mod field;
 mod header;
+pub mod inspect;
+mod interner;
 mod models;
 mod network;
+mod object_index;
 mod parser;
 mod parsing_utils;
+mod processor;
+mod scoreboard;
 mod serde_utils;
+pub mod stats;
+mod timeline;
+mod trim;
+mod visitor;
@@ -1,3 +1,24 @@
 macro_rules! get_or(
     ($e:expr, $s:expr) => (match $e { Some(e) => Ok(e), None => return Err(AttributeError::NotEnoughDataFor($s)) })
 );
+
+// Forward to the `log` crate's macros when the `log` feature is enabled. Otherwise the arguments
+// are still type checked (so variables only used for logging aren't reported as unused), but
+// nothing is formatted or output.
+macro_rules! log_warn(
+    ($($arg:tt)+) => ({
+        #[cfg(feature = "log")]
+        log::warn!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false { let _ = format_args!($($arg)+); }
+    })
+);
+
+macro_rules! log_debug(
+    ($($arg:tt)+) => ({
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false { let _ = format_args!($($arg)+); }
+    })
+);
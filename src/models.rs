@@ -8,10 +8,19 @@
 /// numeric/string types). Asking "why JSON" would be next logical step, and that's due to other
 /// rocket league replay parsers (like Octane) using JSON; however, the output of this library is
 /// not compatible with that of other rocket league replay parsers.
-use crate::network::Frame;
+use crate::actor_state::ActorStateModeler;
+use crate::data::{ARENA_VARIANTS, GAME_MODES, PATCHES, PATCH_ERAS, SEASONS, TITLES};
+use crate::network::attributes::{
+    Attribute, CamSettings, Loadout, Platform, Product, ProductValue, TeamPaint, UniqueId,
+};
+use crate::network::{normalize_object, ActorId, Frame};
+use crate::object_index::ObjectIndex;
+use crate::processor::ReplayProcessor;
+use crate::visitor::FrameVisitor;
 use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
 use serde::{Serialize, Serializer};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 
 /// The structure that a rocket league replay is parsed into.
 #[derive(Serialize, PartialEq, Debug, Clone)]
@@ -30,6 +39,12 @@ pub struct Replay {
     pub content_size: i32,
     pub content_crc: u32,
     pub network_frames: Option<NetworkFrames>,
+
+    /// A copy of the undecoded network data, when retained with
+    /// [`ParserBuilder::retain_network_data`](crate::ParserBuilder::retain_network_data). See
+    /// [`Replay::network_bytes`].
+    #[serde(skip)]
+    pub network_data: Option<Vec<u8>>,
     pub levels: Vec<String>,
     pub keyframes: Vec<KeyFrame>,
     pub debug_info: Vec<DebugInfo>,
@@ -41,6 +56,1335 @@ pub struct Replay {
     pub net_cache: Vec<ClassNetCache>,
 }
 
+impl Replay {
+    /// Returns the first header property with the given key (eg: "TeamSize")
+    pub fn property(&self, key: &str) -> Option<&HeaderProp> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the first header property with the given key
+    pub fn property_mut(&mut self, key: &str) -> Option<&mut HeaderProp> {
+        self.properties
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Replaces the value of the first header property with the given key, returning the previous
+    /// value. The property is appended if it does not exist.
+    pub fn set_property(&mut self, key: &str, value: HeaderProp) -> Option<HeaderProp> {
+        match self.property_mut(key) {
+            Some(prop) => Some(std::mem::replace(prop, value)),
+            None => {
+                self.properties.push((String::from(key), value));
+                None
+            }
+        }
+    }
+
+    /// Removes the first header property with the given key, returning its value
+    pub fn remove_property(&mut self, key: &str) -> Option<HeaderProp> {
+        let index = self.properties.iter().position(|(k, _)| k == key)?;
+        Some(self.properties.remove(index).1)
+    }
+
+    /// Returns the decoded network frames. `None` is returned when the network data was not
+    /// parsed: either the parser was configured with [`NetworkParse::Never`], or with
+    /// [`NetworkParse::IgnoreOnError`] and decoding the network data failed.
+    ///
+    /// [`NetworkParse::Never`]: crate::NetworkParse::Never
+    /// [`NetworkParse::IgnoreOnError`]: crate::NetworkParse::IgnoreOnError
+    pub fn frames(&self) -> Option<&[Frame]> {
+        self.network_frames.as_ref().map(|x| x.frames.as_slice())
+    }
+
+    /// Returns the decoded network frames, or an empty slice when the network data was not parsed
+    /// (see [`Replay::frames`])
+    pub fn frames_or_empty(&self) -> &[Frame] {
+        self.frames().unwrap_or_default()
+    }
+
+    /// Returns a hash of the decoded network frames for detecting when the frames, or how boxcars
+    /// decodes them, change (eg: to invalidate cached analytics). The hash is a 64 bit FNV-1a hash
+    /// of a canonical encoding of every frame, including the names of the models' fields, so it is
+    /// stable across runs and platforms. Replays where the network data was not parsed all share
+    /// the same hash.
+    pub fn content_hash(&self) -> u64 {
+        crate::content_hash::hash(&self.frames())
+    }
+
+    /// Returns a copy of the replay with the names and online ids of players replaced by
+    /// pseudonyms, so that it can be shared without identifying who played. Players are named
+    /// "Player 1" to "Player N" in the order that they are first seen, and each player keeps the
+    /// same pseudonym and pseudonymous id everywhere, so the network data still links a player's
+    /// cars, stats, and header entries.
+    ///
+    /// The scrubbed fields are:
+    ///
+    /// - Header: "PlayerName", the "Name", "OnlineID", and "PartyLeader" of every "PlayerStats"
+    ///   entry, and the "PlayerName" of every "Goals" entry. Player names within "ReplayName" are
+    ///   replaced, as automatically saved replays are named after the player that saved them.
+    /// - Network: player names ("Engine.PlayerReplicationInfo:PlayerName"), unique ids, party
+    ///   leaders, reservations, and "Engine.PlayerReplicationInfo:RemoteUserData". The lobby name
+    ///   and password of private match settings are cleared.
+    /// - Debug info: the online ids in the MMR entries that BakkesMod records.
+    ///
+    /// Online ids keep their platform and become the player's number (for Epic, the number as 32
+    /// hex digits). Bots and split screen players have no online id to scrub. Everything else is
+    /// kept as is, including server names and addresses, match ids, club ids, custom team names,
+    /// and chat in tick marks or other debug info. Only the parsed data is anonymized, so the
+    /// network data should be parsed for the result to be free of player names and ids. The
+    /// [undecoded network data](Replay::network_bytes) is not kept.
+    pub fn anonymize(&self) -> Replay {
+        crate::anonymize::anonymize(self)
+    }
+
+    /// Returns a copy of the replay cut down to the network frames in `start..end`, for sharing a
+    /// clip. The range is clamped to the replay's frames (or the header's "NumFrames" if the
+    /// network data was not parsed).
+    ///
+    /// Actors created before the cut would otherwise be missing, so the first frame of the clip
+    /// is synthesized like a keyframe: it creates every actor alive at `start` and replicates the
+    /// latest value of each of their attributes, including the updates of frame `start` itself.
+    /// Following frames are kept as is. Frame times are rebased so that the clip starts at 0.
+    ///
+    /// The header's "NumFrames" is set to the length of the clip, and the "Goals" and
+    /// "HighLights" entries, keyframes, tick marks, and debug info outside of the range are
+    /// dropped while the rest have their frame rebased. The clip starts with a keyframe.
+    ///
+    /// Limitations:
+    ///
+    /// - Times within attributes (eg: the seconds remaining, or when a boost pad respawns) are
+    ///   not rebased, nor is anything else in the header, like the final score and player stats
+    /// - Keyframe positions index into the original encoded network data, so they are zeroed, and
+    ///   the sizes and CRCs are those of the original replay
+    /// - Attributes that signal one-off events (eg: the latest demolition or stat event of an
+    ///   actor) are replicated again in the first frame, so they may be reported as events at the
+    ///   start of the clip
+    pub fn trim(&self, start: usize, end: usize) -> Replay {
+        crate::trim::trim(self, start, end)
+    }
+
+    /// Applies every network frame in order to a reconstruction of the live actors, calling the
+    /// visitor for each change along with the reconstructed state. This is the state machine
+    /// behind the crate's analytics, for building others on. Does nothing if the network data was
+    /// not parsed.
+    pub fn walk<V: FrameVisitor>(&self, visitor: &mut V) {
+        crate::visitor::walk(self, visitor)
+    }
+
+    /// Folds over every network frame in order, handing the function the accumulator, the frame,
+    /// and the reconstructed state of every live actor once the frame has been applied. This is
+    /// the functional counterpart of [`Replay::walk`] for one off aggregations. Returns `init`
+    /// if the network data was not parsed or has no frames.
+    ///
+    /// ```
+    /// use boxcars::{Attribute, ObjectIndex, ParserBuilder};
+    /// let data = include_bytes!("../assets/replays/good/rumble.replay");
+    /// let replay = ParserBuilder::new(&data[..])
+    ///     .must_parse_network_data()
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let index = ObjectIndex::new(&replay.objects);
+    /// let ball = index.get_object_id("Archetypes.Ball.Ball_Default").unwrap();
+    /// let rigid_body = index.get_object_id("TAGame.RBActor_TA:ReplicatedRBState").unwrap();
+    ///
+    /// let max_ball_speed = replay.reduce(0.0f32, |max, _frame, actors| {
+    ///     let speeds = actors
+    ///         .actor_ids_by_type(ball)
+    ///         .iter()
+    ///         .filter_map(|x| actors.get(*x)?.attribute(rigid_body))
+    ///         .filter_map(|x| match x {
+    ///             Attribute::RigidBody(body) => body.linear_velocity,
+    ///             _ => None,
+    ///         })
+    ///         .map(|v| (v.x * v.x + v.y * v.y + v.z * v.z).sqrt());
+    ///     speeds.fold(max, f32::max)
+    /// });
+    ///
+    /// assert!(max_ball_speed > 0.0);
+    /// ```
+    pub fn reduce<A, F>(&self, init: A, mut f: F) -> A
+    where
+        F: FnMut(A, &Frame, &ActorStateModeler) -> A,
+    {
+        let mut actors = ActorStateModeler::new();
+        self.frames_or_empty().iter().fold(init, |acc, frame| {
+            actors.process_frame(frame);
+            f(acc, frame, &actors)
+        })
+    }
+
+    /// Returns the raw network data: the bytes of the network stream within the body section,
+    /// which follow the keyframes and the stream's size. The body section is what the content CRC
+    /// covers, so the bytes have passed the CRC check if one was performed. The bytes are only
+    /// retained when parsing with
+    /// [`ParserBuilder::retain_network_data`](crate::ParserBuilder::retain_network_data), which
+    /// lets an external decoder (or another version of boxcars) decode the frames of a replay
+    /// whose header and body were parsed by boxcars, eg: to compare decoders.
+    ///
+    /// Returns `None` when the network data was not retained, or after the replay was changed in a
+    /// way that the bytes no longer reflect, like with [`Replay::anonymize`] or [`Replay::trim`].
+    pub fn network_bytes(&self) -> Option<&[u8]> {
+        self.network_data.as_deref()
+    }
+
+    /// Returns where the header and body sections, and the CRCs that cover them, are within the
+    /// replay file, as computed from the sizes recorded when the replay was parsed. Each section
+    /// is prefixed with its size and CRC as little endian 32 bit integers, and the CRC covers only
+    /// the section's data that follows:
+    ///
+    /// ```text
+    ///  0        4        8                      8+H      12+H     16+H                 16+H+B
+    ///  +--------+--------+----------------------+--------+--------+--------------------+
+    ///  | size H |  crc   |     header data      | size B |  crc   |     body data      |
+    ///  +--------+--------+----------------------+--------+--------+--------------------+
+    ///           ^        \____ header_range ____/         ^        \____ body_range ___/
+    ///           header_crc_offset                        body_crc_offset
+    /// ```
+    ///
+    /// A replay that is edited and written again must recompute the CRCs with
+    /// [`calc_crc`](crate::crc::calc_crc) over the new ranges. Negative sizes are treated as
+    /// empty sections.
+    pub fn checksum_sections(&self) -> ChecksumLayout {
+        let header_size = usize::try_from(self.header_size).unwrap_or(0);
+        let content_size = usize::try_from(self.content_size).unwrap_or(0);
+        let header_start = 8;
+        let header_end = header_start + header_size;
+        let body_start = header_end + 8;
+
+        ChecksumLayout {
+            header_range: header_start..header_end,
+            header_crc_offset: 4,
+            body_range: body_start..body_start + content_size,
+            body_crc_offset: header_end + 4,
+        }
+    }
+
+    /// Returns the decoded network frames for in place editing, such as anonymizing player names.
+    /// `None` is returned if the network data was not parsed.
+    ///
+    /// Edits should keep the frames consistent with the rest of the replay so that they can be
+    /// encoded again:
+    ///
+    /// - Object ids must index into `objects` and actor ids must stay below the header's
+    ///   "MaxChannels"
+    /// - An updated attribute must keep the variant that its object's attribute decodes to
+    /// - Frame times must not decrease and the number of frames must match the header's
+    ///   "NumFrames"
+    pub fn network_frames_mut(&mut self) -> Option<&mut Vec<Frame>> {
+        self.network_frames.as_mut().map(|x| &mut x.frames)
+    }
+
+    /// Applies the function to every decoded network frame in order. See
+    /// [`Replay::network_frames_mut`] for the constraints that edits should uphold.
+    pub fn map_frames<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Frame),
+    {
+        if let Some(frames) = self.network_frames_mut() {
+            frames.iter_mut().for_each(f);
+        }
+    }
+
+    /// Returns the index of the last network frame at or before the given replay time. Frame times
+    /// only increase, so the frame is found with a binary search. A time before the first frame
+    /// returns the first frame and a time after the last frame returns the last frame. `None` is
+    /// returned if there are no network frames.
+    pub fn frame_index_at_time(&self, time: f32) -> Option<usize> {
+        let frames = self.frames()?;
+        if frames.is_empty() {
+            return None;
+        }
+
+        let after = frames.partition_point(|x| x.time <= time);
+        Some(after.saturating_sub(1))
+    }
+
+    /// Returns the header's "RecordFPS": the maximum rate (frames per second) at which network
+    /// frames were recorded
+    pub fn record_fps(&self) -> Option<f32> {
+        self.property("RecordFPS").and_then(|prop| prop.as_float())
+    }
+
+    /// Returns the header's "BuildVersion": the build of the game that recorded the replay (eg:
+    /// "221120.42953.406184"), which starts with the date of the build. Replays recorded before
+    /// mid 2016 don't have one.
+    pub fn engine_build(&self) -> Option<&str> {
+        self.property("BuildVersion")
+            .and_then(|prop| prop.as_string())
+    }
+
+    /// Returns a human readable label of the era of the game that recorded the replay, like
+    /// "Season 8 (v2.23)". The season is looked up from the date that the match was played and is
+    /// only known for free to play seasons. The game version is looked up from the engine build
+    /// when it is a known build (eg: "v2.23"). Otherwise it is looked up from the version triplet
+    /// (major.minor.net), which is shared by every patch until one changes the replay format, so
+    /// the label names the earliest patch that could have recorded the replay (eg: "v2.01+").
+    /// When neither is known, the triplet stands in for it: "Season 1 (868.29.10)", or "868.20.2"
+    /// without a known season. Replays without a net version are labeled with major.minor (eg:
+    /// "868.10"), and `None` is returned for very early replays that don't record their version.
+    pub fn patch_label(&self) -> Option<String> {
+        if self.major_version == 0 {
+            return None;
+        }
+
+        let triplet = (
+            self.major_version,
+            self.minor_version,
+            self.net_version.unwrap_or(0),
+        );
+        let version = self
+            .engine_build()
+            .and_then(|build| PATCHES.get(build))
+            .map(|patch| String::from(*patch))
+            .or_else(|| {
+                PATCH_ERAS
+                    .iter()
+                    .find(|(x, _)| *x == triplet)
+                    .map(|(_, patch)| format!("{}+", patch))
+            })
+            .unwrap_or_else(|| match self.net_version {
+                Some(net) => format!("{}.{}.{}", self.major_version, self.minor_version, net),
+                None => format!("{}.{}", self.major_version, self.minor_version),
+            });
+
+        // Dates are written as "2022-12-01 22-14-21" or, in older replays, "2016-06-06:22-12"
+        let season = self
+            .property("Date")
+            .and_then(|prop| prop.as_string())
+            .and_then(|x| x.get(..10))
+            .filter(|x| x.bytes().all(|c| c.is_ascii_digit() || c == b'-'))
+            .and_then(|date| {
+                SEASONS
+                    .iter()
+                    .rev()
+                    .find(|(start, _)| *start <= date)
+                    .map(|(_, season)| *season)
+            });
+
+        match season {
+            Some(season) => Some(format!("{} ({})", season, version)),
+            None => Some(version),
+        }
+    }
+
+    /// Returns the visual variant (time of day, weather, or event skin) of the arena that the match
+    /// was played in, as encoded in the header's unnormalized "MapName" (eg: "EuroStadium_Rainy_P"
+    /// is the stormy Mannfield). Stadium names without a recognized variant are treated as the
+    /// standard skin, and `None` is returned if the header does not name the stadium.
+    pub fn arena_variant(&self) -> Option<ArenaVariant> {
+        let name = self
+            .property("MapName")
+            .and_then(|prop| prop.as_string())?
+            .to_lowercase();
+
+        let name = name.strip_suffix("_p").unwrap_or(&name);
+        let variant = name
+            .split('_')
+            .find_map(|token| ARENA_VARIANTS.get(token).copied())
+            .unwrap_or(ArenaVariant::Standard);
+        Some(variant)
+    }
+
+    /// Returns the name that the replay was saved under (eg: "Kevin got REKT"). Replays that were
+    /// automatically saved are named after the date, player, playlist, and result, while replays
+    /// that were never saved (eg: those downloaded from a match history) have no name.
+    pub fn replay_name(&self) -> Option<&str> {
+        self.property("ReplayName")
+            .and_then(|prop| prop.as_string())
+    }
+
+    /// Returns the header's "PlayerName": the name of the player that recorded the replay
+    pub fn author(&self) -> Option<&str> {
+        self.property("PlayerName")
+            .and_then(|prop| prop.as_string())
+    }
+
+    /// Returns the team (0 for blue and 1 for orange) of the player that recorded the replay
+    pub fn primary_player_team(&self) -> Option<u8> {
+        self.property("PrimaryPlayerTeam")
+            .and_then(|prop| prop.as_i32())
+            .and_then(|x| u8::try_from(x).ok())
+    }
+
+    /// Returns a typed view of every entry in the header's "HighLights" array, which are the
+    /// moments (usually goals) that the in-game replay viewer lets one jump to. An empty vector is
+    /// returned if the replay does not contain highlights.
+    pub fn highlights(&self) -> Vec<Highlight> {
+        self.property("HighLights")
+            .and_then(|prop| prop.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|entry| Highlight::from_props(entry))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the jumps in network frame times that are much longer than the recording's cadence,
+    /// which indicate frames that were dropped while recording or skipped sections (eg: goal
+    /// replays are not recorded). The rate that frames are recorded at varies throughout a replay
+    /// and is often below "RecordFPS", so the expected time between frames is the median of the
+    /// surrounding frame times, but never shorter than the interval implied by "RecordFPS".
+    pub fn frame_gaps(&self) -> Vec<FrameGap> {
+        let frames = self.frames_or_empty();
+        let intervals: Vec<f32> = frames.windows(2).map(|x| x[1].time - x[0].time).collect();
+        let min_interval = self
+            .record_fps()
+            .filter(|x| *x > 0.0)
+            .map_or(0.0, |x| 1.0 / x);
+
+        let mut result = Vec::new();
+        for (i, actual) in intervals.iter().enumerate() {
+            let start = i.saturating_sub(FRAME_GAP_WINDOW);
+            let end = (i + FRAME_GAP_WINDOW + 1).min(intervals.len());
+            let mut window = intervals[start..end].to_vec();
+            window.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let expected = window[window.len() / 2].max(min_interval);
+
+            if expected > 0.0 && *actual > expected * FRAME_GAP_FACTOR {
+                result.push(FrameGap {
+                    frame: i + 1,
+                    expected,
+                    actual: *actual,
+                });
+            }
+        }
+
+        result
+    }
+
+    /// Returns a typed view of every entry in the header's "PlayerStats" array. An empty vector is
+    /// returned if the replay does not contain player stats (eg: the match was abandoned). Fields
+    /// that are not present in a given entry (older replays may omit some) are `None`.
+    pub fn player_stats(&self) -> Vec<PlayerStatsEntry> {
+        self.property("PlayerStats")
+            .and_then(|prop| prop.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|entry| PlayerStatsEntry::from_props(entry))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the nominal number of players on the blue and orange teams (eg: `(3, 3)` for a 3v3
+    /// match) from the header's "TeamSize". Replays without it fall back to counting the players
+    /// of each team in the header's "PlayerStats", which may differ from the nominal size when
+    /// players left the match. See [`events::roster_changes`](crate::events::roster_changes) for
+    /// who joined and left.
+    pub fn team_sizes(&self) -> (u8, u8) {
+        if let Some(size) = self.property("TeamSize").and_then(|x| x.as_i32()) {
+            let size = u8::try_from(size).unwrap_or(0);
+            return (size, size);
+        }
+
+        let stats = self.player_stats();
+        let count = |team| stats.iter().filter(|x| x.team == Some(team)).count() as u8;
+        (count(0), count(1))
+    }
+
+    /// Returns the blue and orange team colors if the match used custom team colors (eg: a club
+    /// match). `None` is returned when the teams use the default colors or if the network data was
+    /// not parsed.
+    pub fn team_colors(&self) -> Option<(TeamColor, TeamColor)> {
+        let club_colors = self
+            .objects
+            .iter()
+            .position(|x| x == "TAGame.Team_TA:ClubColors")?;
+
+        // Each team actor replicates the club colors, but a team actor may only flag its own
+        // side's color as custom, so the latest custom color of each side is merged
+        let mut blue: Option<TeamColor> = None;
+        let mut orange: Option<TeamColor> = None;
+        let updates = self
+            .network_frames
+            .as_ref()?
+            .frames
+            .iter()
+            .flat_map(|frame| frame.updated_actors.iter())
+            .filter(|update| usize::from(update.object_id) == club_colors);
+
+        for update in updates {
+            if let Attribute::ClubColors(colors) = update.attribute {
+                let color = TeamColor {
+                    palette_index: colors.blue_color,
+                    is_custom: colors.blue_flag,
+                };
+                if color.is_custom
+                    || !matches!(
+                        blue,
+                        Some(TeamColor {
+                            is_custom: true,
+                            ..
+                        })
+                    )
+                {
+                    blue = Some(color);
+                }
+
+                let color = TeamColor {
+                    palette_index: colors.orange_color,
+                    is_custom: colors.orange_flag,
+                };
+                if color.is_custom
+                    || !matches!(
+                        orange,
+                        Some(TeamColor {
+                            is_custom: true,
+                            ..
+                        })
+                    )
+                {
+                    orange = Some(color);
+                }
+            }
+        }
+
+        match (blue, orange) {
+            (Some(blue), Some(orange)) if blue.is_custom || orange.is_custom => {
+                Some((blue, orange))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the game mode of the match, as identified by the archetype of the game event in the
+    /// replay's objects, so the network data does not need to be parsed. Older rumble matches use
+    /// the soccar game event, so soccar matches with rumble items are identified as rumble.
+    pub fn game_mode(&self) -> GameMode {
+        let mode = self
+            .objects
+            .iter()
+            .find_map(|x| GAME_MODES.get(x.as_str()).copied())
+            .unwrap_or(GameMode::Unknown);
+
+        let items = || {
+            self.objects
+                .iter()
+                .any(|x| x.starts_with("Archetypes.SpecialPickups."))
+        };
+
+        match mode {
+            GameMode::Soccar if items() => GameMode::Rumble,
+            mode => mode,
+        }
+    }
+
+    /// Returns the game rules that the match was played under, as replicated in the network data.
+    /// The item pool of rumble and other item based modes is read from the object table, so it is
+    /// known even when the network data was not parsed. Standard matches (and replays where the
+    /// network data was not parsed) otherwise return `MatchSettings::default()`.
+    pub fn match_settings(&self) -> MatchSettings {
+        let mut settings = MatchSettings::default();
+
+        // The item pool isn't recorded in the header, but the items mode and every item that it
+        // can hand out are listed in the object table
+        for object in &self.objects {
+            let sub_rules = object.strip_prefix(SUB_RULES_PREFIX);
+            if let Some(mode) = sub_rules.filter(|x| x.starts_with("ItemsMode")) {
+                settings.items_mode = Some(String::from(mode));
+            } else if let Some(item) = object.strip_prefix(RUMBLE_ITEM_PREFIX) {
+                settings.rumble_items.push(String::from(item));
+            }
+        }
+
+        let frames = match self.frames() {
+            Some(x) => x,
+            None => return settings,
+        };
+
+        let updates = frames.iter().flat_map(|frame| frame.updated_actors.iter());
+        for update in updates {
+            let key = match self.objects.get(usize::from(update.object_id)) {
+                Some(x) => x.as_str(),
+                None => continue,
+            };
+
+            // Scales are tracked by their latest value, while boost flags are set per car so any
+            // car with the flag marks the match
+            match (key, &update.attribute) {
+                ("TAGame.Ball_TA:ReplicatedBallScale", Attribute::Float(x)) => {
+                    settings.ball_scale = *x
+                }
+                ("TAGame.Ball_TA:ReplicatedBallMaxLinearSpeedScale", Attribute::Float(x)) => {
+                    settings.ball_max_speed_scale = *x
+                }
+                ("TAGame.Ball_TA:ReplicatedWorldBounceScale", Attribute::Float(x)) => {
+                    settings.ball_bounce_scale = *x
+                }
+                ("TAGame.CarComponent_Boost_TA:BoostModifier", Attribute::Float(x)) => {
+                    settings.boost_strength = *x
+                }
+                ("TAGame.CarComponent_Boost_TA:RechargeRate", Attribute::Float(x)) => {
+                    settings.boost_recharge_rate = Some(*x)
+                }
+                ("TAGame.CarComponent_Boost_TA:bUnlimitedBoost", Attribute::Boolean(x)) => {
+                    settings.unlimited_boost |= *x
+                }
+                ("TAGame.CarComponent_Boost_TA:bNoBoost", Attribute::Boolean(x)) => {
+                    settings.no_boost |= *x
+                }
+                ("TAGame.GameEvent_Soccar_TA:bUnlimitedTime", Attribute::Boolean(x)) => {
+                    settings.unlimited_time = *x
+                }
+                ("ProjectX.GRI_X:ReplicatedGameMutatorIndex", Attribute::Int(x)) => {
+                    settings.mutator_index = Some(*x).filter(|x| *x >= 0)
+                }
+                ("TAGame.GameEvent_SoccarPrivate_TA:MatchSettings", Attribute::PrivateMatch(x)) => {
+                    settings.mutators = x
+                        .mutators
+                        .split(',')
+                        .filter(|x| !x.is_empty())
+                        .map(String::from)
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+
+        settings
+    }
+
+    /// Returns true if the match went to overtime, as replicated on the game event. Replays where
+    /// the network data was not parsed return false.
+    pub fn went_to_overtime(&self) -> bool {
+        self.overtime_duration().is_some()
+    }
+
+    /// Returns how long the overtime lasted in seconds, or `None` if the match didn't go to
+    /// overtime. Overtime is timed from its kickoff (the game event's state turning active after
+    /// it enters overtime) to the match's end: the game event leaving the active and countdown
+    /// states (eg: for the winning goal) or the match being marked as ended. A replay that stops
+    /// before then, as when a team forfeits during overtime, is timed to its last frame.
+    pub fn overtime_duration(&self) -> Option<f32> {
+        let frames = self.frames()?;
+        let position = |key: &str| self.objects.iter().position(|x| x == key);
+        let overtime_key = position("TAGame.GameEvent_Soccar_TA:bOverTime")?;
+        let ended_key = position("TAGame.GameEvent_Soccar_TA:bMatchEnded");
+        let state_key = position("TAGame.GameEvent_TA:ReplicatedStateName");
+        let state_name = |index: i32| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|x| self.names.get(x))
+                .map(|x| x.as_str())
+        };
+
+        let mut overtime: Option<f32> = None;
+        let mut kickoff: Option<f32> = None;
+        for frame in frames {
+            for update in &frame.updated_actors {
+                let key = Some(usize::from(update.object_id));
+                let ended = match &update.attribute {
+                    Attribute::Boolean(true) if key == Some(overtime_key) => {
+                        overtime.get_or_insert(frame.time);
+                        false
+                    }
+                    Attribute::Boolean(true) => key == ended_key,
+                    Attribute::Int(x) if key == state_key && overtime.is_some() => {
+                        match state_name(*x) {
+                            Some("Active") => {
+                                kickoff.get_or_insert(frame.time);
+                                false
+                            }
+                            Some("Countdown") => false,
+                            _ => true,
+                        }
+                    }
+                    _ => false,
+                };
+
+                if let (true, Some(start)) = (ended, kickoff.or(overtime)) {
+                    return Some(frame.time - start);
+                }
+            }
+        }
+
+        let last = frames.last()?;
+        kickoff.or(overtime).map(|start| last.time - start)
+    }
+
+    /// Returns the latest camera settings of each player keyed by their unique id. Camera settings
+    /// are replicated either by a camera settings actor linked to the player or, in older replays,
+    /// on the player directly. Players whose camera settings were never replicated (eg: bots) are
+    /// given the game's [default](CamSettings::default) settings. Empty if the network data was not
+    /// parsed.
+    pub fn camera_settings(&self) -> HashMap<UniqueId, CamSettings> {
+        let mut processor = ReplayProcessor::new(self);
+        let player_settings = processor.object_id("TAGame.PRI_TA:CameraSettings");
+        let camera_player = processor.object_id("TAGame.CameraSettingsActor_TA:PRI");
+        let camera_settings = processor.object_id("TAGame.CameraSettingsActor_TA:ProfileSettings");
+
+        let mut result = HashMap::new();
+        let mut players: Vec<UniqueId> = Vec::new();
+        let mut cameras: Vec<ActorId> = Vec::new();
+        for frame in processor.frames() {
+            processor.process_frame(frame);
+            cameras.retain(|x| processor.actor(*x).is_some());
+            for update in &frame.updated_actors {
+                if Some(update.object_id) == camera_player && !cameras.contains(&update.actor_id) {
+                    cameras.push(update.actor_id);
+                }
+            }
+
+            // The settings, the link to the player, and the player's unique id may be replicated
+            // in any order, so the settings are re-resolved every frame
+            for player in processor.players() {
+                let id = match processor.player_unique_id(player) {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                if !players.contains(id) {
+                    players.push(id.clone());
+                }
+
+                let settings = processor
+                    .actor(player)
+                    .and_then(|state| state.attribute(player_settings?));
+                if let Some(Attribute::CamSettings(settings)) = settings {
+                    result.insert(id.clone(), **settings);
+                }
+            }
+
+            for camera in &cameras {
+                let state = match processor.actor(*camera) {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                let player = camera_player.and_then(|x| state.attribute(x));
+                let settings = camera_settings.and_then(|x| state.attribute(x));
+                if let (
+                    Some(Attribute::ActiveActor(player)),
+                    Some(Attribute::CamSettings(settings)),
+                ) = (player, settings)
+                {
+                    if let Some(id) = processor.player_unique_id(player.actor) {
+                        result.insert(id.clone(), **settings);
+                    }
+                }
+            }
+        }
+
+        for player in players {
+            result.entry(player).or_insert_with(CamSettings::default);
+        }
+
+        result
+    }
+
+    /// The title that each player had equipped, keyed by the player's unique id. Titles are read
+    /// from the player's online loadouts and are mapped to the name displayed in game when known
+    /// (eg: "Season3GrandChampion" becomes "Season 3 Grand Champion"), else the title's id is
+    /// returned as is. Players without a title are omitted. Empty if the network data was not
+    /// parsed.
+    pub fn player_titles(&self) -> HashMap<UniqueId, String> {
+        let mut processor = ReplayProcessor::new(self);
+        let unique_id = processor.object_id("Engine.PlayerReplicationInfo:UniqueId");
+        let loadouts = processor.object_id("TAGame.PRI_TA:ClientLoadoutsOnline");
+
+        let mut result = HashMap::new();
+        for frame in processor.frames() {
+            processor.process_frame(frame);
+
+            // The loadouts and unique id may be replicated in any order
+            for update in &frame.updated_actors {
+                let key = Some(update.object_id);
+                if key != loadouts && key != unique_id {
+                    continue;
+                }
+
+                let id = match processor.player_unique_id(update.actor_id) {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                let products = processor
+                    .actor(update.actor_id)
+                    .and_then(|state| state.attribute(loadouts?));
+                if let Some(Attribute::LoadoutsOnline(products)) = products {
+                    let title = products
+                        .blue
+                        .iter()
+                        .chain(products.orange.iter())
+                        .flatten()
+                        .find_map(|x| match &x.value {
+                            ProductValue::Title(title) if !title.is_empty() => Some(title),
+                            _ => None,
+                        });
+
+                    if let Some(title) = title {
+                        result.insert(id.clone(), title_name(title));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The [normalized](crate::ActorState::object_name) names of the types of every actor spawned
+    /// in the network data, eg: `"Archetypes.Ball.Ball_Default"`. Unlike
+    /// [`ObjectIndex::contains_type`](crate::ObjectIndex::contains_type), which checks the types
+    /// that the replay could spawn, only types with an actor are included. Empty if the network
+    /// data was not parsed.
+    pub fn present_types(&self) -> HashSet<&str> {
+        self.frames_or_empty()
+            .iter()
+            .flat_map(|frame| &frame.new_actors)
+            .filter_map(|actor| self.objects.get(usize::from(actor.object_id)))
+            .map(|name| normalize_object(name))
+            .collect()
+    }
+
+    /// The participants of the match that never played, such as the observers, casters, and
+    /// admins of private and tournament matches, in the order that they were first seen. A
+    /// spectator is a human (with a unique id) whose player replication info was never on a team
+    /// (or was flagged as a spectator), was never given a spectator shortcut slot, and never drove
+    /// a car. Matchmade ("Online") matches can't be spectated, so the players that briefly join
+    /// them without being seated aren't reported. Empty if the network data was not parsed or
+    /// nobody spectated.
+    pub fn spectators(&self) -> Vec<SpectatorInfo> {
+        if self.property("MatchType").and_then(|x| x.as_string()) == Some("Online") {
+            return Vec::new();
+        }
+
+        let mut processor = ReplayProcessor::new(self);
+        let spectator_key = processor.object_id("Engine.PlayerReplicationInfo:bIsSpectator");
+        let shortcut_key = processor.object_id("TAGame.PRI_TA:SpectatorShortcut");
+
+        // Candidates by their player replication info, and the ones that played
+        let mut candidates: Vec<(ActorId, SpectatorInfo)> = Vec::new();
+        let mut played: HashSet<ActorId> = HashSet::new();
+        for (i, frame) in processor.frames().iter().enumerate() {
+            processor.process_frame(frame);
+            for car in processor.cars() {
+                played.extend(processor.car_player(*car));
+            }
+
+            for player in processor.players() {
+                let spectating = matches!(
+                    processor
+                        .actor(player)
+                        .and_then(|x| x.attribute(spectator_key?)),
+                    Some(Attribute::Boolean(true))
+                );
+
+                let seated = processor
+                    .actor(player)
+                    .and_then(|x| x.attribute(shortcut_key?))
+                    .is_some();
+
+                if seated || (processor.player_team(player).is_some() && !spectating) {
+                    played.insert(player);
+                }
+
+                let id = match processor.player_unique_id(player) {
+                    Some(x) if !candidates.iter().any(|(actor, _)| *actor == player) => x,
+                    _ => continue,
+                };
+
+                candidates.push((
+                    player,
+                    SpectatorInfo {
+                        name: processor.player_name(player).map(String::from),
+                        player_id: id.clone(),
+                        frame: i,
+                    },
+                ));
+            }
+        }
+
+        candidates
+            .into_iter()
+            .filter(|(actor, _)| !played.contains(actor))
+            .map(|(_, spectator)| spectator)
+            .collect()
+    }
+
+    /// The number of players on each [platform](UniqueId::platform), counting every player whose
+    /// unique id was replicated in the network data, including players that left early. Bots
+    /// have no unique id, so they are not counted, while split screen players in offline matches
+    /// are counted under [`Platform::SplitScreen`]. Empty if the network data was not parsed.
+    pub fn platform_distribution(&self) -> HashMap<Platform, usize> {
+        let index = ObjectIndex::new(&self.objects);
+        let key = index.get_object_id("Engine.PlayerReplicationInfo:UniqueId");
+
+        let mut players: HashSet<&UniqueId> = HashSet::new();
+        for frame in self.frames_or_empty() {
+            for update in &frame.updated_actors {
+                if let (true, Attribute::UniqueId(id)) =
+                    (Some(update.object_id) == key, &update.attribute)
+                {
+                    players.insert(id);
+                }
+            }
+        }
+
+        let mut result = HashMap::new();
+        for player in players {
+            *result.entry(player.platform()).or_insert(0) += 1;
+        }
+
+        result
+    }
+
+    /// The cosmetics of each player, keyed by the player's unique id, joining the product ids of
+    /// the player's loadout with the paint of each item from their online loadout (both for the
+    /// team the player was last seen on) and with the team paint replicated by their car. Empty
+    /// if the network data was not parsed.
+    pub fn cosmetics(&self) -> HashMap<UniqueId, PlayerCosmetics> {
+        let mut processor = ReplayProcessor::new(self);
+        let unique_id = processor.object_id("Engine.PlayerReplicationInfo:UniqueId");
+        let loadout_keys = [
+            processor.object_id("TAGame.PRI_TA:ClientLoadouts"),
+            processor.object_id("TAGame.PRI_TA:ClientLoadoutsOnline"),
+            processor.object_id("TAGame.PRI_TA:ClientLoadout"),
+            processor.object_id("TAGame.PRI_TA:ClientLoadoutOnline"),
+        ];
+        let team_paint = processor.object_id("TAGame.Car_TA:TeamPaint");
+
+        let mut raw: HashMap<UniqueId, RawCosmetics> = HashMap::new();
+        for frame in processor.frames() {
+            processor.process_frame(frame);
+
+            // The loadouts and unique id may be replicated in any order
+            for update in &frame.updated_actors {
+                let key = Some(update.object_id);
+                if key != unique_id && !loadout_keys.contains(&key) {
+                    continue;
+                }
+
+                let id = match processor.player_unique_id(update.actor_id) {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                let entry = raw.entry(id.clone()).or_default();
+                for key in loadout_keys.iter().flatten() {
+                    match processor
+                        .actor(update.actor_id)
+                        .and_then(|x| x.attribute(*key))
+                    {
+                        Some(Attribute::TeamLoadout(x)) => {
+                            entry.loadouts = Some([x.blue, x.orange])
+                        }
+                        Some(Attribute::Loadout(x)) => entry.loadouts = Some([**x, **x]),
+                        Some(Attribute::LoadoutsOnline(x)) => {
+                            entry.online = Some([x.blue.clone(), x.orange.clone()])
+                        }
+                        Some(Attribute::LoadoutOnline(x)) => {
+                            entry.online = Some([x.clone(), x.clone()])
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            for car in processor.cars() {
+                let player = processor.car_player(*car);
+                let id = match player.and_then(|x| processor.player_unique_id(x)) {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                let paint = team_paint
+                    .and_then(|key| processor.actor(*car)?.attribute(key))
+                    .and_then(|x| match x {
+                        Attribute::TeamPaint(x) => Some(*x),
+                        _ => None,
+                    });
+
+                let team = player.and_then(|x| processor.player_team(x));
+                if paint.is_some() || team.is_some() {
+                    let entry = raw.entry(id.clone()).or_default();
+                    entry.team_paint = paint.or(entry.team_paint);
+                    entry.team = team.or(entry.team);
+                }
+            }
+        }
+
+        raw.into_iter()
+            .map(|(id, raw)| (id, raw.into_cosmetics()))
+            .collect()
+    }
+}
+
+/// The latest cosmetic attributes replicated for a player. Loadouts are stored for the blue and
+/// orange teams.
+#[derive(Default)]
+struct RawCosmetics {
+    loadouts: Option<[Loadout; 2]>,
+    online: Option<[Vec<Vec<Product>>; 2]>,
+    team_paint: Option<TeamPaint>,
+    team: Option<u8>,
+}
+
+impl RawCosmetics {
+    fn into_cosmetics(self) -> PlayerCosmetics {
+        let team = self.team_paint.map(|x| x.team).or(self.team).unwrap_or(0);
+        let team = usize::from(team.min(1));
+        let loadout = self.loadouts.map(|x| x[team]);
+        let online = self.online.map(|mut x| std::mem::take(&mut x[team]));
+
+        // Slots of the online loadout, which follow the game's item slots
+        let paint = |slot: usize| {
+            online
+                .as_ref()?
+                .get(slot)?
+                .iter()
+                .find_map(|x| match x.value {
+                    ProductValue::NewPaint(id) | ProductValue::OldPaint(id) if id != 0 => Some(id),
+                    _ => None,
+                })
+        };
+
+        let item =
+            |f: fn(&Loadout) -> Option<u32>| loadout.as_ref().and_then(f).filter(|x| *x != 0);
+        PlayerCosmetics {
+            body: item(|x| Some(x.body)),
+            body_paint: paint(0),
+            decal: item(|x| Some(x.decal)),
+            decal_paint: paint(1),
+            wheels: item(|x| Some(x.wheels)),
+            wheels_paint: paint(2),
+            boost: item(|x| Some(x.rocket_trail)),
+            boost_paint: paint(3),
+            antenna: item(|x| Some(x.antenna)),
+            antenna_paint: paint(4),
+            topper: item(|x| Some(x.topper)),
+            topper_paint: paint(5),
+            engine_audio: item(|x| x.engine_audio),
+            trail: item(|x| x.trail),
+            trail_paint: paint(14),
+            goal_explosion: item(|x| x.goal_explosion),
+            goal_explosion_paint: paint(15),
+            banner: item(|x| x.banner),
+            team_paint: self.team_paint,
+        }
+    }
+}
+
+/// Iterates over the decoded network frames, yielding nothing when the network data was not parsed
+/// (see [`Replay::frames_or_empty`])
+///
+/// ```
+/// # let data = include_bytes!("../assets/replays/good/rumble.replay");
+/// let replay = boxcars::ParserBuilder::new(&data[..]).parse().unwrap();
+/// let mut updates = 0;
+/// for frame in &replay {
+///     updates += frame.updated_actors.len();
+/// }
+/// # assert!(updates > 0);
+/// ```
+impl<'a> IntoIterator for &'a Replay {
+    type Item = &'a Frame;
+    type IntoIter = std::slice::Iter<'a, Frame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames_or_empty().iter()
+    }
+}
+
+/// The in game name of the title with the given id, or the id if the title is not known
+fn title_name(id: &str) -> String {
+    if let Some(name) = TITLES.get(id) {
+        return String::from(*name);
+    }
+
+    // Competitive season rewards follow a pattern, so they don't need to be listed individually
+    let season = id
+        .strip_prefix("Season")
+        .and_then(|x| x.strip_suffix("GrandChampion"))
+        .filter(|x| !x.is_empty() && x.bytes().all(|b| b.is_ascii_digit()));
+
+    match season {
+        Some(season) => format!("Season {} Grand Champion", season),
+        None => String::from(id),
+    }
+}
+
+/// The game mode of a match
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum GameMode {
+    Soccar,
+    Hoops,
+    Dropshot,
+    Rumble,
+    Snowday,
+    Heatseeker,
+    Gridiron,
+    Knockout,
+
+    /// A training or tutorial session
+    Training,
+
+    /// The replay's game event is not recognized
+    Unknown,
+}
+
+/// The visual variant of an arena. The same base arena can be played in several variants, which
+/// only differ in lighting, weather, and decoration.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ArenaVariant {
+    Standard,
+    Day,
+    Dawn,
+    Dusk,
+    Night,
+    Stormy,
+    Snowy,
+
+    /// The halloween event skin
+    Haunted,
+}
+
+/// Prefix of the object of a match's sub rules, like "Archetypes.Mutators.SubRules.ItemsMode"
+const SUB_RULES_PREFIX: &str = "Archetypes.Mutators.SubRules.";
+
+/// Prefix of the objects of the items that can be handed out, like
+/// "Archetypes.SpecialPickups.SpecialPickup_BallFreeze"
+const RUMBLE_ITEM_PREFIX: &str = "Archetypes.SpecialPickups.SpecialPickup_";
+
+/// The game rules of a match. Mutators are only replicated as the private match's mutator list
+/// and the handful of physics and boost attributes decoded here. Gravity and game speed are not
+/// replicated as attributes, so they can only be inferred from `mutators`.
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct MatchSettings {
+    /// The mutator names from a private match's settings (eg: "BotsNone")
+    pub mutators: Vec<String>,
+
+    /// The index of the game's mutator preset, if one is active
+    pub mutator_index: Option<i32>,
+
+    /// Multiplier of the ball's size
+    pub ball_scale: f32,
+
+    /// Multiplier of the ball's max speed
+    pub ball_max_speed_scale: f32,
+
+    /// Multiplier of how much the ball bounces off the world
+    pub ball_bounce_scale: f32,
+
+    /// Multiplier of the acceleration provided by boost
+    pub boost_strength: f32,
+
+    /// The rate boost is recharged at, if boost recharges
+    pub boost_recharge_rate: Option<f32>,
+    pub unlimited_boost: bool,
+    pub no_boost: bool,
+    pub unlimited_time: bool,
+
+    /// The sub rules that hand out items (eg: "ItemsMode" for rumble, or "ItemsMode_Football"
+    /// for gridiron). `None` in modes without items.
+    pub items_mode: Option<String>,
+
+    /// The items that players can be given in the order that the replay lists them (eg:
+    /// "BallFreeze", "Tornado"). Modes built on items hand out a single mode specific item
+    /// (eg: "Football" in gridiron). Empty in modes without items.
+    pub rumble_items: Vec<String>,
+}
+
+impl MatchSettings {
+    /// Returns true if the match was played under the default rules
+    pub fn is_standard(&self) -> bool {
+        *self == MatchSettings::default()
+    }
+}
+
+impl Default for MatchSettings {
+    fn default() -> Self {
+        MatchSettings {
+            mutators: Vec::new(),
+            mutator_index: None,
+            ball_scale: 1.0,
+            ball_max_speed_scale: 1.0,
+            ball_bounce_scale: 1.0,
+            boost_strength: 1.0,
+            boost_recharge_rate: None,
+            unlimited_boost: false,
+            no_boost: false,
+            unlimited_time: false,
+            items_mode: None,
+            rumble_items: Vec::new(),
+        }
+    }
+}
+
+/// A team's color. Replays do not contain the RGB value of a color, only the index into the game's
+/// team color palette.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct TeamColor {
+    pub palette_index: u8,
+
+    /// If false, the team is using the default color for their side
+    pub is_custom: bool,
+}
+
+/// A participant of the match that never played. See [`Replay::spectators`].
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct SpectatorInfo {
+    pub name: Option<String>,
+    pub player_id: UniqueId,
+
+    /// Index of the first frame where the spectator was seen
+    pub frame: usize,
+}
+
+/// An entry from the header's "PlayerStats" array, which is the authoritative per player summary
+/// of the match. Every field is optional as the properties written vary between replay versions.
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct PlayerStatsEntry {
+    pub name: Option<String>,
+
+    /// The platform of the player, eg: "OnlinePlatform_Steam"
+    pub platform: Option<String>,
+    pub online_id: Option<u64>,
+    pub is_bot: Option<bool>,
+
+    /// The online id of the leader of the party the player queued with
+    pub party_leader: Option<u64>,
+    pub team: Option<i32>,
+    pub score: Option<i32>,
+    pub goals: Option<i32>,
+    pub assists: Option<i32>,
+    pub saves: Option<i32>,
+    pub shots: Option<i32>,
+}
+
+impl PlayerStatsEntry {
+    fn from_props(props: &[(String, HeaderProp)]) -> Self {
+        let mut entry = PlayerStatsEntry::default();
+        for (key, val) in props {
+            match (key.as_str(), val) {
+                ("Name", _) => entry.name = val.as_string().map(String::from),
+                ("Platform", HeaderProp::Byte { value, .. }) => entry.platform = value.clone(),
+                ("OnlineID", _) => entry.online_id = val.as_u64(),
+                ("bBot", _) => entry.is_bot = val.as_bool(),
+                ("PartyLeader", _) => entry.party_leader = val.as_u64(),
+                ("Team", _) => entry.team = val.as_i32(),
+                ("Score", _) => entry.score = val.as_i32(),
+                ("Goals", _) => entry.goals = val.as_i32(),
+                ("Assists", _) => entry.assists = val.as_i32(),
+                ("Saves", _) => entry.saves = val.as_i32(),
+                ("Shots", _) => entry.shots = val.as_i32(),
+                _ => {}
+            }
+        }
+        entry
+    }
+}
+
+/// The cosmetic items of a player, as returned by [`Replay::cosmetics`]. Items are product ids
+/// and paints are paint ids. An empty slot, an unpainted item (paint id 0), and an item that was
+/// not replicated are all `None`. Replays from before an item was introduced don't replicate it
+/// (eg: goal explosions).
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct PlayerCosmetics {
+    pub body: Option<u32>,
+    pub body_paint: Option<u32>,
+    pub decal: Option<u32>,
+    pub decal_paint: Option<u32>,
+    pub wheels: Option<u32>,
+    pub wheels_paint: Option<u32>,
+    pub boost: Option<u32>,
+    pub boost_paint: Option<u32>,
+    pub antenna: Option<u32>,
+    pub antenna_paint: Option<u32>,
+    pub topper: Option<u32>,
+    pub topper_paint: Option<u32>,
+    pub engine_audio: Option<u32>,
+    pub trail: Option<u32>,
+    pub trail_paint: Option<u32>,
+    pub goal_explosion: Option<u32>,
+    pub goal_explosion_paint: Option<u32>,
+    pub banner: Option<u32>,
+
+    /// The colors and paint finishes of the player's car
+    pub team_paint: Option<TeamPaint>,
+}
+
+/// An entry from the header's "HighLights" array
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct Highlight {
+    /// The index of the network frame of the highlight
+    pub frame: Option<usize>,
+
+    /// The object name of the car involved in the highlight, eg: "Car_TA_886"
+    pub car_name: Option<String>,
+
+    /// The object name of the ball, eg: "Ball_TA_230"
+    pub ball_name: Option<String>,
+}
+
+impl Highlight {
+    fn from_props(props: &[(String, HeaderProp)]) -> Self {
+        let mut entry = Highlight::default();
+        for (key, val) in props {
+            match key.as_str() {
+                "frame" => {
+                    entry.frame = val.as_i32().and_then(|x| usize::try_from(x).ok());
+                }
+                "CarName" => entry.car_name = val.as_string().map(String::from),
+                "BallName" => entry.ball_name = val.as_string().map(String::from),
+                _ => {}
+            }
+        }
+        entry
+    }
+}
+
+/// The number of frame intervals on either side of an interval that determine the expected time
+/// between frames in [`Replay::frame_gaps`]
+const FRAME_GAP_WINDOW: usize = 15;
+
+/// How many times longer than expected an interval between frames must be to be a gap
+const FRAME_GAP_FACTOR: f32 = 1.5;
+
+/// A jump in network frame times found by [`Replay::frame_gaps`]
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct FrameGap {
+    /// The index of the frame after the gap
+    pub frame: usize,
+
+    /// The expected time (seconds) between frames around the gap
+    pub expected: f32,
+
+    /// The time (seconds) between the frame and the one before it
+    pub actual: f32,
+}
+
+impl FrameGap {
+    /// The estimated number of frames that are missing from the gap
+    pub fn missing_frames(&self) -> u32 {
+        ((self.actual / self.expected).round() as u32).saturating_sub(1)
+    }
+}
+
+/// The byte ranges of a replay file that its CRCs cover, from [`Replay::checksum_sections`]
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct ChecksumLayout {
+    /// The header's data, covered by the header CRC
+    pub header_range: std::ops::Range<usize>,
+
+    /// Offset of the header CRC, a little endian 32 bit integer
+    pub header_crc_offset: usize,
+
+    /// The body's data, covered by the body (content) CRC
+    pub body_range: std::ops::Range<usize>,
+
+    /// Offset of the body CRC, a little endian 32 bit integer
+    pub body_crc_offset: usize,
+}
+
 /// The frames decoded from the network data
 #[derive(Serialize, PartialEq, Debug, Clone)]
 pub struct NetworkFrames {
@@ -264,7 +1608,7 @@ pub struct ClassNetCache {
 /// map structure because most replay parser do this, so we should be compliant and the data format
 /// doesn't dictate that the keys in a sequence of key value pairs must be distinct. It's true,
 /// JSON doesn't need the keys to be unique: <http://stackoverflow.com/q/21832701/433785>
-fn pair_vec<K, V, S>(inp: &[(K, V)], serializer: S) -> Result<S::Ok, S::Error>
+pub(crate) fn pair_vec<K, V, S>(inp: &[(K, V)], serializer: S) -> Result<S::Ok, S::Error>
 where
     K: Serialize,
     V: Serialize,
@@ -371,6 +1715,22 @@ mod tests {
         assert_eq!(to_json(&HeaderProp::Int(11)), "11");
     }
 
+    #[test]
+    fn player_stats_entry_missing_fields() {
+        let props = vec![
+            (String::from("Name"), HeaderProp::Str(String::from("rusty"))),
+            (String::from("Goals"), HeaderProp::Int(2)),
+            (String::from("bBot"), HeaderProp::Bool(true)),
+        ];
+
+        let entry = PlayerStatsEntry::from_props(&props);
+        assert_eq!(entry.name.as_deref(), Some("rusty"));
+        assert_eq!(entry.goals, Some(2));
+        assert_eq!(entry.is_bot, Some(true));
+        assert_eq!(entry.platform, None);
+        assert_eq!(entry.shots, None);
+    }
+
     #[test]
     fn serialize_header_str() {
         let val = "hello world";
@@ -0,0 +1,126 @@
+//! Approximate equality for the real-valued network types, so that floating point results (eg:
+//! world coordinates and interpolated rotations) can be compared with `approx`'s
+//! `assert_abs_diff_eq!` and `assert_relative_eq!`.
+
+use super::models::{Quaternion, Vector3f};
+use approx::{AbsDiffEq, RelativeEq};
+
+/// Default absolute tolerance for locations and velocities: a thousandth of a world unit, which
+/// is an order of magnitude finer than the hundredth of a unit that locations are replicated in
+const VECTOR_EPSILON: f32 = 1e-3;
+
+/// Default absolute tolerance for the components of a quaternion, which lie within [-1, 1]
+const QUATERNION_EPSILON: f32 = 1e-5;
+
+/// Default relative tolerance for both types
+const MAX_RELATIVE: f32 = 1e-5;
+
+impl AbsDiffEq for Vector3f {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> f32 {
+        VECTOR_EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon)
+            && self.y.abs_diff_eq(&other.y, epsilon)
+            && self.z.abs_diff_eq(&other.z, epsilon)
+    }
+}
+
+impl RelativeEq for Vector3f {
+    fn default_max_relative() -> f32 {
+        MAX_RELATIVE
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: f32, max_relative: f32) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative)
+            && self.y.relative_eq(&other.y, epsilon, max_relative)
+            && self.z.relative_eq(&other.z, epsilon, max_relative)
+    }
+}
+
+impl Quaternion {
+    fn components(&self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// Whether the quaternions are the same rotation per the comparison of their components. A
+    /// quaternion and its negation are the same rotation, so the negation is compared as well.
+    fn same_rotation<F>(&self, other: &Self, eq: F) -> bool
+    where
+        F: Fn(f32, f32) -> bool,
+    {
+        let (a, b) = (self.components(), other.components());
+        let all = |sign: f32| a.iter().zip(b.iter()).all(|(x, y)| eq(*x, sign * y));
+        all(1.0) || all(-1.0)
+    }
+}
+
+/// Quaternions are compared as rotations: a quaternion is equal to its negation
+impl AbsDiffEq for Quaternion {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> f32 {
+        QUATERNION_EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.same_rotation(other, |a, b| a.abs_diff_eq(&b, epsilon))
+    }
+}
+
+/// Quaternions are compared as rotations: a quaternion is equal to its negation
+impl RelativeEq for Quaternion {
+    fn default_max_relative() -> f32 {
+        MAX_RELATIVE
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: f32, max_relative: f32) -> bool {
+        self.same_rotation(other, |a, b| a.relative_eq(&b, epsilon, max_relative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::{assert_abs_diff_eq, assert_abs_diff_ne, assert_relative_eq};
+
+    #[test]
+    fn test_vector_approx_eq() {
+        let a = Vector3f {
+            x: 1000.0,
+            y: -2000.0,
+            z: 17.0,
+        };
+        let b = Vector3f {
+            x: 1000.0004,
+            y: -2000.0,
+            z: 17.0,
+        };
+        assert_ne!(a, b);
+        assert_abs_diff_eq!(a, b);
+        assert_relative_eq!(a, b);
+        assert_abs_diff_ne!(a, Vector3f { x: 1000.01, ..b });
+    }
+
+    #[test]
+    fn test_quaternion_approx_eq() {
+        let a = Quaternion {
+            x: 0.5,
+            y: 0.5,
+            z: 0.5,
+            w: 0.5,
+        };
+        let b = Quaternion {
+            x: -0.5,
+            y: -0.5,
+            z: -0.500_001,
+            w: -0.5,
+        };
+        assert_abs_diff_eq!(a, b);
+        assert_relative_eq!(a, b);
+        assert_abs_diff_ne!(a, Quaternion { w: 0.5, ..b });
+    }
+}
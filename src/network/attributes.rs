@@ -5,9 +5,12 @@ use crate::parsing_utils::{decode_utf16, decode_windows1252};
 use bitter::{BitReader, LittleEndianReader};
 use encoding_rs::WINDOWS_1252;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
+/// How an attribute is decoded from the network data
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum AttributeTag {
+pub(crate) enum AttributeDecoding {
     Boolean,
     Byte,
     AppliedDamage,
@@ -50,6 +53,9 @@ pub(crate) enum AttributeTag {
     RepStatTitle,
     PickupInfo,
     Impulse,
+
+    /// Decoded by the user supplied decoder at the index
+    Custom(u16),
 }
 
 /// The attributes for updated actors in the network data.
@@ -104,6 +110,149 @@ pub enum Attribute {
     RepStatTitle(RepStatTitle),
     PickupInfo(PickupInfo),
     Impulse(Impulse),
+
+    /// Decoded by a decoder registered with
+    /// [`ParserBuilder::register_attribute_decoder`](crate::ParserBuilder::register_attribute_decoder)
+    Custom(Box<CustomAttribute>),
+}
+
+/// The kind of an [`Attribute`] without its data, for filtering and tallying attributes. There is
+/// a tag for every variant of `Attribute`. The discriminants are stable: existing tags keep their
+/// values and new tags are given the next unused value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum AttributeTag {
+    Boolean = 0,
+    Byte = 1,
+    AppliedDamage = 2,
+    DamageState = 3,
+    CamSettings = 4,
+    ClubColors = 5,
+    Demolish = 6,
+    DemolishFx = 7,
+    Enum = 8,
+    Explosion = 9,
+    ExtendedExplosion = 10,
+    FlaggedByte = 11,
+    ActiveActor = 12,
+    Float = 13,
+    GameMode = 14,
+    Int = 15,
+    Int64 = 16,
+    Loadout = 17,
+    TeamLoadout = 18,
+    Location = 19,
+    MusicStinger = 20,
+    PlayerHistoryKey = 21,
+    Pickup = 22,
+    PickupNew = 23,
+    QWord = 24,
+    Welded = 25,
+    Title = 26,
+    TeamPaint = 27,
+    RigidBody = 28,
+    String = 29,
+    UniqueId = 30,
+    Reservation = 31,
+    PartyLeader = 32,
+    PrivateMatch = 33,
+    LoadoutOnline = 34,
+    LoadoutsOnline = 35,
+    StatEvent = 36,
+    Rotation = 37,
+    RepStatTitle = 38,
+    PickupInfo = 39,
+    Impulse = 40,
+    Custom = 41,
+}
+
+impl Attribute {
+    /// The kind of the attribute
+    pub fn tag(&self) -> AttributeTag {
+        match self {
+            Attribute::Boolean(_) => AttributeTag::Boolean,
+            Attribute::Byte(_) => AttributeTag::Byte,
+            Attribute::AppliedDamage(_) => AttributeTag::AppliedDamage,
+            Attribute::DamageState(_) => AttributeTag::DamageState,
+            Attribute::CamSettings(_) => AttributeTag::CamSettings,
+            Attribute::ClubColors(_) => AttributeTag::ClubColors,
+            Attribute::Demolish(_) => AttributeTag::Demolish,
+            Attribute::DemolishFx(_) => AttributeTag::DemolishFx,
+            Attribute::Enum(_) => AttributeTag::Enum,
+            Attribute::Explosion(_) => AttributeTag::Explosion,
+            Attribute::ExtendedExplosion(_) => AttributeTag::ExtendedExplosion,
+            Attribute::FlaggedByte(..) => AttributeTag::FlaggedByte,
+            Attribute::ActiveActor(_) => AttributeTag::ActiveActor,
+            Attribute::Float(_) => AttributeTag::Float,
+            Attribute::GameMode(..) => AttributeTag::GameMode,
+            Attribute::Int(_) => AttributeTag::Int,
+            Attribute::Int64(_) => AttributeTag::Int64,
+            Attribute::Loadout(_) => AttributeTag::Loadout,
+            Attribute::TeamLoadout(_) => AttributeTag::TeamLoadout,
+            Attribute::Location(_) => AttributeTag::Location,
+            Attribute::MusicStinger(_) => AttributeTag::MusicStinger,
+            Attribute::PlayerHistoryKey(_) => AttributeTag::PlayerHistoryKey,
+            Attribute::Pickup(_) => AttributeTag::Pickup,
+            Attribute::PickupNew(_) => AttributeTag::PickupNew,
+            Attribute::QWord(_) => AttributeTag::QWord,
+            Attribute::Welded(_) => AttributeTag::Welded,
+            Attribute::Title(..) => AttributeTag::Title,
+            Attribute::TeamPaint(_) => AttributeTag::TeamPaint,
+            Attribute::RigidBody(_) => AttributeTag::RigidBody,
+            Attribute::String(_) => AttributeTag::String,
+            Attribute::UniqueId(_) => AttributeTag::UniqueId,
+            Attribute::Reservation(_) => AttributeTag::Reservation,
+            Attribute::PartyLeader(_) => AttributeTag::PartyLeader,
+            Attribute::PrivateMatch(_) => AttributeTag::PrivateMatch,
+            Attribute::LoadoutOnline(_) => AttributeTag::LoadoutOnline,
+            Attribute::LoadoutsOnline(_) => AttributeTag::LoadoutsOnline,
+            Attribute::StatEvent(_) => AttributeTag::StatEvent,
+            Attribute::Rotation(_) => AttributeTag::Rotation,
+            Attribute::RepStatTitle(_) => AttributeTag::RepStatTitle,
+            Attribute::PickupInfo(_) => AttributeTag::PickupInfo,
+            Attribute::Impulse(_) => AttributeTag::Impulse,
+            Attribute::Custom(_) => AttributeTag::Custom,
+        }
+    }
+}
+
+/// An attribute decoded by a user supplied decoder
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CustomAttribute {
+    /// The name of the attribute's object, as it was registered
+    pub name: String,
+
+    /// What the decoder returned
+    pub value: Attribute,
+}
+
+/// The signature of a user supplied attribute decoder. It receives the network data positioned at
+/// the start of the attribute and the replay's net version, and returns `None` if the attribute
+/// couldn't be decoded.
+pub type AttributeDecodeFn =
+    dyn Fn(&mut LittleEndianReader<'_>, i32) -> Option<Attribute> + Send + Sync;
+
+/// A user supplied decoder for the attribute with the given object name
+#[derive(Clone)]
+pub(crate) struct CustomDecoder {
+    pub(crate) name: String,
+    pub(crate) decode: Arc<AttributeDecodeFn>,
+}
+
+impl fmt::Debug for CustomDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomDecoder")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl PartialEq for CustomDecoder {
+    fn eq(&self, other: &Self) -> bool {
+        let data = |x: &Arc<AttributeDecodeFn>| Arc::as_ptr(x) as *const ();
+        self.name == other.name && data(&self.decode) == data(&other.decode)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -123,6 +272,21 @@ pub struct CamSettings {
     pub transition: Option<f32>,
 }
 
+/// The camera settings that the game starts players with
+impl Default for CamSettings {
+    fn default() -> Self {
+        CamSettings {
+            fov: 110.0,
+            height: 100.0,
+            angle: -4.0,
+            distance: 270.0,
+            stiffness: 0.5,
+            swivel: 2.5,
+            transition: Some(1.2),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct ClubColors {
     pub blue_flag: bool,
@@ -277,6 +441,36 @@ pub struct UniqueId {
     pub local_id: u8,
 }
 
+impl UniqueId {
+    /// The platform that the player played on, per the kind of their online id
+    pub fn platform(&self) -> Platform {
+        match self.remote_id {
+            RemoteId::PlayStation(_) => Platform::PlayStation,
+            // PsyNet ids are issued to Nintendo Switch players
+            RemoteId::PsyNet(_) | RemoteId::Switch(_) => Platform::Switch,
+            RemoteId::SplitScreen(_) => Platform::SplitScreen,
+            RemoteId::Steam(_) => Platform::Steam,
+            RemoteId::Xbox(_) => Platform::Xbox,
+            RemoteId::QQ(_) => Platform::QQ,
+            RemoteId::Epic(_) => Platform::Epic,
+        }
+    }
+}
+
+/// The platform of a player, as determined by their [`UniqueId`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum Platform {
+    Steam,
+    PlayStation,
+    Xbox,
+    Switch,
+    Epic,
+    QQ,
+
+    /// Players without an online id, like split screen players in an offline match
+    SplitScreen,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct PsyNetId {
     #[serde(serialize_with = "crate::serde_utils::display_it")]
@@ -433,9 +627,9 @@ impl ProductValueDecoder {
         }
     }
 
-    pub fn decode(
+    pub fn decode<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         obj_ind: u32,
         buf: &mut [u8],
     ) -> Option<ProductValue> {
@@ -490,70 +684,72 @@ pub(crate) struct AttributeDecoder {
 }
 
 impl AttributeDecoder {
-    pub fn decode(
+    pub fn decode<R: BitReader>(
         &self,
-        tag: AttributeTag,
-        bits: &mut LittleEndianReader<'_>,
+        tag: AttributeDecoding,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         match tag {
-            AttributeTag::Boolean => self.decode_boolean(bits),
-            AttributeTag::Byte => self.decode_byte(bits),
-            AttributeTag::AppliedDamage => self.decode_applied_damage(bits),
-            AttributeTag::DamageState => self.decode_damage_state(bits),
-            AttributeTag::CamSettings => self.decode_cam_settings(bits),
-            AttributeTag::ClubColors => self.decode_club_colors(bits),
-            AttributeTag::Demolish => self.decode_demolish(bits),
-            AttributeTag::DemolishFx => self.decode_demolish_fx(bits),
-            AttributeTag::Enum => self.decode_enum(bits),
-            AttributeTag::Explosion => self.decode_explosion(bits),
-            AttributeTag::ExtendedExplosion => self.decode_extended_explosion(bits),
-            AttributeTag::ActiveActor => self.decode_active_actor(bits),
-            AttributeTag::FlaggedByte => self.decode_flagged_byte(bits),
-            AttributeTag::Float => self.decode_float(bits),
-            AttributeTag::GameMode => self.decode_game_mode(bits),
-            AttributeTag::Int => self.decode_int(bits),
-            AttributeTag::Int64 => self.decode_int64(bits),
-            AttributeTag::Loadout => self.decode_loadout(bits),
-            AttributeTag::TeamLoadout => self.decode_team_loadout(bits),
-            AttributeTag::Location => self.decode_location(bits),
-            AttributeTag::MusicStinger => self.decode_music_stinger(bits),
-            AttributeTag::Pickup => self.decode_pickup(bits),
-            AttributeTag::PickupNew => self.decode_pickup_new(bits),
-            AttributeTag::PlayerHistoryKey => self.decode_player_history_key(bits),
-            AttributeTag::QWordString => self.decode_qword_string(bits, buf),
-            AttributeTag::Welded => self.decode_welded(bits),
-            AttributeTag::RigidBody => self.decode_rigid_body(bits),
-            AttributeTag::Title => self.decode_title(bits),
-            AttributeTag::TeamPaint => self.decode_team_paint(bits),
-            AttributeTag::NotImplemented => self.decode_not_implemented(bits),
-            AttributeTag::String => self.decode_string(bits, buf),
-            AttributeTag::UniqueId => self.decode_unique_id(bits, buf),
-            AttributeTag::Reservation => self.decode_reservation(bits, buf),
-            AttributeTag::PartyLeader => self.decode_party_leader(bits, buf),
-            AttributeTag::PrivateMatchSettings => self.decode_private_match_settings(bits, buf),
-            AttributeTag::LoadoutOnline => self.decode_loadout_online(bits, buf),
-            AttributeTag::LoadoutsOnline => self.decode_loadouts_online(bits, buf),
-            AttributeTag::StatEvent => self.decode_stat_event(bits),
-            AttributeTag::RotationTag => self.decode_rotation(bits),
-            AttributeTag::RepStatTitle => self.decode_rep_stat_title(bits, buf),
-            AttributeTag::PickupInfo => self.decode_pickup_info(bits),
-            AttributeTag::Impulse => self.decode_impulse(bits),
+            AttributeDecoding::Boolean => self.decode_boolean(bits),
+            AttributeDecoding::Byte => self.decode_byte(bits),
+            AttributeDecoding::AppliedDamage => self.decode_applied_damage(bits),
+            AttributeDecoding::DamageState => self.decode_damage_state(bits),
+            AttributeDecoding::CamSettings => self.decode_cam_settings(bits),
+            AttributeDecoding::ClubColors => self.decode_club_colors(bits),
+            AttributeDecoding::Demolish => self.decode_demolish(bits),
+            AttributeDecoding::DemolishFx => self.decode_demolish_fx(bits),
+            AttributeDecoding::Enum => self.decode_enum(bits),
+            AttributeDecoding::Explosion => self.decode_explosion(bits),
+            AttributeDecoding::ExtendedExplosion => self.decode_extended_explosion(bits),
+            AttributeDecoding::ActiveActor => self.decode_active_actor(bits),
+            AttributeDecoding::FlaggedByte => self.decode_flagged_byte(bits),
+            AttributeDecoding::Float => self.decode_float(bits),
+            AttributeDecoding::GameMode => self.decode_game_mode(bits),
+            AttributeDecoding::Int => self.decode_int(bits),
+            AttributeDecoding::Int64 => self.decode_int64(bits),
+            AttributeDecoding::Loadout => self.decode_loadout(bits),
+            AttributeDecoding::TeamLoadout => self.decode_team_loadout(bits),
+            AttributeDecoding::Location => self.decode_location(bits),
+            AttributeDecoding::MusicStinger => self.decode_music_stinger(bits),
+            AttributeDecoding::Pickup => self.decode_pickup(bits),
+            AttributeDecoding::PickupNew => self.decode_pickup_new(bits),
+            AttributeDecoding::PlayerHistoryKey => self.decode_player_history_key(bits),
+            AttributeDecoding::QWordString => self.decode_qword_string(bits, buf),
+            AttributeDecoding::Welded => self.decode_welded(bits),
+            AttributeDecoding::RigidBody => self.decode_rigid_body(bits),
+            AttributeDecoding::Title => self.decode_title(bits),
+            AttributeDecoding::TeamPaint => self.decode_team_paint(bits),
+            AttributeDecoding::NotImplemented => self.decode_not_implemented(bits),
+            AttributeDecoding::String => self.decode_string(bits, buf),
+            AttributeDecoding::UniqueId => self.decode_unique_id(bits, buf),
+            AttributeDecoding::Reservation => self.decode_reservation(bits, buf),
+            AttributeDecoding::PartyLeader => self.decode_party_leader(bits, buf),
+            AttributeDecoding::PrivateMatchSettings => {
+                self.decode_private_match_settings(bits, buf)
+            }
+            AttributeDecoding::LoadoutOnline => self.decode_loadout_online(bits, buf),
+            AttributeDecoding::LoadoutsOnline => self.decode_loadouts_online(bits, buf),
+            AttributeDecoding::StatEvent => self.decode_stat_event(bits),
+            AttributeDecoding::RotationTag => self.decode_rotation(bits),
+            AttributeDecoding::RepStatTitle => self.decode_rep_stat_title(bits, buf),
+            AttributeDecoding::PickupInfo => self.decode_pickup_info(bits),
+            AttributeDecoding::Impulse => self.decode_impulse(bits),
+
+            // Custom attributes are decoded by the frame decoder, which owns the user's decoders
+            AttributeDecoding::Custom(_) => Err(AttributeError::Unimplemented),
         }
     }
 
-    pub fn decode_byte(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_byte<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_u8()
             .map(Attribute::Byte)
             .ok_or(AttributeError::NotEnoughDataFor("Byte"))
     }
 
-    pub fn decode_player_history_key(
+    pub fn decode_player_history_key<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         bits.read_bits(14)
             .map(|x| x as u16)
@@ -561,33 +757,27 @@ impl AttributeDecoder {
             .ok_or(AttributeError::NotEnoughDataFor("PlayerHistoryKey"))
     }
 
-    fn _decode_flagged_byte(&self, bits: &mut LittleEndianReader<'_>) -> Option<Attribute> {
+    fn _decode_flagged_byte<R: BitReader>(&self, bits: &mut R) -> Option<Attribute> {
         let b = bits.read_bit()?;
         let data = bits.read_u8()?;
         Some(Attribute::FlaggedByte(b, data))
     }
 
-    pub fn decode_flagged_byte(
+    pub fn decode_flagged_byte<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_flagged_byte(bits)
             .ok_or(AttributeError::NotEnoughDataFor("FlaggedByte"))
     }
 
-    pub fn decode_boolean(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_boolean<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_bit()
             .map(Attribute::Boolean)
             .ok_or(AttributeError::NotEnoughDataFor("Boolean"))
     }
 
-    pub fn _decode_applied_damage(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Option<AppliedDamage> {
+    pub fn _decode_applied_damage<R: BitReader>(&self, bits: &mut R) -> Option<AppliedDamage> {
         let id = bits.read_u8()?;
         let position = Vector3f::decode(bits, self.version.net_version())?;
         let damage_index = bits.read_i32()?;
@@ -600,16 +790,16 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_applied_damage(
+    pub fn decode_applied_damage<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_applied_damage(bits)
             .map(Attribute::AppliedDamage)
             .ok_or(AttributeError::NotEnoughDataFor("Applied Damage"))
     }
 
-    fn _decode_damage_state(&self, bits: &mut LittleEndianReader<'_>) -> Option<DamageState> {
+    fn _decode_damage_state<R: BitReader>(&self, bits: &mut R) -> Option<DamageState> {
         let tile_state = bits.read_u8()?;
         let damaged = bits.read_bit()?;
         let offender = bits.read_i32().map(ActorId)?;
@@ -626,16 +816,16 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_damage_state(
+    pub fn decode_damage_state<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_damage_state(bits)
             .map(Attribute::DamageState)
             .ok_or(AttributeError::NotEnoughDataFor("Damage State"))
     }
 
-    fn _decode_cam_settings(&self, bits: &mut LittleEndianReader<'_>) -> Option<CamSettings> {
+    fn _decode_cam_settings<R: BitReader>(&self, bits: &mut R) -> Option<CamSettings> {
         let fov = bits.read_f32()?;
         let height = bits.read_f32()?;
         let angle = bits.read_f32()?;
@@ -659,9 +849,9 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_cam_settings(
+    pub fn decode_cam_settings<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_cam_settings(bits)
             .map(Box::new)
@@ -669,7 +859,7 @@ impl AttributeDecoder {
             .ok_or(AttributeError::NotEnoughDataFor("Cam Settings"))
     }
 
-    fn _decode_club_colors(&self, bits: &mut LittleEndianReader<'_>) -> Option<ClubColors> {
+    fn _decode_club_colors<R: BitReader>(&self, bits: &mut R) -> Option<ClubColors> {
         let blue_flag = bits.read_bit()?;
         let blue_color = bits.read_u8()?;
         let orange_flag = bits.read_bit()?;
@@ -682,16 +872,16 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_club_colors(
+    pub fn decode_club_colors<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_club_colors(bits)
             .map(Attribute::ClubColors)
             .ok_or(AttributeError::NotEnoughDataFor("Club Colors"))
     }
 
-    fn _decode_demolish(&self, bits: &mut LittleEndianReader<'_>) -> Option<Demolish> {
+    fn _decode_demolish<R: BitReader>(&self, bits: &mut R) -> Option<Demolish> {
         let attacker_flag = bits.read_bit()?;
         let attacker = bits.read_i32().map(ActorId)?;
         let victim_flag = bits.read_bit()?;
@@ -708,17 +898,14 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_demolish(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_demolish<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         self._decode_demolish(bits)
             .map(Box::new)
             .map(Attribute::Demolish)
             .ok_or(AttributeError::NotEnoughDataFor("Demolish"))
     }
 
-    pub fn _decode_demolish_fx(&self, bits: &mut LittleEndianReader<'_>) -> Option<DemolishFx> {
+    pub fn _decode_demolish_fx<R: BitReader>(&self, bits: &mut R) -> Option<DemolishFx> {
         let custom_demo_flag = bits.read_bit()?;
         let custom_demo_id = bits.read_i32()?;
         let attacker_flag = bits.read_bit()?;
@@ -740,9 +927,9 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_demolish_fx(
+    pub fn decode_demolish_fx<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_demolish_fx(bits)
             .map(Box::new)
@@ -750,26 +937,23 @@ impl AttributeDecoder {
             .ok_or(AttributeError::NotEnoughDataFor("DemolishFx"))
     }
 
-    pub fn decode_enum(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_enum<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_bits(11)
             .map(|x| x as u16)
             .map(Attribute::Enum)
             .ok_or(AttributeError::NotEnoughDataFor("Enum"))
     }
 
-    pub fn decode_explosion(
+    pub fn decode_explosion<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         decode_explosion(bits, self.version.net_version())
             .map(Attribute::Explosion)
             .ok_or(AttributeError::NotEnoughDataFor("Explosion"))
     }
 
-    fn _decode_stat_event(&self, bits: &mut LittleEndianReader<'_>) -> Option<StatEvent> {
+    fn _decode_stat_event<R: BitReader>(&self, bits: &mut R) -> Option<StatEvent> {
         let unknown1 = bits.read_bit()?;
         let object_id = bits.read_i32()?;
         Some(StatEvent {
@@ -778,18 +962,18 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_stat_event(
+    pub fn decode_stat_event<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_stat_event(bits)
             .map(Attribute::StatEvent)
             .ok_or(AttributeError::NotEnoughDataFor("Stat Event"))
     }
 
-    pub fn decode_rep_stat_title(
+    pub fn decode_rep_stat_title<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         let unknown = bits
@@ -814,9 +998,9 @@ impl AttributeDecoder {
         }))
     }
 
-    pub fn decode_pickup_info(
+    pub fn decode_pickup_info<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         let active = bits
             .read_bit()
@@ -845,10 +1029,7 @@ impl AttributeDecoder {
         }))
     }
 
-    pub fn decode_impulse(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_impulse<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         let compressed_rotation = bits
             .read_i32()
             .ok_or(AttributeError::NotEnoughDataFor("Impulse"))?;
@@ -863,10 +1044,7 @@ impl AttributeDecoder {
         }))
     }
 
-    fn _decode_extended_explosion(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Option<ExtendedExplosion> {
+    fn _decode_extended_explosion<R: BitReader>(&self, bits: &mut R) -> Option<ExtendedExplosion> {
         let explosion = decode_explosion(bits, self.version.net_version())?;
         let unknown1 = bits.read_bit()?;
         let secondary_actor = bits.read_i32().map(ActorId)?;
@@ -877,18 +1055,18 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_extended_explosion(
+    pub fn decode_extended_explosion<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_extended_explosion(bits)
             .map(Attribute::ExtendedExplosion)
             .ok_or(AttributeError::NotEnoughDataFor("Extended Explosion"))
     }
 
-    pub fn decode_active_actor(
+    pub fn decode_active_actor<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         let len = bits.refill_lookahead();
         if len < 33 {
@@ -900,18 +1078,15 @@ impl AttributeDecoder {
         Ok(Attribute::ActiveActor(ActiveActor { active, actor }))
     }
 
-    pub fn decode_float(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_float<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_f32()
             .map(Attribute::Float)
             .ok_or(AttributeError::NotEnoughDataFor("Float"))
     }
 
-    pub fn decode_game_mode(
+    pub fn decode_game_mode<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         let init: u8 = if self.version < VersionTriplet(868, 12, 0) {
             2
@@ -925,37 +1100,28 @@ impl AttributeDecoder {
             .ok_or(AttributeError::NotEnoughDataFor("Game Mode"))
     }
 
-    pub fn decode_int(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_int<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_i32()
             .map(Attribute::Int)
             .ok_or(AttributeError::NotEnoughDataFor("Int"))
     }
 
-    pub fn decode_int64(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_int64<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_i64()
             .map(Attribute::Int64)
             .ok_or(AttributeError::NotEnoughDataFor("Int64"))
     }
 
-    pub fn decode_loadout(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_loadout<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         decode_loadout(bits)
             .map(Box::new)
             .map(Attribute::Loadout)
             .ok_or(AttributeError::NotEnoughDataFor("Loadout"))
     }
 
-    pub fn decode_team_loadout(
+    pub fn decode_team_loadout<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         let blue = decode_loadout(bits).ok_or(AttributeError::NotEnoughDataFor("Team Loadout"))?;
         let orange =
@@ -966,37 +1132,31 @@ impl AttributeDecoder {
         })))
     }
 
-    pub fn decode_location(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_location<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         Vector3f::decode(bits, self.version.net_version())
             .map(Attribute::Location)
             .ok_or(AttributeError::NotEnoughDataFor("Location"))
     }
 
-    fn _decode_music_stinger(&self, bits: &mut LittleEndianReader<'_>) -> Option<MusicStinger> {
+    fn _decode_music_stinger<R: BitReader>(&self, bits: &mut R) -> Option<MusicStinger> {
         let flag = bits.read_bit()?;
         let cue = bits.read_u32()?;
         let trigger = bits.read_u8()?;
         Some(MusicStinger { flag, cue, trigger })
     }
 
-    pub fn decode_music_stinger(
+    pub fn decode_music_stinger<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_music_stinger(bits)
             .map(Attribute::MusicStinger)
             .ok_or(AttributeError::NotEnoughDataFor("Music Stinger"))
     }
 
-    pub fn decode_pickup(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_pickup<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         let instigator = bits
-            .if_get(LittleEndianReader::read_i32)
+            .if_get(R::read_i32)
             .map(|x| x.map(ActorId))
             .ok_or(AttributeError::NotEnoughDataFor("Pickup"))?;
         let picked_up = bits
@@ -1008,12 +1168,12 @@ impl AttributeDecoder {
         }))
     }
 
-    pub fn decode_pickup_new(
+    pub fn decode_pickup_new<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         let instigator = bits
-            .if_get(LittleEndianReader::read_i32)
+            .if_get(R::read_i32)
             .map(|x| x.map(ActorId))
             .ok_or(AttributeError::NotEnoughDataFor("PickupNew"))?;
         let picked_up = bits
@@ -1025,18 +1185,15 @@ impl AttributeDecoder {
         }))
     }
 
-    pub fn decode_qword(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_qword<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         bits.read_u64()
             .map(Attribute::QWord)
             .ok_or(AttributeError::NotEnoughDataFor("QWord"))
     }
 
-    pub fn decode_qword_string(
+    pub fn decode_qword_string<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         if self.is_rl_223 {
@@ -1046,7 +1203,7 @@ impl AttributeDecoder {
         }
     }
 
-    fn _decode_welded(&self, bits: &mut LittleEndianReader<'_>) -> Option<Welded> {
+    fn _decode_welded<R: BitReader>(&self, bits: &mut R) -> Option<Welded> {
         let active = bits.read_bit()?;
         let actor = bits.read_i32().map(ActorId)?;
         let offset = Vector3f::decode(bits, self.version.net_version())?;
@@ -1061,24 +1218,18 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_welded(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_welded<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         self._decode_welded(bits)
             .map(Attribute::Welded)
             .ok_or(AttributeError::NotEnoughDataFor("Welded"))
     }
 
-    pub fn decode_rotation(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_rotation<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         let rot = Rotation::decode(bits).ok_or(AttributeError::NotEnoughDataFor("Rotation"))?;
         Ok(Attribute::Rotation(rot))
     }
 
-    fn _decode_title(&self, bits: &mut LittleEndianReader<'_>) -> Option<Attribute> {
+    fn _decode_title<R: BitReader>(&self, bits: &mut R) -> Option<Attribute> {
         let unknown1 = bits.read_bit()?;
         let unknown2 = bits.read_bit()?;
         let unknown3 = bits.read_u32()?;
@@ -1091,15 +1242,12 @@ impl AttributeDecoder {
             unknown1, unknown2, unknown3, unknown4, unknown5, unknown6, unknown7, unknown8,
         ))
     }
-    pub fn decode_title(
-        &self,
-        bits: &mut LittleEndianReader<'_>,
-    ) -> Result<Attribute, AttributeError> {
+    pub fn decode_title<R: BitReader>(&self, bits: &mut R) -> Result<Attribute, AttributeError> {
         self._decode_title(bits)
             .ok_or(AttributeError::NotEnoughDataFor("Title"))
     }
 
-    fn _decode_team_paint(&self, bits: &mut LittleEndianReader<'_>) -> Option<TeamPaint> {
+    fn _decode_team_paint<R: BitReader>(&self, bits: &mut R) -> Option<TeamPaint> {
         let team = bits.read_u8()?;
         let primary_color = bits.read_u8()?;
         let accent_color = bits.read_u8()?;
@@ -1115,16 +1263,16 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_team_paint(
+    pub fn decode_team_paint<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_team_paint(bits)
             .map(Attribute::TeamPaint)
             .ok_or(AttributeError::NotEnoughDataFor("Team Paint"))
     }
 
-    fn _decode_rigid_body(&self, bits: &mut LittleEndianReader<'_>) -> Option<RigidBody> {
+    fn _decode_rigid_body<R: BitReader>(&self, bits: &mut R) -> Option<RigidBody> {
         let sleeping = bits.read_bit()?;
         let location = Vector3f::decode(bits, self.version.net_version())?;
 
@@ -1151,33 +1299,33 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_rigid_body(
+    pub fn decode_rigid_body<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         self._decode_rigid_body(bits)
             .map(Attribute::RigidBody)
             .ok_or(AttributeError::NotEnoughDataFor("Rigid Body"))
     }
 
-    pub fn decode_not_implemented(
+    pub fn decode_not_implemented<R: BitReader>(
         &self,
-        _bits: &mut LittleEndianReader<'_>,
+        _bits: &mut R,
     ) -> Result<Attribute, AttributeError> {
         Err(AttributeError::Unimplemented)
     }
 
-    pub fn decode_string(
+    pub fn decode_string<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         Ok(Attribute::String(decode_text(bits, buf)?))
     }
 
-    pub fn decode_unique_id(
+    pub fn decode_unique_id<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         decode_unique_id(bits, self.version.net_version(), buf)
@@ -1185,9 +1333,9 @@ impl AttributeDecoder {
             .map(Attribute::UniqueId)
     }
 
-    pub fn decode_reservation(
+    pub fn decode_reservation<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         let component = "Reservation";
@@ -1218,9 +1366,9 @@ impl AttributeDecoder {
         })))
     }
 
-    pub fn decode_party_leader(
+    pub fn decode_party_leader<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         if let Some(system_id) = bits.read_u8() {
@@ -1240,9 +1388,9 @@ impl AttributeDecoder {
         }
     }
 
-    pub fn decode_private_match_settings(
+    pub fn decode_private_match_settings<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         let component = "Private Match";
@@ -1263,9 +1411,9 @@ impl AttributeDecoder {
         })))
     }
 
-    pub fn decode_loadout_online(
+    pub fn decode_loadout_online<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         self.inner_decode_online_loadout(bits, buf)
@@ -1273,9 +1421,9 @@ impl AttributeDecoder {
             .ok_or(AttributeError::NotEnoughDataFor("Loadout Online"))
     }
 
-    fn _decode_loadouts_online(
+    fn _decode_loadouts_online<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Option<LoadoutsOnline> {
         let blue = self.inner_decode_online_loadout(bits, buf)?;
@@ -1290,9 +1438,9 @@ impl AttributeDecoder {
         })
     }
 
-    pub fn decode_loadouts_online(
+    pub fn decode_loadouts_online<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Result<Attribute, AttributeError> {
         self._decode_loadouts_online(bits, buf)
@@ -1300,7 +1448,7 @@ impl AttributeDecoder {
             .ok_or(AttributeError::NotEnoughDataFor("Loadouts online"))
     }
 
-    fn decode_product(&self, bits: &mut LittleEndianReader<'_>, buf: &mut [u8]) -> Option<Product> {
+    fn decode_product<R: BitReader>(&self, bits: &mut R, buf: &mut [u8]) -> Option<Product> {
         let unknown = bits.read_bit()?;
         let obj_ind = bits.read_u32()?;
         let val = self.product_decoder.decode(bits, obj_ind, buf)?;
@@ -1312,9 +1460,9 @@ impl AttributeDecoder {
         })
     }
 
-    fn inner_decode_online_loadout(
+    fn inner_decode_online_loadout<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         buf: &mut [u8],
     ) -> Option<Vec<Vec<Product>>> {
         if let Some(size) = bits.read_u8() {
@@ -1341,7 +1489,7 @@ impl AttributeDecoder {
     }
 }
 
-fn decode_explosion(bits: &mut LittleEndianReader<'_>, net_version: i32) -> Option<Explosion> {
+fn decode_explosion<R: BitReader>(bits: &mut R, net_version: i32) -> Option<Explosion> {
     let flag = bits.read_bit()?;
     let actor = bits.read_i32().map(ActorId)?;
     let location = Vector3f::decode(bits, net_version)?;
@@ -1352,10 +1500,7 @@ fn decode_explosion(bits: &mut LittleEndianReader<'_>, net_version: i32) -> Opti
     })
 }
 
-fn decode_text(
-    bits: &mut LittleEndianReader<'_>,
-    buf: &mut [u8],
-) -> Result<String, AttributeError> {
+fn decode_text<R: BitReader>(bits: &mut R, buf: &mut [u8]) -> Result<String, AttributeError> {
     use std::cmp::Ordering;
 
     let size = bits
@@ -1391,8 +1536,8 @@ fn decode_text(
     }
 }
 
-fn decode_loadout_specials(
-    bits: &mut LittleEndianReader<'_>,
+fn decode_loadout_specials<R: BitReader>(
+    bits: &mut R,
 ) -> Option<(Option<u32>, Option<u32>, Option<u32>)> {
     let engine_audio = bits.read_u32()?;
     let trail = bits.read_u32()?;
@@ -1400,7 +1545,7 @@ fn decode_loadout_specials(
     Some((Some(engine_audio), Some(trail), Some(goal_explosion)))
 }
 
-fn decode_loadout(bits: &mut LittleEndianReader<'_>) -> Option<Loadout> {
+fn decode_loadout<R: BitReader>(bits: &mut R) -> Option<Loadout> {
     let version = bits.read_u8()?;
     let body = bits.read_u32()?;
     let decal = bits.read_u32()?;
@@ -1457,8 +1602,8 @@ fn decode_loadout(bits: &mut LittleEndianReader<'_>) -> Option<Loadout> {
     })
 }
 
-fn decode_unique_id(
-    bits: &mut LittleEndianReader<'_>,
+fn decode_unique_id<R: BitReader>(
+    bits: &mut R,
     net_version: i32,
     buf: &mut [u8],
 ) -> Result<UniqueId, AttributeError> {
@@ -1468,8 +1613,8 @@ fn decode_unique_id(
     decode_unique_id_with_system_id(bits, net_version, system_id, buf)
 }
 
-fn decode_unique_id_with_system_id(
-    bits: &mut LittleEndianReader<'_>,
+fn decode_unique_id_with_system_id<R: BitReader>(
+    bits: &mut R,
     net_version: i32,
     system_id: u8,
     buf: &mut [u8],
@@ -1591,4 +1736,17 @@ mod tests {
                 <= ::std::mem::size_of::<RigidBody>() + ::std::mem::size_of::<usize>()
         );
     }
+
+    #[test]
+    fn test_attribute_tag() {
+        assert_eq!(Attribute::Boolean(true).tag(), AttributeTag::Boolean);
+        assert_eq!(Attribute::GameMode(2, 3).tag(), AttributeTag::GameMode);
+        assert_eq!(Attribute::QWord(4).tag(), AttributeTag::QWord);
+
+        // Discriminants are part of the public API
+        assert_eq!(AttributeTag::Boolean as u8, 0);
+        assert_eq!(AttributeTag::RigidBody as u8, 28);
+        assert_eq!(AttributeTag::Impulse as u8, 40);
+        assert_eq!(AttributeTag::Custom as u8, 41);
+    }
 }
@@ -0,0 +1,89 @@
+use crate::network::attributes::AttributeTag;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The range of bits that decoded attributes of a kind occupied in the network data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BitWidthStats {
+    pub min: usize,
+    pub max: usize,
+
+    /// Number of attributes decoded
+    pub count: usize,
+}
+
+impl BitWidthStats {
+    /// Returns true if every attribute occupied the same number of bits
+    pub fn is_constant(&self) -> bool {
+        self.min == self.max
+    }
+}
+
+/// The bit widths of every kind of attribute decoded from a replay's network data, as recorded
+/// by [`ParserBuilder::record_bit_widths`](crate::ParserBuilder::record_bit_widths)
+///
+/// The `Display` implementation formats the widths as a markdown table ordered by tag.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct BitWidths {
+    pub attributes: HashMap<AttributeTag, BitWidthStats>,
+}
+
+impl BitWidths {
+    pub(crate) fn record(&mut self, tag: AttributeTag, bits: usize) {
+        self.attributes
+            .entry(tag)
+            .and_modify(|x| {
+                x.min = x.min.min(bits);
+                x.max = x.max.max(bits);
+                x.count += 1;
+            })
+            .or_insert(BitWidthStats {
+                min: bits,
+                max: bits,
+                count: 1,
+            });
+    }
+}
+
+impl fmt::Display for BitWidths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tags: Vec<_> = self.attributes.iter().collect();
+        tags.sort_by_key(|(tag, _)| **tag);
+
+        writeln!(f, "| Attribute | Min bits | Max bits | Count |")?;
+        writeln!(f, "|-----------|----------|----------|-------|")?;
+        for (tag, stats) in tags {
+            writeln!(
+                f,
+                "| {:?} | {} | {} | {} |",
+                tag, stats.min, stats.max, stats.count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_report() {
+        let mut widths = BitWidths::default();
+        widths.record(AttributeTag::Int, 32);
+        widths.record(AttributeTag::Boolean, 1);
+        widths.record(AttributeTag::String, 72);
+        widths.record(AttributeTag::String, 40);
+
+        let string = widths.attributes[&AttributeTag::String];
+        assert_eq!((string.min, string.max, string.count), (40, 72, 2));
+        assert!(!string.is_constant());
+        assert!(widths.attributes[&AttributeTag::Int].is_constant());
+
+        let report = widths.to_string();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], "| Boolean | 1 | 1 | 1 |");
+        assert_eq!(lines[4], "| String | 40 | 72 | 2 |");
+    }
+}
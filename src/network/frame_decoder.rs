@@ -2,25 +2,85 @@ use bitter::{BitReader, LittleEndianReader};
 use fnv::FnvHashMap;
 
 use crate::bits::RlBits;
-use crate::errors::{AttributeError, FrameContext, FrameError, NetworkError};
-use crate::network::attributes::{AttributeDecoder, ProductValueDecoder};
+use crate::errors::{AttributeError, FrameContext, FrameError, NetworkError, ParseWarning};
+use crate::network::attributes::{
+    Attribute, AttributeDecoder, AttributeDecoding, CustomAttribute, CustomDecoder,
+};
 use crate::network::models::{
     ActorId, Frame, NewActor, ObjectId, SpawnTrajectory, StreamId, Trajectory, UpdatedAttribute,
 };
-use crate::network::{CacheInfo, VersionTriplet};
-use crate::parser::ReplayBody;
+use crate::network::{BitWidths, CacheInfo, VersionTriplet};
+use std::borrow::Cow;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The number of frames decoded between checks of the deadline, so that the clock isn't read for
+/// every frame
+const DEADLINE_CHECK_FRAMES: usize = 256;
+
+/// The longest delta (in seconds) of a plausible frame boundary when resynchronizing
+const RESYNC_MAX_DELTA: f32 = 1.0;
+
+/// The longest time (in seconds) between the last decoded frame and a plausible frame boundary
+/// when resynchronizing
+const RESYNC_MAX_GAP: f32 = 30.0;
 
-pub(crate) struct FrameDecoder<'a, 'b: 'a> {
-    pub frames_len: usize,
-    pub product_decoder: ProductValueDecoder,
+/// How far (in seconds) the time of the frame following a resynchronized frame may stray from the
+/// resynchronized frame's time plus the following frame's delta
+const RESYNC_TIME_TOLERANCE: f32 = 1e-3;
+
+/// The lookups, derived from the replay's header and body, that are necessary to decode the
+/// network data. These are immutable once decoding starts. The object names are only consulted
+/// when a frame fails to decode, so they are borrowed from the body unless the decoder outlives it.
+#[derive(Debug)]
+pub(crate) struct DecoderInfo<'a> {
+    pub attr_decoder: AttributeDecoder,
     pub max_channels: u32,
     pub channel_bits: u32,
-    pub body: &'a ReplayBody<'b>,
-    pub spawns: &'a Vec<SpawnTrajectory>,
-    pub object_ind_attributes: FnvHashMap<ObjectId, CacheInfo<'a>>,
+    pub objects: Cow<'a, [String]>,
+    pub spawns: Vec<SpawnTrajectory>,
+    pub object_ind_attributes: FnvHashMap<ObjectId, CacheInfo>,
+    pub custom_decoders: Vec<CustomDecoder>,
     pub version: VersionTriplet,
     pub is_lan: bool,
-    pub is_rl_223: bool,
+}
+
+/// Decodes the network data one frame at a time.
+///
+/// The decoder owns the bit cursor into the network data and the lookups derived from the replay's
+/// class net cache, so a replay viewer can step forward frame by frame without re-decoding from
+/// the start. A `FrameDecoder` is created with [`ParserBuilder::frame_decoder`].
+///
+/// The decoder only tracks the minimum actor state needed to decode subsequent frames (which
+/// object each actor id is an instance of). Callers that need the latest attribute values of an
+/// actor must maintain that state themselves from the decoded frames.
+///
+/// [`ParserBuilder::frame_decoder`]: crate::ParserBuilder::frame_decoder
+pub struct FrameDecoder<'a> {
+    info: DecoderInfo<'a>,
+    data: &'a [u8],
+    bits: LittleEndianReader<'a>,
+    frames_len: usize,
+    frames_decoded: usize,
+    finished: bool,
+    actors: FnvHashMap<ActorId, ObjectId>,
+    new_actors: Vec<NewActor>,
+    deleted_actors: Vec<ActorId>,
+    updated_actors: Vec<UpdatedAttribute>,
+    bit_widths: Option<BitWidths>,
+    deadline: Option<(Instant, Duration)>,
+    resyncs: Option<Vec<ParseWarning>>,
+    buf: [u8; 1024],
+}
+
+/// A bit reader positioned the given number of bits into the data
+fn reader_at(data: &[u8], pos: usize) -> LittleEndianReader<'_> {
+    let mut bits = LittleEndianReader::new(&data[pos / 8..]);
+    let offset = (pos % 8) as u32;
+    if offset > 0 {
+        bits.read_bits(offset);
+    }
+    bits
 }
 
 #[derive(Debug)]
@@ -29,10 +89,10 @@ enum DecodedFrame {
     Frame(Frame),
 }
 
-impl<'a, 'b> FrameDecoder<'a, 'b> {
-    fn parse_new_actor(
+impl<'a> DecoderInfo<'a> {
+    fn parse_new_actor<R: BitReader>(
         &self,
-        bits: &mut LittleEndianReader<'_>,
+        bits: &mut R,
         actor_id: ActorId,
     ) -> Result<NewActor, FrameError> {
         let component = "New Actor";
@@ -67,17 +127,289 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
             initial_trajectory: traj,
         })
     }
+}
+
+impl<'a> fmt::Debug for FrameDecoder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameDecoder")
+            .field("frames_len", &self.frames_len)
+            .field("frames_decoded", &self.frames_decoded)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl<'a> FrameDecoder<'a> {
+    pub(crate) fn new(info: DecoderInfo<'a>, network_data: &'a [u8], frames_len: usize) -> Self {
+        FrameDecoder {
+            info,
+            data: network_data,
+            bits: LittleEndianReader::new(network_data),
+            frames_len,
+            frames_decoded: 0,
+            finished: false,
+            actors: FnvHashMap::default(),
+            new_actors: Vec::new(),
+            deleted_actors: Vec::new(),
+            updated_actors: Vec::new(),
+            bit_widths: None,
+            deadline: None,
+            resyncs: None,
+            buf: [0u8; 1024],
+        }
+    }
+
+    /// The number of frames that have been decoded so far
+    pub fn frames_decoded(&self) -> usize {
+        self.frames_decoded
+    }
+
+    /// The number of frames the header declares are in the network data
+    pub fn frames_len(&self) -> usize {
+        self.frames_len
+    }
+
+    /// Limits the number of frames that will be decoded
+    pub(crate) fn truncate(&mut self, frames_len: usize) {
+        self.frames_len = self.frames_len.min(frames_len);
+    }
+
+    /// Decodes the next frame. Returns `None` once all the frames have been decoded. After an
+    /// error is returned, the decoder is considered finished and will only return `None`.
+    pub fn decode_next(&mut self) -> Result<Option<Frame>, NetworkError> {
+        self.next_frame()
+            .map_err(|e| self.frame_error(e, Vec::new()))
+    }
+
+    /// Records the number of bits that every subsequently decoded attribute occupies
+    pub(crate) fn record_bit_widths(&mut self) {
+        self.bit_widths = Some(BitWidths::default());
+    }
+
+    /// The bit widths recorded so far, if recording
+    pub(crate) fn take_bit_widths(&mut self) -> Option<BitWidths> {
+        self.bit_widths.take()
+    }
+
+    /// Fails [`decode_frames`](Self::decode_frames) once the instant has passed. The limit is
+    /// the duration that the deadline was derived from and is only reported in the error.
+    pub(crate) fn set_deadline(&mut self, at: Instant, limit: Duration) {
+        self.deadline = Some((at, limit));
+    }
+
+    /// Has [`decode_frames`](Self::decode_frames) recover from a frame that fails to decode by
+    /// resynchronizing on the next plausible frame boundary, see
+    /// [`ParserBuilder::attempt_resync`](crate::ParserBuilder::attempt_resync)
+    pub(crate) fn attempt_resync(&mut self) {
+        self.resyncs = Some(Vec::new());
+    }
+
+    /// The frames skipped so far by resynchronizing, if attempting to resync
+    pub(crate) fn take_resyncs(&mut self) -> Option<Vec<ParseWarning>> {
+        self.resyncs.take()
+    }
+
+    /// Decodes all the remaining frames
+    pub(crate) fn decode_frames(&mut self) -> Result<Vec<Frame>, NetworkError> {
+        let mut frames: Vec<Frame> = Vec::with_capacity(self.frames_len - self.frames_decoded);
+        let mut next_check = DEADLINE_CHECK_FRAMES;
+        loop {
+            let start = self.position();
+            match self.next_frame() {
+                Ok(Some(frame)) => frames.push(frame),
+                Ok(None) => break,
+                Err(e) if self.resyncs.is_none() => return Err(self.frame_error(e, frames)),
+                Err(e) => match self.resync(start, frames.last().map(|x| x.time)) {
+                    Some((frame, skipped)) => {
+                        log_debug!("Resynchronized after {} frames: {}", skipped, e);
+                        if let Some(resyncs) = self.resyncs.as_mut() {
+                            resyncs.push(ParseWarning::FramesSkipped {
+                                frame: frames.len(),
+                                skipped,
+                                error: e,
+                            });
+                        }
+                        frames.push(frame);
+                    }
+                    None => return Err(self.frame_error(e, frames)),
+                },
+            }
+
+            if let (Some((at, limit)), true) = (self.deadline, frames.len() >= next_check) {
+                next_check += DEADLINE_CHECK_FRAMES;
+                if Instant::now() >= at {
+                    self.finished = true;
+                    return Err(NetworkError::DecodeTimeout(limit, frames));
+                }
+            }
+        }
+
+        Ok(frames)
+    }
+
+    fn next_frame(&mut self) -> Result<Option<Frame>, FrameError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        if self.bits.is_empty() || self.frames_decoded >= self.frames_len {
+            self.finish();
+            return Ok(None);
+        }
+
+        match self.decode_frame() {
+            Ok(DecodedFrame::Frame(frame)) => {
+                self.frames_decoded += 1;
+                Ok(Some(frame))
+            }
+            Ok(DecodedFrame::EndFrame) => {
+                self.finish();
+                Ok(None)
+            }
+            Err(e) => {
+                self.finished = true;
+                Err(e)
+            }
+        }
+    }
+
+    /// The number of bits of the network data that have been consumed
+    fn position(&self) -> usize {
+        self.data.len() * 8 - self.bits.bits_remaining().unwrap_or(0)
+    }
+
+    /// Scans the network data, bit by bit, past the start of the frame that failed to decode for
+    /// the next plausible frame boundary: a time after the last decoded frame and a short delta,
+    /// followed by a frame that decodes and is itself followed by a frame whose time agrees with
+    /// its delta. Decoding resumes from there, and the decoded frame is returned with an estimate
+    /// of how many frames were skipped. Returns `None`, leaving the decoder finished, when no
+    /// boundary is found.
+    fn resync(&mut self, start: usize, last_time: Option<f32>) -> Option<(Frame, usize)> {
+        let last_time = last_time.unwrap_or(0.0);
+        let end = (self.data.len() * 8).saturating_sub(64);
+
+        // What the failed frame decoded is kept for the error should no boundary be found
+        let pending = (
+            self.new_actors.clone(),
+            self.deleted_actors.clone(),
+            self.updated_actors.clone(),
+        );
+
+        for pos in start + 1..end {
+            let mut bits = reader_at(self.data, pos);
+            let (time, delta) = match (bits.read_f32(), bits.read_f32()) {
+                (Some(time), Some(delta)) => (time, delta),
+                _ => break,
+            };
+
+            let plausible = delta > 0.0
+                && delta <= RESYNC_MAX_DELTA
+                && time > last_time
+                && time - last_time <= RESYNC_MAX_GAP;
+            if !plausible {
+                continue;
+            }
+
+            // Trial decode the frame without disturbing what has been decoded so far
+            let actors = self.actors.clone();
+            let widths = self.bit_widths.take();
+            self.new_actors.clear();
+            self.deleted_actors.clear();
+            self.updated_actors.clear();
+            self.bits = reader_at(self.data, pos);
+            let decoded = self.decode_frame();
+            self.bit_widths = widths;
+
+            if let Ok(DecodedFrame::Frame(frame)) = decoded {
+                if self.next_frame_follows(frame.time) {
+                    // Frames are roughly evenly spaced, so the gap in time estimates the number of
+                    // frames lost, which count towards the header's frame count
+                    let frames = ((time - last_time) / delta).round() as usize;
+                    let remaining = self.frames_len.saturating_sub(self.frames_decoded + 1);
+                    let skipped = frames.saturating_sub(1).clamp(1, remaining.max(1));
+                    self.frames_decoded += skipped + 1;
+                    self.finished = false;
+                    return Some((frame, skipped));
+                }
+            }
+
+            self.actors = actors;
+        }
+
+        self.finished = true;
+        self.new_actors = pending.0;
+        self.deleted_actors = pending.1;
+        self.updated_actors = pending.2;
+        None
+    }
+
+    /// Whether the data at the current position is the end of the network data or a frame that
+    /// follows a frame at the given time
+    fn next_frame_follows(&self, time: f32) -> bool {
+        let pos = self.position();
+        if pos + 64 > self.data.len() * 8 {
+            return true;
+        }
+
+        let mut bits = reader_at(self.data, pos);
+        match (bits.read_f32(), bits.read_f32()) {
+            (Some(next), Some(delta)) if next == 0.0 && delta == 0.0 => true,
+            (Some(next), Some(delta)) => {
+                (0.0..=RESYNC_MAX_DELTA).contains(&delta)
+                    && (next - (time + delta)).abs() <= RESYNC_TIME_TOLERANCE
+            }
+            _ => false,
+        }
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+        if self.info.version >= VersionTriplet(868, 24, 10) {
+            // Some qualifying replays are missing trailer (eg: 00bb.replay)
+            if self.bits.read_u32().is_none() {
+                log_debug!("The network data is missing its trailer");
+            }
+        }
+    }
+
+    fn frame_error(&self, e: FrameError, frames: Vec<Frame>) -> NetworkError {
+        NetworkError::FrameError(
+            e,
+            Box::new(FrameContext {
+                objects: self.info.objects.to_vec(),
+                object_attributes: self
+                    .info
+                    .object_ind_attributes
+                    .iter()
+                    .map(|(key, value)| {
+                        (
+                            *key,
+                            value
+                                .attributes
+                                .iter()
+                                .map(|(key2, value)| (*key2, value.object_id))
+                                .collect(),
+                        )
+                    })
+                    .collect(),
+                frames,
+                actors: self.actors.clone(),
+                new_actors: self.new_actors.clone(),
+                updated_actors: self.updated_actors.clone(),
+            }),
+        )
+    }
+
+    fn decode_frame(&mut self) -> Result<DecodedFrame, FrameError> {
+        let info = &self.info;
+        let bits = &mut self.bits;
+        let buf = &mut self.buf[..];
+        let actors = &mut self.actors;
+        let new_actors = &mut self.new_actors;
+        let deleted_actors = &mut self.deleted_actors;
+        let updated_actors = &mut self.updated_actors;
+        let bit_widths = &mut self.bit_widths;
 
-    fn decode_frame(
-        &self,
-        attr_decoder: &AttributeDecoder,
-        bits: &mut LittleEndianReader<'_>,
-        buf: &mut [u8],
-        actors: &mut FnvHashMap<ActorId, ObjectId>,
-        new_actors: &mut Vec<NewActor>,
-        deleted_actors: &mut Vec<ActorId>,
-        updated_actors: &mut Vec<UpdatedAttribute>,
-    ) -> Result<DecodedFrame, FrameError> {
         let time = bits
             .read_f32()
             .ok_or(FrameError::NotEnoughDataFor("Time"))?;
@@ -103,12 +435,12 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
             .ok_or(FrameError::NotEnoughDataFor("Actor data"))?
         {
             let len = bits.refill_lookahead();
-            if len < self.channel_bits + 1 + 1 {
+            if len < info.channel_bits + 1 + 1 {
                 return Err(FrameError::NotEnoughDataFor("Actor Id"));
             }
 
-            let max = u64::from(self.max_channels);
-            let actor_id_raw = bits.peek_bits_max_computed(self.channel_bits, max);
+            let max = u64::from(info.max_channels);
+            let actor_id_raw = bits.peek_bits_max_computed(info.channel_bits, max);
             let actor_id = ActorId(actor_id_raw as i32);
 
             // alive
@@ -118,7 +450,7 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
                     .read_bit()
                     .ok_or(FrameError::NotEnoughDataFor("Is new actor"))?
                 {
-                    let actor = self.parse_new_actor(bits, actor_id)?;
+                    let actor = info.parse_new_actor(bits, actor_id)?;
 
                     // Insert the new actor so we can keep track of it for attribute
                     // updates. It's common for an actor id to already exist, so we
@@ -134,7 +466,7 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
 
                     // Once we have the type we need to look up what attributes are
                     // available for said type
-                    let cache_info = self.object_ind_attributes.get(object_id).ok_or(
+                    let cache_info = info.object_ind_attributes.get(object_id).ok_or(
                         FrameError::MissingCache {
                             actor: actor_id,
                             actor_object: *object_id,
@@ -172,21 +504,40 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
                             },
                         )?;
 
-                        let attribute = attr_decoder.decode(attr.attribute, bits, buf).map_err(
-                            |e| match e {
-                                AttributeError::Unimplemented => FrameError::MissingAttribute {
-                                    actor: actor_id,
-                                    actor_object: *object_id,
-                                    attribute_stream: stream_id,
-                                },
-                                e => FrameError::AttributeError {
-                                    actor: actor_id,
-                                    actor_object: *object_id,
-                                    attribute_stream: stream_id,
-                                    error: e,
-                                },
+                        let start = bit_widths.as_ref().and_then(|_| bits.bits_remaining());
+                        let decoded = match attr.attribute {
+                            AttributeDecoding::Custom(i) => {
+                                let custom = &info.custom_decoders[usize::from(i)];
+                                (custom.decode)(bits, info.version.net_version())
+                                    .map(|value| {
+                                        Attribute::Custom(Box::new(CustomAttribute {
+                                            name: custom.name.clone(),
+                                            value,
+                                        }))
+                                    })
+                                    .ok_or(AttributeError::NotEnoughDataFor("Custom"))
+                            }
+                            tag => info.attr_decoder.decode(tag, bits, buf),
+                        };
+
+                        let attribute = decoded.map_err(|e| match e {
+                            AttributeError::Unimplemented => FrameError::MissingAttribute {
+                                actor: actor_id,
+                                actor_object: *object_id,
+                                attribute_stream: stream_id,
                             },
-                        )?;
+                            e => FrameError::AttributeError {
+                                actor: actor_id,
+                                actor_object: *object_id,
+                                attribute_stream: stream_id,
+                                error: e,
+                            },
+                        })?;
+
+                        if let (Some(widths), Some(start)) = (bit_widths.as_mut(), start) {
+                            let end = bits.bits_remaining().unwrap_or(start);
+                            widths.record(attribute.tag(), start - end);
+                        }
 
                         updated_actors.push(UpdatedAttribute {
                             actor_id,
@@ -202,6 +553,8 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
             }
         }
 
+        // The actor lists are only ever appended to as the data is decoded so draining them
+        // preserves the network order (the actors map is solely used for lookups)
         Ok(DecodedFrame::Frame(Frame {
             time,
             delta,
@@ -210,71 +563,4 @@ impl<'a, 'b> FrameDecoder<'a, 'b> {
             updated_actors: updated_actors.drain(..).collect(),
         }))
     }
-
-    pub fn decode_frames(&self) -> Result<Vec<Frame>, NetworkError> {
-        let attr_decoder = AttributeDecoder {
-            version: self.version,
-            product_decoder: self.product_decoder,
-            is_rl_223: self.is_rl_223,
-        };
-
-        let mut frames: Vec<Frame> = Vec::with_capacity(self.frames_len);
-        let mut actors = FnvHashMap::default();
-        let mut bits = LittleEndianReader::new(self.body.network_data);
-        let mut new_actors = Vec::new();
-        let mut updated_actors = Vec::new();
-        let mut deleted_actors = Vec::new();
-        let mut buf = [0u8; 1024];
-
-        while !bits.is_empty() && frames.len() < self.frames_len {
-            let frame = self
-                .decode_frame(
-                    &attr_decoder,
-                    &mut bits,
-                    &mut buf,
-                    &mut actors,
-                    &mut new_actors,
-                    &mut deleted_actors,
-                    &mut updated_actors,
-                )
-                .map_err(|e| {
-                    NetworkError::FrameError(
-                        e,
-                        Box::new(FrameContext {
-                            objects: self.body.objects.clone(),
-                            object_attributes: self
-                                .object_ind_attributes
-                                .iter()
-                                .map(|(key, value)| {
-                                    (
-                                        *key,
-                                        value
-                                            .attributes
-                                            .iter()
-                                            .map(|(key2, value)| (*key2, value.object_id))
-                                            .collect(),
-                                    )
-                                })
-                                .collect(),
-                            frames: frames.clone(),
-                            actors: actors.clone(),
-                            new_actors: new_actors.clone(),
-                            updated_actors: updated_actors.clone(),
-                        }),
-                    )
-                })?;
-
-            match frame {
-                DecodedFrame::EndFrame => break,
-                DecodedFrame::Frame(frame) => frames.push(frame),
-            }
-        }
-
-        if self.version >= VersionTriplet(868, 24, 10) {
-            // Some qualifying replays are missing trailer (eg: 00bb.replay)
-            let _ = bits.read_u32();
-        }
-
-        Ok(frames)
-    }
 }
@@ -1,31 +1,38 @@
 pub(crate) use self::attributes::*;
+pub use self::bit_widths::{BitWidthStats, BitWidths};
+pub use self::frame_decoder::FrameDecoder;
 pub use self::models::*;
 
+#[cfg(feature = "approx")]
+mod approx_eq;
 pub mod attributes;
+mod bit_widths;
 mod frame_decoder;
 mod models;
 
 use crate::data::{object_classes, ATTRIBUTES, PARENT_CLASSES, SPAWN_STATS};
-use crate::errors::NetworkError;
+use crate::errors::{NetworkError, ParseWarning};
 use crate::header::Header;
 use crate::models::*;
-use crate::network::frame_decoder::FrameDecoder;
+use crate::network::frame_decoder::DecoderInfo;
 use crate::parser::ReplayBody;
 use fnv::FnvHashMap;
+use std::borrow::Cow;
 use std::cmp;
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
-pub(crate) struct CacheInfo<'a> {
+pub(crate) struct CacheInfo {
     max_prop_id: u32,
     prop_id_bits: u32,
-    attributes: &'a FnvHashMap<StreamId, ObjectAttribute>,
+    attributes: FnvHashMap<StreamId, ObjectAttribute>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct ObjectAttribute {
-    attribute: AttributeTag,
+    attribute: AttributeDecoding,
     object_id: ObjectId,
 }
 
@@ -38,10 +45,58 @@ impl VersionTriplet {
     }
 }
 
-pub(crate) fn parse<'a>(
+/// Decodes the network frames. When given a last frame, decoding stops after that frame. When
+/// given bit widths, the width of every decoded attribute is recorded into them, even if decoding
+/// later fails. When given resyncs, frames that fail to decode are skipped by resynchronizing on
+/// the next frame boundary, and every skip is recorded into them.
+pub(crate) fn parse(
     header: &Header,
-    body: &ReplayBody<'a>,
+    body: &ReplayBody<'_>,
+    last_frame: Option<usize>,
+    custom_decoders: &[CustomDecoder],
+    bit_widths: Option<&mut BitWidths>,
+    resyncs: Option<&mut Vec<ParseWarning>>,
+    deadline: Option<(Instant, Duration)>,
 ) -> Result<NetworkFrames, NetworkError> {
+    let mut frame_decoder = decoder(header, body, Cow::Borrowed(&body.objects), custom_decoders)?;
+    if let Some(last) = last_frame {
+        frame_decoder.truncate(last + 1);
+    }
+
+    if let Some((at, limit)) = deadline {
+        frame_decoder.set_deadline(at, limit);
+    }
+
+    if bit_widths.is_some() {
+        frame_decoder.record_bit_widths();
+    }
+
+    if resyncs.is_some() {
+        frame_decoder.attempt_resync();
+    }
+
+    let frames = frame_decoder.decode_frames();
+    if let Some(widths) = bit_widths {
+        *widths = frame_decoder.take_bit_widths().unwrap_or_default();
+    }
+
+    if let Some(resyncs) = resyncs {
+        *resyncs = frame_decoder.take_resyncs().unwrap_or_default();
+    }
+
+    Ok(NetworkFrames { frames: frames? })
+}
+
+/// Constructs a decoder for the network data from the lookups derived from the header and body.
+/// Attributes with a custom decoder are decoded by it instead of by their built in decoding. The
+/// body's object names are given separately so that they can be borrowed by a decoder that does
+/// not outlive the body.
+pub(crate) fn decoder<'a>(
+    header: &Header,
+    body: &ReplayBody<'a>,
+    objects: Cow<'a, [String]>,
+    custom_decoders: &[CustomDecoder],
+) -> Result<FrameDecoder<'a>, NetworkError> {
     let version = VersionTriplet(
         header.major_version,
         header.minor_version,
@@ -99,11 +154,17 @@ pub(crate) fn parse<'a>(
             .map(|x| {
                 let attr = normalized_objects
                     .get(x.object_ind as usize)
-                    .map(|x| {
-                        ATTRIBUTES
-                            .get(x.deref())
-                            .cloned()
-                            .unwrap_or(AttributeTag::NotImplemented)
+                    .map(|name| {
+                        let custom = custom_decoders.iter().position(|decoder| {
+                            decoder.name == body.objects[x.object_ind as usize]
+                        });
+                        match custom {
+                            Some(i) => AttributeDecoding::Custom(i as u16),
+                            None => ATTRIBUTES.get(name.deref()).cloned().unwrap_or_else(|| {
+                                log_debug!("No decoding is known for the attribute {}", name);
+                                AttributeDecoding::NotImplemented
+                            }),
+                        }
                     })
                     .ok_or(NetworkError::StreamTooLargeIndex(x.stream_id, x.object_ind))?;
                 Ok((
@@ -143,6 +204,10 @@ pub(crate) fn parse<'a>(
         // parent and a parent cache id is set, try and find this parent id and carry down
         // their props.
         if !had_parent && cache.parent_id != 0 {
+            log_debug!(
+                "The class hierarchy lacks {}, so its parent was found by cache id",
+                object_name
+            );
             if let Some(parent) = body
                 .net_cache
                 .iter()
@@ -182,21 +247,20 @@ pub(crate) fn parse<'a>(
     }
 
     let object_ind_attributes: FnvHashMap<ObjectId, CacheInfo> = object_ind_attrs
-        .iter()
+        .into_iter()
         .map(|(obj_id, attrs)| {
-            let id = *obj_id;
             let max = attrs.keys().map(|&x| i32::from(x)).max().unwrap_or(2) + 1;
             let max_bit_width = crate::bits::bit_width(max as u64);
-            Ok((
-                id,
+            (
+                obj_id,
                 CacheInfo {
                     max_prop_id: max as u32,
                     prop_id_bits: cmp::max(max_bit_width, 1) - 1,
                     attributes: attrs,
                 },
-            ))
+            )
         })
-        .collect::<Result<FnvHashMap<_, _>, NetworkError>>()?;
+        .collect();
 
     let product_decoder = ProductValueDecoder::create(version, &name_obj_ind);
 
@@ -208,29 +272,29 @@ pub(crate) fn parse<'a>(
     let is_lan = header.match_type().map(|x| x == "Lan").unwrap_or(false);
     let is_rl_223 = matches!(header.build_version(), Some(x) if x >= "221120.42953.406184");
 
-    if let Some(frame_len) = num_frames {
-        if frame_len as usize > body.network_data.len() {
-            return Err(NetworkError::TooManyFrames(frame_len));
-        }
+    // A replay without a frame count has no network data to decode
+    let frames_len = num_frames.unwrap_or(0) as usize;
+    if frames_len > body.network_data.len() {
+        return Err(NetworkError::TooManyFrames(frames_len as i32));
+    }
 
-        let frame_decoder = FrameDecoder {
-            frames_len: frame_len as usize,
-            product_decoder,
-            max_channels,
-            channel_bits,
-            body,
-            spawns: &spawns,
-            object_ind_attributes,
+    let info = DecoderInfo {
+        attr_decoder: AttributeDecoder {
             version,
-            is_lan,
+            product_decoder,
             is_rl_223,
-        };
-        Ok(NetworkFrames {
-            frames: frame_decoder.decode_frames()?,
-        })
-    } else {
-        Ok(NetworkFrames { frames: Vec::new() })
-    }
+        },
+        max_channels,
+        channel_bits,
+        objects,
+        spawns,
+        object_ind_attributes,
+        custom_decoders: custom_decoders.to_vec(),
+        version,
+        is_lan,
+    };
+
+    Ok(FrameDecoder::new(info, body.network_data, frames_len))
 }
 
 #[cfg(test)]
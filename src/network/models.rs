@@ -1,5 +1,5 @@
 use crate::{bits::RlBits, network::attributes::Attribute};
-use bitter::{BitReader, LittleEndianReader};
+use bitter::BitReader;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
@@ -10,7 +10,7 @@ pub struct Vector3f {
 }
 
 impl Vector3f {
-    pub fn decode(bits: &mut LittleEndianReader<'_>, net_version: i32) -> Option<Vector3f> {
+    pub fn decode<R: BitReader>(bits: &mut R, net_version: i32) -> Option<Vector3f> {
         Vector3i::decode(bits, net_version).map(|vec| Vector3f {
             x: (vec.x as f32) / 100.0,
             y: (vec.y as f32) / 100.0,
@@ -28,7 +28,7 @@ pub struct Vector3i {
 }
 
 impl Vector3i {
-    pub fn decode(bits: &mut LittleEndianReader<'_>, net_version: i32) -> Option<Vector3i> {
+    pub fn decode<R: BitReader>(bits: &mut R, net_version: i32) -> Option<Vector3i> {
         if bits.has_bits_remaining(128) {
             unsafe { bits.refill_lookahead_unchecked() }
             let size_bits = bits.peek_bits_max_computed(4, if net_version >= 7 { 22 } else { 20 });
@@ -93,7 +93,7 @@ impl Quaternion {
     }
 
     #[inline]
-    fn compressed_f32(bits: &mut LittleEndianReader<'_>) -> f32 {
+    fn compressed_f32<R: BitReader>(bits: &mut R) -> f32 {
         // algorithm from jjbott/RocketLeagueReplayParser.
         // Note that this code is heavily adapted. I noticed that there were branches that should
         // never execute. Specifically in jjbott implementation:
@@ -111,7 +111,7 @@ impl Quaternion {
         ((res + i32::from(std::i16::MIN)) as f32) * (std::i16::MAX as f32).recip()
     }
 
-    pub fn decode_compressed(bits: &mut LittleEndianReader<'_>) -> Option<Self> {
+    pub fn decode_compressed<R: BitReader>(bits: &mut R) -> Option<Self> {
         let len = bits.refill_lookahead();
         if len >= 3 * 16 {
             let x = Quaternion::compressed_f32(bits);
@@ -123,7 +123,7 @@ impl Quaternion {
         }
     }
 
-    pub fn decode(bits: &mut LittleEndianReader<'_>) -> Option<Self> {
+    pub fn decode<R: BitReader>(bits: &mut R) -> Option<Self> {
         let len = bits.refill_lookahead();
         if len < 2 + 3 * 18 {
             return None;
@@ -162,6 +162,86 @@ impl Quaternion {
             _ => unreachable!(),
         }
     }
+
+    /// Averages the rotations with a normalized linear blend, which closely approximates the true
+    /// mean when the rotations are near each other, as they are in a short window of frames. A
+    /// quaternion and its negation are the same rotation, so each rotation is first flipped into
+    /// the same hemisphere as the first one. Returns the identity rotation for an empty slice.
+    ///
+    /// ```
+    /// # use boxcars::Quaternion;
+    /// let a = Quaternion { x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+    /// let b = Quaternion { x: 0.0, y: 0.0, z: 0.0, w: -1.0 };
+    /// assert_eq!(Quaternion::average(&[a, b]), a);
+    /// ```
+    pub fn average(rotations: &[Quaternion]) -> Quaternion {
+        match rotations.first() {
+            Some(first) => Quaternion::blend(first, rotations.iter()),
+            None => Quaternion::IDENTITY,
+        }
+    }
+
+    /// Smooths a timeline of rotations by replacing each rotation with the [average] of the
+    /// rotations within `radius` entries on either side of it. Entries without a rotation, such as
+    /// frames where a player doesn't have a car, stay empty and are left out of the windows of
+    /// their neighbors.
+    ///
+    /// [average]: Quaternion::average
+    pub fn smooth(rotations: &[Option<Quaternion>], radius: usize) -> Vec<Option<Quaternion>> {
+        rotations
+            .iter()
+            .enumerate()
+            .map(|(i, rotation)| {
+                let center = rotation.as_ref()?;
+                let start = i.saturating_sub(radius);
+                let end = rotations.len().min(i + radius + 1);
+                let window = rotations[start..end].iter().flatten();
+                Some(Quaternion::blend(center, window))
+            })
+            .collect()
+    }
+
+    const IDENTITY: Quaternion = Quaternion {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// Sums the rotations after flipping them into the hemisphere of the reference and normalizes
+    /// the result
+    fn blend<'a, I>(reference: &Quaternion, rotations: I) -> Quaternion
+    where
+        I: Iterator<Item = &'a Quaternion>,
+    {
+        let mut sum = Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 0.0,
+        };
+
+        for q in rotations {
+            let dot = q.x * reference.x + q.y * reference.y + q.z * reference.z + q.w * reference.w;
+            let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+            sum.x += sign * q.x;
+            sum.y += sign * q.y;
+            sum.z += sign * q.z;
+            sum.w += sign * q.w;
+        }
+
+        let norm = (sum.x * sum.x + sum.y * sum.y + sum.z * sum.z + sum.w * sum.w).sqrt();
+        if norm == 0.0 {
+            return Quaternion::IDENTITY;
+        }
+
+        Quaternion {
+            x: sum.x / norm,
+            y: sum.y / norm,
+            z: sum.z / norm,
+            w: sum.w / norm,
+        }
+    }
 }
 
 /// An object's current rotation
@@ -173,7 +253,7 @@ pub struct Rotation {
 }
 
 impl Rotation {
-    pub fn decode(bits: &mut LittleEndianReader<'_>) -> Option<Rotation> {
+    pub fn decode<R: BitReader>(bits: &mut R) -> Option<Rotation> {
         let len = bits.refill_lookahead();
         if len >= 3 * 9 {
             let yaw = if bits.peek_and_consume(1) != 0 {
@@ -196,9 +276,9 @@ impl Rotation {
 
             Some(Rotation { yaw, pitch, roll })
         } else {
-            let yaw = bits.if_get(LittleEndianReader::read_i8)?;
-            let pitch = bits.if_get(LittleEndianReader::read_i8)?;
-            let roll = bits.if_get(LittleEndianReader::read_i8)?;
+            let yaw = bits.if_get(R::read_i8)?;
+            let pitch = bits.if_get(R::read_i8)?;
+            let roll = bits.if_get(R::read_i8)?;
             Some(Rotation { yaw, pitch, roll })
         }
     }
@@ -230,7 +310,11 @@ pub struct UpdatedAttribute {
     pub attribute: Attribute,
 }
 
-/// Contains the time and any new information that occurred during a frame
+/// Contains the time and any new information that occurred during a frame.
+///
+/// The new, deleted, and updated actors are each guaranteed to be in the exact order that they
+/// were decoded from the network data, so re-parsing the same bytes will always yield identical
+/// frames. Consumers that diff or hash frames can rely on this ordering.
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Frame {
     /// The time in seconds that the frame is recorded at
@@ -239,13 +323,13 @@ pub struct Frame {
     /// Time difference between previous frame
     pub delta: f32,
 
-    /// List of new actors seen during the frame
+    /// List of new actors seen during the frame, in network decode order
     pub new_actors: Vec<NewActor>,
 
-    /// List of actor id's that are deleted / destroyed
+    /// List of actor id's that are deleted / destroyed, in network decode order
     pub deleted_actors: Vec<ActorId>,
 
-    /// List of properties updated on the actors
+    /// List of properties updated on the actors, in network decode order
     pub updated_actors: Vec<UpdatedAttribute>,
 }
 
@@ -332,8 +416,8 @@ pub struct Trajectory {
 }
 
 impl Trajectory {
-    pub fn from_spawn(
-        bits: &mut LittleEndianReader<'_>,
+    pub fn from_spawn<R: BitReader>(
+        bits: &mut R,
         sp: SpawnTrajectory,
         net_version: i32,
     ) -> Option<Trajectory> {
@@ -386,6 +470,7 @@ pub(crate) fn normalize_object(name: &str) -> &str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bitter::LittleEndianReader;
 
     #[test]
     fn test_decode_vector() {
@@ -395,6 +480,58 @@ mod tests {
         assert_eq!(v, Vector3i { x: 0, y: 0, z: 93 });
     }
 
+    #[test]
+    fn test_average_quaternions() {
+        // Rotations of 10 and 20 degrees about the z axis average to 15 degrees
+        let about_z = |degrees: f32| {
+            let (sin, cos) = (degrees.to_radians() / 2.0).sin_cos();
+            Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: sin,
+                w: cos,
+            }
+        };
+
+        let expected = about_z(15.0);
+        let negated = |q: Quaternion| Quaternion {
+            x: -q.x,
+            y: -q.y,
+            z: -q.z,
+            w: -q.w,
+        };
+
+        for rotations in [
+            [about_z(10.0), about_z(20.0)],
+            [about_z(10.0), negated(about_z(20.0))],
+        ]
+        .iter()
+        {
+            let average = Quaternion::average(rotations);
+            assert!((average.z - expected.z).abs() < 1e-4);
+            assert!((average.w - expected.w).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_smooth_quaternions() {
+        let q = |z: f32| {
+            let w = (1.0 - z * z).sqrt();
+            Some(Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z,
+                w,
+            })
+        };
+
+        let smoothed = Quaternion::smooth(&[q(0.1), None, q(0.3), q(0.2), q(0.1)], 1);
+        assert_eq!(smoothed.len(), 5);
+        assert_eq!(smoothed[0], q(0.1));
+        assert_eq!(smoothed[1], None);
+        assert!((smoothed[3].unwrap().z - 0.2).abs() < 1e-3);
+    }
+
     #[test]
     fn test_decode_rotation() {
         let mut bitter = LittleEndianReader::new(&[0b0000_0101, 0b0000_0000]);
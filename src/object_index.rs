@@ -0,0 +1,158 @@
+use crate::interner::{Interner, Symbol};
+use crate::network::{normalize_object, ObjectId};
+
+/// A bidirectional lookup between an object's name and its id. Object ids are the index of the
+/// name in `Replay::objects`. Names are [interned](Interner), so each is stored once no matter
+/// how many objects share it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ObjectIndex {
+    names: Interner,
+
+    /// The symbol of each object's name, indexed by object id
+    symbols: Vec<Symbol>,
+
+    /// The first object with each name, indexed by symbol
+    ids: Vec<ObjectId>,
+}
+
+impl ObjectIndex {
+    pub fn new(objects: &[String]) -> Self {
+        let bytes = objects.iter().map(|x| x.len()).sum();
+        let mut names = Interner::with_capacity(objects.len(), bytes);
+        let mut symbols = Vec::with_capacity(objects.len());
+        let mut ids = Vec::with_capacity(objects.len());
+
+        for (i, name) in objects.iter().enumerate() {
+            // In the unlikely event of duplicate names, the first occurrence wins
+            let symbol = names.intern(name);
+            if symbol.index() == ids.len() {
+                ids.push(ObjectId(i as i32));
+            }
+            symbols.push(symbol);
+        }
+
+        ObjectIndex {
+            names,
+            symbols,
+            ids,
+        }
+    }
+
+    /// Returns the id of the object with the given name
+    pub fn get_object_id(&self, name: &str) -> Option<ObjectId> {
+        self.names.get(name).map(|x| self.ids[x.index()])
+    }
+
+    /// Returns the name of the object with the given id
+    pub fn get_name(&self, id: ObjectId) -> Option<&str> {
+        self.names.resolve(self.symbol(id)?)
+    }
+
+    /// Returns the interned symbol of the object's name
+    pub fn symbol(&self, id: ObjectId) -> Option<Symbol> {
+        self.symbols.get(usize::from(id)).copied()
+    }
+
+    /// Returns the id of the first object whose name has the given symbol
+    pub fn symbol_object_id(&self, symbol: Symbol) -> Option<ObjectId> {
+        self.ids.get(symbol.index()).copied()
+    }
+
+    /// Returns true if the replay's objects include the given type. Both the given name and the
+    /// objects' names are [normalized](crate::ActorState::object_name), so any
+    /// instance of a level specific object, like a boost pad, matches every other. An object's
+    /// presence only means that the replay could spawn actors of the type: see
+    /// [`Replay::present_types`](crate::Replay::present_types) for the types that were spawned.
+    pub fn contains_type(&self, name: &str) -> bool {
+        if self.get_object_id(name).is_some() {
+            return true;
+        }
+
+        let name = normalize_object(name);
+        self.ids
+            .iter()
+            .filter_map(|id| self.get_name(*id))
+            .any(|x| normalize_object(x) == name)
+    }
+
+    /// The interned object names
+    pub fn names(&self) -> &Interner {
+        &self.names
+    }
+
+    /// Returns the ids of the objects with any of the given names. Names that are not present in
+    /// the replay are skipped.
+    pub fn get_object_ids(&self, names: &[&str]) -> Vec<ObjectId> {
+        names
+            .iter()
+            .filter_map(|name| self.get_object_id(name))
+            .collect()
+    }
+
+    /// The number of objects in the index
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_index_lookups() {
+        let objects = vec![
+            String::from("Core.Object"),
+            String::from("Archetypes.Ball.Ball_Default"),
+        ];
+        let index = ObjectIndex::new(&objects);
+        assert_eq!(
+            index.get_object_id("Archetypes.Ball.Ball_Default"),
+            Some(ObjectId(1))
+        );
+        assert_eq!(index.get_name(ObjectId(0)), Some("Core.Object"));
+        assert_eq!(index.get_name(ObjectId(2)), None);
+        assert_eq!(index.get_object_id("abc"), None);
+        assert_eq!(
+            index.get_object_ids(&["abc", "Core.Object"]),
+            vec![ObjectId(0)]
+        );
+        assert_eq!(index.len(), 2);
+        assert!(index.contains_type("Core.Object"));
+        assert!(!index.contains_type("abc"));
+    }
+
+    #[test]
+    fn test_object_index_contains_normalized_type() {
+        let objects = vec![String::from(
+            "stadium_foggy_p.TheWorld:PersistentLevel.VehiclePickup_Boost_TA_30",
+        )];
+        let index = ObjectIndex::new(&objects);
+        assert!(index.contains_type("TheWorld:PersistentLevel.VehiclePickup_Boost_TA"));
+        assert!(index.contains_type("Park_P.TheWorld:PersistentLevel.VehiclePickup_Boost_TA_4"));
+        assert!(!index.contains_type("TheWorld:PersistentLevel.CrowdActor_TA"));
+    }
+
+    #[test]
+    fn test_object_index_duplicates() {
+        let objects = vec![
+            String::from("Core.Object"),
+            String::from("Engine.Actor"),
+            String::from("Core.Object"),
+        ];
+        let index = ObjectIndex::new(&objects);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.names().len(), 2);
+        assert_eq!(index.get_object_id("Core.Object"), Some(ObjectId(0)));
+        assert_eq!(index.get_name(ObjectId(2)), Some("Core.Object"));
+        assert_eq!(index.symbol(ObjectId(2)), index.symbol(ObjectId(0)));
+
+        let symbol = index.symbol(ObjectId(1)).unwrap();
+        assert_eq!(index.names().resolve(symbol), Some("Engine.Actor"));
+        assert_eq!(index.symbol_object_id(symbol), Some(ObjectId(1)));
+    }
+}
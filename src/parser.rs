@@ -187,18 +187,25 @@
 //! With all this information, we can decode the attribute! Since there are 40 attribute types,
 //! it's not feasible to document them all here. It may take a lot of guesswork to determine the
 //! attribute type of a new attribute introduced in a released patch. Basically the recommendation
-//! is to look at the source code. Attribute parsing reuses all the concepts we've gone over
+//! is to look at the source code. Attribute parsing reuses all the concepts we've gone over. Once
+//! a new attribute is understood, it can be decoded without changing boxcars by registering a
+//! decoder with `ParserBuilder::register_attribute_decoder`.
 //!
 //! The only thing left is the other branch when the "actor is alive" bit is off. This means that
 //! the actor is deleted and that the given actor id can be recycled.
 
 use crate::core_parser::CoreParser;
 use crate::crc::calc_crc;
-use crate::errors::{NetworkError, ParseError};
+use crate::errors::{NetworkError, ParseError, ParseWarning};
 use crate::header::{self, Header};
 use crate::models::*;
-use crate::network;
+use crate::network::attributes::{Attribute, CustomDecoder};
+use crate::network::{self, BitWidths, FrameDecoder};
 use crate::parsing_utils::{le_f32, le_i32};
+use bitter::LittleEndianReader;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Determines under what circumstances the parser should perform the crc check for replay
 /// corruption. Since the crc check is the most time consuming part when parsing the header,
@@ -243,6 +250,13 @@ pub struct ParserBuilder<'a> {
     data: &'a [u8],
     crc_check: Option<CrcCheck>,
     network_parse: Option<NetworkParse>,
+    stop_at_first_goal: bool,
+    net_version_range: Option<(i32, i32)>,
+    deadline: Option<Duration>,
+    verify_frame_count: bool,
+    retain_network_data: bool,
+    attempt_resync: bool,
+    custom_decoders: Vec<CustomDecoder>,
 }
 
 impl<'a> ParserBuilder<'a> {
@@ -251,6 +265,13 @@ impl<'a> ParserBuilder<'a> {
             data,
             crc_check: None,
             network_parse: None,
+            stop_at_first_goal: false,
+            net_version_range: None,
+            deadline: None,
+            verify_frame_count: false,
+            retain_network_data: false,
+            attempt_resync: false,
+            custom_decoders: Vec::new(),
         }
     }
 
@@ -294,13 +315,158 @@ impl<'a> ParserBuilder<'a> {
         self
     }
 
+    /// Stops decoding the network data after the frame of the first goal, as recorded in the
+    /// header's goal timeline. The decoded frames then cover only the opening play, which is much
+    /// quicker to decode than the whole match. Replays without any goals in the header are decoded
+    /// in full.
+    pub fn stop_at_first_goal(mut self) -> ParserBuilder<'a> {
+        self.stop_at_first_goal = true;
+        self
+    }
+
+    /// Fails parsing with [`ParseError::NetVersionOutOfRange`] when the replay's net version is
+    /// below `min` or above `max` (inclusive). The net version is checked as soon as the header is
+    /// parsed, so out of range replays are rejected before the body and network data are read,
+    /// which makes it cheap to sort replays by format era. Replays that predate net versions are
+    /// considered to have a net version of zero.
+    pub fn require_net_version_range(mut self, min: i32, max: i32) -> ParserBuilder<'a> {
+        self.net_version_range = Some((min, max));
+        self
+    }
+
+    /// Fails parsing with [`NetworkError::DecodeTimeout`], which carries the frames decoded so
+    /// far, when decoding the network data is still in progress once the duration has elapsed
+    /// since parsing started. This bounds the time spent on a pathological replay. The clock is
+    /// only read every 256 frames, so decoding can overrun the deadline by as long as it takes to
+    /// decode that many frames. The timeout is returned even when network errors are configured
+    /// to be ignored. [`ParserBuilder::frame_decoder`] does not consult the deadline.
+    pub fn with_deadline(mut self, deadline: Duration) -> ParserBuilder<'a> {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Fails parsing with [`ParseError::FrameCountMismatch`] when the number of decoded frames
+    /// differs from the number of frames declared in the header, which is a sign that the network
+    /// data was silently truncated or decoded out of sync. Off by default. The check only applies
+    /// when the network data must be parsed: when network errors are ignored, a mismatch is
+    /// reported as a [`ParseWarning::FrameCountMismatch`](crate::ParseWarning::FrameCountMismatch)
+    /// instead, as it always is. Replays that stop at their first goal or don't declare a frame
+    /// count are not checked.
+    pub fn verify_frame_count(mut self) -> ParserBuilder<'a> {
+        self.verify_frame_count = true;
+        self
+    }
+
+    /// Keeps a copy of the undecoded network data in the replay, available from
+    /// [`Replay::network_bytes`], for decoding the network data with another decoder. The data is
+    /// retained whether or not the network data is parsed, at the cost of holding onto a copy of
+    /// the stream (typically most of the replay file).
+    pub fn retain_network_data(mut self) -> ParserBuilder<'a> {
+        self.retain_network_data = true;
+        self
+    }
+
+    /// Recovers from a frame that fails to decode, as when it contains an attribute that boxcars
+    /// can't decode, instead of failing the network data. The network data is scanned past the
+    /// start of the failed frame for the next plausible frame boundary: a frame time after the
+    /// last decoded frame with a short delta, where a frame decodes that is followed by a frame
+    /// whose time agrees with its delta. Decoding resumes there, and each gap is reported as a
+    /// [`ParseWarning::FramesSkipped`](crate::ParseWarning::FramesSkipped) with an estimate of
+    /// the number of frames skipped. The warning only reaches the caller when parsing with
+    /// [`ParserBuilder::collect_errors`], otherwise it is only logged. Off by default.
+    ///
+    /// The recovery is best effort and inherently lossy: the skipped frames are missing from the
+    /// decoded frames, actors spawned within them are unknown (so later frames that update them
+    /// fail and are skipped too), and a boundary may not be found at all, in which case the
+    /// original error is returned. [`ParserBuilder::frame_decoder`] does not resync.
+    pub fn attempt_resync(mut self) -> ParserBuilder<'a> {
+        self.attempt_resync = true;
+        self
+    }
+
+    /// Decodes the attribute with the given object name (eg: "TAGame.Car_TA:NewAttribute") with
+    /// the given decoder instead of the built in decoding, which lets attributes introduced by a
+    /// new patch, or not yet supported by boxcars, be decoded without forking the crate. The
+    /// decoder is handed the network data positioned at the start of the attribute, to be read
+    /// through bitter's `BitReader` trait, and the replay's net version. What it returns is
+    /// stored as an [`Attribute::Custom`] that carries the object name. Returning `None` fails
+    /// the network data with an error for the attribute. Registering the same object name again
+    /// replaces the previous decoder.
+    ///
+    /// Attributes are not delimited in the network data, so the decoder must read exactly as many
+    /// bits as the attribute occupies. A decoder that reads too few or too many bits leaves the
+    /// stream misaligned: every subsequent attribute and frame is decoded from the wrong
+    /// position, which usually surfaces as an error many attributes later, or worse, as
+    /// nonsensical values without an error.
+    pub fn register_attribute_decoder<F>(mut self, object_name: &str, decoder: F) -> Self
+    where
+        F: Fn(&mut LittleEndianReader<'_>, i32) -> Option<Attribute> + Send + Sync + 'static,
+    {
+        self.custom_decoders.retain(|x| x.name != object_name);
+        self.custom_decoders.push(CustomDecoder {
+            name: String::from(object_name),
+            decode: Arc::new(decoder),
+        });
+        self
+    }
+
     pub fn parse(self) -> Result<Replay, ParseError> {
+        let network_parse = self.network_parse.unwrap_or(NetworkParse::IgnoreOnError);
+        self.into_parser(network_parse).parse()
+    }
+
+    /// Parses the header and body, but instead of decoding the network data, returns a
+    /// [`FrameDecoder`] that decodes the frames one at a time on demand. The returned replay will
+    /// not contain any network frames. The options that only apply while the whole network data
+    /// is decoded (the network parse option, stopping at the first goal, the deadline, frame count
+    /// verification, and resyncing) are not consulted.
+    pub fn frame_decoder(self) -> Result<(Replay, FrameDecoder<'a>), ParseError> {
+        self.into_parser(NetworkParse::Never).parse_with_decoder()
+    }
+
+    /// Parses the replay like [`ParserBuilder::parse`] while collecting the recoverable oddities
+    /// encountered along the way, which are otherwise silent. See [`ParseWarning`] for the kinds
+    /// of warnings reported. The warnings are empty for replays that parse cleanly.
+    pub fn collect_errors(self) -> Result<(Replay, Vec<ParseWarning>), ParseError> {
+        let network_parse = self.network_parse.unwrap_or(NetworkParse::IgnoreOnError);
+        let mut parser = self.into_parser(network_parse);
+        parser.warnings = Some(Vec::new());
+        let replay = parser.parse()?;
+        Ok((replay, parser.warnings.unwrap_or_default()))
+    }
+
+    /// Parses the replay like [`ParserBuilder::parse`] while recording how many bits every
+    /// decoded attribute occupied in the network data, aggregated per kind of attribute. Kinds
+    /// whose minimum and maximum widths are equal are constant width (for this replay), which
+    /// makes the widths a ground truth for skipping attributes without decoding them. The widths
+    /// are empty when the network data is not parsed, and cover the attributes decoded before an
+    /// error when network errors are ignored. Parsing otherwise doesn't pay for the recording.
+    pub fn record_bit_widths(self) -> Result<(Replay, BitWidths), ParseError> {
+        let network_parse = self.network_parse.unwrap_or(NetworkParse::IgnoreOnError);
+        let mut parser = self.into_parser(network_parse);
+        parser.bit_widths = Some(BitWidths::default());
+        let replay = parser.parse()?;
+        Ok((replay, parser.bit_widths.unwrap_or_default()))
+    }
+
+    /// Moves the builder's options onto a parser that parses the network data as given
+    fn into_parser(self, network_parse: NetworkParse) -> Parser<'a> {
         let mut parser = Parser::new(
             self.data,
             self.crc_check.unwrap_or(CrcCheck::OnError),
-            self.network_parse.unwrap_or(NetworkParse::IgnoreOnError),
+            network_parse,
         );
-        parser.parse()
+        parser.stop_at_first_goal = self.stop_at_first_goal;
+        parser.net_version_range = self.net_version_range;
+        // The clock is only read when a deadline is set, as it isn't available on every target
+        parser.deadline = self
+            .deadline
+            .and_then(|limit| Some((Instant::now().checked_add(limit)?, limit)));
+        parser.verify_frame_count = self.verify_frame_count;
+        parser.retain_network_data = self.retain_network_data;
+        parser.attempt_resync = self.attempt_resync;
+        parser.custom_decoders = self.custom_decoders;
+        parser
     }
 }
 
@@ -319,12 +485,73 @@ pub struct ReplayBody<'a> {
     pub network_data: &'a [u8],
 }
 
+/// The sections of a replay prior to decoding the network data
+struct RawReplay<'a> {
+    header_size: i32,
+    header_crc: u32,
+    header: Header,
+    content_size: i32,
+    content_crc: u32,
+    body: ReplayBody<'a>,
+}
+
+impl<'a> RawReplay<'a> {
+    fn into_replay(
+        self,
+        network_frames: Option<NetworkFrames>,
+        retain_network_data: bool,
+    ) -> Replay {
+        let RawReplay {
+            header_size,
+            header_crc,
+            header,
+            content_size,
+            content_crc,
+            body,
+        } = self;
+
+        Replay {
+            header_size,
+            header_crc,
+            major_version: header.major_version,
+            minor_version: header.minor_version,
+            net_version: header.net_version,
+            game_type: header.game_type,
+            properties: header.properties,
+            content_size,
+            content_crc,
+            network_frames,
+            network_data: Some(body.network_data)
+                .filter(|_| retain_network_data)
+                .map(|x| x.to_vec()),
+            levels: body.levels,
+            keyframes: body.keyframes,
+            debug_info: body.debug_info,
+            tick_marks: body.tick_marks,
+            packages: body.packages,
+            objects: body.objects,
+            names: body.names,
+            class_indices: body.class_indices,
+            net_cache: body.net_cache,
+        }
+    }
+}
+
 /// Holds the current state of parsing a replay
 #[derive(Debug, Clone, PartialEq)]
 pub struct Parser<'a> {
     core: CoreParser<'a>,
     crc_check: CrcCheck,
     network_parse: NetworkParse,
+    stop_at_first_goal: bool,
+    net_version_range: Option<(i32, i32)>,
+    deadline: Option<(Instant, Duration)>,
+    verify_frame_count: bool,
+    retain_network_data: bool,
+    attempt_resync: bool,
+    custom_decoders: Vec<CustomDecoder>,
+    warnings: Option<Vec<ParseWarning>>,
+    bit_widths: Option<BitWidths>,
 }
 
 impl<'a> Parser<'a> {
@@ -333,10 +560,77 @@ impl<'a> Parser<'a> {
             core: CoreParser::new(data),
             crc_check,
             network_parse,
+            stop_at_first_goal: false,
+            net_version_range: None,
+            deadline: None,
+            verify_frame_count: false,
+            retain_network_data: false,
+            attempt_resync: false,
+            custom_decoders: Vec::new(),
+            warnings: None,
+            bit_widths: None,
         }
     }
 
     fn parse(&mut self) -> Result<Replay, ParseError> {
+        let raw = self.parse_sections()?;
+        let network: Option<NetworkFrames> = match self.network_parse {
+            NetworkParse::Always => Some(
+                self.parse_network(&raw.header, &raw.body)
+                    .map_err(ParseError::from)?,
+            ),
+            NetworkParse::IgnoreOnError => match self.parse_network(&raw.header, &raw.body) {
+                Ok(frames) => Some(frames),
+                Err(e @ NetworkError::DecodeTimeout(..)) => return Err(ParseError::from(e)),
+                Err(e) => {
+                    self.warn(ParseWarning::NetworkDataSkipped(Box::new(e)));
+                    None
+                }
+            },
+            NetworkParse::Never => None,
+        };
+
+        // A replay that stopped at its first goal is knowingly short of the header's frame count
+        let truncated = self.stop_at_first_goal && raw.header.first_goal_frame().is_some();
+        if let (Some(frames), false) = (network.as_ref(), truncated) {
+            match raw.header.num_frames() {
+                None if !raw.body.network_data.is_empty() => {
+                    self.warn(ParseWarning::MissingFrameCount)
+                }
+                Some(expected) if expected as usize != frames.frames.len() => {
+                    let expected = expected as usize;
+                    let decoded = frames.frames.len();
+                    if self.verify_frame_count && self.network_parse == NetworkParse::Always {
+                        return Err(ParseError::FrameCountMismatch(expected, decoded));
+                    }
+
+                    self.warn(ParseWarning::FrameCountMismatch { expected, decoded })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(raw.into_replay(network, self.retain_network_data))
+    }
+
+    /// Logs the warning and collects it if warnings are being collected
+    fn warn(&mut self, warning: ParseWarning) {
+        log_warn!("{}", warning);
+        if let Some(warnings) = self.warnings.as_mut() {
+            warnings.push(warning);
+        }
+    }
+
+    fn parse_with_decoder(&mut self) -> Result<(Replay, FrameDecoder<'a>), ParseError> {
+        let raw = self.parse_sections()?;
+        // The decoder is handed back alongside the replay, which takes the body's object names
+        let objects = Cow::Owned(raw.body.objects.clone());
+        let decoder = network::decoder(&raw.header, &raw.body, objects, &self.custom_decoders)
+            .map_err(ParseError::from)?;
+        Ok((raw.into_replay(None, self.retain_network_data), decoder))
+    }
+
+    fn parse_sections(&mut self) -> Result<RawReplay<'a>, ParseError> {
         let header_size = self.core.take_i32("header size")?;
         let header_crc = self.core.take_u32("header crc")?;
 
@@ -347,6 +641,13 @@ impl<'a> Parser<'a> {
         let header =
             self.crc_section(header_data, header_crc as u32, "header", Self::parse_header)?;
 
+        if let Some((min, max)) = self.net_version_range {
+            let version = header.net_version.unwrap_or(0);
+            if version < min || version > max {
+                return Err(ParseError::NetVersionOutOfRange(version, min, max));
+            }
+        }
+
         let content_size = self.core.take_i32("content size")?;
         let content_crc = self.core.take_u32("content crc")?;
 
@@ -356,38 +657,13 @@ impl<'a> Parser<'a> {
 
         let body = self.crc_section(content_data, content_crc as u32, "body", Self::parse_body)?;
 
-        let network: Option<NetworkFrames> = match self.network_parse {
-            NetworkParse::Always => Some(
-                self.parse_network(&header, &body)
-                    .map_err(|x| ParseError::NetworkError(Box::new(x)))?,
-            ),
-            NetworkParse::IgnoreOnError => self
-                .parse_network(&header, &body)
-                .map_err(|x| ParseError::NetworkError(Box::new(x)))
-                .ok(),
-            NetworkParse::Never => None,
-        };
-
-        Ok(Replay {
+        Ok(RawReplay {
             header_size,
             header_crc,
-            major_version: header.major_version,
-            minor_version: header.minor_version,
-            net_version: header.net_version,
-            game_type: header.game_type,
-            properties: header.properties,
+            header,
             content_size,
             content_crc,
-            network_frames: network,
-            levels: body.levels,
-            keyframes: body.keyframes,
-            debug_info: body.debug_info,
-            tick_marks: body.tick_marks,
-            packages: body.packages,
-            objects: body.objects,
-            names: body.names,
-            class_indices: body.class_indices,
-            net_cache: body.net_cache,
+            body,
         })
     }
 
@@ -396,7 +672,29 @@ impl<'a> Parser<'a> {
         header: &Header,
         body: &ReplayBody<'_>,
     ) -> Result<NetworkFrames, NetworkError> {
-        network::parse(header, body)
+        let last_frame = if self.stop_at_first_goal {
+            header.first_goal_frame().map(|x| x.max(0) as usize)
+        } else {
+            None
+        };
+
+        let mut resyncs = Vec::new();
+        let attempt_resync = self.attempt_resync;
+        let frames = network::parse(
+            header,
+            body,
+            last_frame,
+            &self.custom_decoders,
+            self.bit_widths.as_mut(),
+            Some(&mut resyncs).filter(|_| attempt_resync),
+            self.deadline,
+        );
+
+        for warning in resyncs {
+            self.warn(warning);
+        }
+
+        frames
     }
 
     fn parse_header(&mut self) -> Result<Header, ParseError> {
@@ -428,8 +726,15 @@ impl<'a> Parser<'a> {
             CrcCheck::OnError => result.map_err(|e| -> ParseError {
                 let actual = calc_crc(data);
                 if actual != crc as u32 {
+                    log_warn!(
+                        "The {} failed to parse and its crc of {} does not match the expected {}",
+                        section,
+                        actual,
+                        crc
+                    );
                     ParseError::CorruptReplay(String::from(section), Box::new(e))
                 } else {
+                    log_debug!("The {} failed to parse though its crc matches", section);
                     e
                 }
             }),
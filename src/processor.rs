@@ -0,0 +1,607 @@
+use crate::actor_state::{ActorState, ActorStateModeler};
+use crate::data::GAME_MODES;
+use crate::errors::ProcessorError;
+use crate::models::Replay;
+use crate::network::attributes::{Attribute, RigidBody, UniqueId};
+use crate::network::{ActorId, Frame, ObjectId, Quaternion, Vector3f};
+use crate::object_index::ObjectIndex;
+use crate::stats::BoostInterpolator;
+use fnv::FnvHashMap;
+use serde::Serialize;
+
+pub(crate) const RIGID_BODY_STATE_KEY: &str = "TAGame.RBActor_TA:ReplicatedRBState";
+pub(crate) const IGNORE_SYNCING_KEY: &str = "TAGame.RBActor_TA:bIgnoreSyncing";
+pub(crate) const PLAYER_REPLICATION_KEY: &str = "Engine.Pawn:PlayerReplicationInfo";
+pub(crate) const PLAYER_NAME_KEY: &str = "Engine.PlayerReplicationInfo:PlayerName";
+pub(crate) const UNIQUE_ID_KEY: &str = "Engine.PlayerReplicationInfo:UniqueId";
+pub(crate) const TEAM_KEY: &str = "Engine.PlayerReplicationInfo:Team";
+pub(crate) const VEHICLE_KEY: &str = "TAGame.CarComponent_TA:Vehicle";
+pub(crate) const BOOST_AMOUNT_KEY: &str = "TAGame.CarComponent_Boost_TA:ReplicatedBoostAmount";
+pub(crate) const COMPONENT_ACTIVE_KEY: &str = "TAGame.CarComponent_TA:ReplicatedActive";
+
+/// The game's state, replicated as an index into the replay's names
+pub(crate) const GAME_STATE_KEY: &str = "TAGame.GameEvent_TA:ReplicatedStateName";
+
+/// The game's state while the ball is in play, which follows a kickoff's countdown
+pub(crate) const ACTIVE_STATE: &str = "Active";
+
+pub(crate) const CAR_TYPE: &str = "Archetypes.Car.Car_Default";
+pub(crate) const BOOST_TYPE: &str = "Archetypes.CarComponents.CarComponent_Boost";
+pub(crate) const JUMP_TYPE: &str = "Archetypes.CarComponents.CarComponent_Jump";
+pub(crate) const DOUBLE_JUMP_TYPE: &str = "Archetypes.CarComponents.CarComponent_DoubleJump";
+pub(crate) const DODGE_TYPE: &str = "Archetypes.CarComponents.CarComponent_Dodge";
+pub(crate) const TEAM_TYPES: [&str; 2] = ["Archetypes.Teams.Team0", "Archetypes.Teams.Team1"];
+
+pub(crate) const BALL_TYPES: [&str; 12] = [
+    "Archetypes.Ball.Ball_Default",
+    "Archetypes.Ball.Ball_Basketball",
+    "Archetypes.Ball.Ball_BasketBall",
+    "Archetypes.Ball.Ball_BasketBall_Mutator",
+    "Archetypes.Ball.Ball_Puck",
+    "Archetypes.Ball.CubeBall",
+    "Archetypes.Ball.Ball_Breakout",
+    "Archetypes.Ball.Ball_Anniversary",
+    "Archetypes.Ball.Ball_Football",
+    "Archetypes.Ball.Ball_God",
+    "Archetypes.Ball.Ball_Haunted",
+    "Archetypes.Ball.Ball_Training",
+];
+
+/// Prefix of the attributes replicated by game events, whatever their archetype
+const GAME_EVENT_ATTRIBUTE_PREFIX: &str = "TAGame.GameEvent_";
+
+/// Replays with a net version below this encode rigid body rotations as compressed rotators
+const QUATERNION_NET_VERSION: i32 = 7;
+
+/// Replays with a net version below this encode rigid body positions in hundredths of a world unit
+/// and velocities in tenths of a world unit per second
+const WORLD_UNITS_NET_VERSION: i32 = 5;
+
+/// The components that a car delegates its abilities to. Each is an actor of its own that links
+/// back to the car it belongs to.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum CarComponent {
+    Boost,
+    Jump,
+    DoubleJump,
+    Dodge,
+}
+
+/// The ball's state at a frame
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub enum BallFrame {
+    /// There is no ball or it hasn't replicated its rigid body yet, as between a goal and the
+    /// next kickoff
+    Empty,
+    Data {
+        /// The ball's rigid body in world units
+        rigid_body: RigidBody,
+
+        /// Whether the ball is excluded from syncing, see
+        /// [`ReplayProcessor::ignore_syncing`]
+        ignore_syncing: bool,
+    },
+}
+
+/// A player's state at a frame
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub enum PlayerFrame {
+    /// The player has no car, as when demolished or spectating, or the car hasn't replicated
+    /// its state yet
+    Empty,
+    Data {
+        /// The car's rigid body in world units
+        rigid_body: RigidBody,
+
+        /// The car's boost amount (0 - 255) interpolated between replicated updates
+        boost_amount: f32,
+    },
+}
+
+/// Walks the network frames of a replay while tracking the state of every actor and the links
+/// between them: which actor is the ball, which player is driving each car, and which boost,
+/// jump, double jump, and dodge components belong to each car. It is the building block of the
+/// crate's analyses and can be used to derive new ones.
+///
+/// ```
+/// use boxcars::{BallFrame, ParserBuilder, ReplayProcessor};
+/// let data = include_bytes!("../assets/replays/good/rumble.replay");
+/// let replay = ParserBuilder::new(&data[..])
+///     .must_parse_network_data()
+///     .parse()
+///     .unwrap();
+///
+/// let mut ball_frames = 0;
+/// let mut processor = ReplayProcessor::new(&replay);
+/// processor.for_each_frame(|processor, _index, _frame| {
+///     if let BallFrame::Data { .. } = processor.get_ball_frame() {
+///         ball_frames += 1;
+///     }
+/// });
+///
+/// assert!(ball_frames > 0);
+/// ```
+pub struct ReplayProcessor<'a> {
+    replay: &'a Replay,
+    modeler: ActorStateModeler,
+    index: ObjectIndex,
+    ball_types: Vec<ObjectId>,
+    game_event_types: Vec<ObjectId>,
+    game_event_attributes: Vec<ObjectId>,
+    keys: Keys,
+    ball_actor: Option<ActorId>,
+    car_players: FnvHashMap<ActorId, ActorId>,
+    unique_ids: Vec<(UniqueId, ActorId)>,
+    boost_interpolator: BoostInterpolator,
+    car_boosts: FnvHashMap<ActorId, (BoostInterpolator, f32)>,
+    legacy_units: bool,
+    legacy_rotation: bool,
+}
+
+impl<'a> ReplayProcessor<'a> {
+    /// A processor positioned before the replay's first frame
+    pub fn new(replay: &'a Replay) -> Self {
+        let index = ObjectIndex::new(&replay.objects);
+        let net_version = replay.net_version.unwrap_or(0);
+        let game_event_types: Vec<&str> = GAME_MODES.keys().copied().collect();
+        let game_event_attributes = replay
+            .objects
+            .iter()
+            .filter(|x| x.starts_with(GAME_EVENT_ATTRIBUTE_PREFIX) && x.contains(':'))
+            .filter_map(|x| index.get_object_id(x))
+            .collect();
+
+        ReplayProcessor {
+            replay,
+            modeler: ActorStateModeler::new(),
+            ball_types: index.get_object_ids(&BALL_TYPES),
+            game_event_types: index.get_object_ids(&game_event_types),
+            game_event_attributes,
+            keys: Keys::new(&index),
+            index,
+            ball_actor: None,
+            car_players: FnvHashMap::default(),
+            unique_ids: Vec::new(),
+            boost_interpolator: BoostInterpolator::for_replay(replay),
+            car_boosts: FnvHashMap::default(),
+            legacy_units: net_version < WORLD_UNITS_NET_VERSION,
+            legacy_rotation: net_version < QUATERNION_NET_VERSION,
+        }
+    }
+
+    /// The replay being processed
+    pub fn replay(&self) -> &'a Replay {
+        self.replay
+    }
+
+    /// The decoded network frames of the replay or an empty slice if they were not parsed
+    pub fn frames(&self) -> &'a [Frame] {
+        self.replay.frames_or_empty()
+    }
+
+    /// The id of the object with the given name
+    pub fn object_id(&self, name: &str) -> Option<ObjectId> {
+        self.index.get_object_id(name)
+    }
+
+    /// Processes every frame of the replay from the first, calling `f` once each frame has been
+    /// applied with the frame's index. Meant to be called on a freshly created processor.
+    pub fn for_each_frame<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Self, usize, &'a Frame),
+    {
+        for (i, frame) in self.frames().iter().enumerate() {
+            self.process_frame(frame);
+            f(self, i, frame);
+        }
+    }
+
+    /// Applies the next frame to the tracked state. Frames must be processed in order.
+    pub fn process_frame(&mut self, frame: &Frame) {
+        self.modeler.process_frame(frame);
+
+        // Remember who drove each car, as a car is unlinked from its player when demolished
+        for new_actor in &frame.new_actors {
+            if Some(new_actor.object_id) == self.keys.car {
+                self.car_players.remove(&new_actor.actor_id);
+                self.car_boosts.remove(&new_actor.actor_id);
+            }
+        }
+
+        // Remember the player replication info actor of every player that has appeared, so that
+        // players can be looked up by their unique id after they leave
+        for update in &frame.updated_actors {
+            if Some(update.object_id) != self.keys.unique_id {
+                continue;
+            }
+
+            if let Attribute::UniqueId(unique_id) = &update.attribute {
+                match self
+                    .unique_ids
+                    .iter_mut()
+                    .find(|(id, _)| id == unique_id.as_ref())
+                {
+                    Some((_, actor)) => *actor = update.actor_id,
+                    None => self
+                        .unique_ids
+                        .push((unique_id.as_ref().clone(), update.actor_id)),
+                }
+            }
+        }
+
+        let drivers: Vec<_> = self
+            .cars()
+            .iter()
+            .filter_map(
+                |car| match self.attribute(*car, self.keys.player_replication) {
+                    Some(Attribute::ActiveActor(x)) if x.active => Some((*car, x.actor)),
+                    _ => None,
+                },
+            )
+            .collect();
+        self.car_players.extend(drivers);
+
+        // The most recently created ball takes precedence, else keep the current ball if it is
+        // still alive, else fall back to any other live ball.
+        let spawned = frame
+            .new_actors
+            .iter()
+            .rev()
+            .find(|x| self.ball_types.contains(&x.object_id))
+            .map(|x| x.actor_id);
+
+        self.ball_actor = spawned
+            .or_else(|| self.ball_actor.filter(|x| self.modeler.get(*x).is_some()))
+            .or_else(|| {
+                self.ball_types
+                    .iter()
+                    .flat_map(|x| self.modeler.actor_ids_by_type(*x))
+                    .last()
+                    .copied()
+            });
+
+        let boosts: Vec<_> = self
+            .cars()
+            .iter()
+            .filter_map(|car| {
+                let amount = self.car_boost(*car)?;
+                Some((*car, amount, self.car_boosting(*car).unwrap_or(false)))
+            })
+            .collect();
+
+        for (car, amount, boosting) in boosts {
+            let template = self.boost_interpolator;
+            let (interpolator, interpolated) =
+                self.car_boosts.entry(car).or_insert((template, 0.0));
+            *interpolated = interpolator.update(amount, boosting, frame.delta);
+        }
+    }
+
+    /// The state of the actor, if it is alive
+    pub fn actor(&self, actor_id: ActorId) -> Option<&ActorState> {
+        self.modeler.get(actor_id)
+    }
+
+    /// The actor id of the ball, if one is alive
+    pub fn ball_actor(&self) -> Option<ActorId> {
+        self.ball_actor
+    }
+
+    /// The ball's state at the current frame
+    pub fn get_ball_frame(&self) -> BallFrame {
+        // A freshly spawned ball may not have replicated its rigid body yet
+        match self.ball_actor.and_then(|x| Some((x, self.rigid_body(x)?))) {
+            Some((ball, rigid_body)) => BallFrame::Data {
+                rigid_body,
+                ignore_syncing: self.ignore_syncing(ball),
+            },
+            None => BallFrame::Empty,
+        }
+    }
+
+    /// The state of the player's car at the current frame. Players are looked up by unique id
+    /// among every player that has appeared so far.
+    pub fn get_frame_for_player(&self, player: &UniqueId) -> Result<PlayerFrame, ProcessorError> {
+        let player_actor = self
+            .player_actor(player)
+            .ok_or_else(|| ProcessorError::PlayerNotFound(player.clone()))?;
+        let car = self
+            .player_car(player_actor)
+            .ok_or_else(|| ProcessorError::CarNotFound(player.clone()))?;
+        let rigid_body = self
+            .rigid_body(car)
+            .ok_or(ProcessorError::RigidBodyMissing(car))?;
+        let boost_amount = self
+            .car_interpolated_boost(car)
+            .ok_or(ProcessorError::BoostMissing(car))?;
+
+        Ok(PlayerFrame::Data {
+            rigid_body,
+            boost_amount,
+        })
+    }
+
+    /// The state of every player that has appeared so far, in order of appearance. Players whose
+    /// state can't be determined, as when they are demolished or have left, are empty.
+    pub fn get_player_frames(&self) -> Vec<(UniqueId, PlayerFrame)> {
+        self.unique_ids
+            .iter()
+            .map(|(player, _)| {
+                let frame = self.get_frame_for_player(player).unwrap_or_else(|e| {
+                    log_debug!("no frame for player: {}", e);
+                    PlayerFrame::Empty
+                });
+                (player.clone(), frame)
+            })
+            .collect()
+    }
+
+    /// The ball's latest rigid body in world units
+    pub fn ball_rigid_body(&self) -> Option<RigidBody> {
+        self.ball_actor.and_then(|x| self.rigid_body(x))
+    }
+
+    /// The actor's latest rigid body in world units
+    pub fn rigid_body(&self, actor_id: ActorId) -> Option<RigidBody> {
+        match self.attribute(actor_id, self.keys.rigid_body)? {
+            Attribute::RigidBody(body) => Some(self.to_world(*body)),
+            _ => None,
+        }
+    }
+
+    /// True while the actor's rigid body is excluded from syncing, as when the ball is frozen or
+    /// teleported around a goal or kickoff. Its rigid body updates are not continuous motion and
+    /// shouldn't be interpolated or compared across the flag's changes.
+    pub fn ignore_syncing(&self, actor_id: ActorId) -> bool {
+        matches!(
+            self.attribute(actor_id, self.keys.ignore_syncing),
+            Some(Attribute::Boolean(true))
+        )
+    }
+
+    /// The actor id of the game event, which runs the match's rules and replicates its clock.
+    /// Game events with a known archetype are preferred, else the game event of a custom mode is
+    /// identified as any actor that has replicated a game event attribute.
+    pub fn game_event(&self) -> Option<ActorId> {
+        self.game_event_types
+            .iter()
+            .find_map(|x| self.modeler.actor_ids_by_type(*x).first().copied())
+            .or_else(|| {
+                self.modeler
+                    .actors()
+                    .find(|(_, state)| {
+                        state
+                            .attributes()
+                            .any(|(id, _)| self.game_event_attributes.contains(&id))
+                    })
+                    .map(|(id, _)| id)
+            })
+    }
+
+    /// The ids of every live car in the order that they were created
+    pub fn cars(&self) -> &[ActorId] {
+        self.keys
+            .car
+            .map(|x| self.modeler.actor_ids_by_type(x))
+            .unwrap_or_default()
+    }
+
+    /// The ids of every live player replication info actor, ordered by actor id. Player
+    /// replication info actors are identified by their replicated name, as their type varies
+    /// between game modes.
+    pub fn players(&self) -> Vec<ActorId> {
+        let mut players: Vec<ActorId> = match self.keys.player_name {
+            Some(key) => self
+                .modeler
+                .actors()
+                .filter(|(_, state)| state.attribute(key).is_some())
+                .map(|(id, _)| id)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        players.sort_unstable();
+        players
+    }
+
+    /// The player replication info actor that is driving the car or last drove it
+    pub fn car_player(&self, car: ActorId) -> Option<ActorId> {
+        self.car_players.get(&car).copied()
+    }
+
+    /// The live car driven by the player replication info actor
+    pub fn player_car(&self, player: ActorId) -> Option<ActorId> {
+        self.cars()
+            .iter()
+            .rev()
+            .find(|car| self.car_player(**car) == Some(player))
+            .copied()
+    }
+
+    /// The latest player replication info actor of the player with the unique id. The actor
+    /// may no longer be alive if the player left.
+    pub fn player_actor(&self, player: &UniqueId) -> Option<ActorId> {
+        self.unique_ids
+            .iter()
+            .find(|(id, _)| id == player)
+            .map(|(_, actor)| *actor)
+    }
+
+    /// The name of the player behind the player replication info actor
+    pub fn player_name(&self, player: ActorId) -> Option<&str> {
+        match self.attribute(player, self.keys.player_name)? {
+            Attribute::String(x) => Some(x.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The unique id of the player behind the player replication info actor
+    pub fn player_unique_id(&self, player: ActorId) -> Option<&UniqueId> {
+        match self.attribute(player, self.keys.unique_id)? {
+            Attribute::UniqueId(x) => Some(x.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// The team of the player behind the player replication info actor: 0 for blue and 1 for
+    /// orange
+    pub fn player_team(&self, player: ActorId) -> Option<u8> {
+        let team = match self.attribute(player, self.keys.team)? {
+            Attribute::ActiveActor(x) if x.active => x.actor,
+            _ => return None,
+        };
+
+        let object_id = self.modeler.get(team)?.object_id();
+        self.keys
+            .teams
+            .iter()
+            .position(|x| *x == Some(object_id))
+            .map(|x| x as u8)
+    }
+
+    /// The car's latest replicated boost amount (0 - 255)
+    pub fn car_boost(&self, car: ActorId) -> Option<u8> {
+        self.boost_components(car)
+            .find_map(|x| match self.attribute(x, self.keys.boost_amount) {
+                Some(Attribute::Byte(amount)) => Some(*amount),
+                _ => None,
+            })
+    }
+
+    /// Whether the car is boosting: its boost component is active while the replicated counter
+    /// is odd
+    pub fn car_boosting(&self, car: ActorId) -> Option<bool> {
+        self.boost_components(car).find_map(|x| {
+            match self.attribute(x, self.keys.component_active) {
+                Some(Attribute::Byte(active)) => Some(active % 2 == 1),
+                _ => None,
+            }
+        })
+    }
+
+    /// The car's boost amount (0 - 255) as of the latest processed frame, interpolated between
+    /// replicated updates while the car is boosting
+    pub fn car_interpolated_boost(&self, car: ActorId) -> Option<f32> {
+        self.car_boosts.get(&car).map(|(_, amount)| *amount)
+    }
+
+    /// The car's live component of the given kind
+    pub fn car_component(&self, car: ActorId, kind: CarComponent) -> Option<ActorId> {
+        let component_type = match kind {
+            CarComponent::Boost => self.keys.boost,
+            CarComponent::Jump => self.keys.jump,
+            CarComponent::DoubleJump => self.keys.double_jump,
+            CarComponent::Dodge => self.keys.dodge,
+        };
+
+        self.components(car, component_type).last()
+    }
+
+    /// The boost components of the car
+    fn boost_components(&self, car: ActorId) -> impl Iterator<Item = ActorId> + '_ {
+        self.components(car, self.keys.boost)
+    }
+
+    /// The car's components of the given type
+    fn components(
+        &self,
+        car: ActorId,
+        component_type: Option<ObjectId>,
+    ) -> impl Iterator<Item = ActorId> + '_ {
+        component_type
+            .map(|x| self.modeler.actor_ids_by_type(x))
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(move |x| match self.attribute(*x, self.keys.vehicle) {
+                Some(Attribute::ActiveActor(vehicle)) => vehicle.actor == car,
+                _ => false,
+            })
+    }
+
+    fn attribute(&self, actor_id: ActorId, key: Option<ObjectId>) -> Option<&Attribute> {
+        self.modeler.get(actor_id)?.attribute(key?)
+    }
+
+    /// Normalizes the rigid body to world units and a quaternion rotation
+    pub fn to_world(&self, mut body: RigidBody) -> RigidBody {
+        if self.legacy_units {
+            body.location = scale(body.location, 100.0);
+            body.linear_velocity = body.linear_velocity.map(|x| scale(x, 10.0));
+        }
+
+        if self.legacy_rotation {
+            body.rotation = rotator_to_quaternion(body.rotation);
+        }
+
+        body
+    }
+}
+
+/// Object ids of the attributes and types of interest. A replay may not contain all of them.
+struct Keys {
+    rigid_body: Option<ObjectId>,
+    ignore_syncing: Option<ObjectId>,
+    player_replication: Option<ObjectId>,
+    player_name: Option<ObjectId>,
+    unique_id: Option<ObjectId>,
+    team: Option<ObjectId>,
+    vehicle: Option<ObjectId>,
+    boost_amount: Option<ObjectId>,
+    component_active: Option<ObjectId>,
+    car: Option<ObjectId>,
+    boost: Option<ObjectId>,
+    jump: Option<ObjectId>,
+    double_jump: Option<ObjectId>,
+    dodge: Option<ObjectId>,
+    teams: [Option<ObjectId>; 2],
+}
+
+impl Keys {
+    fn new(index: &ObjectIndex) -> Self {
+        Keys {
+            rigid_body: index.get_object_id(RIGID_BODY_STATE_KEY),
+            ignore_syncing: index.get_object_id(IGNORE_SYNCING_KEY),
+            player_replication: index.get_object_id(PLAYER_REPLICATION_KEY),
+            player_name: index.get_object_id(PLAYER_NAME_KEY),
+            unique_id: index.get_object_id(UNIQUE_ID_KEY),
+            team: index.get_object_id(TEAM_KEY),
+            vehicle: index.get_object_id(VEHICLE_KEY),
+            boost_amount: index.get_object_id(BOOST_AMOUNT_KEY),
+            component_active: index.get_object_id(COMPONENT_ACTIVE_KEY),
+            car: index.get_object_id(CAR_TYPE),
+            boost: index.get_object_id(BOOST_TYPE),
+            jump: index.get_object_id(JUMP_TYPE),
+            double_jump: index.get_object_id(DOUBLE_JUMP_TYPE),
+            dodge: index.get_object_id(DODGE_TYPE),
+            teams: [
+                index.get_object_id(TEAM_TYPES[0]),
+                index.get_object_id(TEAM_TYPES[1]),
+            ],
+        }
+    }
+}
+
+/// Older replays store the pitch, yaw, and roll (as fractions of pi) in the x, y, and z fields of
+/// the quaternion. Converts them to a quaternion with the same conventions as Unreal's rotators.
+pub(crate) fn rotator_to_quaternion(rotator: Quaternion) -> Quaternion {
+    let half = |x: f32| (x * std::f32::consts::PI / 2.0).sin_cos();
+    let (sp, cp) = half(rotator.x);
+    let (sy, cy) = half(rotator.y);
+    let (sr, cr) = half(rotator.z);
+
+    Quaternion {
+        x: cr * sp * sy - sr * cp * cy,
+        y: -cr * sp * cy - sr * cp * sy,
+        z: cr * cp * sy - sr * sp * cy,
+        w: cr * cp * cy + sr * sp * sy,
+    }
+}
+
+pub(crate) fn scale(v: Vector3f, by: f32) -> Vector3f {
+    Vector3f {
+        x: v.x * by,
+        y: v.y * by,
+        z: v.z * by,
+    }
+}
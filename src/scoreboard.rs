@@ -0,0 +1,77 @@
+use crate::models::Replay;
+use crate::network::attributes::{Attribute, UniqueId};
+use crate::processor::ReplayProcessor;
+
+const SCORE_KEY: &str = "TAGame.PRI_TA:MatchScore";
+const GOALS_KEY: &str = "TAGame.PRI_TA:MatchGoals";
+const ASSISTS_KEY: &str = "TAGame.PRI_TA:MatchAssists";
+const SAVES_KEY: &str = "TAGame.PRI_TA:MatchSaves";
+const SHOTS_KEY: &str = "TAGame.PRI_TA:MatchShots";
+
+/// The in-match scoreboard as replicated on each player's replication info actor
+pub struct ScoreboardState;
+
+/// A player's row of the scoreboard
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct ScoreboardEntry {
+    pub player_id: Option<UniqueId>,
+    pub name: Option<String>,
+
+    /// The player's team: 0 for blue and 1 for orange
+    pub team: u8,
+    pub score: i32,
+    pub goals: i32,
+    pub assists: i32,
+    pub saves: i32,
+    pub shots: i32,
+}
+
+impl ScoreboardState {
+    /// Reconstructs the scoreboard as of the network frame with the given index (a frame past the
+    /// end is the final scoreboard). Every player on a team at the frame has an entry, so players
+    /// that have left and spectators are absent, and stats that haven't been replicated yet, as
+    /// for a player that just joined, are zero. Entries are ordered by team and then by score,
+    /// like the in-game scoreboard. Empty if the network data was not parsed.
+    ///
+    /// The final scoreboard may trail the header's "PlayerStats", which include what was awarded
+    /// after the last network frame, such as the points of a match winning goal.
+    pub fn at_frame(replay: &Replay, frame: usize) -> Vec<ScoreboardEntry> {
+        let mut processor = ReplayProcessor::new(replay);
+        let frames = processor.frames();
+        for x in &frames[..frames.len().min(frame.saturating_add(1))] {
+            processor.process_frame(x);
+        }
+
+        let keys = [SCORE_KEY, GOALS_KEY, ASSISTS_KEY, SAVES_KEY, SHOTS_KEY]
+            .map(|x| processor.object_id(x));
+        let mut entries: Vec<ScoreboardEntry> = processor
+            .players()
+            .into_iter()
+            .filter_map(|player| {
+                let team = processor.player_team(player)?;
+                let stat = |key: Option<_>| {
+                    let state = processor.actor(player)?;
+                    match state.attribute(key?)? {
+                        Attribute::Int(x) => Some(*x),
+                        _ => None,
+                    }
+                };
+
+                let [score, goals, assists, saves, shots] = keys.map(|key| stat(key).unwrap_or(0));
+                Some(ScoreboardEntry {
+                    player_id: processor.player_unique_id(player).cloned(),
+                    name: processor.player_name(player).map(String::from),
+                    team,
+                    score,
+                    goals,
+                    assists,
+                    saves,
+                    shots,
+                })
+            })
+            .collect();
+
+        entries.sort_by_key(|x| (x.team, -x.score));
+        entries
+    }
+}
@@ -0,0 +1,1889 @@
+//! # Stats
+//!
+//! Coaching oriented analytics composed from the [`events`]. As with events, the
+//! analytics assume a standard soccar field and may be inaccurate for other game modes.
+
+use crate::events::{self, Touch};
+use crate::field::{self, distance};
+use crate::models::Replay;
+use crate::network::attributes::{Attribute, RigidBody, UniqueId};
+use crate::network::{normalize_object, ActorId, ObjectId, Vector3f};
+use crate::processor::{ReplayProcessor, ACTIVE_STATE, GAME_STATE_KEY};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+/// The maximum distance (uu) from the center spot for the ball to be placed for a kickoff
+const KICKOFF_SPOT_DISTANCE: f32 = 1.0;
+
+/// The maximum horizontal speed (uu/s) of the ball while it waits at the center spot for a
+/// kickoff. The ball settles vertically after it is spawned.
+const KICKOFF_MAX_BALL_SPEED: f32 = 1.0;
+
+/// Touches by opposing teams closer together than this (in seconds) are considered simultaneous
+const KICKOFF_NEUTRAL_SECONDS: f32 = 0.1;
+
+/// Seconds after the first touch of a kickoff that the ball's position decides the winner
+const KICKOFF_WIN_SECONDS: f32 = 2.0;
+
+/// A ball closer than this (uu) to the midfield line does not favor either team
+const KICKOFF_MIDFIELD_DISTANCE: f32 = 250.0;
+
+/// The maximum number of seconds after the first touch of a kickoff for a goal to be credited to
+/// the kickoff
+const KICKOFF_GOAL_SECONDS: f32 = 10.0;
+
+/// The maximum number of seconds after the countdown of a kickoff ends for the winning team's
+/// first touch to be timed
+const KICKOFF_FIRST_TOUCH_SECONDS: f32 = 5.0;
+
+/// A full tank of boost as replicated in the network data
+const MAX_BOOST: f32 = 255.0;
+
+/// A pad that granted more boost (as a percentage of a full tank) than this is a large pad. The
+/// extra margin accounts for boost collected from another pad before the amount is replicated.
+const LARGE_PAD_MIN_GAIN: f32 = 30.0;
+
+/// The maximum number of seconds between a pickup and the replicated boost amount that reflects it
+const PICKUP_GAIN_SECONDS: f32 = 0.25;
+
+/// A pad that took longer than this (seconds) to respawn is a large pad. Small pads respawn after
+/// 4 seconds and large pads after 10.
+const LARGE_PAD_MIN_RESPAWN: f32 = 7.0;
+
+/// The normalized object name of every boost pad actor
+const BOOST_PAD_OBJECT: &str = "TheWorld:PersistentLevel.VehiclePickup_Boost_TA";
+
+/// A car above this height (uu) that is away from the walls is in the air for an aerial. A single
+/// jump from the ground does not reach it.
+const AERIAL_MIN_HEIGHT: f32 = 300.0;
+
+/// A car within this distance (uu) of a wall may be driving on it rather than flying
+const AERIAL_WALL_MARGIN: f32 = 200.0;
+
+/// The default distance (uu) from the center of a goal line that the ball threatens the goal
+pub const THREAT_DISTANCE: f32 = 2500.0;
+
+/// Both players of a [duel](duel_pressure) have their car within this distance (uu) of the ball
+pub const DUEL_RADIUS: f32 = 800.0;
+
+/// A duel lasts at least this many seconds, so that cars passing by each other near the ball
+/// don't count
+pub const DUEL_MIN_SECONDS: f32 = 0.25;
+
+/// The winner of a duel is the duelist that touches the ball within this many seconds after it
+pub const DUEL_WIN_SECONDS: f32 = 1.0;
+
+/// A car further than this distance (uu) past the ball, along the length of the field and away
+/// from its own goal, is not back on defense. The slack spares a defender that is level with the
+/// ball as it is challenged.
+pub const FIRST_MAN_BACK_MARGIN: f32 = 200.0;
+
+/// A [first man back violation](first_man_back_violations) lasts at least this many seconds, so
+/// that a defender crossing paths with the ball doesn't count
+pub const FIRST_MAN_BACK_MIN_SECONDS: f32 = 0.25;
+
+/// Car physics values that the boost and speed analytics depend on. The values that applied to a
+/// replay are chosen with [`PhysicsConstants::for_replay`], and they can be overridden by passing
+/// custom constants to the `_with` variants of the analytics.
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct PhysicsConstants {
+    /// Boost consumed (as replicated: 255 is a full tank) per second of boosting
+    pub boost_used_per_second: f32,
+
+    /// The boost (as a percentage of a full tank) granted by a small boost pad
+    pub small_pad_boost: f32,
+
+    /// The speed (uu/s) at which a car becomes supersonic
+    pub supersonic_speed: f32,
+
+    /// The maximum speed (uu/s) of a car
+    pub max_car_speed: f32,
+}
+
+/// The physics constants by the first replay version (major, minor) that they apply to, from
+/// newest to oldest:
+///
+/// - All known versions: 86 boost per second (a third of a tank), 12% per small pad, supersonic at
+///   2200 uu/s, and a top speed of 2300 uu/s
+///
+/// No patch is known to have changed these values yet. A patch that does adds a range here.
+const PHYSICS_CONSTANTS: [((i32, i32), PhysicsConstants); 1] = [(
+    (0, 0),
+    PhysicsConstants {
+        boost_used_per_second: 80.0 / 0.93,
+        small_pad_boost: 12.0,
+        supersonic_speed: 2200.0,
+        max_car_speed: 2300.0,
+    },
+)];
+
+impl PhysicsConstants {
+    /// The constants of the patch that recorded the replay
+    pub fn for_replay(replay: &Replay) -> Self {
+        let version = (replay.major_version, replay.minor_version);
+        PHYSICS_CONSTANTS
+            .iter()
+            .find(|(since, _)| *since <= version)
+            .map_or(
+                PHYSICS_CONSTANTS[PHYSICS_CONSTANTS.len() - 1].1,
+                |(_, x)| *x,
+            )
+    }
+}
+
+impl Default for PhysicsConstants {
+    /// The constants of the latest known patch
+    fn default() -> Self {
+        PHYSICS_CONSTANTS[0].1
+    }
+}
+
+/// The result of a kickoff
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct KickoffOutcome {
+    /// The kickoff's position in the match, starting at zero
+    pub kickoff_index: usize,
+
+    /// Index of the frame where the ball was placed at the center spot
+    pub frame: usize,
+
+    /// Replay time of the first touch, or of the kickoff's frame if the ball was never touched
+    pub time: f32,
+
+    /// The player who first touched the ball. `None` for neutral kickoffs and when the player is
+    /// unknown.
+    pub first_toucher: Option<UniqueId>,
+
+    /// True when players from both teams touched the ball simultaneously
+    pub neutral: bool,
+
+    /// The team that advanced the ball into their opponent's half: 0 for blue and 1 for orange.
+    /// `None` if the ball stayed around midfield or was never touched.
+    pub winning_team: Option<u8>,
+
+    /// Seconds between the first touch and a goal scored by the winning team, when the goal
+    /// quickly followed the kickoff
+    pub led_to_goal_within: Option<f32>,
+
+    /// Seconds from the end of the countdown to the winning team's first touch. `None` if there
+    /// is no winning team, the end of the countdown was not seen, or the winning team did not
+    /// touch the ball within 5 seconds of it.
+    pub time_to_first_touch: Option<f32>,
+}
+
+/// Detects each kickoff as the ball waiting at the center spot and reports who first touched the
+/// ball and which team came away with the advantage. The countdown of a kickoff ends when the
+/// game's state becomes active while the ball waits at the center spot, as in
+/// [`kickoff_positions`].
+pub fn kickoff_outcomes(replay: &Replay) -> Vec<KickoffOutcome> {
+    let mut processor = ReplayProcessor::new(replay);
+    let state_key = processor.object_id(GAME_STATE_KEY);
+    let active = replay.names.iter().position(|x| x == ACTIVE_STATE);
+    let mut state = None;
+
+    // The frame that each kickoff started at, the time its countdown ended, and the ball's
+    // position (y) for every frame
+    let mut kickoffs: Vec<usize> = Vec::new();
+    let mut countdowns: Vec<Option<f32>> = Vec::new();
+    let mut ball_y: Vec<Option<f32>> = Vec::with_capacity(processor.frames().len());
+    let mut detector = KickoffDetector::default();
+    for (i, frame) in processor.frames().iter().enumerate() {
+        // Keyframes re-send the state, so only a change to the active state ends a countdown
+        let previous = state;
+        for update in &frame.updated_actors {
+            if let (true, Attribute::Int(x)) =
+                (Some(update.object_id) == state_key, &update.attribute)
+            {
+                state = usize::try_from(*x).ok();
+            }
+        }
+
+        let go = state.is_some() && state == active && previous != active;
+        if let (true, Some(countdown)) = (go && detector.waiting, countdowns.last_mut()) {
+            countdown.get_or_insert(frame.time);
+        }
+
+        processor.process_frame(frame);
+        let body = processor.ball_rigid_body();
+        ball_y.push(body.map(|x| x.location.y));
+        if detector.update(body.as_ref()) {
+            kickoffs.push(i);
+            countdowns.push(None);
+        }
+    }
+
+    let frames = processor.frames();
+    let touches = events::touches(replay);
+    let goals = events::goals_from_network(replay);
+
+    kickoffs
+        .iter()
+        .enumerate()
+        .map(|(kickoff_index, &frame)| {
+            let end = kickoffs
+                .get(kickoff_index + 1)
+                .copied()
+                .unwrap_or(usize::MAX);
+            let kickoff_touches: Vec<&Touch> = touches
+                .iter()
+                .filter(|x| x.frame >= frame && x.frame < end)
+                .collect();
+
+            let first = match kickoff_touches.first() {
+                Some(x) => *x,
+                None => {
+                    return KickoffOutcome {
+                        kickoff_index,
+                        frame,
+                        time: frames[frame].time,
+                        first_toucher: None,
+                        neutral: false,
+                        winning_team: None,
+                        led_to_goal_within: None,
+                        time_to_first_touch: None,
+                    }
+                }
+            };
+
+            let neutral = kickoff_touches.iter().skip(1).any(|x| {
+                x.time - first.time < KICKOFF_NEUTRAL_SECONDS
+                    && x.team.is_some()
+                    && first.team.is_some()
+                    && x.team != first.team
+            });
+
+            // The ball's position shortly after the first touch, or right before it left play
+            let decided = frames
+                .iter()
+                .enumerate()
+                .skip(first.frame)
+                .take_while(|(i, x)| *i < end && x.time - first.time <= KICKOFF_WIN_SECONDS)
+                .filter_map(|(i, _)| ball_y[i])
+                .last();
+
+            let winning_team = match decided {
+                Some(y) if y.abs() >= KICKOFF_MIDFIELD_DISTANCE => {
+                    Some(1 - field::defending_team(y))
+                }
+                _ => None,
+            };
+
+            let led_to_goal_within = goals
+                .iter()
+                .filter(|x| x.frame >= first.frame && x.frame < end)
+                .find(|x| Some(x.scoring_team) == winning_team)
+                .map(|x| x.time - first.time)
+                .filter(|x| *x <= KICKOFF_GOAL_SECONDS);
+
+            let time_to_first_touch = countdowns[kickoff_index].and_then(|go| {
+                let touch = kickoff_touches
+                    .iter()
+                    .find(|x| x.team.is_some() && x.team == winning_team)?;
+                Some(touch.time - go).filter(|x| *x <= KICKOFF_FIRST_TOUCH_SECONDS)
+            });
+
+            KickoffOutcome {
+                kickoff_index,
+                frame,
+                time: first.time,
+                first_toucher: if neutral {
+                    None
+                } else {
+                    first.player_id.clone()
+                },
+                neutral,
+                winning_team,
+                led_to_goal_within,
+                time_to_first_touch,
+            }
+        })
+        .collect()
+}
+
+/// Seconds from the end of each kickoff's countdown to the first touch by the team that won it,
+/// in the order of [`kickoff_outcomes`]. This is how quickly the winning team reached the ball,
+/// and is `None` for the same kickoffs as [`KickoffOutcome::time_to_first_touch`].
+pub fn time_to_first_touch(replay: &Replay) -> Vec<Option<f32>> {
+    kickoff_outcomes(replay)
+        .into_iter()
+        .map(|x| x.time_to_first_touch)
+        .collect()
+}
+
+/// What a player did on a kickoff
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum KickoffRole {
+    /// The team's player closest to the ball when it was first touched
+    Taker,
+
+    /// Another player who had left their defensive third by the first touch
+    Cheat,
+
+    /// Another player who stayed in their defensive third
+    Back,
+}
+
+/// Where a player was when a kickoff started
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct KickoffPlayer {
+    pub player: Option<String>,
+    pub player_id: Option<UniqueId>,
+
+    /// Location of the player's car in world units
+    pub location: Vector3f,
+
+    /// The standard spawn that the car was at. `None` when the car was elsewhere, as in modes
+    /// and team sizes with other spawns.
+    pub spawn: Option<field::KickoffSpawn>,
+
+    /// `None` if the ball was not touched before the next kickoff or the player's car was not on
+    /// the field at the first touch
+    pub role: Option<KickoffRole>,
+}
+
+/// Each team's setup for a kickoff
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct KickoffFormation {
+    /// The kickoff's position in the match, the same as in [`kickoff_outcomes`]
+    pub kickoff_index: usize,
+
+    /// Index of the frame where the countdown ended and the players could move
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// The team's players ordered by their distance to the ball
+    pub blue: Vec<KickoffPlayer>,
+    pub orange: Vec<KickoffPlayer>,
+}
+
+/// Reports where every player was when the countdown of each kickoff ended, classified by the
+/// standard spawn they were at, and the role that they took. Kickoffs are detected as in
+/// [`kickoff_outcomes`] and the countdown ends when the game's state becomes active while the
+/// ball waits at the center spot. Kickoffs whose countdown was not seen ending, as when the
+/// game's state is not replicated, are omitted.
+pub fn kickoff_positions(replay: &Replay) -> Vec<KickoffFormation> {
+    let mut processor = ReplayProcessor::new(replay);
+    let state_key = processor.object_id(GAME_STATE_KEY);
+    let active = replay.names.iter().position(|x| x == ACTIVE_STATE);
+    let touches = events::touches(replay);
+
+    let mut formations: Vec<KickoffFormation> = Vec::new();
+    let mut detector = KickoffDetector::default();
+    let mut kickoff_index = None;
+    let mut state = None;
+
+    // The formation whose roles are decided by the first touch at the frame, along with the
+    // actor of each of its players
+    let mut first_touch: Option<(usize, usize, [Vec<ActorId>; 2])> = None;
+    for (i, frame) in processor.frames().iter().enumerate() {
+        // Keyframes re-send the state, so only a change to the active state ends a countdown
+        let previous = state;
+        for update in &frame.updated_actors {
+            if let (true, Attribute::Int(x)) =
+                (Some(update.object_id) == state_key, &update.attribute)
+            {
+                state = usize::try_from(*x).ok();
+            }
+        }
+
+        // Positions are taken from the last frame of the countdown, before any car has moved
+        let go = state.is_some() && state == active && previous != active;
+        if let Some(index) = kickoff_index.filter(|_| go) {
+            kickoff_index = None;
+            let (formation, players) = kickoff_formation(&processor, index, i, frame.time);
+            first_touch = touches
+                .iter()
+                .find(|x| x.frame >= i)
+                .map(|x| (formations.len(), x.frame, players));
+            formations.push(formation);
+        }
+
+        processor.process_frame(frame);
+        if detector.update(processor.ball_rigid_body().as_ref()) {
+            kickoff_index = Some(detector.kickoffs - 1);
+            first_touch = None;
+        } else if !detector.waiting {
+            kickoff_index = None;
+        }
+
+        if matches!(first_touch, Some((_, touch, _)) if touch == i) {
+            if let Some((formation, _, players)) = first_touch.take() {
+                assign_kickoff_roles(&processor, &mut formations[formation], &players);
+            }
+        }
+    }
+
+    formations
+}
+
+fn kickoff_formation(
+    processor: &ReplayProcessor,
+    kickoff_index: usize,
+    frame: usize,
+    time: f32,
+) -> (KickoffFormation, [Vec<ActorId>; 2]) {
+    let mut teams: [Vec<(ActorId, KickoffPlayer)>; 2] = [Vec::new(), Vec::new()];
+    for car in processor.cars() {
+        let player = match processor.car_player(*car) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let team = processor.player_team(player);
+        let body = processor.rigid_body(*car);
+        if let (Some(team), Some(body)) = (team, body) {
+            if let Some(x) = teams.get_mut(usize::from(team)) {
+                x.push((
+                    player,
+                    KickoffPlayer {
+                        player: processor.player_name(player).map(String::from),
+                        player_id: processor.player_unique_id(player).cloned(),
+                        location: body.location,
+                        spawn: field::kickoff_spawn(&body.location),
+                        role: None,
+                    },
+                ));
+            }
+        }
+    }
+
+    for players in teams.iter_mut() {
+        players.sort_by(|(_, a), (_, b)| {
+            let a = length(a.location.x, a.location.y);
+            let b = length(b.location.x, b.location.y);
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+
+    let actors = [&teams[0], &teams[1]].map(|x| x.iter().map(|(actor, _)| *actor).collect());
+    let [blue, orange] = teams.map(|x| x.into_iter().map(|(_, player)| player).collect());
+    let formation = KickoffFormation {
+        kickoff_index,
+        frame,
+        time,
+        blue,
+        orange,
+    };
+    (formation, actors)
+}
+
+/// Assigns every player their role from where their car is at the first touch of the kickoff
+fn assign_kickoff_roles(
+    processor: &ReplayProcessor,
+    formation: &mut KickoffFormation,
+    actors: &[Vec<ActorId>; 2],
+) {
+    let ball = match processor.ball_rigid_body() {
+        Some(x) => x.location,
+        None => return,
+    };
+
+    let mut locations: HashMap<ActorId, Vector3f> = HashMap::new();
+    for car in processor.cars() {
+        if let (Some(player), Some(body)) = (processor.car_player(*car), processor.rigid_body(*car))
+        {
+            locations.insert(player, body.location);
+        }
+    }
+
+    let mut teams = [&mut formation.blue, &mut formation.orange];
+    for (team, (players, actors)) in teams.iter_mut().zip(actors.iter()).enumerate() {
+        let at_touch: Vec<Option<Vector3f>> =
+            actors.iter().map(|x| locations.get(x).copied()).collect();
+
+        let taker = at_touch
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| x.map(|x| (i, distance(&x, &ball))))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i);
+
+        for (i, (player, location)) in players.iter_mut().zip(at_touch).enumerate() {
+            player.role = location.map(|x| {
+                if Some(i) == taker {
+                    KickoffRole::Taker
+                } else if field::third(x.y, team as u8) == field::Third::Defensive {
+                    KickoffRole::Back
+                } else {
+                    KickoffRole::Cheat
+                }
+            });
+        }
+    }
+}
+
+/// Detects kickoffs as the ball being placed at the center spot and waiting there
+#[derive(Debug, Default)]
+struct KickoffDetector {
+    waiting: bool,
+
+    /// Number of kickoffs detected so far
+    kickoffs: usize,
+}
+
+impl KickoffDetector {
+    /// Returns true if a kickoff starts with the ball's state at the frame
+    fn update(&mut self, body: Option<&RigidBody>) -> bool {
+        // The ball is briefly absent while it is respawned, which shouldn't start a new kickoff
+        let at_spot = match body {
+            Some(body) => {
+                let speed = body.linear_velocity.map_or(0.0, |v| length(v.x, v.y));
+                length(body.location.x, body.location.y) < KICKOFF_SPOT_DISTANCE
+                    && speed < KICKOFF_MAX_BALL_SPEED
+            }
+            None => self.waiting,
+        };
+
+        let started = at_spot && !self.waiting;
+        if started {
+            self.kickoffs += 1;
+        }
+        self.waiting = at_spot;
+        started
+    }
+}
+
+/// How economically a player used their boost. Boost amounts are a percentage of a full tank.
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct BoostEfficiency {
+    /// Distance driven (uu) for every percent of boost consumed: `distance / boost used`.
+    /// `None` if the player never consumed boost.
+    pub uu_per_boost: Option<f32>,
+
+    /// Seconds spent with an empty tank
+    pub time_empty: f32,
+
+    /// Seconds spent with a full tank
+    pub time_full: f32,
+
+    /// Boost picked up that did not fit in the tank: `max(0, pad boost - (100 - boost before
+    /// the pickup))` summed over every pickup
+    pub wasted_collected: f32,
+}
+
+/// Computes the boost efficiency of every player, keyed by the player's unique id. `None` is
+/// returned for matches with unlimited boost, as boost is never consumed.
+///
+/// The distance driven is the sum of the distances between a car's consecutive positions. Boost
+/// used is the sum of decreases in a car's replicated boost amount and times are measured from
+/// the replicated amount as well, so both are only as precise as the amount's replication.
+/// Pad sizes are not replicated: a pad is considered large once a pickup from it has granted more
+/// boost than a small pad can.
+pub fn boost_efficiency(replay: &Replay) -> Option<HashMap<UniqueId, BoostEfficiency>> {
+    boost_efficiency_with(replay, &PhysicsConstants::for_replay(replay))
+}
+
+/// Computes the [boost efficiency](boost_efficiency) of every player with custom physics
+/// constants
+pub fn boost_efficiency_with(
+    replay: &Replay,
+    constants: &PhysicsConstants,
+) -> Option<HashMap<UniqueId, BoostEfficiency>> {
+    if replay.match_settings().unlimited_boost {
+        return None;
+    }
+
+    let mut processor = ReplayProcessor::new(replay);
+    let pickup_keys = [
+        processor.object_id("TAGame.VehiclePickup_TA:ReplicatedPickupData"),
+        processor.object_id("TAGame.VehiclePickup_TA:NewReplicatedPickupData"),
+    ];
+
+    let mut cars: HashMap<ActorId, CarBoost> = HashMap::new();
+    let mut players: HashMap<UniqueId, PlayerBoost> = HashMap::new();
+    let mut pickups: Vec<PadPickup> = Vec::new();
+
+    let frames = processor.frames();
+    for (i, frame) in frames.iter().enumerate() {
+        // Keyframes re-send the state of every pad, so only a changed state is a pickup
+        let mut picked: Vec<(ActorId, ObjectId)> = Vec::new();
+        for update in &frame.updated_actors {
+            if !pickup_keys.contains(&Some(update.object_id)) {
+                continue;
+            }
+
+            let instigator = match &update.attribute {
+                Attribute::Pickup(x) => x.instigator,
+                Attribute::PickupNew(x) => x.instigator,
+                _ => None,
+            };
+
+            let pad = processor.actor(update.actor_id);
+            let previous = pad.and_then(|x| x.attribute(update.object_id));
+            if let (Some(car), Some(pad)) = (instigator, pad) {
+                if previous != Some(&update.attribute) {
+                    picked.push((car, pad.object_id()));
+                }
+            }
+        }
+
+        for actor_id in &frame.deleted_actors {
+            cars.remove(actor_id);
+        }
+
+        processor.process_frame(frame);
+        let duration = frames.get(i + 1).map_or(0.0, |x| x.time - frame.time);
+
+        for (car, pad) in picked {
+            let player = processor
+                .car_player(car)
+                .and_then(|x| processor.player_unique_id(x));
+            let state = cars.get_mut(&car);
+            if let (Some(player), Some(state)) = (player, state) {
+                if let Some(before) = state.boost {
+                    state.pending.push(pickups.len());
+                    pickups.push(PadPickup {
+                        player: player.clone(),
+                        pad,
+                        time: frame.time,
+                        before,
+                        gain: None,
+                    });
+                }
+            }
+        }
+
+        for car in processor.cars() {
+            let player = processor
+                .car_player(*car)
+                .and_then(|x| processor.player_unique_id(x));
+            let player = match player {
+                Some(x) => players.entry(x.clone()).or_default(),
+                None => continue,
+            };
+
+            let state = cars.entry(*car).or_default();
+            if let Some(body) = processor.rigid_body(*car) {
+                if let Some(previous) = state.location {
+                    player.distance += distance(&previous, &body.location);
+                }
+                state.location = Some(body.location);
+            }
+
+            let boost = match processor.car_boost(*car) {
+                Some(x) => f32::from(x) * 100.0 / MAX_BOOST,
+                None => continue,
+            };
+
+            if let Some(previous) = state.boost.filter(|x| *x != boost) {
+                player.boost_used += (previous - boost).max(0.0);
+                for index in state.pending.drain(..) {
+                    let pickup = &mut pickups[index];
+                    if frame.time - pickup.time <= PICKUP_GAIN_SECONDS {
+                        pickup.gain = Some(boost - pickup.before);
+                    }
+                }
+            }
+            state.boost = Some(boost);
+
+            if boost <= 0.0 {
+                player.time_empty += duration;
+            } else if boost >= 100.0 {
+                player.time_full += duration;
+            }
+        }
+    }
+
+    let large_pads: HashSet<ObjectId> = pickups
+        .iter()
+        .filter(|x| matches!(x.gain, Some(gain) if gain > LARGE_PAD_MIN_GAIN))
+        .map(|x| x.pad)
+        .collect();
+
+    for pickup in &pickups {
+        let amount = if large_pads.contains(&pickup.pad) {
+            100.0
+        } else {
+            constants.small_pad_boost
+        };
+
+        if let Some(player) = players.get_mut(&pickup.player) {
+            player.wasted_collected += (amount - (100.0 - pickup.before)).max(0.0);
+        }
+    }
+
+    let result = players
+        .into_iter()
+        .map(|(id, x)| {
+            let efficiency = BoostEfficiency {
+                uu_per_boost: Some(x.distance / x.boost_used).filter(|_| x.boost_used > 0.0),
+                time_empty: x.time_empty,
+                time_full: x.time_full,
+                wasted_collected: x.wasted_collected,
+            };
+            (id, efficiency)
+        })
+        .collect();
+
+    Some(result)
+}
+
+/// Derives a smooth boost amount for every frame from the sparse replicated amounts of a car.
+/// The game replicates a car's boost amount (0 - 255) only now and then while it is being
+/// consumed, so the replicated amount drops in steps. Between replications, the interpolated
+/// amount drains at the rate of [`PhysicsConstants::boost_used_per_second`] while the car's boost
+/// is active, and holds otherwise.
+///
+/// Whenever a new amount is replicated, the interpolated amount snaps to it, so a pad pickup is an
+/// instantaneous jump rather than a ramp. An interpolator tracks a single car: it should be
+/// [reset](BoostInterpolator::reset) when the car is replaced, like after a demolition.
+///
+/// ```rust
+/// use boxcars::stats::BoostInterpolator;
+///
+/// let mut boost = BoostInterpolator::new(100.0);
+/// assert_eq!(boost.update(85, false, 0.1), 85.0);
+/// assert_eq!(boost.update(85, true, 0.1), 75.0);
+/// assert_eq!(boost.update(85, true, 0.1), 65.0);
+///
+/// // A pickup replicates a larger amount
+/// assert_eq!(boost.update(255, false, 0.1), 255.0);
+/// ```
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct BoostInterpolator {
+    boost_used_per_second: f32,
+    replicated: Option<u8>,
+    amount: f32,
+}
+
+impl BoostInterpolator {
+    /// An interpolator that drains the boost amount at the given rate (as replicated: 255 is a full
+    /// tank) per second of boosting
+    pub fn new(boost_used_per_second: f32) -> Self {
+        BoostInterpolator {
+            boost_used_per_second,
+            replicated: None,
+            amount: 0.0,
+        }
+    }
+
+    /// An interpolator with the boost consumption of the patch that recorded the replay. Boost is
+    /// not consumed in matches with unlimited boost.
+    pub fn for_replay(replay: &Replay) -> Self {
+        if replay.match_settings().unlimited_boost {
+            BoostInterpolator::new(0.0)
+        } else {
+            BoostInterpolator::new(PhysicsConstants::for_replay(replay).boost_used_per_second)
+        }
+    }
+
+    /// Advances to the next frame given the car's latest replicated boost amount, whether its
+    /// boost is active, and the time since the previous frame. Returns the interpolated amount
+    /// (0 - 255).
+    pub fn update(&mut self, replicated: u8, active: bool, delta: f32) -> f32 {
+        if self.replicated != Some(replicated) {
+            self.replicated = Some(replicated);
+            self.amount = f32::from(replicated);
+        } else if active {
+            self.amount = (self.amount - delta * self.boost_used_per_second).max(0.0);
+        }
+
+        self.amount
+    }
+
+    /// Forgets the car being tracked, so that the next amount is taken as is
+    pub fn reset(&mut self) {
+        self.replicated = None;
+        self.amount = 0.0;
+    }
+}
+
+/// A period during which a boost pad could not be picked up
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct PadCooldown {
+    /// Index of the frame where the pad was picked up
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// Name of the player that picked up the pad
+    pub player: Option<String>,
+
+    /// Unique id of the player that picked up the pad
+    pub player_id: Option<UniqueId>,
+
+    /// Index of the frame where the pad respawned. `None` if the replay ended first.
+    pub respawn_frame: Option<usize>,
+
+    /// Replay time of the respawn
+    pub respawn_time: Option<f32>,
+}
+
+/// When a boost pad was available throughout a match
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct BoostPadTimeline {
+    /// The object name of the pad, eg: "stadium_p.TheWorld:PersistentLevel.VehiclePickup_Boost_TA_30"
+    pub name: String,
+
+    /// The index of the pad in [`field::BOOST_PADS`]. `None` if the pad was never picked up or
+    /// was picked up away from any standard pad, as happens in non-standard arenas.
+    pub pad: Option<usize>,
+
+    /// The average location of the cars when they picked up the pad
+    pub location: Option<Vector3f>,
+
+    /// Whether the pad is a large pad, from how long it took to respawn or else from the standard
+    /// pad. `None` if neither is known.
+    pub large: Option<bool>,
+
+    /// Every time the pad was picked up, in order
+    pub cooldowns: Vec<PadCooldown>,
+}
+
+impl BoostPadTimeline {
+    /// Returns true if the pad could be picked up at the given replay time
+    pub fn is_available(&self, time: f32) -> bool {
+        !self
+            .cooldowns
+            .iter()
+            .any(|x| x.time <= time && !matches!(x.respawn_time, Some(respawn) if respawn <= time))
+    }
+}
+
+/// Reconstructs when every boost pad was picked up and when it respawned. Pads are listed in the
+/// order that they were first replicated and include pads that were never picked up, which are
+/// available for the whole match.
+///
+/// The network data identifies pads by level object names that differ between arenas, so the
+/// physical pad is found from where the cars were when they picked it up. Keyframes re-send the
+/// state of every pad, so only a change in state is a pickup or a respawn. A respawn is only seen
+/// once it is replicated, so a pad may appear unavailable for longer than its respawn time (eg:
+/// during a goal replay), and every pad respawns early at a kickoff. Pickups are read from both the
+/// legacy `ReplicatedPickupData` and the `NewReplicatedPickupData` of recent replays.
+pub fn boost_pads(replay: &Replay) -> Vec<BoostPadTimeline> {
+    let mut processor = ReplayProcessor::new(replay);
+    let pickup_keys = [
+        processor.object_id("TAGame.VehiclePickup_TA:ReplicatedPickupData"),
+        processor.object_id("TAGame.VehiclePickup_TA:NewReplicatedPickupData"),
+    ];
+
+    let mut pads: Vec<BoostPadTimeline> = Vec::new();
+    let mut indices: HashMap<ObjectId, usize> = HashMap::new();
+    let mut actors: HashMap<ActorId, usize> = HashMap::new();
+    let mut locations: Vec<Vec<Vector3f>> = Vec::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            actors.remove(actor_id);
+        }
+
+        for new_actor in &frame.new_actors {
+            let name = match replay.objects.get(usize::from(new_actor.object_id)) {
+                Some(x) if normalize_object(x) == BOOST_PAD_OBJECT => x,
+                _ => continue,
+            };
+
+            let index = *indices.entry(new_actor.object_id).or_insert_with(|| {
+                pads.push(BoostPadTimeline {
+                    name: name.clone(),
+                    pad: None,
+                    location: None,
+                    large: None,
+                    cooldowns: Vec::new(),
+                });
+                locations.push(Vec::new());
+                pads.len() - 1
+            });
+            actors.insert(new_actor.actor_id, index);
+        }
+
+        let mut picked: Vec<(usize, ActorId)> = Vec::new();
+        for update in &frame.updated_actors {
+            if !pickup_keys.contains(&Some(update.object_id)) {
+                continue;
+            }
+
+            let instigator = match &update.attribute {
+                Attribute::Pickup(x) => x.instigator,
+                Attribute::PickupNew(x) => x.instigator,
+                _ => continue,
+            };
+
+            // A pad may be created in the same frame as its first pickup, so it is looked up
+            // before the frame is processed
+            let previous = processor
+                .actor(update.actor_id)
+                .and_then(|x| x.attribute(update.object_id));
+            let index = match actors.get(&update.actor_id) {
+                Some(x) if previous != Some(&update.attribute) => *x,
+                _ => continue,
+            };
+
+            // A pad is picked up while the pickup names the car that took it
+            let cooldowns = &mut pads[index].cooldowns;
+            if let Some(last) = cooldowns.last_mut().filter(|x| x.respawn_frame.is_none()) {
+                last.respawn_frame = Some(i);
+                last.respawn_time = Some(frame.time);
+            }
+
+            if let Some(car) = instigator {
+                picked.push((index, car));
+            }
+        }
+
+        processor.process_frame(frame);
+
+        for (index, car) in picked {
+            let player = processor.car_player(car);
+            pads[index].cooldowns.push(PadCooldown {
+                frame: i,
+                time: frame.time,
+                player: player
+                    .and_then(|x| processor.player_name(x))
+                    .map(String::from),
+                player_id: player.and_then(|x| processor.player_unique_id(x)).cloned(),
+                respawn_frame: None,
+                respawn_time: None,
+            });
+
+            if let Some(body) = processor.rigid_body(car) {
+                locations[index].push(body.location);
+            }
+        }
+    }
+
+    for (pad, locations) in pads.iter_mut().zip(locations.iter()) {
+        if !locations.is_empty() {
+            let n = locations.len() as f32;
+            let location = Vector3f {
+                x: locations.iter().map(|a| a.x).sum::<f32>() / n,
+                y: locations.iter().map(|a| a.y).sum::<f32>() / n,
+                z: locations.iter().map(|a| a.z).sum::<f32>() / n,
+            };
+            pad.pad = field::boost_pad_at(&location);
+            pad.location = Some(location);
+        }
+
+        // Kickoffs respawn every pad early and a respawn may be replicated late, so the typical
+        // respawn time is what tells the pad sizes apart
+        let mut respawns: Vec<f32> = pad
+            .cooldowns
+            .iter()
+            .filter_map(|x| Some(x.respawn_time? - x.time))
+            .collect();
+        respawns.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        pad.large = match respawns.get(respawns.len() / 2) {
+            Some(x) => Some(*x > LARGE_PAD_MIN_RESPAWN),
+            None => pad.pad.map(|x| field::BOOST_PADS[x].large),
+        };
+    }
+
+    pads
+}
+
+/// How often a player went up for aerials and how they touched the ball
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct AerialStats {
+    /// Touches made while the player's car was in the air
+    pub aerial_touches: u32,
+
+    /// Touches made while the player's car was on the ground or a wall
+    pub ground_touches: u32,
+
+    /// Number of times the player's car left the ground for an aerial
+    pub aerials_started: u32,
+}
+
+/// Counts the aerials and splits the [touches](events::touches) of every player by whether their
+/// car was in the air, keyed by the player's unique id. Players that never touched the ball are
+/// included with zero touches.
+///
+/// A car is in the air once it is higher than a single jump can reach while away from the walls,
+/// so an aerial starts when a car rises through that height and wall driving is not counted.
+pub fn aerial_count(replay: &Replay) -> HashMap<UniqueId, AerialStats> {
+    let mut touched: HashMap<usize, Vec<UniqueId>> = HashMap::new();
+    for touch in events::touches(replay) {
+        if let Some(player) = touch.player_id {
+            touched.entry(touch.frame).or_default().push(player);
+        }
+    }
+
+    let mut processor = ReplayProcessor::new(replay);
+    let mut airborne: HashMap<ActorId, bool> = HashMap::new();
+    let mut result: HashMap<UniqueId, AerialStats> = HashMap::new();
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            airborne.remove(actor_id);
+        }
+
+        processor.process_frame(frame);
+        let touchers = touched.get(&i);
+        for car in processor.cars() {
+            let player = processor
+                .car_player(*car)
+                .and_then(|x| processor.player_unique_id(x));
+            let stats = match player {
+                Some(x) => result.entry(x.clone()).or_default(),
+                None => continue,
+            };
+
+            let was_airborne = airborne.get(car).copied().unwrap_or(false);
+            let is_airborne = match processor.rigid_body(*car) {
+                Some(body) => {
+                    body.location.z > AERIAL_MIN_HEIGHT
+                        && field::wall_distance(&body.location) > AERIAL_WALL_MARGIN
+                }
+                None => was_airborne,
+            };
+
+            airborne.insert(*car, is_airborne);
+            if is_airborne && !was_airborne {
+                stats.aerials_started += 1;
+            }
+
+            let touches = touchers
+                .iter()
+                .flat_map(|x| x.iter())
+                .filter(|x| Some(*x) == player)
+                .count() as u32;
+            if is_airborne {
+                stats.aerial_touches += touches;
+            } else {
+                stats.ground_touches += touches;
+            }
+        }
+    }
+
+    result
+}
+
+/// The unique id of the player that last touched the ball as of every frame, per
+/// [`events::touches`]. The last toucher is carried forward until the next touch, even across
+/// goals and kickoffs. Frames before the first touch, and frames after a touch whose player could
+/// not be identified, are `None`. Returns an empty list if the network data was not parsed.
+pub fn last_touch_timeline(replay: &Replay) -> Vec<Option<UniqueId>> {
+    let frames = replay.frames_or_empty();
+    let mut touches = events::touches(replay).into_iter().peekable();
+    let mut last = None;
+    let mut result = Vec::with_capacity(frames.len());
+    for i in 0..frames.len() {
+        while let Some(touch) = touches.next_if(|x| x.frame <= i) {
+            last = touch.player_id;
+        }
+
+        result.push(last.clone());
+    }
+
+    result
+}
+
+/// The unique id of the player whose car is closest to the ball at every frame, measured in 3D
+/// between the reconstructed positions of the car and the ball. Frames without a ball, or without
+/// a car whose player and position are known, are `None`. When cars are exactly as close, the
+/// one with the lowest actor id is taken, so the result is deterministic. Returns an empty list if
+/// the network data was not parsed.
+pub fn closest_to_ball_timeline(replay: &Replay) -> Vec<Option<UniqueId>> {
+    let mut processor = ReplayProcessor::new(replay);
+    let frames = processor.frames();
+    let mut result = Vec::with_capacity(frames.len());
+    for frame in frames {
+        processor.process_frame(frame);
+        let ball = match processor.ball_rigid_body() {
+            Some(x) => x.location,
+            None => {
+                result.push(None);
+                continue;
+            }
+        };
+
+        let mut closest: Option<(f32, ActorId, &UniqueId)> = None;
+        for car in processor.cars() {
+            let (body, id) = match (
+                processor.rigid_body(*car),
+                processor
+                    .car_player(*car)
+                    .and_then(|x| processor.player_unique_id(x)),
+            ) {
+                (Some(body), Some(id)) => (body, id),
+                _ => continue,
+            };
+
+            let car_distance = distance(&body.location, &ball);
+            let closer = match closest {
+                Some((x, actor, _)) => car_distance < x || (car_distance == x && *car < actor),
+                None => true,
+            };
+
+            if closer {
+                closest = Some((car_distance, *car, id));
+            }
+        }
+
+        result.push(closest.map(|(_, _, id)| id.clone()));
+    }
+
+    result
+}
+
+/// The share of a match's live time that each team and player was the last to touch the ball
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct Possession {
+    /// Seconds that the game's state was active
+    pub live_seconds: f32,
+
+    /// Percentage of live time that blue last touched the ball
+    pub blue: f32,
+
+    /// Percentage of live time that orange last touched the ball
+    pub orange: f32,
+
+    /// Percentage of live time that no team had possession: from each kickoff until the ball is
+    /// first touched, and after touches by players of an unknown team
+    pub neutral: f32,
+
+    /// Percentage of live time that each player last touched the ball paired with the player's
+    /// unique id, in the order that the players first had possession
+    pub players: Vec<(UniqueId, f32)>,
+}
+
+/// Computes each team's and player's possession of the ball as the percentage of live time that
+/// they were the last to touch it, per [`events::touches`], weighted by the frames' deltas. Only
+/// frames where the game's state is active count, which excludes countdowns and the time after
+/// goals, and possession is reset while play is stopped so that each kickoff starts neutral. The
+/// team and neutral percentages sum to 100, unless the game's state is never replicated, in which
+/// case the live time is zero and every percentage is zero.
+pub fn ball_possession_percentage(replay: &Replay) -> Possession {
+    let processor = ReplayProcessor::new(replay);
+    let state_key = processor.object_id(GAME_STATE_KEY);
+    let active = replay.names.iter().position(|x| x == ACTIVE_STATE);
+    let mut touches = events::touches(replay).into_iter().peekable();
+
+    let mut state = None;
+    let mut last: Option<Touch> = None;
+    let mut teams = [0.0f32; 2];
+    let mut neutral = 0.0;
+    let mut players: Vec<(UniqueId, f32)> = Vec::new();
+    for (i, frame) in processor.frames().iter().enumerate() {
+        for update in &frame.updated_actors {
+            if let (true, Attribute::Int(x)) =
+                (Some(update.object_id) == state_key, &update.attribute)
+            {
+                state = usize::try_from(*x).ok();
+            }
+        }
+
+        while let Some(touch) = touches.next_if(|x| x.frame <= i) {
+            last = Some(touch);
+        }
+
+        if state.is_none() || state != active {
+            last = None;
+            continue;
+        }
+
+        match last.as_ref().and_then(|x| x.team) {
+            Some(team) => teams[usize::from(team.min(1))] += frame.delta,
+            None => neutral += frame.delta,
+        }
+
+        if let Some(player) = last.as_ref().and_then(|x| x.player_id.as_ref()) {
+            match players.iter_mut().find(|(id, _)| id == player) {
+                Some((_, seconds)) => *seconds += frame.delta,
+                None => players.push((player.clone(), frame.delta)),
+            }
+        }
+    }
+
+    let live_seconds = teams[0] + teams[1] + neutral;
+    let percent = |x: f32| {
+        if live_seconds > 0.0 {
+            x / live_seconds * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    Possession {
+        live_seconds,
+        blue: percent(teams[0]),
+        orange: percent(teams[1]),
+        neutral: percent(neutral),
+        players: players
+            .into_iter()
+            .map(|(player, seconds)| (player, percent(seconds)))
+            .collect(),
+    }
+}
+
+/// Whether the ball threatened a goal during a frame
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct ThreatFrame {
+    /// Index of the frame
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+
+    /// The team whose goal was threatened: 0 for blue and 1 for orange. `None` when the ball was
+    /// away from both goals, moving away from them, already in a goal, or absent.
+    pub threatened_team: Option<u8>,
+}
+
+/// Classifies every frame by whether the ball was within [`THREAT_DISTANCE`] of a goal and moving
+/// toward it. See [`threat_timeline_within`] to use a different distance.
+pub fn threat_timeline(replay: &Replay) -> Vec<ThreatFrame> {
+    threat_timeline_within(replay, THREAT_DISTANCE)
+}
+
+/// Classifies every frame by whether the ball was within the distance (uu) of the center of a
+/// goal line and moving toward it, per [`field::threatened_team`]. Consecutive frames that
+/// threaten the same team form a period of pressure on that team.
+pub fn threat_timeline_within(replay: &Replay, distance: f32) -> Vec<ThreatFrame> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut result = Vec::with_capacity(processor.frames().len());
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+        let threatened_team = processor.ball_rigid_body().and_then(|body| {
+            let velocity = body.linear_velocity?;
+            field::threatened_team(&body.location, &velocity, distance)
+        });
+
+        result.push(ThreatFrame {
+            frame: i,
+            time: frame.time,
+            threatened_team,
+        });
+    }
+
+    result
+}
+
+/// The analytics of one or more matches for every player, paired with the player's unique id.
+/// Summaries of individual matches are combined with [`MatchSummary::merge`] into season long
+/// aggregates.
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct MatchSummary {
+    pub players: Vec<(UniqueId, PlayerSummary)>,
+}
+
+/// A player's analytics summed, or for rates averaged, across the matches they played
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct PlayerSummary {
+    /// Number of matches the player appeared in
+    pub matches: u32,
+
+    /// Touches and aerials summed across matches. See [`aerial_count`].
+    pub aerials: AerialStats,
+
+    /// Average of the [`BoostEfficiency::uu_per_boost`] of the matches where it is known
+    pub uu_per_boost: Option<f32>,
+
+    /// Number of matches that contribute to `uu_per_boost`, which weighs the average when merging
+    pub uu_per_boost_matches: u32,
+
+    /// Seconds spent with an empty tank summed across matches
+    pub time_empty: f32,
+
+    /// Seconds spent with a full tank summed across matches
+    pub time_full: f32,
+
+    /// Boost collected that did not fit in the tank summed across matches
+    pub wasted_collected: f32,
+}
+
+impl MatchSummary {
+    /// Adds the other summary into this one. Counts are summed and rates are averaged for players
+    /// in both summaries, while players in only one summary are kept as is.
+    pub fn merge(&mut self, other: &MatchSummary) {
+        for (id, player) in &other.players {
+            self.player_mut(id).merge(player);
+        }
+    }
+
+    /// The summary of the player with the unique id
+    pub fn player(&self, id: &UniqueId) -> Option<&PlayerSummary> {
+        self.players
+            .iter()
+            .find(|(x, _)| x == id)
+            .map(|(_, player)| player)
+    }
+
+    fn player_mut(&mut self, id: &UniqueId) -> &mut PlayerSummary {
+        let index = match self.players.iter().position(|(x, _)| x == id) {
+            Some(x) => x,
+            None => {
+                self.players.push((id.clone(), PlayerSummary::default()));
+                self.players.len() - 1
+            }
+        };
+
+        &mut self.players[index].1
+    }
+}
+
+impl PlayerSummary {
+    fn merge(&mut self, other: &PlayerSummary) {
+        self.matches += other.matches;
+        self.aerials.aerial_touches += other.aerials.aerial_touches;
+        self.aerials.ground_touches += other.aerials.ground_touches;
+        self.aerials.aerials_started += other.aerials.aerials_started;
+        self.time_empty += other.time_empty;
+        self.time_full += other.time_full;
+        self.wasted_collected += other.wasted_collected;
+
+        let matches = self.uu_per_boost_matches + other.uu_per_boost_matches;
+        self.uu_per_boost = match (self.uu_per_boost, other.uu_per_boost) {
+            (Some(a), Some(b)) => Some(
+                (a * self.uu_per_boost_matches as f32 + b * other.uu_per_boost_matches as f32)
+                    / matches as f32,
+            ),
+            (a, b) => a.or(b),
+        };
+        self.uu_per_boost_matches = matches;
+    }
+}
+
+/// Summarizes the [aerials](aerial_count) and [boost efficiency](boost_efficiency) of every
+/// player in the match. Boost fields are left empty for matches with unlimited boost.
+pub fn match_summary(replay: &Replay) -> MatchSummary {
+    let mut summary = MatchSummary::default();
+    for (id, aerials) in aerial_count(replay) {
+        let player = summary.player_mut(&id);
+        player.aerials = aerials;
+    }
+
+    for (id, boost) in boost_efficiency(replay).unwrap_or_default() {
+        let player = summary.player_mut(&id);
+        player.uu_per_boost = boost.uu_per_boost;
+        player.uu_per_boost_matches = u32::from(boost.uu_per_boost.is_some());
+        player.time_empty = boost.time_empty;
+        player.time_full = boost.time_full;
+        player.wasted_collected = boost.wasted_collected;
+    }
+
+    for (_, player) in &mut summary.players {
+        player.matches = 1;
+    }
+
+    summary
+}
+
+/// The fraction of a player's time spent in each third of the field. The fractions sum to one for
+/// players that were on the field.
+#[derive(Serialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct ThirdsBreakdown {
+    pub defensive: f32,
+    pub neutral: f32,
+    pub offensive: f32,
+}
+
+/// Computes how each player's time on the field splits between the thirds of the field relative to
+/// their team, keyed by the player's unique id. Every frame is weighted by its delta. Time without
+/// a car, like after a demolition or before joining, is excluded, so the fractions are relative to
+/// the time the player was present.
+pub fn time_in_thirds(replay: &Replay) -> HashMap<UniqueId, ThirdsBreakdown> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut totals: HashMap<UniqueId, ThirdsBreakdown> = HashMap::new();
+
+    for frame in processor.frames() {
+        processor.process_frame(frame);
+        for car in processor.cars() {
+            let player = match processor.car_player(*car) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let (id, team, body) = match (
+                processor.player_unique_id(player),
+                processor.player_team(player),
+                processor.rigid_body(*car),
+            ) {
+                (Some(id), Some(team), Some(body)) => (id, team, body),
+                _ => continue,
+            };
+
+            let total = totals.entry(id.clone()).or_default();
+            match field::third(body.location.y, team) {
+                field::Third::Defensive => total.defensive += frame.delta,
+                field::Third::Neutral => total.neutral += frame.delta,
+                field::Third::Offensive => total.offensive += frame.delta,
+            }
+        }
+    }
+
+    for total in totals.values_mut() {
+        let present = total.defensive + total.neutral + total.offensive;
+        if present > 0.0 {
+            total.defensive /= present;
+            total.neutral /= present;
+            total.offensive /= present;
+        }
+    }
+
+    totals
+}
+
+/// How long a player was supersonic
+#[derive(Serialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct SupersonicStats {
+    /// Seconds spent at supersonic speed
+    pub time: f32,
+
+    /// Seconds of the supersonic time spent boosting, which keeps a car supersonic without
+    /// gaining any speed
+    pub boosting: f32,
+}
+
+/// Computes how long each player was supersonic and how much of that time they were boosting,
+/// keyed by the player's unique id. Every frame is weighted by its delta. Players are included
+/// once they have a car, so players that never went supersonic have zero times.
+///
+/// A car is supersonic while the speed of its replicated velocity is at least
+/// [`PhysicsConstants::supersonic_speed`], and boosting while its boost component is active.
+pub fn supersonic_time(replay: &Replay) -> HashMap<UniqueId, SupersonicStats> {
+    supersonic_time_with(replay, &PhysicsConstants::for_replay(replay))
+}
+
+/// Computes the [supersonic time](supersonic_time) of every player with custom physics constants
+pub fn supersonic_time_with(
+    replay: &Replay,
+    constants: &PhysicsConstants,
+) -> HashMap<UniqueId, SupersonicStats> {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut totals: HashMap<UniqueId, SupersonicStats> = HashMap::new();
+
+    for frame in processor.frames() {
+        processor.process_frame(frame);
+        for car in processor.cars() {
+            let id = match processor
+                .car_player(*car)
+                .and_then(|x| processor.player_unique_id(x))
+            {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let total = totals.entry(id.clone()).or_default();
+            let speed = processor
+                .rigid_body(*car)
+                .and_then(|x| x.linear_velocity)
+                .map_or(0.0, |v| (v.x * v.x + v.y * v.y + v.z * v.z).sqrt());
+            if speed < constants.supersonic_speed {
+                continue;
+            }
+
+            total.time += frame.delta;
+            if processor.car_boosting(*car).unwrap_or(false) {
+                total.boosting += frame.delta;
+            }
+        }
+    }
+
+    totals
+}
+
+/// Where a team's players were during a frame
+#[derive(Serialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct TeamShape {
+    /// Number of the team's players with a car on the field
+    pub players: usize,
+
+    /// Average location of the team's cars. `None` when no player has a car on the field.
+    pub center_of_mass: Option<Vector3f>,
+
+    /// Mean distance (uu) between every pair of the team's cars. `None` when fewer than two
+    /// players have a car on the field.
+    pub spacing: Option<f32>,
+}
+
+/// The shape of both teams during a frame
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct FormationFrame {
+    /// Index of the frame
+    pub frame: usize,
+
+    /// Replay time of the frame
+    pub time: f32,
+    pub blue: TeamShape,
+    pub orange: TeamShape,
+}
+
+/// A team's shape averaged over the match, with every frame weighted by its delta
+#[derive(Serialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct FormationSummary {
+    /// Average center of mass over the frames where the team had a car on the field
+    pub center_of_mass: Option<Vector3f>,
+
+    /// Average spacing over the frames where the team had at least two cars on the field
+    pub spacing: Option<f32>,
+}
+
+/// Each team's center of mass and spacing throughout a match
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Formation {
+    /// An entry for every network frame
+    pub frames: Vec<FormationFrame>,
+    pub blue: FormationSummary,
+    pub orange: FormationSummary,
+}
+
+/// Computes every team's center of mass and spacing (the mean distance between teammates) for
+/// every frame from the locations of the cars on the field. A compact team that follows the ball
+/// is ball chasing, while a spread out team whose center trails the ball is rotating. Players
+/// without a car, like after a demolition, don't count toward their team's shape for the frame.
+pub fn center_of_mass(replay: &Replay) -> Formation {
+    let mut processor = ReplayProcessor::new(replay);
+    let mut frames = Vec::with_capacity(processor.frames().len());
+    let mut totals = [FormationTotal::default(), FormationTotal::default()];
+
+    for (i, frame) in processor.frames().iter().enumerate() {
+        processor.process_frame(frame);
+        let mut locations: [Vec<Vector3f>; 2] = [Vec::new(), Vec::new()];
+        for car in processor.cars() {
+            let team = processor
+                .car_player(*car)
+                .and_then(|x| processor.player_team(x));
+            if let (Some(team), Some(body)) = (team, processor.rigid_body(*car)) {
+                if let Some(x) = locations.get_mut(usize::from(team)) {
+                    x.push(body.location);
+                }
+            }
+        }
+
+        let [blue, orange] = locations.map(|x| team_shape(&x));
+        for (total, shape) in totals.iter_mut().zip([blue, orange].iter()) {
+            total.add(shape, frame.delta);
+        }
+
+        frames.push(FormationFrame {
+            frame: i,
+            time: frame.time,
+            blue,
+            orange,
+        });
+    }
+
+    let [blue, orange] = totals.map(|x| x.summary());
+    Formation {
+        frames,
+        blue,
+        orange,
+    }
+}
+
+fn team_shape(locations: &[Vector3f]) -> TeamShape {
+    let players = locations.len();
+    let center_of_mass = if players > 0 {
+        let n = players as f32;
+        Some(Vector3f {
+            x: locations.iter().map(|a| a.x).sum::<f32>() / n,
+            y: locations.iter().map(|a| a.y).sum::<f32>() / n,
+            z: locations.iter().map(|a| a.z).sum::<f32>() / n,
+        })
+    } else {
+        None
+    };
+
+    let mut pairs = 0;
+    let mut total = 0.0;
+    for (i, a) in locations.iter().enumerate() {
+        for b in &locations[i + 1..] {
+            pairs += 1;
+            total += distance(a, b);
+        }
+    }
+
+    TeamShape {
+        players,
+        center_of_mass,
+        spacing: if pairs > 0 {
+            Some(total / pairs as f32)
+        } else {
+            None
+        },
+    }
+}
+
+/// Delta weighted sums of a team's shape
+#[derive(Debug, Default)]
+struct FormationTotal {
+    center: [f32; 3],
+    center_time: f32,
+    spacing: f32,
+    spacing_time: f32,
+}
+
+impl FormationTotal {
+    fn add(&mut self, shape: &TeamShape, delta: f32) {
+        if let Some(center) = shape.center_of_mass {
+            self.center[0] += center.x * delta;
+            self.center[1] += center.y * delta;
+            self.center[2] += center.z * delta;
+            self.center_time += delta;
+        }
+
+        if let Some(spacing) = shape.spacing {
+            self.spacing += spacing * delta;
+            self.spacing_time += delta;
+        }
+    }
+
+    fn summary(&self) -> FormationSummary {
+        FormationSummary {
+            center_of_mass: Some(self.center_time)
+                .filter(|x| *x > 0.0)
+                .map(|x| Vector3f {
+                    x: self.center[0] / x,
+                    y: self.center[1] / x,
+                    z: self.center[2] / x,
+                }),
+            spacing: Some(self.spacing_time)
+                .filter(|x| *x > 0.0)
+                .map(|x| self.spacing / x),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CarBoost {
+    location: Option<Vector3f>,
+    boost: Option<f32>,
+
+    /// Indices of the pickups awaiting the replicated amount that includes them
+    pending: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+struct PlayerBoost {
+    distance: f32,
+    boost_used: f32,
+    time_empty: f32,
+    time_full: f32,
+    wasted_collected: f32,
+}
+
+#[derive(Debug)]
+struct PadPickup {
+    player: UniqueId,
+    pad: ObjectId,
+    time: f32,
+    before: f32,
+    gain: Option<f32>,
+}
+
+/// Two opposing players contesting the ball
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct Duel {
+    /// Index of the first frame of the duel
+    pub start_frame: usize,
+
+    /// Index of the last frame of the duel
+    pub end_frame: usize,
+
+    /// Unique ids of the blue and orange player, in that order
+    pub players: (UniqueId, UniqueId),
+
+    /// The player that came away with the ball. `None` when neither duelist touched the ball,
+    /// or someone else touched it first.
+    pub winner: Option<UniqueId>,
+}
+
+/// Detects the duels of a match: the periods where a blue and an orange player contest the ball
+/// closely. During every frame of a duel:
+///
+/// - both players have their car within [`DUEL_RADIUS`] of the ball
+/// - the ball is between the two cars, so the players are challenging each other rather than
+///   one following the other
+///
+/// In a scramble where more than two players qualify, only the closest pair (with the least sum
+/// of distances to the ball) is dueling. A duel ends when its pair no longer qualifies or is no
+/// longer the closest pair, and it must last at least [`DUEL_MIN_SECONDS`].
+///
+/// The winner is the duelist of the first [touch](events::touches) from the last frame of the duel
+/// up to [`DUEL_WIN_SECONDS`] after it, or if there is none, of the last touch during the duel.
+pub fn duel_pressure(replay: &Replay) -> Vec<Duel> {
+    let mut processor = ReplayProcessor::new(replay);
+    let frames = processor.frames();
+    let mut duels: Vec<Duel> = Vec::new();
+    let mut current: Option<Duel> = None;
+
+    for (i, frame) in frames.iter().enumerate() {
+        processor.process_frame(frame);
+        let pair = processor
+            .ball_rigid_body()
+            .and_then(|ball| closest_duel_pair(&processor, &ball.location));
+
+        match (current.as_mut(), pair) {
+            (Some(duel), Some(pair)) if duel.players == pair => duel.end_frame = i,
+            (_, pair) => {
+                duels.extend(current.take());
+                current = pair.map(|players| Duel {
+                    start_frame: i,
+                    end_frame: i,
+                    players,
+                    winner: None,
+                });
+            }
+        }
+    }
+
+    duels.extend(current);
+    duels.retain(|x| frames[x.end_frame].time - frames[x.start_frame].time >= DUEL_MIN_SECONDS);
+
+    let touches = events::touches(replay);
+    for duel in &mut duels {
+        let deadline = frames[duel.end_frame].time + DUEL_WIN_SECONDS;
+        let after = touches
+            .iter()
+            .skip_while(|x| x.frame < duel.end_frame)
+            .take_while(|x| x.time <= deadline)
+            .next();
+        let during = touches
+            .iter()
+            .rev()
+            .find(|x| x.frame >= duel.start_frame && x.frame < duel.end_frame);
+
+        let (blue, orange) = &duel.players;
+        duel.winner = after
+            .or(during)
+            .and_then(|x| x.player_id.as_ref())
+            .filter(|x| *x == blue || *x == orange)
+            .cloned();
+    }
+
+    duels
+}
+
+/// A period where a team had nobody back on defense while the ball was coming at its goal
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct FirstManBackViolation {
+    /// The exposed team: 0 for blue and 1 for orange
+    pub team: u8,
+
+    /// Index of the first frame of the violation
+    pub start_frame: usize,
+
+    /// Index of the last frame of the violation
+    pub end_frame: usize,
+}
+
+/// Detects the periods where a team was caught with no one back, ordered by their first frame.
+/// During every frame of a violation:
+///
+/// - the ball is in the team's half and moving toward its goal, per [`field::threatened_team`]
+/// - every one of the team's cars on the field is more than [`FIRST_MAN_BACK_MARGIN`] past the
+///   ball, further from the team's goal than the ball is
+///
+/// Only the players with a car on the field are judged, so a short handed team, as after a
+/// demolition or a player leaving, is exposed when its remaining players are all forward. A team
+/// without any car on the field is not. A violation must last at least
+/// [`FIRST_MAN_BACK_MIN_SECONDS`].
+pub fn first_man_back_violations(replay: &Replay) -> Vec<FirstManBackViolation> {
+    let mut processor = ReplayProcessor::new(replay);
+    let frames = processor.frames();
+    let mut violations: Vec<FirstManBackViolation> = Vec::new();
+    let mut current: [Option<FirstManBackViolation>; 2] = [None, None];
+
+    for (i, frame) in frames.iter().enumerate() {
+        processor.process_frame(frame);
+        let exposed = processor.ball_rigid_body().and_then(|ball| {
+            let velocity = ball.linear_velocity?;
+            let team = field::threatened_team(&ball.location, &velocity, f32::INFINITY)?;
+            Some(team).filter(|x| nobody_back(&processor, *x, &ball.location))
+        });
+
+        for (team, violation) in (0u8..).zip(current.iter_mut()) {
+            if exposed == Some(team) {
+                violation
+                    .get_or_insert(FirstManBackViolation {
+                        team,
+                        start_frame: i,
+                        end_frame: i,
+                    })
+                    .end_frame = i;
+            } else {
+                violations.extend(violation.take());
+            }
+        }
+    }
+
+    violations.extend(current.iter_mut().filter_map(|x| x.take()));
+    violations.retain(|x| {
+        frames[x.end_frame].time - frames[x.start_frame].time >= FIRST_MAN_BACK_MIN_SECONDS
+    });
+    violations.sort_by_key(|x| x.start_frame);
+    violations
+}
+
+/// Whether the team has cars on the field and all of them are past the ball
+fn nobody_back(processor: &ReplayProcessor, team: u8, ball: &Vector3f) -> bool {
+    // How far up the field, toward the opponent's goal, a y coordinate is for the team
+    let upfield = |y: f32| if team == 0 { y } else { -y };
+    let mut players = 0;
+    for car in processor.cars() {
+        let car_team = processor
+            .car_player(*car)
+            .and_then(|x| processor.player_team(x));
+        let body = match (car_team, processor.rigid_body(*car)) {
+            (Some(x), Some(body)) if x == team => body,
+            _ => continue,
+        };
+
+        if upfield(body.location.y) <= upfield(ball.y) + FIRST_MAN_BACK_MARGIN {
+            return false;
+        }
+
+        players += 1;
+    }
+
+    players > 0
+}
+
+/// The blue and orange player of the closest pair of cars that are contesting the ball
+fn closest_duel_pair(processor: &ReplayProcessor, ball: &Vector3f) -> Option<(UniqueId, UniqueId)> {
+    let mut cars: [Vec<(&UniqueId, Vector3f, f32)>; 2] = [Vec::new(), Vec::new()];
+    for car in processor.cars() {
+        let player = match processor.car_player(*car) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let (id, team, body) = match (
+            processor.player_unique_id(player),
+            processor.player_team(player),
+            processor.rigid_body(*car),
+        ) {
+            (Some(id), Some(team), Some(body)) => (id, team, body),
+            _ => continue,
+        };
+
+        let ball_distance = distance(&body.location, ball);
+        if let (true, Some(x)) = (
+            ball_distance <= DUEL_RADIUS,
+            cars.get_mut(usize::from(team)),
+        ) {
+            x.push((id, body.location, ball_distance));
+        }
+    }
+
+    let [blue, orange] = &cars;
+    let mut best: Option<(f32, (&UniqueId, &UniqueId))> = None;
+    for (blue_id, blue_location, blue_distance) in blue {
+        for (orange_id, orange_location, orange_distance) in orange {
+            let score = blue_distance + orange_distance;
+            let closer = !matches!(best, Some((x, _)) if x <= score);
+            if closer && is_between(ball, blue_location, orange_location) {
+                best = Some((score, (*blue_id, *orange_id)));
+            }
+        }
+    }
+
+    best.map(|(_, (blue, orange))| (blue.clone(), orange.clone()))
+}
+
+/// Whether the point projects onto the segment between `a` and `b`
+fn is_between(point: &Vector3f, a: &Vector3f, b: &Vector3f) -> bool {
+    let ab = (b.x - a.x, b.y - a.y, b.z - a.z);
+    let ap = (point.x - a.x, point.y - a.y, point.z - a.z);
+    let dot = ab.0 * ap.0 + ab.1 * ap.1 + ab.2 * ap.2;
+    let length_squared = ab.0 * ab.0 + ab.1 * ab.1 + ab.2 * ab.2;
+    dot > 0.0 && dot < length_squared
+}
+
+fn length(x: f32, y: f32) -> f32 {
+    (x * x + y * y).sqrt()
+}
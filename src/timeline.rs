@@ -0,0 +1,148 @@
+use crate::errors::TimelineError;
+use crate::events;
+use crate::models::Replay;
+use crate::network::attributes::{RigidBody, UniqueId};
+use crate::processor::ReplayProcessor;
+use crate::stats::BoostInterpolator;
+use fnv::FnvHashMap;
+
+/// The state of a single player and the ball throughout a replay
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct PlayerTimeline {
+    pub player: UniqueId,
+
+    /// The player's latest name
+    pub name: Option<String>,
+
+    /// The player's latest team: 0 for blue and 1 for orange
+    pub team: Option<u8>,
+
+    /// An entry for every network frame
+    pub frames: Vec<PlayerTimelineFrame>,
+}
+
+impl PlayerTimeline {
+    /// Whether the player had a car at the frame. `false` for frames outside of the replay.
+    pub fn is_alive(&self, frame: usize) -> bool {
+        matches!(self.frames.get(frame), Some(x) if x.alive)
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct PlayerTimelineFrame {
+    pub time: f32,
+
+    /// Whether the player has a car in play. A player is not alive before their first car spawns,
+    /// from when their car is demolished until it respawns (the demolished car lingers for a
+    /// moment before it is removed), or while they otherwise don't have a car. A car may be alive
+    /// before its rigid body is first replicated.
+    pub alive: bool,
+
+    /// The rigid body (in world units) of the player's car. `None` while the player doesn't have
+    /// a car, such as before they join or while they respawn after a demolition.
+    pub car: Option<RigidBody>,
+
+    /// The car's latest replicated boost amount (0 - 255). It is replicated sparsely while
+    /// boosting, so it drops in steps.
+    pub boost: Option<u8>,
+
+    /// The car's boost amount (0 - 255) interpolated between the replicated amounts with a
+    /// [`BoostInterpolator`], which drains smoothly while boosting
+    pub interpolated_boost: Option<f32>,
+
+    /// The rigid body (in world units) of the ball, if one is alive
+    pub ball: Option<RigidBody>,
+}
+
+/// Reconstructs the frames of the given player's car alongside the ball. Every actor is still
+/// decoded and tracked so that the player's state is correct, but only the player's car, boost,
+/// and the ball are retained, which is far less than the state of every player.
+pub fn single_player_timeline(
+    replay: &Replay,
+    player: &UniqueId,
+) -> Result<PlayerTimeline, TimelineError> {
+    if replay.frames().is_none() {
+        return Err(TimelineError::NetworkDataMissing);
+    }
+
+    let mut processor = ReplayProcessor::new(replay);
+    let mut frames = Vec::with_capacity(processor.frames().len());
+    let mut name = None;
+    let mut team = None;
+    let mut found = false;
+    let mut interpolator = BoostInterpolator::for_replay(replay);
+    let mut last_car = None;
+    let mut demos = FnvHashMap::default();
+    let mut demolished = None;
+
+    for frame in processor.frames() {
+        processor.process_frame(frame);
+
+        let car = processor.cars().iter().copied().find_map(|car| {
+            let pri = processor.car_player(car)?;
+            if processor.player_unique_id(pri) == Some(player) {
+                Some((car, pri))
+            } else {
+                None
+            }
+        });
+
+        // The player's replication info outlives their car
+        let pri = car.map(|(_, pri)| pri).or_else(|| {
+            frame
+                .updated_actors
+                .iter()
+                .map(|x| x.actor_id)
+                .find(|x| processor.player_unique_id(*x) == Some(player))
+        });
+
+        if let Some(pri) = pri {
+            found = true;
+            name = processor.player_name(pri).map(String::from).or(name);
+            team = processor.player_team(pri).or(team);
+        }
+
+        let car = car.map(|(car, _)| car);
+        if car != last_car {
+            interpolator.reset();
+            demolished = None;
+            last_car = car;
+        }
+
+        // The demolished car lingers until it is removed and the player respawns with a new car
+        for update in &frame.updated_actors {
+            if let Some((_, victim, _)) = events::new_demolition(&mut demos, frame, update) {
+                if Some(victim) == car {
+                    demolished = car;
+                }
+            }
+        }
+
+        let boost = car.and_then(|x| processor.car_boost(x));
+        let boosting = car.and_then(|x| processor.car_boosting(x)).unwrap_or(false);
+        let interpolated_boost = boost.map(|x| interpolator.update(x, boosting, frame.delta));
+        if boost.is_none() {
+            interpolator.reset();
+        }
+
+        frames.push(PlayerTimelineFrame {
+            time: frame.time,
+            alive: car.is_some() && car != demolished,
+            car: car.and_then(|x| processor.rigid_body(x)),
+            boost,
+            interpolated_boost,
+            ball: processor.ball_rigid_body(),
+        });
+    }
+
+    if !found {
+        return Err(TimelineError::PlayerNotFound(player.clone()));
+    }
+
+    Ok(PlayerTimeline {
+        player: player.clone(),
+        name,
+        team,
+        frames,
+    })
+}
@@ -0,0 +1,192 @@
+//! Cuts a replay down to a range of network frames.
+//!
+//! Actors that are alive at the start of the range were created in frames that are cut, so the
+//! first frame of the trimmed replay is synthesized like a keyframe: it creates every live actor
+//! and replicates the latest value of each of their attributes, so that the state of the trimmed
+//! replay matches the original at every frame in the range.
+
+use crate::models::{HeaderProp, KeyFrame, Replay};
+use crate::network::{ActorId, Frame, NewActor, UpdatedAttribute};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// An actor alive at the cut, with the latest update of each attribute in the order that the
+/// attributes were first replicated
+#[derive(Debug)]
+struct LiveActor {
+    new_actor: NewActor,
+    attributes: Vec<UpdatedAttribute>,
+}
+
+/// The actors that are alive after the frames are applied. Follows the same rules as
+/// [`ActorStateModeler`](crate::ActorStateModeler) so that the synthesized frame recreates the
+/// state that the modeler would have.
+fn live_actors(frames: &[Frame]) -> BTreeMap<ActorId, LiveActor> {
+    let mut actors: BTreeMap<ActorId, LiveActor> = BTreeMap::new();
+    for frame in frames {
+        for actor_id in &frame.deleted_actors {
+            actors.remove(actor_id);
+        }
+
+        for new_actor in &frame.new_actors {
+            let same_type = actors
+                .get(&new_actor.actor_id)
+                .map(|x| x.new_actor.object_id == new_actor.object_id);
+
+            if same_type != Some(true) {
+                let actor = LiveActor {
+                    new_actor: *new_actor,
+                    attributes: Vec::new(),
+                };
+                actors.insert(new_actor.actor_id, actor);
+            }
+        }
+
+        for update in &frame.updated_actors {
+            if let Some(actor) = actors.get_mut(&update.actor_id) {
+                let existing = actor
+                    .attributes
+                    .iter_mut()
+                    .find(|x| x.object_id == update.object_id);
+
+                match existing {
+                    Some(x) => *x = update.clone(),
+                    None => actor.attributes.push(update.clone()),
+                }
+            }
+        }
+    }
+
+    actors
+}
+
+/// Rebases the frame to the start of the range, or `None` if it lies outside of the range
+fn rebase(frame: i32, start: usize, end: usize) -> Option<i32> {
+    let frame = usize::try_from(frame).ok()?;
+    if frame < start || frame >= end {
+        return None;
+    }
+
+    i32::try_from(frame - start).ok()
+}
+
+/// Keeps the entries of a header array (eg: "Goals") whose "frame" lies in the range and rebases
+/// their frame. Entries without a frame are kept.
+fn trim_header_entries(
+    entries: Vec<Vec<(String, HeaderProp)>>,
+    start: usize,
+    end: usize,
+) -> Vec<Vec<(String, HeaderProp)>> {
+    entries
+        .into_iter()
+        .filter_map(|mut entry| {
+            for (key, value) in &mut entry {
+                if let ("frame", HeaderProp::Int(x)) = (key.as_str(), value) {
+                    *x = rebase(*x, start, end)?;
+                }
+            }
+            Some(entry)
+        })
+        .collect()
+}
+
+pub(crate) fn trim(replay: &Replay, start: usize, end: usize) -> Replay {
+    let frames = replay.frames();
+    let num_frames = match frames {
+        Some(x) => x.len(),
+        None => replay
+            .property("NumFrames")
+            .and_then(|prop| prop.as_i32())
+            .and_then(|x| usize::try_from(x).ok())
+            .unwrap_or(0),
+    };
+
+    let end = end.min(num_frames);
+    let start = start.min(end);
+    let len = end - start;
+
+    // Times are rebased to the first frame in the range. Without network frames, there is nothing
+    // to rebase to, so times are left as is.
+    let base_time = frames.and_then(|x| x.get(start)).map_or(0.0, |x| x.time);
+    let frame_time = |frame: usize, time: f32| {
+        frames
+            .and_then(|x| x.get(frame))
+            .map_or(time, |x| x.time - base_time)
+    };
+
+    let mut result = replay.clone();
+    result.network_data = None;
+    if let (Some(network), Some(frames)) = (result.network_frames.as_mut(), frames) {
+        network.frames = match frames.get(start..end) {
+            Some([_, rest @ ..]) => {
+                let actors = live_actors(&frames[..=start]);
+                let synthesized = Frame {
+                    time: 0.0,
+                    delta: 0.0,
+                    new_actors: actors.values().map(|x| x.new_actor).collect(),
+                    deleted_actors: Vec::new(),
+                    updated_actors: actors.into_values().flat_map(|x| x.attributes).collect(),
+                };
+
+                let rest = rest.iter().map(|frame| Frame {
+                    time: frame.time - base_time,
+                    ..frame.clone()
+                });
+
+                std::iter::once(synthesized).chain(rest).collect()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    for (key, prop) in &mut result.properties {
+        match (key.as_str(), prop) {
+            ("NumFrames", HeaderProp::Int(x)) => *x = i32::try_from(len).unwrap_or(i32::MAX),
+            ("Goals", HeaderProp::Array(entries)) | ("HighLights", HeaderProp::Array(entries)) => {
+                *entries = trim_header_entries(std::mem::take(entries), start, end);
+            }
+            _ => {}
+        }
+    }
+
+    // The first frame of the range acts as a keyframe. Keyframe positions are bit offsets into
+    // the encoded network data, which no longer apply to the trimmed frames, so they are zeroed.
+    let first_keyframe = KeyFrame {
+        time: 0.0,
+        frame: 0,
+        position: 0,
+    };
+
+    let keyframes = replay.keyframes.iter().filter_map(|x| {
+        let frame = rebase(x.frame, start, end).filter(|x| *x != 0)?;
+        Some(KeyFrame {
+            time: frame_time(start + frame as usize, x.time),
+            frame,
+            position: 0,
+        })
+    });
+
+    result.keyframes = if len == 0 {
+        Vec::new()
+    } else {
+        std::iter::once(first_keyframe).chain(keyframes).collect()
+    };
+
+    result.tick_marks = std::mem::take(&mut result.tick_marks)
+        .into_iter()
+        .filter_map(|mut x| {
+            x.frame = rebase(x.frame, start, end)?;
+            Some(x)
+        })
+        .collect();
+
+    result.debug_info = std::mem::take(&mut result.debug_info)
+        .into_iter()
+        .filter_map(|mut x| {
+            x.frame = rebase(x.frame, start, end)?;
+            Some(x)
+        })
+        .collect();
+
+    result
+}
@@ -0,0 +1,90 @@
+use crate::actor_state::ActorStateModeler;
+use crate::models::Replay;
+use crate::network::{ActorId, Frame, NewActor, UpdatedAttribute};
+
+/// Callbacks for the changes in every network frame, driven by [`Replay::walk`]. Each callback
+/// receives the index of the frame and the reconstructed state of every live actor, so an
+/// analytic only has to react to the changes it is interested in. Every callback does nothing by
+/// default.
+///
+/// Within a frame, deleted actors are visited first, then new actors, and finally attribute
+/// updates, in the order that [`ActorStateModeler`] applies them.
+///
+/// ```rust
+/// use boxcars::{ActorStateModeler, FrameVisitor, NewActor};
+///
+/// /// Counts how many actors were created
+/// #[derive(Default)]
+/// struct Spawns(usize);
+///
+/// impl FrameVisitor for Spawns {
+///     fn on_new_actor(&mut self, _frame: usize, _actor: &NewActor, _actors: &ActorStateModeler) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let data = include_bytes!("../assets/replays/good/rumble.replay");
+/// let replay = boxcars::ParserBuilder::new(&data[..])
+///     .must_parse_network_data()
+///     .parse()
+///     .unwrap();
+///
+/// let mut spawns = Spawns::default();
+/// replay.walk(&mut spawns);
+/// assert!(spawns.0 > 0);
+/// ```
+pub trait FrameVisitor {
+    /// Called when an actor is created, after it has been added to the actors. Keyframes re-send
+    /// actors that already exist, which are not visited again unless their type changed.
+    fn on_new_actor(&mut self, _frame: usize, _actor: &NewActor, _actors: &ActorStateModeler) {}
+
+    /// Called when a live actor is deleted (or replaced by an actor of another type), before it
+    /// is removed from the actors so that its last state can be inspected
+    fn on_deleted_actor(&mut self, _frame: usize, _actor_id: ActorId, _actors: &ActorStateModeler) {
+    }
+
+    /// Called when an attribute of a live actor is replicated, before the update is applied so
+    /// that the actor's state still holds the previous value. Keyframes re-send unchanged values.
+    fn on_updated_attribute(
+        &mut self,
+        _frame: usize,
+        _update: &UpdatedAttribute,
+        _actors: &ActorStateModeler,
+    ) {
+    }
+
+    /// Called after every change in the frame has been applied
+    fn on_frame_end(&mut self, _frame: usize, _data: &Frame, _actors: &ActorStateModeler) {}
+}
+
+pub(crate) fn walk<V: FrameVisitor>(replay: &Replay, visitor: &mut V) {
+    let mut actors = ActorStateModeler::new();
+    for (i, frame) in replay.frames_or_empty().iter().enumerate() {
+        for actor_id in &frame.deleted_actors {
+            if actors.get(*actor_id).is_some() {
+                visitor.on_deleted_actor(i, *actor_id, &actors);
+                actors.delete_actor(*actor_id);
+            }
+        }
+
+        for new_actor in &frame.new_actors {
+            match actors.get(new_actor.actor_id) {
+                Some(x) if x.object_id() == new_actor.object_id => continue,
+                Some(_) => visitor.on_deleted_actor(i, new_actor.actor_id, &actors),
+                None => {}
+            }
+
+            actors.new_actor(new_actor);
+            visitor.on_new_actor(i, new_actor, &actors);
+        }
+
+        for update in &frame.updated_actors {
+            if actors.get(update.actor_id).is_some() {
+                visitor.on_updated_attribute(i, update, &actors);
+                actors.update_attribute(update);
+            }
+        }
+
+        visitor.on_frame_end(i, frame, &actors);
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "log")]
+
+use boxcars::ParserBuilder;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::RefCell;
+
+thread_local! {
+    static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+struct ThreadLogger;
+
+impl Log for ThreadLogger {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let entry = (record.level(), record.args().to_string());
+        RECORDS.with(|x| x.borrow_mut().push(entry));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: ThreadLogger = ThreadLogger;
+
+fn logged<F: FnOnce()>(f: F) -> Vec<(Level, String)> {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(LevelFilter::Trace);
+    RECORDS.with(|x| x.borrow_mut().clear());
+    f();
+    RECORDS.with(|x| x.borrow_mut().drain(..).collect())
+}
+
+#[test]
+fn test_skipped_network_data_is_logged() {
+    let data = include_bytes!("../assets/replays/bad/fuzz-large-object-id.replay");
+    let records = logged(|| {
+        let replay = ParserBuilder::new(&data[..])
+            .never_check_crc()
+            .ignore_network_data_on_error()
+            .parse()
+            .unwrap();
+        assert!(replay.network_frames.is_none());
+    });
+
+    let warnings: Vec<_> = records.iter().filter(|x| x.0 == Level::Warn).collect();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].1.starts_with("Network data was skipped"));
+}
+
+#[test]
+fn test_clean_replay_logs_no_warnings() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let records = logged(|| {
+        ParserBuilder::new(&data[..])
+            .must_parse_network_data()
+            .parse()
+            .unwrap();
+    });
+
+    assert!(records.iter().all(|x| x.0 != Level::Warn));
+}
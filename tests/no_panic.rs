@@ -0,0 +1,206 @@
+//! Parsing untrusted input must never panic: every malformed replay should result in an error.
+//! These tests feed the parser the replays that fuzzing previously found to be problematic along
+//! with deterministically mangled copies of valid replays. The number of mangled copies per
+//! replay can be raised through the `BOXCARS_NO_PANIC_ITERATIONS` environment variable to search
+//! more thoroughly than the default, which is kept low enough for CI.
+
+use boxcars::ParserBuilder;
+use std::panic;
+
+const DEFAULT_ITERATIONS: u64 = 16;
+
+const CORPUS: [(&str, &[u8]); 10] = [
+    (
+        "fuzz-corpus",
+        include_bytes!("../assets/replays/bad/fuzz-corpus.replay"),
+    ),
+    (
+        "fuzz-large-object-id",
+        include_bytes!("../assets/replays/bad/fuzz-large-object-id.replay"),
+    ),
+    (
+        "fuzz-list-too-large",
+        include_bytes!("../assets/replays/bad/fuzz-list-too-large.replay"),
+    ),
+    (
+        "fuzz-slice-index",
+        include_bytes!("../assets/replays/bad/fuzz-slice-index.replay"),
+    ),
+    (
+        "fuzz-string-too-long",
+        include_bytes!("../assets/replays/bad/fuzz-string-too-long.replay"),
+    ),
+    (
+        "fuzz-string-too-long2",
+        include_bytes!("../assets/replays/bad/fuzz-string-too-long2.replay"),
+    ),
+    (
+        "fuzz-too-many-frames",
+        include_bytes!("../assets/replays/bad/fuzz-too-many-frames.replay"),
+    ),
+    (
+        "small-frames",
+        include_bytes!("../assets/replays/good/small-frames.replay"),
+    ),
+    ("07e9", include_bytes!("../assets/replays/good/07e9.replay")),
+    (
+        "no-frames",
+        include_bytes!("../assets/replays/good/no-frames.replay"),
+    ),
+];
+
+/// Values that tend to find the edges of length and index checks
+const INTERESTING: [u32; 8] = [
+    0,
+    1,
+    0x7f,
+    0xff,
+    0xffff,
+    0x7fff_ffff,
+    0x8000_0000,
+    0xffff_ffff,
+];
+
+/// A xorshift generator so that a failing input can be reproduced from its seed
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next() % n as u64) as usize
+        }
+    }
+}
+
+fn mangle(data: &[u8], rng: &mut Rng) -> Vec<u8> {
+    let mut data = data.to_vec();
+    for _ in 0..=rng.below(4) {
+        let len = data.len();
+        match rng.below(6) {
+            0 if len > 0 => {
+                let pos = rng.below(len);
+                data[pos] ^= 1 << rng.below(8);
+            }
+            1 if len > 0 => {
+                let pos = rng.below(len);
+                data[pos] = INTERESTING[rng.below(INTERESTING.len())] as u8;
+            }
+            2 if len >= 4 => {
+                let pos = rng.below(len - 3);
+                let value = INTERESTING[rng.below(INTERESTING.len())];
+                data[pos..pos + 4].copy_from_slice(&value.to_le_bytes());
+            }
+            3 => data.truncate(rng.below(len)),
+            4 if len > 0 => {
+                let start = rng.below(len);
+                let end = start + rng.below((len - start).min(64));
+                data.drain(start..end);
+            }
+            5 if len > 0 => {
+                let start = rng.below(len);
+                let end = start + rng.below((len - start).min(64));
+                let chunk = data[start..end].to_vec();
+                let pos = rng.below(len);
+                data.splice(pos..pos, chunk);
+            }
+            _ => {}
+        }
+    }
+    data
+}
+
+/// A named way of parsing a replay
+type Configuration = (&'static str, fn(&[u8]));
+
+/// Parses the data with every combination of crc checking and network parsing, the same as the
+/// fuzz targets, and with resyncing past frames that fail to decode, returning the name of the
+/// configuration that panicked
+fn parse_all_ways(data: &[u8]) -> Result<(), &'static str> {
+    let configurations: [Configuration; 5] = [
+        ("crc-body", |x| {
+            let _ = ParserBuilder::new(x)
+                .always_check_crc()
+                .must_parse_network_data()
+                .parse();
+        }),
+        ("no-crc-body", |x| {
+            let _ = ParserBuilder::new(x)
+                .never_check_crc()
+                .must_parse_network_data()
+                .parse();
+        }),
+        ("crc-no-body", |x| {
+            let _ = ParserBuilder::new(x)
+                .always_check_crc()
+                .never_parse_network_data()
+                .parse();
+        }),
+        ("no-crc-no-body", |x| {
+            let _ = ParserBuilder::new(x)
+                .never_check_crc()
+                .never_parse_network_data()
+                .parse();
+        }),
+        ("no-crc-resync", |x| {
+            let _ = ParserBuilder::new(x)
+                .never_check_crc()
+                .must_parse_network_data()
+                .attempt_resync()
+                .collect_errors();
+        }),
+    ];
+
+    for (name, parse) in configurations.iter() {
+        if panic::catch_unwind(|| parse(data)).is_err() {
+            return Err(name);
+        }
+    }
+
+    Ok(())
+}
+
+fn iterations() -> u64 {
+    std::env::var("BOXCARS_NO_PANIC_ITERATIONS")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS)
+}
+
+#[test]
+fn test_corpus_does_not_panic() {
+    for (name, data) in CORPUS.iter() {
+        if let Err(config) = parse_all_ways(data) {
+            panic!("{} panicked when parsed as {}", name, config);
+        }
+    }
+}
+
+#[test]
+fn test_mangled_replays_do_not_panic() {
+    let iterations = iterations();
+    let mut failures = Vec::new();
+    for (name, data) in CORPUS.iter() {
+        for seed in 1..=iterations {
+            let mut rng = Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            let mangled = mangle(data, &mut rng);
+            if let Err(config) = parse_all_ways(&mangled) {
+                failures.push(format!("{} (seed {}) as {}", name, seed, config));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "mangled replays panicked: {:#?}",
+        failures
+    );
+}
@@ -1,8 +1,14 @@
-use boxcars::attributes::{ActiveActor, Demolish, Pickup, RigidBody, StatEvent, Welded};
+use boxcars::attributes::{
+    ActiveActor, CamSettings, Demolish, Pickup, RemoteId, RigidBody, StatEvent, UniqueId, Welded,
+};
 use boxcars::{
-    self, ActorId, NetworkError, ParseError, ParserBuilder, Quaternion, Trajectory, Vector3f,
-    Vector3i,
+    self, cache, events, stats, ActorId, ActorStateModeler, ArenaVariant, BallFrame, CarComponent,
+    Frame, FrameVisitor, GameMode, HeaderProp, MatchSettings, NetworkError, NewActor, ParseError,
+    ParseWarning, ParserBuilder, Platform, PlayerFrame, ProcessorError, Quaternion,
+    ReplayProcessor, Trajectory, UpdatedAttribute, Vector3f, Vector3i,
 };
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 #[test]
 fn test_sample1() {
@@ -257,6 +263,52 @@ fn test_error_extraction() {
     }
 }
 
+#[test]
+fn test_error_recoverable() {
+    use std::error::Error;
+
+    let data = include_bytes!("../assets/replays/bad/fuzz-large-object-id.replay");
+    let err = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap_err();
+    assert!(err.is_recoverable());
+    assert!(err.source().is_some());
+    assert_eq!(err.to_string(), err.source().unwrap().to_string());
+
+    let err = ParserBuilder::new(&data[..100])
+        .never_check_crc()
+        .parse()
+        .unwrap_err();
+    assert!(!err.is_recoverable());
+}
+
+#[test]
+fn test_collect_errors() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let (replay, warnings) = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .collect_errors()
+        .unwrap();
+    assert!(replay.network_frames.is_some());
+    assert_eq!(warnings, vec![]);
+
+    let data = include_bytes!("../assets/replays/bad/fuzz-large-object-id.replay");
+    let (replay, warnings) = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .ignore_network_data_on_error()
+        .collect_errors()
+        .unwrap();
+    assert!(replay.network_frames.is_none());
+    match warnings.as_slice() {
+        [ParseWarning::NetworkDataSkipped(e)] => {
+            assert!(matches!(**e, NetworkError::ObjectIdOutOfRange(_)))
+        }
+        x => panic!("Expecting skipped network data. not {:?}", x),
+    }
+}
+
 #[test]
 fn test_quaternions() {
     let data = include_bytes!("../assets/replays/good/01d3e5.replay");
@@ -462,3 +514,3280 @@ fn test_rumble_actor_id() {
         .collect();
     assert_eq!(pickups[264].instigator, Some(ActorId(-1)));
 }
+
+#[test]
+fn test_player_stats() {
+    let data = include_bytes!("../assets/replays/good/159a4.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let stats = replay.player_stats();
+    assert_eq!(stats.len(), 6);
+
+    let names: Vec<&str> = stats.iter().filter_map(|x| x.name.as_deref()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "comagoosie",
+            "philipbollhoefer",
+            "SyCoz-Chaos",
+            "MLbestplayer",
+            "{Whiff} TheGreatRobert0",
+            "sluggish37",
+        ]
+    );
+
+    assert_eq!(stats.iter().filter(|x| x.team == Some(0)).count(), 3);
+    assert_eq!(stats.iter().filter(|x| x.team == Some(1)).count(), 3);
+    assert!(stats.iter().all(|x| x.is_bot == Some(false)));
+
+    let sycoz = &stats[2];
+    assert_eq!(sycoz.platform.as_deref(), Some("OnlinePlatform_PS4"));
+    assert_eq!(sycoz.online_id, Some(3373421750759248985));
+    assert_eq!(sycoz.score, Some(820));
+    assert_eq!(sycoz.goals, Some(2));
+    assert_eq!(sycoz.assists, Some(2));
+    assert_eq!(sycoz.saves, Some(1));
+    assert_eq!(sycoz.shots, Some(5));
+    assert_eq!(sycoz.party_leader, None);
+}
+
+#[test]
+fn test_frame_order_deterministic() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let parse = || {
+        ParserBuilder::new(&data[..])
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+            .network_frames
+            .unwrap()
+            .frames
+    };
+
+    let first = parse();
+    let second = parse();
+    assert_eq!(first, second);
+
+    // Within a frame, attributes for an actor are decoded contiguously, so an actor should never
+    // reappear after another actor's updates have started
+    for frame in &first {
+        let mut seen = Vec::new();
+        for update in &frame.updated_actors {
+            if seen.last() != Some(&update.actor_id) {
+                assert!(!seen.contains(&update.actor_id));
+                seen.push(update.actor_id);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_frame_decoder_decode_next() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let expected = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap()
+        .network_frames
+        .unwrap()
+        .frames;
+
+    let (replay, mut decoder) = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .frame_decoder()
+        .unwrap();
+    assert!(replay.network_frames.is_none());
+    assert_eq!(decoder.frames_decoded(), 0);
+
+    let first = decoder.decode_next().unwrap().unwrap();
+    assert_eq!(first, expected[0]);
+    assert_eq!(decoder.frames_decoded(), 1);
+
+    let mut frames = vec![first];
+    while let Some(frame) = decoder.decode_next().unwrap() {
+        frames.push(frame);
+    }
+
+    assert_eq!(frames, expected);
+    assert_eq!(decoder.frames_decoded(), expected.len());
+    assert!(decoder.decode_next().unwrap().is_none());
+}
+
+#[test]
+fn test_team_colors() {
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let (blue, orange) = replay.team_colors().unwrap();
+    assert_eq!(blue.palette_index, 40);
+    assert!(blue.is_custom);
+    assert_eq!(orange.palette_index, 208);
+    assert!(orange.is_custom);
+
+    let data = include_bytes!("../assets/replays/good/5a06.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.team_colors(), None);
+}
+
+#[test]
+fn test_match_settings() {
+    let data = include_bytes!("../assets/replays/good/d044.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let settings = replay.match_settings();
+    assert!(!settings.is_standard());
+    assert_eq!(settings.ball_scale, 0.75);
+    assert_eq!(settings.ball_max_speed_scale, 2.5);
+    assert_eq!(settings.ball_bounce_scale, 1.75);
+    assert!(!settings.unlimited_boost);
+
+    let data = include_bytes!("../assets/replays/good/07e9.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(replay.match_settings().unlimited_boost);
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.match_settings(), MatchSettings::default());
+}
+
+#[test]
+fn test_rumble_items() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let settings = replay.match_settings();
+    assert_eq!(settings.items_mode.as_deref(), Some("ItemsMode"));
+    assert_eq!(settings.rumble_items.len(), 11);
+    for item in &[
+        "BallFreeze",
+        "BallSpring",
+        "Swapper",
+        "Tornado",
+        "StrongHit",
+    ] {
+        assert!(settings.rumble_items.iter().any(|x| x == item));
+    }
+
+    // Modes built on a single item
+    let data = include_bytes!("../assets/replays/good/gridiron.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let settings = replay.match_settings();
+    assert_eq!(settings.items_mode.as_deref(), Some("ItemsMode_Football"));
+    assert_eq!(settings.rumble_items, vec![String::from("Football")]);
+
+    // Standard modes have no items
+    let data = include_bytes!("../assets/replays/good/00bb.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let settings = replay.match_settings();
+    assert_eq!(settings.items_mode, None);
+    assert!(settings.rumble_items.is_empty());
+    assert!(settings.is_standard());
+}
+
+#[test]
+fn test_camera_settings() {
+    let data = include_bytes!("../assets/replays/good/rlcs2.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // Includes the casters and spectators
+    let settings = replay.camera_settings();
+    assert_eq!(settings.len(), 10);
+
+    let kaydop = UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(76561198067659334),
+        local_id: 0,
+    };
+    assert_eq!(
+        settings.get(&kaydop),
+        Some(&CamSettings {
+            fov: 110.0,
+            height: 110.0,
+            angle: -3.0,
+            distance: 270.0,
+            stiffness: 0.7,
+            swivel: 3.2,
+            transition: Some(1.0),
+        })
+    );
+
+    // Players whose settings were never replicated are given the defaults
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let settings = replay.camera_settings();
+    assert_eq!(settings.len(), 8);
+    let defaults = settings
+        .values()
+        .filter(|x| **x == CamSettings::default())
+        .count();
+    assert_eq!(defaults, 6);
+}
+
+#[test]
+fn test_player_titles() {
+    let data = include_bytes!("../assets/replays/good/00bb.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let titles = replay.player_titles();
+    let steam = |id| UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(id),
+        local_id: 0,
+    };
+
+    assert_eq!(titles.len(), 4);
+    assert_eq!(
+        titles.get(&steam(76561198343671934)).map(|x| x.as_str()),
+        Some("Season 10 Grand Champion")
+    );
+    assert_eq!(
+        titles.get(&steam(76561198357142889)).map(|x| x.as_str()),
+        Some("Soccer Senpai")
+    );
+
+    // Unknown titles fall back to their id
+    assert_eq!(
+        titles.get(&steam(76561198799319382)).map(|x| x.as_str()),
+        Some("AutoTour_01_SuperSonicLegend_2")
+    );
+
+    // Players in the header without an equipped title are omitted
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(replay.player_titles().is_empty());
+}
+
+#[test]
+fn test_kickoff_outcomes() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let kickoffs = stats::kickoff_outcomes(&replay);
+    assert_eq!(kickoffs.len(), 10);
+    assert!(kickoffs
+        .iter()
+        .enumerate()
+        .all(|(i, x)| x.kickoff_index == i));
+
+    // Both teams hit the ball a frame apart
+    assert!(kickoffs[1].neutral);
+    assert_eq!(kickoffs[1].first_toucher, None);
+
+    let kickoff = &kickoffs[7];
+    assert!(!kickoff.neutral);
+    assert_eq!(
+        kickoff.first_toucher.as_ref().map(|x| &x.remote_id),
+        Some(&RemoteId::Steam(76561198101748375))
+    );
+    assert_eq!(kickoff.winning_team, Some(0));
+
+    // Blue reached the ball less than 2 seconds after the countdown ended
+    let fastest = kickoff.time_to_first_touch.unwrap();
+    assert!((fastest - 1.889).abs() < 0.01);
+    assert_eq!(
+        stats::time_to_first_touch(&replay),
+        kickoffs
+            .iter()
+            .map(|x| x.time_to_first_touch)
+            .collect::<Vec<_>>()
+    );
+
+    let data = include_bytes!("../assets/replays/good/rlcs.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // Blue won the kickoff but first touched the ball over 6 seconds after the countdown ended
+    let kickoffs = stats::kickoff_outcomes(&replay);
+    assert_eq!(kickoffs[2].winning_team, Some(0));
+    assert_eq!(kickoffs[2].time_to_first_touch, None);
+    assert!(kickoffs[3].time_to_first_touch.is_some());
+
+    // Neutral kickoffs without a winner are not timed
+    assert_eq!(kickoffs[1].winning_team, None);
+    assert_eq!(kickoffs[1].time_to_first_touch, None);
+}
+
+#[test]
+fn test_game_mode_ball_resolution() {
+    let replays: [(&[u8], GameMode); 10] = [
+        (
+            include_bytes!("../assets/replays/good/3d07e.replay"),
+            GameMode::Soccar,
+        ),
+        (
+            include_bytes!("../assets/replays/good/4742.replay"),
+            GameMode::Hoops,
+        ),
+        (
+            include_bytes!("../assets/replays/good/07e9.replay"),
+            GameMode::Hoops,
+        ),
+        (
+            include_bytes!("../assets/replays/good/204c.replay"),
+            GameMode::Dropshot,
+        ),
+        (
+            include_bytes!("../assets/replays/good/fecd.replay"),
+            GameMode::Rumble,
+        ),
+        (
+            include_bytes!("../assets/replays/good/rumble.replay"),
+            GameMode::Rumble,
+        ),
+        (
+            include_bytes!("../assets/replays/good/d1d5.replay"),
+            GameMode::Snowday,
+        ),
+        (
+            include_bytes!("../assets/replays/good/d4f3b_heat.replay"),
+            GameMode::Heatseeker,
+        ),
+        (
+            include_bytes!("../assets/replays/good/gridiron.replay"),
+            GameMode::Gridiron,
+        ),
+        (
+            include_bytes!("../assets/replays/good/43a9.replay"),
+            GameMode::Training,
+        ),
+    ];
+
+    for (data, mode) in replays.iter() {
+        let replay = ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap();
+        assert_eq!(replay.game_mode(), *mode);
+
+        let minimal = boxcars::export::minimal_replay(&replay);
+        assert!(
+            minimal.frames.iter().any(|x| x.ball.is_some()),
+            "{:?}",
+            mode
+        );
+    }
+
+    // Knockout is played without a ball
+    let data = include_bytes!("../assets/replays/good/029d.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.game_mode(), GameMode::Knockout);
+}
+
+#[test]
+fn test_boost_efficiency() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let efficiency = stats::boost_efficiency(&replay).unwrap();
+    assert_eq!(efficiency.len(), 8);
+
+    let player = efficiency
+        .iter()
+        .find(|(id, _)| id.remote_id == RemoteId::Steam(76561198101748375))
+        .map(|(_, x)| x)
+        .unwrap();
+    let uu_per_boost = player.uu_per_boost.unwrap();
+    assert!((uu_per_boost - 564.4).abs() < 0.1);
+    assert!((player.time_empty - 38.1).abs() < 0.1);
+    assert!((player.time_full - 54.3).abs() < 0.1);
+    assert!((player.wasted_collected - 188.5).abs() < 0.1);
+
+    let data = include_bytes!("../assets/replays/good/07e9.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(stats::boost_efficiency(&replay), None);
+}
+
+#[test]
+fn test_physics_constants() {
+    let parse = |data: &[u8]| {
+        ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+    };
+
+    let old = parse(&include_bytes!("../assets/replays/good/3d07e.replay")[..]);
+    let new = parse(&include_bytes!("../assets/replays/good/029d.replay")[..]);
+    assert_ne!(
+        (old.major_version, old.minor_version),
+        (new.major_version, new.minor_version)
+    );
+
+    for replay in &[&old, &new] {
+        let constants = stats::PhysicsConstants::for_replay(replay);
+        assert_eq!(constants, stats::PhysicsConstants::default());
+        assert_eq!(constants.small_pad_boost, 12.0);
+        assert_eq!(constants.supersonic_speed, 2200.0);
+        assert!((constants.boost_used_per_second - 86.0).abs() < 0.1);
+    }
+
+    // Overriding the constants changes the results
+    let constants = stats::PhysicsConstants {
+        small_pad_boost: 0.0,
+        ..stats::PhysicsConstants::default()
+    };
+    let default = stats::boost_efficiency(&old).unwrap();
+    let custom = stats::boost_efficiency_with(&old, &constants).unwrap();
+    let wasted = |x: &std::collections::HashMap<UniqueId, stats::BoostEfficiency>| {
+        x.values().map(|x| x.wasted_collected).sum::<f32>()
+    };
+    assert!(wasted(&custom) < wasted(&default));
+}
+
+#[test]
+fn test_roster_changes() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    assert_eq!(replay.team_sizes(), (3, 3));
+
+    let changes = events::roster_changes(&replay);
+    let left = changes
+        .iter()
+        .find(|x| x.action == events::RosterAction::Left && !x.bot)
+        .unwrap();
+    assert_eq!(left.frame, 69);
+    assert_eq!(left.name.as_deref(), Some("Sasquatch7802"));
+    assert_eq!(left.team, 1);
+
+    // A bot takes over the leaver's car shortly after
+    let takeover = changes.iter().find(|x| x.replaces.is_some()).unwrap();
+    assert_eq!(takeover.frame, 71);
+    assert_eq!(takeover.action, events::RosterAction::Joined);
+    assert_eq!(takeover.name.as_deref(), Some("Sticks"));
+    assert_eq!(takeover.replaces.as_deref(), Some("Sasquatch7802"));
+    assert!(takeover.bot);
+
+    // The bot leaves when a human joins the team
+    let joined = changes
+        .iter()
+        .filter(|x| x.action == events::RosterAction::Joined && !x.bot)
+        .count();
+    assert_eq!(joined, 3);
+    assert!(changes
+        .iter()
+        .any(|x| x.frame == 935 && x.name.as_deref() == Some("Sticks")));
+
+    // The replay predates the timed out flag, so leave reasons aren't recorded
+    assert!(changes.iter().all(|x| x.reason.is_none()));
+
+    let parse = |data: &[u8]| {
+        let replay = ParserBuilder::new(data)
+            .must_parse_network_data()
+            .parse()
+            .unwrap();
+        events::roster_changes(&replay)
+    };
+
+    // Matar's connection dropped before they rejoined
+    let changes = parse(&include_bytes!("../assets/replays/good/4bc3b.replay")[..]);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].frame, 5087);
+    assert_eq!(changes[0].name.as_deref(), Some("Matar"));
+    assert_eq!(changes[0].reason, Some(events::LeaveReason::Disconnected));
+    assert_eq!(changes[1].action, events::RosterAction::Joined);
+    assert_eq!(changes[1].reason, None);
+
+    // A player that quit is replaced by a bot, whose own leave has no reason
+    let changes = parse(&include_bytes!("../assets/replays/good/epic.replay")[..]);
+    let quit = changes.iter().find(|x| x.reason.is_some()).unwrap();
+    assert_eq!(quit.name.as_deref(), Some("charlieeeee_52"));
+    assert_eq!(quit.reason, Some(events::LeaveReason::Quit));
+    assert!(changes.iter().filter(|x| x.bot).all(|x| x.reason.is_none()));
+}
+
+#[test]
+fn test_mechanics() {
+    let parse = |data: &[u8]| {
+        ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+    };
+
+    let replay = parse(&include_bytes!("../assets/replays/good/42f2.replay")[..]);
+    let mechanics = events::mechanics(&replay);
+    assert_eq!(mechanics.len(), 1);
+    assert_eq!(mechanics[0].kind, events::MechanicKind::CeilingShot);
+    assert_eq!(mechanics[0].frame, 10699);
+    assert_eq!(mechanics[0].player.as_deref(), Some("Schutzein"));
+
+    let replay = parse(&include_bytes!("../assets/replays/good/e2f9d.replay")[..]);
+    let mechanics = events::mechanics(&replay);
+    let dash = mechanics
+        .iter()
+        .find(|x| x.kind == events::MechanicKind::WallDash)
+        .unwrap();
+    assert_eq!(dash.frame, 5689);
+    assert_eq!(dash.player.as_deref(), Some("Ice Bjorns^mejsofgurks"));
+}
+
+#[test]
+fn test_scoreboard_state() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // Leavers are absent from the final scoreboard, which agrees with the header
+    let frames = replay.frames_or_empty().len();
+    let scoreboard = boxcars::ScoreboardState::at_frame(&replay, frames - 1);
+    let header = replay.player_stats();
+    assert_eq!(scoreboard.len(), header.len());
+    for stats in &header {
+        let entry = scoreboard.iter().find(|x| x.name == stats.name).unwrap();
+        assert_eq!(Some(i32::from(entry.team)), stats.team);
+        assert_eq!(Some(entry.goals), stats.goals);
+        assert_eq!(Some(entry.assists), stats.assists);
+        assert_eq!(Some(entry.saves), stats.saves);
+        assert_eq!(Some(entry.shots), stats.shots);
+    }
+
+    // Ordered by team and then score
+    assert_eq!(scoreboard[0].name.as_deref(), Some("TheGoldenGarp"));
+    assert_eq!(scoreboard[3].name.as_deref(), Some("Grim Reefer"));
+
+    // A player that just joined starts from zero
+    let scoreboard = boxcars::ScoreboardState::at_frame(&replay, 1000);
+    let joined = scoreboard
+        .iter()
+        .find(|x| x.name.as_deref() == Some("PUT YOUR WEIGHT ON IT"))
+        .unwrap();
+    assert_eq!((joined.score, joined.goals, joined.shots), (0, 0, 0));
+    assert!(scoreboard
+        .iter()
+        .all(|x| x.name.as_deref() != Some("Sticks")));
+}
+
+#[test]
+fn test_register_attribute_decoder() {
+    use bitter::BitReader;
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let builtin = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let custom = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .register_attribute_decoder("TAGame.PRI_TA:MatchScore", |bits, _| {
+            bits.read_i32().map(|x| boxcars::Attribute::Int(x * 10))
+        })
+        .parse()
+        .unwrap();
+
+    let scores = |replay: &boxcars::Replay| -> Vec<boxcars::Attribute> {
+        let key = replay
+            .objects
+            .iter()
+            .position(|x| x == "TAGame.PRI_TA:MatchScore")
+            .unwrap();
+        replay
+            .frames_or_empty()
+            .iter()
+            .flat_map(|x| x.updated_actors.iter())
+            .filter(|x| usize::from(x.object_id) == key)
+            .map(|x| x.attribute.clone())
+            .collect()
+    };
+
+    let expected: Vec<boxcars::Attribute> = scores(&builtin)
+        .into_iter()
+        .map(|x| match x {
+            boxcars::Attribute::Int(x) => {
+                boxcars::Attribute::Custom(Box::new(boxcars::CustomAttribute {
+                    name: String::from("TAGame.PRI_TA:MatchScore"),
+                    value: boxcars::Attribute::Int(x * 10),
+                }))
+            }
+            x => panic!("unexpected score: {:?}", x),
+        })
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(scores(&custom), expected);
+    assert_eq!(
+        builtin.frames_or_empty().len(),
+        custom.frames_or_empty().len()
+    );
+
+    // A decoder that gives up fails the network data
+    let failed = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .register_attribute_decoder("TAGame.PRI_TA:MatchScore", |_, _| None)
+        .parse();
+    assert!(failed.is_err());
+}
+
+#[test]
+fn test_attempt_resync() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let clean = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // Simulate an attribute that can't be decoded by giving up on ball hits
+    let (replay, warnings) = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .attempt_resync()
+        .register_attribute_decoder("TAGame.Ball_TA:HitTeamNum", |_, _| None)
+        .collect_errors()
+        .unwrap();
+
+    let skipped: Vec<(usize, usize)> = warnings
+        .iter()
+        .filter_map(|x| match x {
+            ParseWarning::FramesSkipped { frame, skipped, .. } => Some((*frame, *skipped)),
+            _ => None,
+        })
+        .collect();
+
+    // The first ball hits are on frames 125, 126, and 128
+    assert_eq!(skipped.len(), 115);
+    assert_eq!(skipped[0], (125, 2));
+    assert_eq!(skipped[1], (126, 1));
+
+    let frames = replay.frames_or_empty();
+    let clean_frames = clean.frames_or_empty();
+    assert_eq!(frames.len(), 8042);
+    assert_eq!(
+        frames.len() + skipped.iter().map(|(_, x)| x).sum::<usize>(),
+        clean_frames.len()
+    );
+    assert_eq!(frames[..125], clean_frames[..125]);
+    assert_eq!(frames[125], clean_frames[127]);
+    assert!(frames.iter().all(|x| clean_frames.contains(x)));
+
+    assert!(matches!(
+        warnings.last(),
+        Some(ParseWarning::FrameCountMismatch {
+            expected: 8727,
+            decoded: 8042
+        })
+    ));
+
+    // Resyncing is opt-in
+    let failed = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .register_attribute_decoder("TAGame.Ball_TA:HitTeamNum", |_, _| None)
+        .parse();
+    assert!(failed.is_err());
+}
+
+#[test]
+fn test_center_of_mass() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let formation = stats::center_of_mass(&replay);
+    assert_eq!(
+        formation.frames.len(),
+        replay.network_frames.unwrap().frames.len()
+    );
+
+    // Each team spends the match on average in their own half
+    let blue = formation.blue.center_of_mass.unwrap();
+    let orange = formation.orange.center_of_mass.unwrap();
+    assert!(blue.y < 0.0);
+    assert!(orange.y > 0.0);
+    assert!(formation.blue.spacing.unwrap() > 0.0);
+
+    let frame = &formation.frames[500];
+    assert_eq!(frame.frame, 500);
+    assert_eq!(frame.blue.players, 3);
+    assert_eq!(frame.orange.players, 3);
+
+    // Spacing is only known when at least two teammates are on the field
+    assert!(formation
+        .frames
+        .iter()
+        .flat_map(|x| [x.blue, x.orange])
+        .all(|x| (x.players >= 2) == x.spacing.is_some()
+            && (x.players >= 1) == x.center_of_mass.is_some()));
+    assert!(formation.frames.iter().any(|x| x.blue.players == 1));
+}
+
+#[test]
+fn test_replay_metadata() {
+    let data = include_bytes!("../assets/replays/good/d044.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.replay_name(), Some("Kevin got REKT"));
+    assert_eq!(replay.author(), Some("JohnnyVicious66 [Friendly] BE"));
+    assert_eq!(replay.primary_player_team(), Some(1));
+    assert_eq!((&replay).into_iter().count(), 0);
+
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.author(), None);
+    let highlights = replay.highlights();
+    assert_eq!(highlights.len(), 12);
+    assert_eq!(
+        highlights[0],
+        boxcars::Highlight {
+            frame: Some(473),
+            car_name: Some(String::from("Car_TA_886")),
+            ball_name: Some(String::from("Ball_TA_230")),
+        }
+    );
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.replay_name(), None);
+    assert_eq!(replay.primary_player_team(), None);
+}
+
+#[test]
+fn test_boost_pads() {
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let pads = stats::boost_pads(&replay);
+    assert_eq!(pads.len(), 33);
+    let pad = |suffix: &str| {
+        pads.iter()
+            .find(|x| x.name.ends_with(suffix))
+            .unwrap()
+            .clone()
+    };
+
+    // Picked up at the first kickoff and respawned 4 seconds later
+    let small = pad("VehiclePickup_Boost_TA_43");
+    assert_eq!(small.pad, Some(0));
+    assert_eq!(small.large, Some(false));
+    let cooldown = &small.cooldowns[0];
+    assert_eq!(cooldown.frame, 120);
+    assert_eq!(cooldown.respawn_frame, Some(231));
+    assert!(cooldown.player_id.is_some());
+    assert!(!small.is_available(cooldown.time));
+    assert!(small.is_available(cooldown.respawn_time.unwrap()));
+
+    // Still respawning when the replay ended
+    let large = pad("VehiclePickup_Boost_TA_64");
+    assert_eq!(large.pad, Some(18));
+    assert_eq!(large.large, Some(true));
+    assert_eq!(large.cooldowns.len(), 5);
+    assert_eq!(large.cooldowns[4].respawn_frame, None);
+    assert!(!large.is_available(f32::MAX));
+
+    // Every pad actor is a different standard pad
+    let mut matched: Vec<usize> = pads.iter().filter_map(|x| x.pad).collect();
+    matched.sort_unstable();
+    matched.dedup();
+    assert_eq!(matched.len(), 33);
+
+    let data = include_bytes!("../assets/replays/good/e2f9d.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let pads = stats::boost_pads(&replay);
+    let unused = pads
+        .iter()
+        .find(|x| x.name.ends_with("VehiclePickup_Boost_TA_41"))
+        .unwrap();
+    assert_eq!(unused.pad, None);
+    assert_eq!(unused.large, None);
+    assert!(unused.cooldowns.is_empty());
+    assert!(unused.is_available(100.0));
+}
+
+#[test]
+fn test_boost_pad_pickup_layouts() {
+    fn pickups(data: &[u8]) -> (usize, usize) {
+        let replay = ParserBuilder::new(data)
+            .must_parse_network_data()
+            .parse()
+            .unwrap();
+
+        let new_layout = replay
+            .network_frames
+            .as_ref()
+            .unwrap()
+            .frames
+            .iter()
+            .flat_map(|x| x.updated_actors.iter())
+            .filter(|x| matches!(x.attribute, boxcars::Attribute::PickupNew(_)))
+            .count();
+
+        let cooldowns = stats::boost_pads(&replay)
+            .iter()
+            .map(|x| x.cooldowns.len())
+            .sum();
+        (new_layout, cooldowns)
+    }
+
+    // Older replays replicate pickups with the legacy layout
+    let (new_layout, cooldowns) = pickups(include_bytes!("../assets/replays/good/rlcs.replay"));
+    assert_eq!(new_layout, 0);
+    assert_eq!(cooldowns, 533);
+
+    // Recent replays only replicate the new layout
+    let (new_layout, cooldowns) = pickups(include_bytes!("../assets/replays/good/00bb.replay"));
+    assert!(new_layout > 0);
+    assert_eq!(cooldowns, 449);
+}
+
+#[test]
+fn test_anonymize() {
+    fn online_ids(id: &RemoteId) -> Vec<String> {
+        match id {
+            RemoteId::Steam(x) | RemoteId::Xbox(x) | RemoteId::QQ(x) => vec![x.to_string()],
+            RemoteId::PsyNet(x) => vec![x.online_id.to_string()],
+            RemoteId::Switch(x) => vec![x.online_id.to_string()],
+            RemoteId::PlayStation(x) => vec![x.online_id.to_string(), x.name.clone()],
+            RemoteId::Epic(x) => vec![x.clone()],
+            RemoteId::SplitScreen(_) => vec![],
+        }
+    }
+
+    for data in [
+        &include_bytes!("../assets/replays/good/029d.replay")[..],
+        &include_bytes!("../assets/replays/good/3d07e.replay")[..],
+    ]
+    .iter()
+    {
+        let replay = ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap();
+
+        // Every human player's name and online id
+        let mut secrets: Vec<String> = replay
+            .player_stats()
+            .into_iter()
+            .filter(|x| x.is_bot != Some(true))
+            .flat_map(|x| x.name.into_iter().chain(x.online_id.map(|x| x.to_string())))
+            .collect();
+        for frame in &replay.network_frames.as_ref().unwrap().frames {
+            for update in &frame.updated_actors {
+                match &update.attribute {
+                    boxcars::Attribute::UniqueId(x) => secrets.extend(online_ids(&x.remote_id)),
+                    boxcars::Attribute::Reservation(x) => {
+                        secrets.extend(online_ids(&x.unique_id.remote_id))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        secrets.retain(|x| x.len() > 3);
+        secrets.sort();
+        secrets.dedup();
+        assert!(!secrets.is_empty());
+
+        let anonymized = replay.anonymize();
+        let json = serde_json::to_string(&anonymized).unwrap();
+        for secret in &secrets {
+            assert!(!json.contains(secret.as_str()), "{} remains", secret);
+        }
+
+        // The header and the network data agree on each player's pseudonym
+        let stats = anonymized.player_stats();
+        let names: Vec<&str> = stats.iter().filter_map(|x| x.name.as_deref()).collect();
+        assert_eq!(names[0], "Player 1");
+        let frames = &anonymized.network_frames.as_ref().unwrap().frames;
+        for name in names {
+            assert!(frames
+                .iter()
+                .flat_map(|x| &x.updated_actors)
+                .any(|x| matches!(&x.attribute, boxcars::Attribute::String(s) if s == name)));
+        }
+
+        assert_eq!(
+            anonymized.frames().map(|x| x.len()),
+            replay.frames().map(|x| x.len())
+        );
+    }
+
+    // The ids that BakkesMod records are replaced with the same pseudonymous ids as the frames
+    let data = include_bytes!("../assets/replays/good/029d.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap()
+        .anonymize();
+    let ids: Vec<String> = replay
+        .network_frames
+        .as_ref()
+        .unwrap()
+        .frames
+        .iter()
+        .flat_map(|x| &x.updated_actors)
+        .filter_map(|x| match &x.attribute {
+            boxcars::Attribute::UniqueId(x) => online_ids(&x.remote_id).into_iter().next(),
+            _ => None,
+        })
+        .collect();
+    let mmr: Vec<&str> = replay
+        .debug_info
+        .iter()
+        .filter(|x| x.user.starts_with("MMR:"))
+        .map(|x| x.user.split('|').nth(1).unwrap())
+        .collect();
+    assert!(!mmr.is_empty());
+    assert!(mmr.iter().all(|x| ids.iter().any(|id| id == x)));
+}
+
+#[test]
+fn test_delta_round_trip() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let frames = &replay.network_frames.as_ref().unwrap().frames;
+    let deltas = boxcars::delta::encode(frames);
+    assert_eq!(deltas.len(), frames.len());
+
+    // Every decoded state matches the actor state modeler
+    let mut decoder = boxcars::delta::Decoder::new();
+    let mut modeler = boxcars::ActorStateModeler::new();
+    let mut state_attributes = 0;
+    for (frame, delta) in frames.iter().zip(&deltas) {
+        modeler.process_frame(frame);
+        let state = decoder.apply(delta);
+        assert_eq!(state.time, frame.time);
+        assert_eq!(state.actors.len(), modeler.actors().count());
+        for (actor_id, actor) in modeler.actors() {
+            let snapshot = &state.actors[&actor_id];
+            assert_eq!(snapshot.object_id, actor.object_id());
+            assert_eq!(snapshot.name_id, actor.name_id());
+            assert_eq!(snapshot.attributes.len(), actor.attributes().count());
+            for (object_id, attribute) in actor.attributes() {
+                assert_eq!(snapshot.attributes.get(&object_id), Some(attribute));
+            }
+        }
+        state_attributes += state.attribute_count();
+    }
+
+    // Only a small fraction of the state changes from one frame to the next, and less than what
+    // the network frames re-send
+    let changes: usize = deltas.iter().map(|x| x.changed.len()).sum();
+    let updates: usize = frames.iter().map(|x| x.updated_actors.len()).sum();
+    assert!(changes * 20 < state_attributes);
+    assert!(changes < updates);
+}
+
+#[test]
+fn test_kickoff_positions() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let formations = stats::kickoff_positions(&replay);
+    assert_eq!(formations.len(), stats::kickoff_outcomes(&replay).len());
+
+    let first = &formations[0];
+    assert_eq!(first.kickoff_index, 0);
+    assert_eq!(first.frame, 83);
+    let spawns: Vec<_> = first.blue.iter().map(|x| x.spawn).collect();
+    assert_eq!(
+        spawns,
+        vec![
+            Some(boxcars::field::KickoffSpawn::Corner),
+            Some(boxcars::field::KickoffSpawn::OffCenter),
+            Some(boxcars::field::KickoffSpawn::OffCenter),
+        ]
+    );
+    assert_eq!(first.blue[0].player.as_deref(), Some("Sasha Kaun"));
+    assert_eq!(first.blue[0].role, Some(stats::KickoffRole::Taker));
+    assert_eq!(first.orange[2].player.as_deref(), Some("jeffreyj1"));
+    assert_eq!(first.orange[2].role, Some(stats::KickoffRole::Cheat));
+
+    // Every team has a single taker and every car starts at a standard spawn
+    for formation in &formations {
+        for team in [&formation.blue, &formation.orange].iter() {
+            assert_eq!(team.len(), 3);
+            assert!(team.iter().all(|x| x.spawn.is_some()));
+            let takers = team
+                .iter()
+                .filter(|x| x.role == Some(stats::KickoffRole::Taker))
+                .count();
+            assert_eq!(takers, 1);
+        }
+    }
+
+    // Uneven teams, and only the first kickoff replicates the end of its countdown
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let formations = stats::kickoff_positions(&replay);
+    assert_eq!(formations.len(), 1);
+    assert_eq!(formations[0].blue.len(), 2);
+    assert_eq!(formations[0].orange.len(), 1);
+    assert_eq!(
+        formations[0].blue[1].spawn,
+        Some(boxcars::field::KickoffSpawn::Back)
+    );
+
+    // Older replays have other spawns
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let formations = stats::kickoff_positions(&replay);
+    assert_eq!(formations.len(), 10);
+    assert!(formations[0].blue.iter().all(|x| x.spawn.is_none()));
+}
+
+#[test]
+fn test_record_bit_widths() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let (replay, widths) = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .record_bit_widths()
+        .unwrap();
+
+    // Every decoded attribute is recorded
+    let updates: usize = replay
+        .frames_or_empty()
+        .iter()
+        .map(|x| x.updated_actors.len())
+        .sum();
+    let recorded: usize = widths.attributes.values().map(|x| x.count).sum();
+    assert_eq!(recorded, updates);
+
+    let byte = widths.attributes[&boxcars::AttributeTag::Byte];
+    assert_eq!((byte.min, byte.max), (8, 8));
+    assert!(widths.attributes[&boxcars::AttributeTag::Int].is_constant());
+    assert!(!widths.attributes[&boxcars::AttributeTag::RigidBody].is_constant());
+    assert!(widths.to_string().contains("| Float | 32 | 32 |"));
+
+    let (_, widths) = ParserBuilder::new(&data[..])
+        .never_parse_network_data()
+        .record_bit_widths()
+        .unwrap();
+    assert!(widths.attributes.is_empty());
+}
+
+#[test]
+fn test_game_event_detection() {
+    let data = include_bytes!("../assets/replays/good/gridiron.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let event = events::game_event(&replay).unwrap();
+    assert_eq!(
+        event.archetype,
+        "GameInfo_FootBall.GameInfo.GameInfo_FootBall:Archetype"
+    );
+    assert_eq!(event.mode, boxcars::GameMode::Gridiron);
+
+    let clock = events::game_clock(&replay);
+    assert_eq!(clock.len(), 301);
+    assert_eq!(clock[0].seconds_remaining, 300);
+    assert_eq!(clock.last().unwrap().seconds_remaining, 0);
+    assert!(clock
+        .windows(2)
+        .all(|x| x[0].seconds_remaining > x[1].seconds_remaining));
+
+    // A game event archetype that isn't known is still found by the attributes it replicates
+    let data = include_bytes!("../assets/replays/good/4742.replay");
+    let mut replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let clock = events::game_clock(&replay);
+    for object in replay.objects.iter_mut() {
+        if object == "Archetypes.GameEvent.GameEvent_Basketball" {
+            *object = String::from("Archetypes.GameEvent.GameEvent_FreezeTag");
+        }
+    }
+
+    let event = events::game_event(&replay).unwrap();
+    assert_eq!(event.archetype, "Archetypes.GameEvent.GameEvent_FreezeTag");
+    assert_eq!(event.mode, boxcars::GameMode::Unknown);
+    assert_eq!(events::game_clock(&replay), clock);
+}
+
+#[test]
+fn test_goal_contributions() {
+    let data = include_bytes!("../assets/replays/good/soccar-lan.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let contributions = events::goal_contributions(&replay);
+    let name = |x: &Option<boxcars::events::Touch>| x.as_ref().and_then(|x| x.player.clone());
+    let attributions: Vec<_> = contributions
+        .iter()
+        .map(|x| (name(&x.scorer), name(&x.assister)))
+        .collect();
+
+    let expected = [
+        ("Moses", "GarrettG"),
+        ("kuxir97", "Markydooda"),
+        ("Markydooda", "kuxir97"),
+        ("M1k3Rules", "Markydooda"),
+        ("GarrettG", "Moses"),
+    ];
+    let expected: Vec<_> = expected
+        .iter()
+        .map(|(scorer, assister)| (Some(scorer.to_string()), Some(assister.to_string())))
+        .collect();
+    assert_eq!(attributions, expected);
+
+    for contribution in &contributions {
+        assert!(!contribution.own_goal);
+        assert_eq!(contribution.possession.last(), contribution.scorer.as_ref());
+        assert!(contribution
+            .possession
+            .iter()
+            .all(|x| x.team == Some(contribution.goal.scoring_team)));
+    }
+}
+
+#[test]
+fn test_require_net_version_range() {
+    let data = include_bytes!("../assets/replays/good/gridiron.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .require_net_version_range(10, 10)
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.net_version, Some(10));
+
+    let err = ParserBuilder::new(&data[..])
+        .require_net_version_range(11, 20)
+        .parse()
+        .unwrap_err();
+    assert_eq!(err, ParseError::NetVersionOutOfRange(10, 11, 20));
+    assert_eq!(
+        err.to_string(),
+        "Net version 10 is outside of the required range of 11 to 20"
+    );
+
+    // Replays without a net version are considered version zero
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let err = ParserBuilder::new(&data[..])
+        .require_net_version_range(1, 20)
+        .frame_decoder()
+        .unwrap_err();
+    assert_eq!(err, ParseError::NetVersionOutOfRange(0, 1, 20));
+}
+
+#[test]
+fn test_last_touch_timeline() {
+    let data = include_bytes!("../assets/replays/good/soccar-lan.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let timeline = stats::last_touch_timeline(&replay);
+    let touches = events::touches(&replay);
+    assert_eq!(
+        timeline.len(),
+        replay.network_frames.as_ref().unwrap().frames.len()
+    );
+
+    let first = touches[0].frame;
+    assert!(timeline[..first].iter().all(|x| x.is_none()));
+    for touch in &touches {
+        let last = touches
+            .iter()
+            .rev()
+            .find(|x| x.frame == touch.frame)
+            .unwrap();
+        assert_eq!(timeline[touch.frame], last.player_id);
+    }
+
+    // The last toucher only changes on the frames of touches
+    for i in 1..timeline.len() {
+        if timeline[i] != timeline[i - 1] {
+            assert!(touches.iter().any(|x| x.frame == i));
+        }
+    }
+}
+
+#[test]
+fn test_overtime_duration() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let mut replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    assert!(replay.went_to_overtime());
+
+    // Overtime kicked off at frame 7937 and was won by the goal at frame 8662
+    let frames = &replay.network_frames.as_ref().unwrap().frames;
+    let expected = frames[8662].time - frames[7937].time;
+    assert_eq!(replay.overtime_duration(), Some(expected));
+    assert!((replay.overtime_duration().unwrap() - 35.0).abs() < 0.1);
+
+    // A replay that ends during overtime is timed to its last frame
+    let frames = &mut replay.network_frames.as_mut().unwrap().frames;
+    frames.truncate(8300);
+    let expected = frames[8299].time - frames[7937].time;
+    assert_eq!(replay.overtime_duration(), Some(expected));
+
+    let data = include_bytes!("../assets/replays/good/soccar-lan.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    assert!(!replay.went_to_overtime());
+    assert_eq!(replay.overtime_duration(), None);
+}
+
+#[test]
+fn test_goal_explosions() {
+    let data = include_bytes!("../assets/replays/good/00bb.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let explosions = events::goal_explosions(&replay);
+    let header = events::header_goals(&replay);
+    assert_eq!(explosions.len(), header.len());
+    for (explosion, goal) in explosions.iter().zip(header.iter()) {
+        assert_eq!(explosion.frame, goal.frame);
+        assert_eq!(Some(i32::from(explosion.scoring_team)), goal.team);
+        assert_eq!(explosion.attacker, goal.player_name);
+        assert!(explosion.attacker_id.is_some());
+        assert!(explosion.location.y.abs() > 5100.0);
+    }
+
+    // Older replays don't replicate who scored
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let explosions = events::goal_explosions(&replay);
+    let frames: Vec<_> = explosions.iter().map(|x| x.frame).collect();
+    assert_eq!(
+        frames,
+        vec![996, 2006, 2686, 4278, 4837, 5666, 6984, 7490, 8662]
+    );
+    assert!(explosions.iter().all(|x| x.attacker.is_none()));
+}
+
+#[test]
+fn test_deadline() {
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let full = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // The deadline has passed by the time it is first checked, after 256 frames
+    let err = ParserBuilder::new(&data[..])
+        .ignore_network_data_on_error()
+        .with_deadline(Duration::from_nanos(1))
+        .parse()
+        .unwrap_err();
+    match err {
+        ParseError::NetworkError(e) => match *e {
+            NetworkError::DecodeTimeout(deadline, frames) => {
+                assert_eq!(deadline, Duration::from_nanos(1));
+                assert_eq!(frames.len(), 256);
+                assert_eq!(frames, &full.frames_or_empty()[..256]);
+            }
+            e => panic!("Expecting a timeout, not {:?}", e),
+        },
+        e => panic!("Expecting a network error, not {:?}", e),
+    }
+
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .with_deadline(Duration::from_secs(3600))
+        .parse()
+        .unwrap();
+    assert_eq!(replay, full);
+}
+
+#[test]
+fn test_ball_possession_percentage() {
+    let data = include_bytes!("../assets/replays/good/soccar-lan.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let possession = stats::ball_possession_percentage(&replay);
+
+    // A five minute match without overtime, excluding countdowns and goal replays
+    assert!(possession.live_seconds > 300.0 && possession.live_seconds < 330.0);
+    let total = possession.blue + possession.orange + possession.neutral;
+    assert!((total - 100.0).abs() < 0.01);
+    assert!(possession.neutral > 0.0 && possession.neutral < 10.0);
+    assert!(possession.blue > 40.0 && possession.orange > 40.0);
+
+    let players: f32 = possession.players.iter().map(|(_, x)| x).sum();
+    assert_eq!(possession.players.len(), 6);
+    assert!(players <= possession.blue + possession.orange + 0.01);
+
+    let json = serde_json::to_value(&possession).unwrap();
+    assert_eq!(json["players"].as_array().unwrap().len(), 6);
+}
+
+#[test]
+fn test_jumps() {
+    let data = include_bytes!("../assets/replays/good/soccar-lan.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let jumps = events::jumps(&replay);
+    assert!(jumps.windows(2).all(|x| x[0].frame <= x[1].frame));
+
+    let players: HashSet<_> = jumps.iter().filter_map(|x| x.player_id.clone()).collect();
+    assert_eq!(players.len(), 6);
+
+    // A double jump is always preceded by a jump of the same player
+    let double_jumps = jumps
+        .iter()
+        .filter(|x| x.kind == events::JumpKind::DoubleJump);
+    assert_eq!(double_jumps.clone().count(), 59);
+    for double_jump in double_jumps {
+        assert!(jumps.iter().any(|x| x.kind == events::JumpKind::Jump
+            && x.frame <= double_jump.frame
+            && x.player_id == double_jump.player_id));
+    }
+}
+
+#[test]
+fn test_cache_round_trip() {
+    let replays: [&[u8]; 4] = [
+        include_bytes!("../assets/replays/good/rumble.replay"),
+        include_bytes!("../assets/replays/good/epic.replay"),
+        include_bytes!("../assets/replays/good/gridiron.replay"),
+        include_bytes!("../assets/replays/good/no-frames.replay"),
+    ];
+
+    for data in replays.iter() {
+        let replay = ParserBuilder::new(data)
+            .must_parse_network_data()
+            .parse()
+            .unwrap();
+
+        let cached = cache::serialize(&replay);
+        assert!(cached.starts_with(&cache::MAGIC));
+        assert_eq!(cache::deserialize(&cached).unwrap(), replay);
+    }
+}
+
+#[test]
+fn test_own_goals_from_network() {
+    let data = include_bytes!("../assets/replays/good/c0bca.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // The first two goals were put in by the conceding team, and the header credits them to the
+    // scoring team's last toucher instead
+    let goals = events::goals_from_network(&replay);
+    let kinds: Vec<_> = goals.iter().map(|x| x.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            events::GoalKind::OwnGoal,
+            events::GoalKind::OwnGoal,
+            events::GoalKind::Regular,
+            events::GoalKind::Regular,
+            events::GoalKind::Regular,
+        ]
+    );
+
+    let contributions = events::goal_contributions(&replay);
+    let header = events::header_goals(&replay);
+    for ((goal, contribution), header) in goals.iter().zip(&contributions).zip(&header) {
+        let scorer = contribution.scorer.as_ref().unwrap();
+        assert_eq!(
+            contribution.own_goal,
+            goal.kind == events::GoalKind::OwnGoal
+        );
+        assert_eq!(header.team, Some(i32::from(goal.scoring_team)));
+        assert_eq!(
+            contribution.own_goal,
+            scorer.team != Some(goal.scoring_team)
+        );
+        assert_eq!(contribution.own_goal, header.player_name != scorer.player);
+    }
+}
+
+#[test]
+fn test_verify_frame_count() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .verify_frame_count()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.frames_or_empty().len(), 8727);
+
+    // Declare more frames in the header than the network data holds
+    let mut data = data.to_vec();
+    let key = b"NumFrames\0";
+    let at = data.windows(key.len()).position(|x| x == key).unwrap();
+    let value = at + key.len() + 4 + "IntProperty\0".len() + 8;
+    data[value..value + 4].copy_from_slice(&8737i32.to_le_bytes());
+
+    let err = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .verify_frame_count()
+        .parse()
+        .unwrap_err();
+    assert_eq!(err, ParseError::FrameCountMismatch(8737, 8727));
+    assert!(err.is_recoverable());
+
+    // The check is off by default, and lenient parsing only warns
+    assert!(ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .is_ok());
+
+    let (replay, warnings) = ParserBuilder::new(&data[..])
+        .ignore_network_data_on_error()
+        .verify_frame_count()
+        .collect_errors()
+        .unwrap();
+    assert_eq!(replay.frames_or_empty().len(), 8727);
+    assert_eq!(
+        warnings,
+        vec![ParseWarning::FrameCountMismatch {
+            expected: 8737,
+            decoded: 8727
+        }]
+    );
+}
+
+#[test]
+fn test_cosmetics() {
+    let data = include_bytes!("../assets/replays/good/00bb.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let cosmetics = replay.cosmetics();
+    assert_eq!(cosmetics.len(), 6);
+
+    let steam = |id| UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(id),
+        local_id: 0,
+    };
+
+    // A painted body, wheels, trail, and goal explosion
+    let painted = cosmetics[&steam(76561198859387974)];
+    assert_eq!(painted.body, Some(4770));
+    assert_eq!(painted.body_paint, Some(1));
+    assert_eq!(painted.wheels, Some(1580));
+    assert_eq!(painted.wheels_paint, Some(12));
+    assert_eq!(painted.boost, Some(63));
+    assert_eq!(painted.boost_paint, None);
+    assert_eq!(painted.goal_explosion_paint, Some(12));
+    assert_eq!(painted.team_paint.map(|x| x.team), Some(1));
+
+    // Nothing painted and no decal
+    let plain = cosmetics[&steam(76561198904138005)];
+    assert_eq!(plain.body, Some(23));
+    assert_eq!(plain.decal, None);
+    assert_eq!(plain.wheels, Some(376));
+    assert_eq!(plain.body_paint, None);
+    assert_eq!(plain.wheels_paint, None);
+    assert_eq!(plain.team_paint.map(|x| x.team), Some(0));
+}
+
+#[test]
+fn test_trim() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .always_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let clip = replay.trim(1500, 2500);
+    let frames = clip.network_frames.as_ref().unwrap().frames.as_slice();
+    assert_eq!(frames.len(), 1000);
+    assert_eq!(frames[0].time, 0.0);
+    assert!(frames.windows(2).all(|x| x[0].time <= x[1].time));
+
+    let num_frames = clip
+        .properties
+        .iter()
+        .find(|(key, _)| key == "NumFrames")
+        .map(|(_, prop)| prop.clone());
+    assert_eq!(num_frames, Some(HeaderProp::Int(1000)));
+
+    // Only the goal at frame 2006 (and its tick mark) is within the clip
+    let goals = events::header_goals(&clip);
+    assert_eq!(goals.len(), 1);
+    assert_eq!(goals[0].frame, 506);
+    assert_eq!(clip.tick_marks.len(), 1);
+    assert_eq!(clip.tick_marks[0].frame, 461);
+    assert_eq!(clip.keyframes[0].frame, 0);
+
+    // Actor state is the same as the original's at every frame in the clip
+    let original = &replay.network_frames.as_ref().unwrap().frames;
+    let mut expected = boxcars::ActorStateModeler::new();
+    for frame in &original[..1500] {
+        expected.process_frame(frame);
+    }
+
+    let mut actual = boxcars::ActorStateModeler::new();
+    for (i, frame) in frames.iter().enumerate() {
+        expected.process_frame(&original[1500 + i]);
+        actual.process_frame(frame);
+        if i % 100 == 0 || i == frames.len() - 1 {
+            let expected: HashMap<_, _> = expected.actors().collect();
+            let actual: HashMap<_, _> = actual.actors().collect();
+            assert_eq!(actual, expected, "frame {}", i);
+        }
+    }
+
+    // The clip is a standalone replay that survives being encoded and decoded
+    let decoded = cache::deserialize(&cache::serialize(&clip)).unwrap();
+    assert_eq!(decoded, clip);
+}
+
+#[test]
+fn test_network_bytes() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .always_check_crc()
+        .never_parse_network_data()
+        .retain_network_data()
+        .parse()
+        .unwrap();
+
+    // The network data is within the body and prefixed by its size
+    let bytes = replay.network_bytes().unwrap();
+    assert!(!bytes.is_empty());
+    assert!(bytes.len() < replay.content_size as usize);
+
+    let body_start = 16 + replay.header_size as usize;
+    let body = &data[body_start..body_start + replay.content_size as usize];
+    let offset = body.windows(64).position(|x| x == &bytes[..64]).unwrap();
+    assert_eq!(&body[offset..offset + bytes.len()], bytes);
+    assert_eq!(
+        &body[offset - 4..offset],
+        &(bytes.len() as i32).to_le_bytes()[..]
+    );
+
+    // The bytes are only retained on request and are dropped when anonymizing
+    let default = ParserBuilder::new(&data[..])
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(default.network_bytes(), None);
+    assert_eq!(replay.anonymize().network_bytes(), None);
+}
+
+#[test]
+fn test_platform_distribution() {
+    let data = include_bytes!("../assets/replays/good/204c.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let platforms = replay.platform_distribution();
+    assert_eq!(platforms.len(), 3);
+    assert_eq!(platforms.get(&Platform::Xbox), Some(&4));
+    assert_eq!(platforms.get(&Platform::Switch), Some(&1));
+    assert_eq!(platforms.get(&Platform::Epic), Some(&1));
+
+    // Bots are not counted
+    let data = include_bytes!("../assets/replays/good/a184.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let platforms = replay.platform_distribution();
+    assert_eq!(platforms.len(), 1);
+    assert_eq!(platforms.get(&Platform::Steam), Some(&1));
+
+    let switch = UniqueId {
+        system_id: 7,
+        remote_id: RemoteId::PsyNet(Default::default()),
+        local_id: 0,
+    };
+    assert_eq!(switch.platform(), Platform::Switch);
+}
+
+#[test]
+fn test_spectators() {
+    let parse = |data: &[u8]| {
+        ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+    };
+
+    // The tournament's admin and observers were in the lobby from the start
+    let replay = parse(&include_bytes!("../assets/replays/good/rlcs2.replay")[..]);
+    let spectators = replay.spectators();
+    let names: Vec<_> = spectators.iter().map(|x| x.name.as_deref()).collect();
+    assert_eq!(
+        names,
+        vec![
+            Some("RLCS Admin"),
+            Some("RLCS Observer 2"),
+            Some("RLCS Observer 1"),
+            Some("RLCS Observer 3")
+        ]
+    );
+    assert!(spectators.iter().all(|x| x.frame == 0));
+    assert_eq!(spectators[0].player_id.platform(), Platform::Steam);
+
+    // Matchmade replays are never spectated, even with players that joined without a seat
+    let replay = parse(&include_bytes!("../assets/replays/good/netversion.replay")[..]);
+    assert!(replay.spectators().is_empty());
+
+    let replay = parse(&include_bytes!("../assets/replays/good/tourny.replay")[..]);
+    assert!(replay.spectators().is_empty());
+}
+
+#[test]
+fn test_continuous_clock() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let clock = events::continuous_clock(&replay);
+    let updates = events::game_clock(&replay);
+    assert_eq!(
+        clock.len(),
+        replay.network_frames.as_ref().unwrap().frames.len()
+    );
+
+    // The clock is held at five minutes through the first kickoff's countdown, which ends at
+    // frame 85
+    assert!(clock[..85].iter().all(|x| *x == Some(300.0)));
+    assert!(clock[85].unwrap() < 300.0);
+
+    for (i, update) in updates.iter().enumerate() {
+        let seconds = update.seconds_remaining as f32;
+        assert_eq!(clock[update.frame], Some(seconds));
+
+        // Between replicated seconds, the clock stays within the displayed second and reaches
+        // within a few frames of the next one by the time that it is replicated
+        let next = updates.get(i + 1);
+        let end = next.map_or(clock.len(), |x| x.frame);
+        let (low, high) = if update.overtime {
+            (seconds, seconds + 1.0)
+        } else {
+            (seconds - 1.0, seconds)
+        };
+
+        let values: Vec<f32> = clock[update.frame..end]
+            .iter()
+            .map(|x| x.unwrap())
+            .collect();
+        assert!(values.iter().all(|x| *x >= low && *x <= high));
+        assert!(values
+            .windows(2)
+            .all(|x| (x[1] >= x[0]) == update.overtime || x[1] == x[0]));
+
+        if let Some(next) = next.filter(|x| x.overtime == update.overtime) {
+            let last = values.last().unwrap();
+            assert!((last - next.seconds_remaining as f32).abs() < 0.2);
+        }
+    }
+
+    // Overtime counts up from zero
+    let overtime = updates.iter().find(|x| x.overtime).unwrap();
+    assert_eq!(overtime.seconds_remaining, 0);
+    assert!(clock.last().unwrap().unwrap() > 30.0);
+}
+
+#[test]
+fn test_saves() {
+    let data = include_bytes!("../assets/replays/good/rlcs.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let saves = events::saves(&replay);
+    let touches = events::touches(&replay);
+    let goals = events::goals_from_network(&replay);
+    assert_eq!(saves.len(), 10);
+    assert_eq!(saves.iter().filter(|x| x.epic).count(), 5);
+
+    let first = &saves[0];
+    assert_eq!(first.frame, 1694);
+    assert_eq!(first.saver.player.as_deref(), Some("Maestro."));
+    assert_eq!(first.shooter.player.as_deref(), Some("Paschy90"));
+
+    // The header credits Maestro with 4 saves
+    let maestro = saves
+        .iter()
+        .filter(|x| x.saver.player.as_deref() == Some("Maestro."))
+        .count();
+    assert_eq!(maestro, 3);
+
+    for save in &saves {
+        assert_eq!(save.shooter.team, save.saver.team.map(|x| 1 - x));
+        assert!(save.shooter.frame < save.frame);
+        assert!(save.seconds_to_goal <= 1.0);
+
+        // The ball was kept out until the next touch
+        let next = touches.iter().find(|x| x.frame > save.frame).unwrap();
+        assert!(!goals
+            .iter()
+            .any(|x| x.frame >= save.frame && x.frame < next.frame));
+    }
+}
+
+#[test]
+fn test_checksum_sections() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let layout = replay.checksum_sections();
+    let crc_at = |offset: usize| {
+        u32::from_le_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ])
+    };
+
+    assert_eq!(layout.header_crc_offset, 4);
+    assert_eq!(layout.header_range.len(), replay.header_size as usize);
+    assert_eq!(layout.body_range.len(), replay.content_size as usize);
+
+    // The body runs to the end of the file
+    assert_eq!(layout.body_range.end, data.len());
+
+    assert_eq!(crc_at(layout.header_crc_offset), replay.header_crc);
+    assert_eq!(crc_at(layout.body_crc_offset), replay.content_crc);
+    assert_eq!(
+        boxcars::crc::calc_crc(&data[layout.header_range.clone()]),
+        replay.header_crc
+    );
+    assert_eq!(
+        boxcars::crc::calc_crc(&data[layout.body_range]),
+        replay.content_crc
+    );
+}
+
+#[test]
+fn test_dribbles() {
+    let data = include_bytes!("../assets/replays/good/128ed.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let dribbles = events::dribbles(&replay);
+    assert_eq!(dribbles.len(), 5);
+    assert!(dribbles.iter().all(|x| x.duration >= 1.0));
+    assert!(dribbles
+        .windows(2)
+        .all(|x| x[0].start_frame <= x[1].start_frame));
+
+    // A dribble that ends in a flick, and a long carry that doesn't
+    let flick = &dribbles[0];
+    assert_eq!(flick.player.as_deref(), Some("SquishyMuffinz"));
+    assert_eq!((flick.start_frame, flick.end_frame), (964, 1025));
+    assert!(flick.flick);
+
+    let carry = &dribbles[1];
+    assert_eq!((carry.start_frame, carry.end_frame), (1366, 1639));
+    assert!(!carry.flick);
+    assert!((carry.duration - 11.62).abs() < 0.01);
+
+    let frames = replay.frames_or_empty();
+    let elapsed = frames[carry.end_frame].time - frames[carry.start_frame].time;
+    assert_eq!(carry.duration, elapsed);
+}
+
+#[test]
+fn test_present_types() {
+    let rumble_item = "Archetypes.SpecialPickups.SpecialPickup_BallFreeze";
+    let is_rumble = |x: &&str| x.starts_with("Archetypes.SpecialPickups.");
+
+    let data = include_bytes!("../assets/replays/good/00bb.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let types = replay.present_types();
+    assert!(types.contains("Archetypes.Ball.Ball_Default"));
+    assert!(types.contains("TheWorld:PersistentLevel.VehiclePickup_Boost_TA"));
+    assert!(!types.iter().any(is_rumble));
+
+    let objects = boxcars::ObjectIndex::new(&replay.objects);
+    assert!(!objects.contains_type(rumble_item));
+    assert!(objects.contains_type("TheWorld:PersistentLevel.VehiclePickup_Boost_TA"));
+
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let types = replay.present_types();
+    assert!(types.contains(rumble_item));
+    assert!(types.iter().any(is_rumble));
+    assert!(boxcars::ObjectIndex::new(&replay.objects).contains_type(rumble_item));
+
+    // Every spawned type is one of the replay's objects
+    let objects = boxcars::ObjectIndex::new(&replay.objects);
+    assert!(types.iter().all(|x| objects.contains_type(x)));
+
+    let replay = ParserBuilder::new(&data[..])
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(replay.present_types().is_empty());
+}
+
+#[test]
+fn test_closest_to_ball_timeline() {
+    let data = include_bytes!("../assets/replays/good/rlcs.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let timeline = boxcars::stats::closest_to_ball_timeline(&replay);
+    assert_eq!(
+        timeline.len(),
+        replay.network_frames.as_ref().unwrap().frames.len()
+    );
+
+    // Deevo is the closest to the ball when they touch it at frame 188
+    let touches = boxcars::events::touches(&replay);
+    let touch = touches.iter().find(|x| x.frame == 188).unwrap();
+    assert_eq!(touch.player.as_deref(), Some("Deevo"));
+    assert_eq!(timeline[188], touch.player_id);
+
+    // The toucher is nearly always the closest player at the moment of the touch
+    let closest = touches
+        .iter()
+        .filter(|x| x.player_id.is_some() && timeline[x.frame] == x.player_id)
+        .count();
+    assert!(closest * 10 >= touches.len() * 9);
+
+    // Deterministic between runs
+    assert_eq!(timeline, boxcars::stats::closest_to_ball_timeline(&replay));
+}
+
+#[test]
+fn test_walk() {
+    #[derive(Default)]
+    struct Counter {
+        created: usize,
+        deleted: usize,
+        changed: usize,
+        live: Vec<usize>,
+    }
+
+    impl FrameVisitor for Counter {
+        fn on_new_actor(&mut self, _frame: usize, actor: &NewActor, actors: &ActorStateModeler) {
+            assert!(actors.get(actor.actor_id).is_some());
+            self.created += 1;
+        }
+
+        fn on_deleted_actor(
+            &mut self,
+            _frame: usize,
+            actor_id: ActorId,
+            actors: &ActorStateModeler,
+        ) {
+            assert!(actors.get(actor_id).is_some());
+            self.deleted += 1;
+        }
+
+        fn on_updated_attribute(
+            &mut self,
+            _frame: usize,
+            update: &UpdatedAttribute,
+            actors: &ActorStateModeler,
+        ) {
+            let previous = actors
+                .get(update.actor_id)
+                .and_then(|x| x.attribute(update.object_id));
+            if previous != Some(&update.attribute) {
+                self.changed += 1;
+            }
+        }
+
+        fn on_frame_end(&mut self, frame: usize, _data: &Frame, actors: &ActorStateModeler) {
+            assert_eq!(frame, self.live.len());
+            self.live.push(actors.actors().count());
+        }
+    }
+
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let mut counter = Counter::default();
+    replay.walk(&mut counter);
+
+    // The walk reconstructs the same actors as the modeler
+    let mut modeler = ActorStateModeler::new();
+    let live: Vec<usize> = replay
+        .frames_or_empty()
+        .iter()
+        .map(|x| {
+            modeler.process_frame(x);
+            modeler.actors().count()
+        })
+        .collect();
+    assert_eq!(counter.live, live);
+    assert_eq!(counter.created - counter.deleted, live[live.len() - 1]);
+
+    // Keyframes re-send unchanged values
+    let updates: usize = replay
+        .frames_or_empty()
+        .iter()
+        .map(|x| x.updated_actors.len())
+        .sum();
+    assert!(counter.changed > 0 && counter.changed < updates);
+
+    // Without network data, nothing is visited
+    let replay = ParserBuilder::new(&data[..])
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    let mut counter = Counter::default();
+    replay.walk(&mut counter);
+    assert!(counter.live.is_empty());
+}
+
+#[test]
+fn test_ping_and_server_performance() {
+    let data = include_bytes!("../assets/replays/good/d1d5.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let pings = events::ping_timeline(&replay);
+    assert_eq!(pings.len(), 6);
+
+    let samples = pings
+        .iter()
+        .find(|(id, _)| id.remote_id == RemoteId::Steam(76561197999218586))
+        .map(|(_, x)| x)
+        .unwrap();
+    assert_eq!(samples.len(), 24);
+    assert_eq!(samples[0].frame, 0);
+    assert_eq!(samples[0].ping_ms, 128);
+    assert!(samples.windows(2).all(|x| x[0].ping_ms != x[1].ping_ms));
+
+    let performance = events::server_performance_timeline(&replay);
+    let states: Vec<_> = performance.iter().map(|x| (x.frame, x.state)).collect();
+    assert_eq!(states, vec![(347, 1), (388, 0)]);
+
+    let data = include_bytes!("../assets/replays/good/07e9.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(events::ping_timeline(&replay).is_empty());
+    assert!(events::server_performance_timeline(&replay).is_empty());
+}
+
+#[test]
+fn test_ignore_syncing() {
+    let data = include_bytes!("../assets/replays/good/9e35b.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let key = replay
+        .objects
+        .iter()
+        .position(|x| x == "TAGame.RBActor_TA:bIgnoreSyncing")
+        .unwrap();
+    let flags: Vec<bool> = replay
+        .frames_or_empty()
+        .iter()
+        .flat_map(|x| x.updated_actors.iter())
+        .filter(|x| usize::from(x.object_id) == key)
+        .map(|x| match x.attribute {
+            boxcars::Attribute::Boolean(flag) => flag,
+            ref x => panic!("Expected boolean, not {:?}", x),
+        })
+        .collect();
+    assert_eq!(flags.iter().filter(|x| **x).count(), 76);
+    assert_eq!(flags.iter().filter(|x| !**x).count(), 54);
+}
+
+#[test]
+fn test_match_summary_merge() {
+    let parse = |data: &[u8]| {
+        ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+    };
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let first = stats::match_summary(&parse(&data[..]));
+    assert_eq!(first.players.len(), 8);
+
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let second = stats::match_summary(&parse(&data[..]));
+
+    let mut season = first.clone();
+    season.merge(&first);
+    season.merge(&second);
+    assert_eq!(
+        season.players.len(),
+        first.players.len() + second.players.len()
+    );
+
+    let (id, single) = first
+        .players
+        .iter()
+        .find(|(id, _)| id.remote_id == RemoteId::Steam(76561198101748375))
+        .unwrap();
+    let merged = season.player(id).unwrap();
+    assert_eq!(single.matches, 1);
+    assert_eq!(merged.matches, 2);
+    assert_eq!(
+        merged.aerials.ground_touches,
+        single.aerials.ground_touches * 2
+    );
+    assert!((merged.time_empty - single.time_empty * 2.0).abs() < 0.01);
+    assert_eq!(merged.uu_per_boost_matches, 2);
+    assert!((merged.uu_per_boost.unwrap() - 564.4).abs() < 0.1);
+
+    // Players from only some of the replays are kept as is
+    for (id, player) in &second.players {
+        assert_eq!(season.player(id), Some(player));
+    }
+
+    // Summaries are stored as json
+    let json = serde_json::to_value(&season).unwrap();
+    assert_eq!(
+        json["players"].as_array().unwrap().len(),
+        season.players.len()
+    );
+}
+
+#[test]
+fn test_threat_timeline() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let timeline = stats::threat_timeline(&replay);
+    assert_eq!(timeline.len(), 8727);
+
+    let threatened = |team| {
+        timeline
+            .iter()
+            .filter(|x| x.threatened_team == Some(team))
+            .count()
+    };
+    assert_eq!(threatened(0), 394);
+    assert_eq!(threatened(1), 576);
+
+    // The conceding team is under threat right before every goal
+    for goal in events::goals_from_network(&replay) {
+        let before = &timeline[goal.frame - 1];
+        assert_eq!(before.threatened_team, Some(1 - goal.scoring_team));
+    }
+
+    let close = stats::threat_timeline_within(&replay, 1000.0);
+    let close = close.iter().filter(|x| x.threatened_team.is_some()).count();
+    assert_eq!(close, 22);
+}
+
+#[test]
+fn test_time_in_thirds() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let thirds = stats::time_in_thirds(&replay);
+    assert_eq!(thirds.len(), 7);
+    for x in thirds.values() {
+        assert!((x.defensive + x.neutral + x.offensive - 1.0).abs() < 0.001);
+    }
+
+    let player = thirds
+        .iter()
+        .find(|(id, _)| id.remote_id == RemoteId::Steam(76561198101748375))
+        .map(|(_, x)| x)
+        .unwrap();
+    assert!((player.defensive - 0.4405).abs() < 0.001);
+    assert!((player.neutral - 0.2952).abs() < 0.001);
+    assert!((player.offensive - 0.2643).abs() < 0.001);
+}
+
+#[test]
+fn test_supersonic_time() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let supersonic = stats::supersonic_time(&replay);
+    assert_eq!(supersonic.len(), 8);
+    assert!(supersonic.values().all(|x| x.boosting <= x.time));
+
+    let player = supersonic
+        .iter()
+        .find(|(id, _)| id.remote_id == RemoteId::Steam(76561198101748375))
+        .map(|(_, x)| x)
+        .unwrap();
+    assert!((player.time - 14.251).abs() < 0.01);
+    assert!((player.boosting - 0.376).abs() < 0.01);
+
+    // Nobody reaches an unattainable speed
+    let constants = stats::PhysicsConstants {
+        supersonic_speed: 10000.0,
+        ..stats::PhysicsConstants::for_replay(&replay)
+    };
+    let supersonic = stats::supersonic_time_with(&replay, &constants);
+    assert!(supersonic.values().all(|x| x.time == 0.0));
+}
+
+#[test]
+fn test_aerial_count() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let aerials = stats::aerial_count(&replay);
+    assert_eq!(aerials.len(), 8);
+
+    let player = aerials
+        .iter()
+        .find(|(id, _)| id.remote_id == RemoteId::Steam(76561198097277524))
+        .map(|(_, x)| x)
+        .unwrap();
+    assert_eq!(player.aerial_touches, 2);
+    assert_eq!(player.ground_touches, 34);
+    assert_eq!(player.aerials_started, 17);
+
+    // Players that never touched the ball are still reported
+    let idle = aerials
+        .values()
+        .filter(|x| x.aerial_touches + x.ground_touches == 0)
+        .count();
+    assert_eq!(idle, 2);
+}
+
+#[test]
+fn test_duel_pressure() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let duels = stats::duel_pressure(&replay);
+    assert_eq!(duels.len(), 77);
+    assert_eq!(duels.iter().filter(|x| x.winner.is_some()).count(), 44);
+
+    // The first duel is won by the blue player
+    let first = &duels[0];
+    assert_eq!((first.start_frame, first.end_frame), (361, 367));
+    assert_eq!(
+        first.players.0.remote_id,
+        RemoteId::Steam(76561198101748375)
+    );
+    assert_eq!(
+        first.players.1.remote_id,
+        RemoteId::Steam(76561198097277524)
+    );
+    assert_eq!(first.winner.as_ref(), Some(&first.players.0));
+
+    // Duels don't overlap and are only won by a duelist
+    for (duel, next) in duels.iter().zip(duels.iter().skip(1)) {
+        assert!(duel.start_frame <= duel.end_frame);
+        assert!(duel.end_frame < next.start_frame);
+    }
+
+    for duel in &duels {
+        assert_ne!(duel.players.0, duel.players.1);
+        if let Some(winner) = &duel.winner {
+            assert!(*winner == duel.players.0 || *winner == duel.players.1);
+        }
+    }
+}
+
+#[test]
+fn test_anonymize_player_names() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let mut replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let player_name = replay
+        .objects
+        .iter()
+        .position(|x| x == "Engine.PlayerReplicationInfo:PlayerName")
+        .unwrap();
+
+    replay.map_frames(|frame| {
+        for update in &mut frame.updated_actors {
+            if usize::from(update.object_id) == player_name {
+                update.attribute = boxcars::Attribute::String(String::from("anonymous"));
+            }
+        }
+    });
+
+    if let Some(HeaderProp::Array(stats)) = replay.property_mut("PlayerStats") {
+        for entry in stats {
+            for (key, value) in entry {
+                if key == "Name" {
+                    *value = HeaderProp::Str(String::from("anonymous"));
+                }
+            }
+        }
+    }
+
+    let old = replay.set_property("PlayerName", HeaderProp::Str(String::from("anonymous")));
+    assert!(old.is_some());
+
+    assert!(replay
+        .player_stats()
+        .iter()
+        .all(|x| x.name.as_deref() == Some("anonymous")));
+
+    let names: Vec<_> = replay
+        .network_frames_mut()
+        .unwrap()
+        .iter()
+        .flat_map(|x| x.updated_actors.iter())
+        .filter(|x| usize::from(x.object_id) == player_name)
+        .map(|x| &x.attribute)
+        .collect();
+    assert!(!names.is_empty());
+    assert!(names
+        .iter()
+        .all(|x| **x == boxcars::Attribute::String(String::from("anonymous"))));
+
+    assert!(replay.remove_property("PlayerName").is_some());
+    assert!(replay.remove_property("PlayerName").is_none());
+}
+
+#[test]
+fn test_frame_index_at_time() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let frames = &replay.network_frames.as_ref().unwrap().frames;
+    let first = frames[0].time;
+    let last = frames[frames.len() - 1].time;
+
+    assert_eq!(replay.frame_index_at_time(first - 10.0), Some(0));
+    assert_eq!(replay.frame_index_at_time(first), Some(0));
+    assert_eq!(replay.frame_index_at_time(last), Some(frames.len() - 1));
+    assert_eq!(
+        replay.frame_index_at_time(last + 10.0),
+        Some(frames.len() - 1)
+    );
+
+    for t in &[150.0, 222.22, 300.5, 501.0] {
+        let i = replay.frame_index_at_time(*t).unwrap();
+        assert!(frames[i].time <= *t);
+        assert!(frames[i + 1].time > *t);
+    }
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.frame_index_at_time(200.0), None);
+}
+
+#[test]
+fn test_frames_accessors() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.frames().map(|x| x.len()), Some(8727));
+    assert_eq!(replay.frames_or_empty().len(), 8727);
+
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(replay.frames().is_none());
+    assert!(replay.frames_or_empty().is_empty());
+}
+
+#[test]
+fn test_stop_at_first_goal() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let full = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    let (replay, warnings) = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .stop_at_first_goal()
+        .collect_errors()
+        .unwrap();
+
+    // The first goal is scored on frame 996
+    let frames = replay.frames_or_empty();
+    assert_eq!(frames.len(), 997);
+    assert_eq!(frames, &full.frames_or_empty()[..997]);
+    assert!(warnings.is_empty());
+
+    // Without goals, all frames are decoded
+    let data = include_bytes!("../assets/replays/good/029d.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .stop_at_first_goal()
+        .parse()
+        .unwrap();
+    let num_frames = replay
+        .properties
+        .iter()
+        .find(|(key, _)| key == "NumFrames")
+        .and_then(|(_, prop)| prop.as_i32());
+    assert_eq!(Some(replay.frames_or_empty().len() as i32), num_frames);
+}
+
+#[test]
+fn test_content_hash() {
+    let parse = |data: &[u8]| {
+        ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+    };
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let hash = parse(&data[..]).content_hash();
+    assert_eq!(hash, parse(&data[..]).content_hash());
+    assert_eq!(hash, 0xa918_a22e_2212_1a0f);
+
+    let rumble = include_bytes!("../assets/replays/good/rumble.replay");
+    assert_ne!(hash, parse(&rumble[..]).content_hash());
+
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_ne!(hash, replay.content_hash());
+}
+
+#[test]
+fn test_frame_gaps() {
+    let parse = |data: &[u8]| {
+        ParserBuilder::new(data)
+            .never_check_crc()
+            .must_parse_network_data()
+            .parse()
+            .unwrap()
+    };
+
+    // Frames are recorded at a variable rate but never faster than "RecordFPS"
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let replay = parse(&data[..]);
+    let fps = replay.record_fps().unwrap();
+    assert_eq!(fps, 30.0);
+    let frames = replay.frames_or_empty();
+    for (i, frame) in frames.iter().enumerate().skip(1) {
+        let expected = frames[i - 1].time + 1.0 / fps;
+        assert!(frame.time >= expected - 0.001, "frame {}", i);
+        assert!(frame.time < expected + 1.0 / fps, "frame {}", i);
+    }
+    assert_eq!(replay.frame_gaps(), Vec::new());
+
+    // The rate in this replay is closer to 20 fps, which isn't a gap, but goal replays are
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = parse(&data[..]);
+    let gaps = replay.frame_gaps();
+    assert_eq!(gaps.len(), 8);
+    assert_eq!(gaps[1].frame, 2071);
+    assert!((gaps[1].expected - 0.0484).abs() < 0.001);
+    assert!((gaps[1].actual - 9.04).abs() < 0.01);
+    assert_eq!(gaps[1].missing_frames(), 186);
+}
+
+#[test]
+fn test_interpolated_boost() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let player = UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(76561198097277524),
+        local_id: 0,
+    };
+
+    let timeline = boxcars::single_player_timeline(&replay, &player).unwrap();
+    let frames = &timeline.frames;
+
+    // The replicated amount holds while boosting up to a pad grab, while the interpolated amount
+    // drains and then jumps with the pickup
+    assert_eq!(frames[642].boost, Some(112));
+    assert_eq!(frames[649].boost, Some(112));
+    let drained = frames[642..650]
+        .windows(2)
+        .all(|x| x[1].interpolated_boost < x[0].interpolated_boost);
+    assert!(drained);
+    assert!(frames[649].interpolated_boost.unwrap() < 30.0);
+    assert_eq!(frames[650].boost, Some(255));
+    assert_eq!(frames[650].interpolated_boost, Some(255.0));
+
+    // Both agree whenever an amount is replicated, and the interpolated amount changes far more
+    // often
+    for x in frames.windows(2).filter(|x| x[0].boost != x[1].boost) {
+        assert_eq!(x[1].interpolated_boost, x[1].boost.map(f32::from));
+    }
+
+    let changes = |f: fn(&boxcars::PlayerTimelineFrame) -> Option<f32>| {
+        frames.windows(2).filter(|x| f(&x[0]) != f(&x[1])).count()
+    };
+    let raw = changes(|x| x.boost.map(f32::from));
+    let interpolated = changes(|x| x.interpolated_boost);
+    assert!(interpolated > raw * 5);
+}
+
+#[test]
+fn test_player_alive() {
+    let data = include_bytes!("../assets/replays/good/00bb.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let player = UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(76561198343671934),
+        local_id: 0,
+    };
+
+    let timeline = boxcars::single_player_timeline(&replay, &player).unwrap();
+    let demo = events::demolitions(&replay)
+        .into_iter()
+        .find(|x| x.victim.as_deref() == Some("Cmoney"))
+        .unwrap();
+    assert_eq!(demo.frame, 220);
+
+    // The demolished car lingers until frame 274, and the player respawns at frame 302
+    assert!(timeline.is_alive(219));
+    assert!((220..302).all(|x| !timeline.is_alive(x)));
+    assert!(timeline.frames[273].car.is_some());
+    assert!(timeline.frames[274].car.is_none());
+    assert!(timeline.is_alive(302));
+    assert!(timeline.frames[302].car.is_none());
+    assert!(!timeline.is_alive(timeline.frames.len()));
+
+    // A player who joins mid match isn't alive until their first car spawns
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..]).parse().unwrap();
+    let player = UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(76561198063133260),
+        local_id: 0,
+    };
+
+    let timeline = boxcars::single_player_timeline(&replay, &player).unwrap();
+    assert_eq!(timeline.frames.iter().position(|x| x.alive), Some(956));
+}
+
+#[test]
+fn test_single_player_timeline() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let player = UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(76561198101748375),
+        local_id: 0,
+    };
+
+    let timeline = boxcars::single_player_timeline(&replay, &player).unwrap();
+    assert_eq!(timeline.name.as_deref(), Some("comagoosie"));
+    assert_eq!(timeline.team, Some(0));
+    assert_eq!(timeline.frames.len(), replay.frames_or_empty().len());
+
+    let with_car = timeline.frames.iter().filter(|x| x.car.is_some()).count();
+    assert!(with_car > timeline.frames.len() / 2);
+    assert!(timeline
+        .frames
+        .iter()
+        .filter(|x| x.car.is_some())
+        .all(|x| x.car.unwrap().location.z > 0.0));
+    assert!(timeline.frames.iter().any(|x| x.boost == Some(255)));
+    assert!(timeline.frames.iter().any(|x| x.ball.is_some()));
+
+    let stranger = UniqueId {
+        remote_id: RemoteId::Steam(1),
+        ..player.clone()
+    };
+    assert_eq!(
+        boxcars::single_player_timeline(&replay, &stranger),
+        Err(boxcars::TimelineError::PlayerNotFound(stranger))
+    );
+
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        boxcars::single_player_timeline(&replay, &player),
+        Err(boxcars::TimelineError::NetworkDataMissing)
+    );
+}
+
+#[test]
+fn test_goals_from_network() {
+    let data = include_bytes!("../assets/replays/good/42f2.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let header = events::header_goals(&replay);
+    let network = events::goals_from_network(&replay);
+    assert_eq!(header.len(), 7);
+    assert_eq!(network.len(), 7);
+
+    for (goal, header) in network.iter().zip(header.iter()) {
+        assert_eq!(i32::from(goal.scoring_team), header.team.unwrap());
+        assert!(goal.location.y.abs() > 4000.0);
+    }
+
+    // The ball crosses the line a frame before the header records some of the goals
+    let discrepancies = events::reconcile_goals(&header, &network);
+    assert!(!discrepancies.is_empty());
+    for discrepancy in discrepancies {
+        match discrepancy {
+            events::GoalDiscrepancy::FrameOffset {
+                header_frame,
+                network_frame,
+            } => assert!(header_frame - network_frame <= 2),
+            x => panic!("unexpected discrepancy: {:?}", x),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_minimal_json() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let minimal = boxcars::export::minimal_replay(&replay);
+    assert_eq!(
+        minimal.schema_version,
+        boxcars::export::MINIMAL_SCHEMA_VERSION
+    );
+    // Players that left are replaced mid match
+    assert_eq!(minimal.players.len(), 10);
+    assert_eq!(minimal.players[0].name, "comagoosie");
+    assert_eq!(minimal.players[0].key(), "Steam:76561198101748375");
+    // Bots have no unique id and are told apart by name
+    assert!(minimal
+        .players
+        .iter()
+        .any(|x| x.id.is_none() && x.key() == x.name));
+    assert_eq!(
+        minimal.frames.len(),
+        replay.network_frames.as_ref().unwrap().frames.len()
+    );
+    assert!(minimal.frames.iter().any(|x| x.cars.len() == 6));
+    assert!(minimal
+        .events
+        .iter()
+        .any(|x| matches!(x, boxcars::export::MinimalEvent::Goal { .. })));
+
+    let minimal_json = replay.to_minimal_json().unwrap();
+    let full_json = serde_json::to_string(&replay).unwrap();
+    assert!(minimal_json.len() * 2 < full_json.len());
+
+    let value: serde_json::Value = serde_json::from_str(&minimal_json).unwrap();
+    assert_eq!(value["schema_version"], 2);
+    assert_eq!(value["players"][0]["id"], "Steam:76561198101748375");
+    assert!(value["frames"][500]["ball"]["position"].is_array());
+
+    // Downsampling keeps every fourth frame and snaps events to the nearest kept frame
+    let downsampled = minimal.clone().downsample(4);
+    assert_eq!(
+        downsampled.frames.len(),
+        minimal.frames.iter().step_by(4).count()
+    );
+    assert_eq!(downsampled.frames[10], minimal.frames[40]);
+    assert_eq!(downsampled.events.len(), minimal.events.len());
+    let frame = |x: &boxcars::export::MinimalEvent| match *x {
+        boxcars::export::MinimalEvent::Goal { frame, .. }
+        | boxcars::export::MinimalEvent::Demolition { frame, .. }
+        | boxcars::export::MinimalEvent::Touch { frame, .. } => frame,
+    };
+    for (original, kept) in minimal.events.iter().zip(downsampled.events.iter()) {
+        let snapped = frame(kept) * 4;
+        assert!((snapped as isize - frame(original) as isize).abs() <= 2);
+        assert!(frame(kept) < downsampled.frames.len());
+    }
+    assert_eq!(minimal.clone().downsample(1), minimal);
+
+    let value: serde_json::Value =
+        serde_json::from_str(&replay.to_minimal_json_with_stride(4).unwrap()).unwrap();
+    assert_eq!(
+        value["frames"].as_array().unwrap().len(),
+        downsampled.frames.len()
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_analysis_json() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let value: serde_json::Value =
+        serde_json::from_str(&replay.to_analysis_json().unwrap()).unwrap();
+    assert_eq!(value["properties"]["TeamSize"], 3);
+
+    // Includes the players who left mid match
+    let players = value["players"].as_object().unwrap();
+    assert_eq!(players.len(), 8);
+    assert_eq!(players["Steam:76561198101748375"]["name"], "comagoosie");
+    assert_eq!(players["Steam:76561198101748375"]["team"], 0);
+    assert_eq!(players["PlayStation:TheGoldenGarp"]["team"], 0);
+
+    let frames = value["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), replay.frames_or_empty().len());
+
+    // The first car spawns on the blue side facing orange's goal
+    let car = frames
+        .iter()
+        .flat_map(|x| x["new_actors"].as_array().unwrap())
+        .find(|x| x["object"] == "Archetypes.Car.Car_Default")
+        .unwrap();
+    assert_eq!(car["position"], serde_json::json!([256.0, -3072.0, 18.0]));
+    let rotation: Vec<f64> = car["rotation"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x.as_f64().unwrap())
+        .collect();
+    assert!((rotation[2] - std::f64::consts::FRAC_1_SQRT_2).abs() < 0.01);
+    assert!((rotation[3] - std::f64::consts::FRAC_1_SQRT_2).abs() < 0.01);
+
+    let update = frames
+        .iter()
+        .flat_map(|x| x["updated_actors"].as_array().unwrap())
+        .find(|x| x["player"] == "Steam:76561198101748375" && x["value"]["RigidBody"].is_object())
+        .unwrap();
+    assert_eq!(update["attribute"], "TAGame.RBActor_TA:ReplicatedRBState");
+    assert_eq!(
+        update["value"]["RigidBody"]["rotation"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+
+    // The value tree has the same shape, and can be extended before it is written
+    let mut tree = serde_json::Value::from(&replay);
+    assert_eq!(
+        tree,
+        boxcars::export::AnalysisReplay::new(&replay).to_json_value()
+    );
+    assert_eq!(tree["properties"], value["properties"]);
+    assert_eq!(tree["players"], value["players"]);
+    assert_eq!(tree["frames"].as_array().unwrap().len(), frames.len());
+
+    tree["source"] = serde_json::json!("3d07e.replay");
+    let written: serde_json::Value = serde_json::from_str(&tree.to_string()).unwrap();
+    assert_eq!(written["source"], "3d07e.replay");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_pretty() {
+    use boxcars::export::{to_json_pretty, to_writer_pretty, JsonSchema};
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let raw = to_json_pretty(&replay, JsonSchema::Raw).unwrap();
+    assert!(raw.starts_with("{\n  \"header_size\""));
+    let value: serde_json::Value = serde_json::from_str(&raw).unwrap();
+    assert_eq!(
+        value,
+        serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&replay).unwrap())
+            .unwrap()
+    );
+
+    let analysis = to_json_pretty(&replay, JsonSchema::Analysis).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&analysis).unwrap();
+    assert_eq!(
+        value,
+        serde_json::from_str::<serde_json::Value>(&replay.to_analysis_json().unwrap()).unwrap()
+    );
+
+    let mut written = Vec::new();
+    to_writer_pretty(&mut written, &replay, JsonSchema::Analysis).unwrap();
+    let written: serde_json::Value = serde_json::from_slice(&written).unwrap();
+    assert_eq!(written, value);
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_arrow() {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Float32Type, Int32Type};
+    use arrow_array::Array;
+    use arrow_schema::DataType;
+
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let batch = boxcars::export::to_arrow(&replay).unwrap();
+    let schema = batch.schema();
+    let names: Vec<&str> = schema.fields().iter().map(|x| x.name().as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["frame", "time", "entity", "name", "x", "y", "z", "qx", "qy", "qz", "qw", "boost"]
+    );
+    assert_eq!(
+        schema.field(2).data_type(),
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    );
+    assert!(schema.field(11).is_nullable());
+
+    // A row for the ball and each car present in every frame
+    let minimal = boxcars::export::minimal_replay(&replay);
+    let rows: usize = minimal
+        .frames
+        .iter()
+        .map(|x| x.cars.len() + usize::from(x.ball.is_some()))
+        .sum();
+    assert_eq!(batch.num_rows(), rows);
+
+    // The ball and the ten players share the dictionary
+    let entity = batch.column(2).as_dictionary::<Int32Type>();
+    assert_eq!(entity.values().len(), 11);
+    let values = entity.values().as_string::<i32>();
+    assert!(values.iter().any(|x| x == Some("ball")));
+    assert!(values.iter().any(|x| x == Some("Steam:76561198101748375")));
+
+    // Only cars carry the player's name
+    let name = batch.column(3).as_dictionary::<Int32Type>();
+    let names = name.values().as_string::<i32>();
+    assert!(names.iter().any(|x| x == Some("comagoosie")));
+
+    // The ball has no boost, while cars do once it is replicated
+    let boost = batch.column(11).as_primitive::<Float32Type>();
+    let ball = entity.keys().value(0);
+    assert_eq!(values.value(ball as usize), "ball");
+    assert!(name.is_null(0));
+    assert!(boost.is_null(0));
+    assert!(boost.iter().flatten().all(|x| (0.0..=100.0).contains(&x)));
+    assert!(boost.null_count() < batch.num_rows());
+}
+
+#[cfg(feature = "gltf")]
+#[test]
+fn test_gltf() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let mut glb = Vec::new();
+    boxcars::export::to_gltf(&replay, &mut glb).unwrap();
+
+    let u32_at = |at: usize| u32::from_le_bytes([glb[at], glb[at + 1], glb[at + 2], glb[at + 3]]);
+    assert_eq!(&glb[..4], b"glTF");
+    assert_eq!(u32_at(4), 2);
+    assert_eq!(u32_at(8) as usize, glb.len());
+
+    let json_len = u32_at(12) as usize;
+    assert_eq!(&glb[16..20], b"JSON");
+    let gltf: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+    let bin_len = u32_at(20 + json_len) as usize;
+    assert_eq!(&glb[24 + json_len..28 + json_len], b"BIN\0");
+    assert_eq!(28 + json_len + bin_len, glb.len());
+
+    // The ball and the ten players (including those who left)
+    let nodes = gltf["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 11);
+    assert_eq!(nodes[0]["name"], "Ball");
+    assert_eq!(nodes[1]["name"], "Steam:76561198101748375");
+    assert_eq!(nodes[1]["extras"]["player"], "comagoosie");
+    assert_eq!(gltf["asset"]["version"], "2.0");
+
+    let animation = &gltf["animations"][0];
+    assert_eq!(animation["channels"].as_array().unwrap().len(), 33);
+
+    let accessors = gltf["accessors"].as_array().unwrap();
+    let keyframes = accessors[0]["count"].as_u64().unwrap();
+    let duration = accessors[0]["max"][0].as_f64().unwrap();
+    assert_eq!(accessors[0]["min"][0], 0.0);
+    assert!((keyframes as f64 - duration * 30.0).abs() <= 1.0);
+    assert!(accessors.iter().all(|x| x["count"] == keyframes));
+    assert!(gltf["buffers"][0]["byteLength"].as_u64().unwrap() as usize <= bin_len);
+}
+
+#[test]
+fn test_demolitions_and_touches() {
+    let data = include_bytes!("../assets/replays/good/c0bca.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let demolitions = events::demolitions(&replay);
+    assert_eq!(demolitions.len(), 1);
+    assert_eq!(demolitions[0].frame, 7259);
+    assert_eq!(demolitions[0].attacker.as_deref(), Some("DustGuts"));
+    assert_eq!(demolitions[0].victim.as_deref(), Some("Teebs"));
+
+    let touches = events::touches(&replay);
+    assert!(!touches.is_empty());
+    assert!(touches
+        .iter()
+        .all(|x| x.player.is_some() && x.team.is_some()));
+    assert!(touches.windows(2).all(|x| x[0].frame < x[1].frame));
+}
+
+#[test]
+fn test_rumble_events() {
+    use boxcars::events::{RumbleAction, RumbleItem};
+
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let rumble = events::rumble(&replay);
+    let count = |action| rumble.iter().filter(|x| x.action == action).count();
+    assert_eq!(count(RumbleAction::PickedUp), 91);
+    assert_eq!(count(RumbleAction::Used), 79);
+    assert_eq!(count(RumbleAction::Expired), 11);
+    assert!(rumble.iter().all(|x| x.player.is_some()));
+
+    let freezer: Vec<_> = rumble
+        .iter()
+        .filter(|x| x.player.as_deref() == Some("comagoosie") && x.frame <= 506)
+        .map(|x| (x.frame, x.item.clone(), x.action))
+        .collect();
+    assert_eq!(
+        freezer,
+        vec![
+            (301, RumbleItem::Freezer, RumbleAction::PickedUp),
+            (506, RumbleItem::Freezer, RumbleAction::Expired),
+        ]
+    );
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(events::rumble(&replay).is_empty());
+}
+
+#[test]
+fn test_replay_processor() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let mut ball_frames = 0;
+    let mut player_frames = 0;
+    let mut components = 0;
+    let mut players = 0;
+    let mut processor = ReplayProcessor::new(&replay);
+    processor.for_each_frame(|processor, _index, _frame| {
+        if let BallFrame::Data { .. } = processor.get_ball_frame() {
+            ball_frames += 1;
+        }
+
+        let frames = processor.get_player_frames();
+        players = frames.len();
+        for (_, frame) in frames {
+            if let PlayerFrame::Data { boost_amount, .. } = frame {
+                assert!((0.0..=255.0).contains(&boost_amount));
+                player_frames += 1;
+            }
+        }
+
+        for car in processor.cars() {
+            if processor.car_component(*car, CarComponent::Jump).is_some()
+                && processor.car_component(*car, CarComponent::Dodge).is_some()
+            {
+                components += 1;
+            }
+        }
+    });
+
+    assert_eq!(ball_frames, 8725);
+    assert_eq!(player_frames, 49643);
+    assert!(components > 0);
+    assert_eq!(players, 8);
+
+    let unknown = UniqueId {
+        system_id: 1,
+        remote_id: RemoteId::Steam(1),
+        local_id: 0,
+    };
+    assert_eq!(
+        processor.get_frame_for_player(&unknown),
+        Err(ProcessorError::PlayerNotFound(unknown.clone()))
+    );
+}
+
+#[test]
+fn test_int64_attributes() {
+    // Club ids and the epoch times of the match's time limit are replicated as 64 bit integers.
+    // Decoding them with any other width would throw every following attribute out of alignment.
+    let data = include_bytes!("../assets/replays/good/difficulty.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let mut values = HashSet::new();
+    for frame in &replay.network_frames.as_ref().unwrap().frames {
+        for update in &frame.updated_actors {
+            if let boxcars::Attribute::Int64(x) = update.attribute {
+                let name = replay.objects[usize::from(update.object_id)].as_str();
+                values.insert((name, x));
+            }
+        }
+    }
+
+    assert!(values.contains(&("TAGame.PRI_TA:ClubID", 2485461)));
+    assert!(values.contains(&("TAGame.MaxTimeWarningData_TA:EndGameEpochTime", 1601244899)));
+    assert!(values.contains(&(
+        "TAGame.MaxTimeWarningData_TA:EndGameWarningEpochTime",
+        1601244779
+    )));
+}
+
+#[test]
+fn test_first_man_back_violations() {
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let violations = stats::first_man_back_violations(&replay);
+    assert_eq!(violations.len(), 23);
+    assert!(violations
+        .windows(2)
+        .all(|x| x[0].start_frame <= x[1].start_frame));
+
+    // Blue is caught forward for the almost three seconds leading up to orange's goal
+    let goal = events::goals_from_network(&replay)
+        .into_iter()
+        .find(|x| x.scoring_team == 1)
+        .unwrap();
+    assert_eq!(goal.frame, 2006);
+    assert!(violations.contains(&stats::FirstManBackViolation {
+        team: 0,
+        start_frame: 1948,
+        end_frame: 2005,
+    }));
+}
+
+#[test]
+fn test_patch_label() {
+    let label = |data: &[u8]| {
+        let replay = ParserBuilder::new(data)
+            .never_check_crc()
+            .never_parse_network_data()
+            .parse()
+            .unwrap();
+        (
+            replay.engine_build().map(String::from),
+            replay.patch_label(),
+        )
+    };
+
+    let (build, patch) = label(include_bytes!("../assets/replays/good/59d3.replay"));
+    assert_eq!(build.as_deref(), Some("221120.42953.406184"));
+    assert_eq!(patch.as_deref(), Some("Season 8 (v2.23)"));
+
+    // An unknown build of the season is labeled by the earliest patch with its version triplet
+    let (build, patch) = label(include_bytes!("../assets/replays/good/204c.replay"));
+    assert_eq!(build.as_deref(), Some("221024.70985.401801"));
+    assert_eq!(patch.as_deref(), Some("Season 8 (v2.01+)"));
+
+    let (build, patch) = label(include_bytes!("../assets/replays/good/epic.replay"));
+    assert_eq!(build.as_deref(), Some("200917.51558.292065"));
+    assert_eq!(patch.as_deref(), Some("Season 1 (v1.78+)"));
+
+    let (build, patch) = label(include_bytes!("../assets/replays/good/01d3e5.replay"));
+    assert_eq!(build.as_deref(), Some("180517.71295.194805"));
+    assert_eq!(patch.as_deref(), Some("v1.44"));
+
+    // Before free to play, without a known season or version
+    let (build, patch) = label(include_bytes!("../assets/replays/good/3381.replay"));
+    assert_eq!(build.as_deref(), Some("170926.12545.172527"));
+    assert_eq!(patch.as_deref(), Some("868.20.2"));
+
+    let (build, patch) = label(include_bytes!("../assets/replays/good/551c.replay"));
+    assert_eq!(build, None);
+    assert_eq!(patch.as_deref(), Some("868.10"));
+
+    // Very early replays don't record their version
+    let (build, patch) = label(include_bytes!("../assets/replays/good/6688.replay"));
+    assert_eq!(build, None);
+    assert_eq!(patch, None);
+}
+
+#[test]
+fn test_flip_resets() {
+    let data = include_bytes!("../assets/replays/good/436d.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    // Upside down under the ball with the wheels against it, and then dodging on the way down
+    let resets = events::flip_resets(&replay);
+    let frames: Vec<_> = resets.iter().map(|x| (x.frame, x.used_frame)).collect();
+    assert_eq!(frames, vec![(309, 355), (1513, 1549)]);
+    assert!(resets.iter().all(|x| x.player.as_deref() == Some("Zemo")));
+
+    let data = include_bytes!("../assets/replays/good/3d07e.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+    assert!(events::flip_resets(&replay).is_empty());
+}
+
+#[test]
+fn test_reduce() {
+    let data = include_bytes!("../assets/replays/good/rumble.replay");
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .must_parse_network_data()
+        .parse()
+        .unwrap();
+
+    let frames = replay.reduce(0, |count, _frame, _actors| count + 1);
+    assert_eq!(frames, replay.network_frames.as_ref().unwrap().frames.len());
+
+    // The state handed to the fold is the state after the frame was applied
+    let mut modeler = ActorStateModeler::new();
+    let live = replay.reduce(Vec::new(), |mut live, frame, actors| {
+        modeler.process_frame(frame);
+        assert_eq!(actors.actors().count(), modeler.actors().count());
+        live.push(actors.actors().count());
+        live
+    });
+    assert!(live.iter().any(|x| *x > 0));
+
+    let replay = ParserBuilder::new(&data[..])
+        .never_check_crc()
+        .never_parse_network_data()
+        .parse()
+        .unwrap();
+    assert_eq!(replay.reduce(7, |_, _, _| 0), 7);
+}
+
+#[test]
+fn test_arena_variant() {
+    let replays: [(&[u8], Option<ArenaVariant>); 10] = [
+        (
+            include_bytes!("../assets/replays/good/rumble.replay"),
+            Some(ArenaVariant::Stormy),
+        ),
+        (
+            include_bytes!("../assets/replays/good/65e98.replay"),
+            Some(ArenaVariant::Stormy),
+        ),
+        (
+            include_bytes!("../assets/replays/good/epic.replay"),
+            Some(ArenaVariant::Night),
+        ),
+        (
+            include_bytes!("../assets/replays/good/00bb.replay"),
+            Some(ArenaVariant::Dawn),
+        ),
+        (
+            include_bytes!("../assets/replays/good/e4598.replay"),
+            Some(ArenaVariant::Dusk),
+        ),
+        (
+            include_bytes!("../assets/replays/good/d4f3b_heat.replay"),
+            Some(ArenaVariant::Day),
+        ),
+        (
+            include_bytes!("../assets/replays/good/d1d5.replay"),
+            Some(ArenaVariant::Snowy),
+        ),
+        (
+            include_bytes!("../assets/replays/good/9a2cd.replay"),
+            Some(ArenaVariant::Haunted),
+        ),
+        (
+            include_bytes!("../assets/replays/good/3d07e.replay"),
+            Some(ArenaVariant::Standard),
+        ),
+        (include_bytes!("../assets/replays/good/7588d.replay"), None),
+    ];
+
+    for (data, expected) in replays.iter() {
+        let replay = ParserBuilder::new(data)
+            .never_check_crc()
+            .never_parse_network_data()
+            .parse()
+            .unwrap();
+        assert_eq!(replay.arena_variant(), *expected);
+    }
+}